@@ -0,0 +1,734 @@
+// HIR → C source emission
+
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write as _;
+
+use ast_types::{BinaryOp, UnaryOp};
+use neuro_hir::{HirExpr, HirExprKind, HirFunction, HirItem, HirProgram, HirStmt, HirType};
+use shared_types::Literal;
+
+use crate::errors::{CodegenError, CodegenResult};
+use crate::types::{c_type, wrapping_type};
+
+/// Where the value of a control-flow expression goes.
+#[derive(Debug, Clone)]
+enum Dest {
+    /// Evaluated for its effects only.
+    Discard,
+    /// Returned from the enclosing function.
+    Return,
+    /// Assigned to the named C variable.
+    Assign(String),
+}
+
+/// An enclosing Neuro loop, innermost last.
+struct LoopFrame {
+    label: Option<String>,
+    /// Where `break value` sends its value.
+    dest: Dest,
+    /// Suffix of the loop's `goto` labels.
+    id: usize,
+    break_used: bool,
+    continue_used: bool,
+}
+
+/// Translates one program to a C translation unit.
+pub(crate) struct Emitter<'p> {
+    functions: HashSet<&'p str>,
+    out: String,
+    indent: usize,
+    /// Neuro binding name → C variable name, one map per block.
+    scopes: Vec<HashMap<String, String>>,
+    /// Declarations of each Neuro name so far in the current function.
+    declared: HashMap<String, usize>,
+    next_temp: usize,
+    loops: Vec<LoopFrame>,
+}
+
+impl<'p> Emitter<'p> {
+    pub(crate) fn new(program: &'p HirProgram) -> Self {
+        let functions = program
+            .items
+            .iter()
+            .filter_map(|item| match item {
+                HirItem::Function(f) => Some(f.name.as_str()),
+                _ => None,
+            })
+            .collect();
+        Self {
+            functions,
+            out: String::new(),
+            indent: 0,
+            scopes: Vec::new(),
+            declared: HashMap::new(),
+            next_temp: 0,
+            loops: Vec::new(),
+        }
+    }
+
+    /// The whole translation unit: a header comment, the includes, a prototype per
+    /// function so definition order does not matter, the definitions, and a C
+    /// `main` calling the program's `main` when it has one.
+    pub(crate) fn emit_program(
+        mut self,
+        program: &HirProgram,
+        source_path: &str,
+    ) -> CodegenResult<String> {
+        let mut functions = Vec::new();
+        for item in &program.items {
+            match item {
+                HirItem::Function(f) => functions.push(f),
+                // Trait declarations only order vtables; static dispatch erases them.
+                HirItem::Trait(_) => {}
+                HirItem::Struct(s) => return Err(unsupported(format!("struct `{}`", s.name))),
+                HirItem::Enum(e) => return Err(unsupported(format!("enum `{}`", e.name))),
+                HirItem::Impl(i) => {
+                    return Err(unsupported(format!("the methods of `{}`", i.type_name)))
+                }
+                HirItem::Const(c) => return Err(unsupported(format!("constant `{}`", c.name))),
+                HirItem::Closure(_) => return Err(unsupported("closures".to_string())),
+            }
+        }
+
+        let path = source_path.replace("*/", "*\\/");
+        self.out
+            .push_str(&format!("/* Generated by neurc from {path} */\n"));
+        self.out
+            .push_str("#include <stdbool.h>\n#include <stdint.h>\n\n");
+        for f in &functions {
+            self.out.push_str(&format!("{};\n", signature(f)?));
+        }
+        for f in &functions {
+            self.out.push('\n');
+            self.function(f)?;
+        }
+
+        if let Some(main) = functions.iter().find(|f| f.name == "main") {
+            if !main.params.is_empty() {
+                return Err(unsupported("a `main` with parameters".to_string()));
+            }
+            self.out.push_str("\nint main(void) {\n");
+            if main.return_type == HirType::Void {
+                self.out.push_str("    nr_main();\n    return 0;\n");
+            } else {
+                self.out.push_str("    return (int)nr_main();\n");
+            }
+            self.out.push_str("}\n");
+        }
+        Ok(self.out)
+    }
+
+    fn function(&mut self, f: &HirFunction) -> CodegenResult<()> {
+        self.declared.clear();
+        self.next_temp = 0;
+        self.scopes.push(HashMap::new());
+        for param in &f.params {
+            self.declare(&param.name);
+        }
+        // `signature` names the parameters the way `declare` just bound them.
+        self.line(&format!("{} {{", signature(f)?));
+        let dest = if f.return_type == HirType::Void {
+            Dest::Discard
+        } else {
+            Dest::Return
+        };
+        self.indent += 1;
+        self.stmts(&f.body, &dest)?;
+        self.indent -= 1;
+        self.line("}");
+        self.scopes.pop();
+        Ok(())
+    }
+
+    // ----- statements -----
+
+    /// The statements of a block, its tail sent to `dest`. The caller supplies the
+    /// surrounding braces.
+    fn stmts(&mut self, stmts: &[HirStmt], dest: &Dest) -> CodegenResult<()> {
+        self.scopes.push(HashMap::new());
+        let result = self.stmts_in_scope(stmts, dest);
+        self.scopes.pop();
+        result
+    }
+
+    fn stmts_in_scope(&mut self, stmts: &[HirStmt], dest: &Dest) -> CodegenResult<()> {
+        let Some((tail, init)) = stmts.split_last() else {
+            return Ok(());
+        };
+        for stmt in init {
+            self.stmt(stmt)?;
+        }
+        match (tail, dest) {
+            (_, Dest::Discard) => self.stmt(tail),
+            (HirStmt::Expr(expr), _) => self.expr_into(expr, dest),
+            (
+                HirStmt::If {
+                    condition,
+                    then_block,
+                    else_if_blocks,
+                    else_block: Some(else_block),
+                    ..
+                },
+                _,
+            ) => self.if_chain(
+                condition,
+                then_block,
+                else_if_blocks,
+                Some(else_block),
+                dest,
+            ),
+            _ => self.stmt(tail),
+        }
+    }
+
+    fn stmt(&mut self, stmt: &HirStmt) -> CodegenResult<()> {
+        match stmt {
+            HirStmt::VarDecl { name, ty, init, .. } => self.var_decl(name, ty, init.as_ref()),
+            HirStmt::Const {
+                name, ty, value, ..
+            } => self.var_decl(name, ty, Some(value)),
+            HirStmt::Assignment { target, value, .. } => {
+                let target = self.lookup(target)?;
+                self.expr_into(value, &Dest::Assign(target))
+            }
+            HirStmt::Return { value, .. } => match value {
+                Some(value) => self.expr_into(value, &Dest::Return),
+                None => {
+                    self.line("return;");
+                    Ok(())
+                }
+            },
+            HirStmt::If {
+                condition,
+                then_block,
+                else_if_blocks,
+                else_block,
+                ..
+            } => self.if_chain(
+                condition,
+                then_block,
+                else_if_blocks,
+                else_block.as_deref(),
+                &Dest::Discard,
+            ),
+            HirStmt::While {
+                label,
+                condition,
+                body,
+                ..
+            } => {
+                let condition = self.expr(condition)?;
+                self.looped(
+                    &format!("while ({})", bare(&condition)),
+                    label,
+                    Dest::Discard,
+                    |this| this.stmts(body, &Dest::Discard),
+                )
+            }
+            HirStmt::ForRange {
+                label,
+                iterator,
+                start,
+                end,
+                inclusive,
+                body,
+                ..
+            } => self.for_range(label, iterator, start, end, *inclusive, body),
+            HirStmt::Loop { label, body, .. } => {
+                self.looped("for (;;)", label, Dest::Discard, |this| {
+                    this.stmts(body, &Dest::Discard)
+                })
+            }
+            HirStmt::Break { label, value, .. } => self.break_stmt(label, value.as_ref()),
+            HirStmt::Continue { label, .. } => {
+                let index = self.loop_index(label)?;
+                if index + 1 == self.loops.len() {
+                    self.line("continue;");
+                } else {
+                    self.loops[index].continue_used = true;
+                    let id = self.loops[index].id;
+                    self.line(&format!("goto t_continue_{id};"));
+                }
+                Ok(())
+            }
+            HirStmt::Expr(expr) => self.expr_into(expr, &Dest::Discard),
+            HirStmt::ForEach { .. } => Err(unsupported("`for` over a collection".to_string())),
+            HirStmt::FieldAssignment { .. } => Err(unsupported("field assignment".to_string())),
+            HirStmt::DerefAssignment { .. } => {
+                Err(unsupported("assignment through a reference".to_string()))
+            }
+            HirStmt::IndexAssignment { .. } => {
+                Err(unsupported("array element assignment".to_string()))
+            }
+        }
+    }
+
+    /// A local declaration. The new name is bound after the initializer is emitted,
+    /// so `val x = x + 1` reads the outer `x`.
+    fn var_decl(&mut self, name: &str, ty: &HirType, init: Option<&HirExpr>) -> CodegenResult<()> {
+        let c_ty = c_type(ty)?;
+        let var = self.fresh_name(name);
+        match init {
+            Some(init) if is_control_flow(init) => {
+                self.line(&format!("{c_ty} {var};"));
+                self.expr_into(init, &Dest::Assign(var.clone()))?;
+            }
+            Some(init) => {
+                let value = self.expr(init)?;
+                self.line(&format!("{c_ty} {var} = {};", bare(&value)));
+            }
+            None => self.line(&format!("{c_ty} {var};")),
+        }
+        self.bind(name, var);
+        Ok(())
+    }
+
+    fn if_chain(
+        &mut self,
+        condition: &HirExpr,
+        then_block: &[HirStmt],
+        else_if_blocks: &[(HirExpr, Vec<HirStmt>)],
+        else_block: Option<&[HirStmt]>,
+        dest: &Dest,
+    ) -> CodegenResult<()> {
+        let condition = self.expr(condition)?;
+        self.line(&format!("if ({}) {{", bare(&condition)));
+        self.block_body(then_block, dest)?;
+        for (condition, block) in else_if_blocks {
+            let condition = self.expr(condition)?;
+            self.line(&format!("}} else if ({}) {{", bare(&condition)));
+            self.block_body(block, dest)?;
+        }
+        if let Some(block) = else_block {
+            self.line("} else {");
+            self.block_body(block, dest)?;
+        }
+        self.line("}");
+        Ok(())
+    }
+
+    /// `for (T i = start, end = ...; ...)`, evaluating the bounds once. The inclusive
+    /// form tests for the last iteration before stepping, so a range ending at the
+    /// type's maximum neither overflows nor loops forever.
+    fn for_range(
+        &mut self,
+        label: &Option<String>,
+        iterator: &str,
+        start: &HirExpr,
+        end: &HirExpr,
+        inclusive: bool,
+        body: &[HirStmt],
+    ) -> CodegenResult<()> {
+        let c_ty = c_type(&start.ty)?;
+        let start = self.expr(start)?;
+        let end = self.expr(end)?;
+        let var = self.fresh_name(iterator);
+        let bound = self.temp("end");
+        let header = if inclusive {
+            let more = self.temp("more");
+            format!(
+                "for ({c_ty} {var} = {}, {bound} = {}, {more} = {var} <= {bound}; {more}; \
+                 {more} = {var} != {bound}, {var} += {more})",
+                bare(&start),
+                bare(&end)
+            )
+        } else {
+            format!(
+                "for ({c_ty} {var} = {}, {bound} = {}; {var} < {bound}; {var}++)",
+                bare(&start),
+                bare(&end)
+            )
+        };
+        self.scopes.push(HashMap::new());
+        self.bind(iterator, var);
+        let result = self.looped(&header, label, Dest::Discard, |this| {
+            this.stmts(body, &Dest::Discard)
+        });
+        self.scopes.pop();
+        result
+    }
+
+    /// Emit a loop statement `header { body }` with a frame for its `break`s and
+    /// `continue`s. Those targeting an outer loop jump to labels placed here.
+    fn looped(
+        &mut self,
+        header: &str,
+        label: &Option<String>,
+        dest: Dest,
+        body: impl FnOnce(&mut Self) -> CodegenResult<()>,
+    ) -> CodegenResult<()> {
+        let id = self.next_temp;
+        self.next_temp += 1;
+        self.loops.push(LoopFrame {
+            label: label.clone(),
+            dest,
+            id,
+            break_used: false,
+            continue_used: false,
+        });
+        self.line(&format!("{header} {{"));
+        self.indent += 1;
+        let result = body(self);
+        let frame = self.loops.pop();
+        result?;
+        let frame = frame.ok_or_else(|| internal("loop frame missing"))?;
+        if frame.continue_used {
+            self.line(&format!("t_continue_{id}:;"));
+        }
+        self.indent -= 1;
+        self.line("}");
+        if frame.break_used {
+            self.line(&format!("t_break_{id}:;"));
+        }
+        Ok(())
+    }
+
+    fn break_stmt(&mut self, label: &Option<String>, value: Option<&HirExpr>) -> CodegenResult<()> {
+        let index = self.loop_index(label)?;
+        let dest = self.loops[index].dest.clone();
+        if let Some(value) = value {
+            self.expr_into(value, &dest)?;
+            if matches!(dest, Dest::Return) {
+                return Ok(());
+            }
+        }
+        if index + 1 == self.loops.len() {
+            self.line("break;");
+        } else {
+            self.loops[index].break_used = true;
+            let id = self.loops[index].id;
+            self.line(&format!("goto t_break_{id};"));
+        }
+        Ok(())
+    }
+
+    /// The frame a `break` or `continue` targets: the named loop, or the innermost.
+    fn loop_index(&self, label: &Option<String>) -> CodegenResult<usize> {
+        match label {
+            None => self.loops.len().checked_sub(1),
+            Some(label) => self
+                .loops
+                .iter()
+                .rposition(|frame| frame.label.as_ref() == Some(label)),
+        }
+        .ok_or_else(|| internal("`break` or `continue` outside a loop"))
+    }
+
+    // ----- expressions -----
+
+    /// Emit `expr` as statements sending its value to `dest`. Block-valued
+    /// expressions become C statements; anything else is a single C expression.
+    fn expr_into(&mut self, expr: &HirExpr, dest: &Dest) -> CodegenResult<()> {
+        if expr.ty == HirType::Void && matches!(dest, Dest::Return) {
+            self.expr_into(expr, &Dest::Discard)?;
+            self.line("return;");
+            return Ok(());
+        }
+        match &expr.kind {
+            HirExprKind::If {
+                condition,
+                then_block,
+                else_if_blocks,
+                else_block,
+            } => self.if_chain(
+                condition,
+                then_block,
+                else_if_blocks,
+                else_block.as_deref(),
+                dest,
+            ),
+            HirExprKind::Block { stmts } | HirExprKind::Unsafe { stmts } => {
+                self.line("{");
+                self.block_body(stmts, dest)?;
+                self.line("}");
+                Ok(())
+            }
+            HirExprKind::Loop { label, body } => {
+                self.looped("for (;;)", label, dest.clone(), |this| {
+                    this.stmts(body, &Dest::Discard)
+                })
+            }
+            _ => {
+                let value = self.expr(expr)?;
+                match dest {
+                    Dest::Discard if matches!(expr.kind, HirExprKind::Call { .. }) => {
+                        self.line(&format!("{value};"))
+                    }
+                    Dest::Discard => self.line(&format!("(void){value};")),
+                    Dest::Return => self.line(&format!("return {};", bare(&value))),
+                    Dest::Assign(var) => self.line(&format!("{var} = {};", bare(&value))),
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Braces-less block contents, one level deeper.
+    fn block_body(&mut self, stmts: &[HirStmt], dest: &Dest) -> CodegenResult<()> {
+        self.indent += 1;
+        let result = self.stmts(stmts, dest);
+        self.indent -= 1;
+        result
+    }
+
+    /// `expr` as one C expression. Compound results are parenthesized, so they nest
+    /// as operands without precedence concerns.
+    fn expr(&mut self, expr: &HirExpr) -> CodegenResult<String> {
+        match &expr.kind {
+            HirExprKind::Literal(literal) => literal_value(literal, &expr.ty),
+            HirExprKind::Variable(name) => {
+                if self.is_local(name) {
+                    self.lookup(name)
+                } else if self.functions.contains(name.as_str()) {
+                    Err(unsupported("function values".to_string()))
+                } else {
+                    Err(CodegenError::UndefinedVariable(name.clone()))
+                }
+            }
+            HirExprKind::Binary { op, left, right } => {
+                let (l, r) = (self.expr(left)?, self.expr(right)?);
+                binary(*op, &l, &r, &left.ty, &expr.ty)
+            }
+            HirExprKind::Unary { op, operand } => {
+                let value = self.expr(operand)?;
+                let c_ty = c_type(&expr.ty)?;
+                Ok(match op {
+                    UnaryOp::Negate => match wrapping_type(&expr.ty) {
+                        Some(wide) => format!("(({c_ty})-({wide}){value})"),
+                        None => format!("(-{value})"),
+                    },
+                    UnaryOp::Not => format!("(!{value})"),
+                    UnaryOp::BitNot => format!("(({c_ty})~{value})"),
+                })
+            }
+            HirExprKind::Call { callee, args } => {
+                let HirExprKind::Variable(name) = &callee.kind else {
+                    return Err(unsupported("calls through function values".to_string()));
+                };
+                if self.is_local(name) {
+                    return Err(unsupported("calls through function values".to_string()));
+                }
+                if !self.functions.contains(name.as_str()) {
+                    return Err(unsupported(format!("calls to `{name}`")));
+                }
+                let mut c_args = Vec::with_capacity(args.len());
+                for arg in args {
+                    let arg = self.expr(arg)?;
+                    c_args.push(bare(&arg).to_string());
+                }
+                Ok(format!("nr_{name}({})", c_args.join(", ")))
+            }
+            HirExprKind::Cast { value } => {
+                let value = self.expr(value)?;
+                Ok(format!("(({}){value})", c_type(&expr.ty)?))
+            }
+            HirExprKind::If { .. }
+            | HirExprKind::Block { .. }
+            | HirExprKind::Unsafe { .. }
+            | HirExprKind::Loop { .. } => Err(unsupported(
+                "a block-valued expression as an operand".to_string(),
+            )),
+            HirExprKind::Match { .. } => Err(unsupported("`match`".to_string())),
+            HirExprKind::StructLiteral { .. } | HirExprKind::FieldAccess { .. } => {
+                Err(unsupported("structs".to_string()))
+            }
+            HirExprKind::Path { .. } => Err(unsupported("associated functions".to_string())),
+            HirExprKind::Reference { .. }
+            | HirExprKind::Deref { .. }
+            | HirExprKind::DynCoerce { .. } => Err(unsupported("references".to_string())),
+            HirExprKind::Range { .. } => Err(unsupported("ranges".to_string())),
+            HirExprKind::ArrayLiteral { .. }
+            | HirExprKind::Index { .. }
+            | HirExprKind::ArrayRest { .. } => Err(unsupported("arrays".to_string())),
+            HirExprKind::TupleLiteral { .. } | HirExprKind::TupleIndex { .. } => {
+                Err(unsupported("tuples".to_string()))
+            }
+            HirExprKind::EnumConstruct { .. } => Err(unsupported("enums".to_string())),
+            HirExprKind::NewtypeConstruct { .. } | HirExprKind::NewtypeAccess { .. } => {
+                Err(unsupported("newtypes".to_string()))
+            }
+            HirExprKind::Closure { .. } => Err(unsupported("closures".to_string())),
+        }
+    }
+
+    // ----- names -----
+
+    /// A C name for a new binding of `name`, distinct from every other in the
+    /// function so shadowing never collides: `v_x`, then `v2_x`, `v3_x`, ...
+    fn fresh_name(&mut self, name: &str) -> String {
+        let count = self.declared.entry(name.to_string()).or_insert(0);
+        *count += 1;
+        match *count {
+            1 => format!("v_{name}"),
+            n => format!("v{n}_{name}"),
+        }
+    }
+
+    fn declare(&mut self, name: &str) -> String {
+        let var = self.fresh_name(name);
+        self.bind(name, var.clone());
+        var
+    }
+
+    fn bind(&mut self, name: &str, var: String) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.insert(name.to_string(), var);
+        }
+    }
+
+    fn is_local(&self, name: &str) -> bool {
+        self.scopes.iter().any(|scope| scope.contains_key(name))
+    }
+
+    fn lookup(&self, name: &str) -> CodegenResult<String> {
+        self.scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.get(name).cloned())
+            .ok_or_else(|| CodegenError::UndefinedVariable(name.to_string()))
+    }
+
+    /// A compiler temporary or label name, `t_<what>_<n>`; Neuro bindings are all
+    /// `v`-prefixed, so the two never clash.
+    fn temp(&mut self, what: &str) -> String {
+        let id = self.next_temp;
+        self.next_temp += 1;
+        format!("t_{what}_{id}")
+    }
+
+    fn line(&mut self, text: &str) {
+        let _ = writeln!(self.out, "{}{text}", "    ".repeat(self.indent));
+    }
+}
+
+/// `static T nr_name(T v_a, ...)`. Parameters take the names a fresh function
+/// scope's `declare` gives them.
+fn signature(f: &HirFunction) -> CodegenResult<String> {
+    let mut seen: HashMap<&str, usize> = HashMap::new();
+    let mut params = Vec::with_capacity(f.params.len());
+    for param in &f.params {
+        let count = seen.entry(param.name.as_str()).or_insert(0);
+        *count += 1;
+        let var = match *count {
+            1 => format!("v_{}", param.name),
+            n => format!("v{n}_{}", param.name),
+        };
+        params.push(format!("{} {var}", c_type(&param.ty)?));
+    }
+    let params = if params.is_empty() {
+        "void".to_string()
+    } else {
+        params.join(", ")
+    };
+    Ok(format!(
+        "static {} nr_{}({params})",
+        c_type(&f.return_type)?,
+        f.name
+    ))
+}
+
+fn binary(
+    op: BinaryOp,
+    l: &str,
+    r: &str,
+    operand: &HirType,
+    ty: &HirType,
+) -> CodegenResult<String> {
+    let symbol = match op {
+        BinaryOp::Add => "+",
+        BinaryOp::Subtract => "-",
+        BinaryOp::Multiply => "*",
+        BinaryOp::Divide => "/",
+        BinaryOp::Modulo => "%",
+        BinaryOp::Equal => "==",
+        BinaryOp::NotEqual => "!=",
+        BinaryOp::Less => "<",
+        BinaryOp::Greater => ">",
+        BinaryOp::LessEqual => "<=",
+        BinaryOp::GreaterEqual => ">=",
+        BinaryOp::And => "&&",
+        BinaryOp::Or => "||",
+        BinaryOp::BitAnd => "&",
+        BinaryOp::BitOr => "|",
+        BinaryOp::BitXor => "^",
+        BinaryOp::Shl => "<<",
+        BinaryOp::NullCoalesce => return Err(unsupported("`??`".to_string())),
+    };
+    if op.is_comparison() || matches!(op, BinaryOp::And | BinaryOp::Or) {
+        return Ok(format!("({l} {symbol} {r})"));
+    }
+    let c_ty = c_type(ty)?;
+    match op {
+        BinaryOp::Add | BinaryOp::Subtract | BinaryOp::Multiply | BinaryOp::Shl => {
+            match wrapping_type(ty) {
+                Some(wide) if op == BinaryOp::Shl => Ok(format!("(({c_ty})(({wide}){l} << {r}))")),
+                Some(wide) => Ok(format!("(({c_ty})(({wide}){l} {symbol} ({wide}){r}))")),
+                None => Ok(format!("({l} {symbol} {r})")),
+            }
+        }
+        BinaryOp::Modulo if matches!(operand, HirType::F32 | HirType::F64) => {
+            Err(unsupported("`%` on floating-point values".to_string()))
+        }
+        BinaryOp::Divide if matches!(operand, HirType::F32 | HirType::F64) => {
+            Ok(format!("({l} / {r})"))
+        }
+        _ => Ok(format!("(({c_ty})({l} {symbol} {r}))")),
+    }
+}
+
+fn literal_value(literal: &Literal, ty: &HirType) -> CodegenResult<String> {
+    Ok(match literal {
+        Literal::Integer(value, ..) => match ty {
+            HirType::I64 if *value == i64::MIN => "INT64_MIN".to_string(),
+            HirType::I64 => format!("INT64_C({value})"),
+            HirType::U64 => format!("UINT64_C({})", *value as u64),
+            HirType::U32 => format!("{}u", *value as u32),
+            _ if *value < 0 => format!("({value})"),
+            _ => value.to_string(),
+        },
+        Literal::Float(value, _) => match ty {
+            HirType::F32 => format!("{:?}f", *value as f32),
+            _ => format!("{value:?}"),
+        },
+        Literal::Boolean(value) => value.to_string(),
+        Literal::Char(c) => format!("{}u", u32::from(*c)),
+        Literal::String(_) => return Err(unsupported("strings".to_string())),
+    })
+}
+
+/// Whether `expr` lowers to C statements rather than a C expression.
+fn is_control_flow(expr: &HirExpr) -> bool {
+    matches!(
+        expr.kind,
+        HirExprKind::If { .. }
+            | HirExprKind::Block { .. }
+            | HirExprKind::Unsafe { .. }
+            | HirExprKind::Loop { .. }
+    )
+}
+
+/// `text` without one pair of parentheses enclosing all of it, for positions that
+/// need none: conditions, initializers, arguments, statements.
+fn bare(text: &str) -> &str {
+    let Some(inner) = text.strip_prefix('(').and_then(|t| t.strip_suffix(')')) else {
+        return text;
+    };
+    let mut depth = 0usize;
+    for c in inner.chars() {
+        match c {
+            '(' => depth += 1,
+            ')' if depth == 0 => return text,
+            ')' => depth -= 1,
+            _ => {}
+        }
+    }
+    inner
+}
+
+fn unsupported(what: String) -> CodegenError {
+    CodegenError::Unsupported(what)
+}
+
+fn internal(message: &str) -> CodegenError {
+    CodegenError::InternalError(message.to_string())
+}
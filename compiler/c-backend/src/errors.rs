@@ -0,0 +1,26 @@
+// C emission error definitions
+
+use thiserror::Error;
+
+/// Why a program could not be translated to C.
+#[derive(Debug, Error)]
+pub enum CodegenError {
+    /// The program uses a type the C backend has no representation for.
+    #[error("the C backend does not support the type {0}")]
+    UnsupportedType(String),
+
+    /// The program uses a construct the C backend does not translate.
+    #[error("the C backend does not support {0}")]
+    Unsupported(String),
+
+    /// A variable that is not in scope; lowering only produces bound names, so this
+    /// is an internal error.
+    #[error("internal compiler error: undefined variable {0}")]
+    UndefinedVariable(String),
+
+    #[error("internal compiler error: {0}")]
+    InternalError(String),
+}
+
+/// Result type for C emission.
+pub type CodegenResult<T> = Result<T, CodegenError>;
@@ -0,0 +1,156 @@
+// Feature slice for C source generation.
+// Public API: the `compile()` entry point, which translates typed HIR to a portable
+// C translation unit for targets without LLVM.
+
+mod emit;
+mod errors;
+mod types;
+
+pub use errors::{CodegenError, CodegenResult};
+
+use neuro_hir::HirProgram;
+
+use emit::Emitter;
+
+/// Translate a typed HIR program to C source.
+///
+/// The output is a single C99 translation unit using only `<stdbool.h>` and
+/// `<stdint.h>`, so any hosted C compiler builds it: each Neuro function becomes a
+/// `static` C function `nr_<name>`, and a program `main` gets a C `main` that
+/// returns its value as the exit status.
+///
+/// The C backend covers the scalar core of the language: the integer, float,
+/// `bool`, and `char` types; arithmetic, comparison, logical, and bitwise
+/// operators; casts; locals and assignment; `if`, `while`, `for` ranges, and
+/// `loop` (with labels and `break` values); and direct calls. Anything else
+/// (strings, structs, enums, arrays, references, closures, `match`, built-in
+/// functions) is a [`CodegenError::Unsupported`] or
+/// [`CodegenError::UnsupportedType`] error.
+///
+/// Integer `+`, `-`, `*`, and `<<` wrap on overflow as in an `-O1` and higher
+/// LLVM build; the C backend never emits overflow checks. Division by zero and
+/// over-wide shifts are undefined behaviour, as they are in the LLVM output.
+///
+/// `source_path` names the module in the generated file's header comment.
+///
+/// # Examples
+///
+/// ```
+/// use syntax_parsing::parse;
+/// use hir_lowering::lower_program;
+///
+/// let source = "func add(a: i32, b: i32) -> i32 { return a + b }";
+/// let ast = parse(source).unwrap();
+/// let hir = lower_program(&ast).unwrap();
+/// let c_source = c_backend::compile(&hir, "example.nr").unwrap();
+/// assert!(c_source.contains("static int32_t nr_add(int32_t v_a, int32_t v_b)"));
+/// ```
+pub fn compile(program: &HirProgram, source_path: &str) -> CodegenResult<String> {
+    Emitter::new(program).emit_program(program, source_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process::Command;
+
+    /// Parse and lower `source` to typed HIR. Mirrors the `parse → lower → compile`
+    /// pipeline `neurc` runs (lowering assumes well-typedness). No module constants
+    /// are folded, so array lengths in `source` must be literals.
+    fn lower(source: &str) -> HirProgram {
+        let ast = syntax_parsing::parse(source).expect("parsing failed");
+        hir_lowering::lower_program(&ast).expect("HIR lowering failed")
+    }
+
+    /// Build `c_source` with the system `cc` and run it, returning its exit code.
+    fn run_c(c_source: &str) -> i32 {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let c_path = dir.path().join("program.c");
+        let exe_path = dir.path().join("program");
+        std::fs::write(&c_path, c_source).expect("write C source");
+        let status = Command::new("cc")
+            .args(["-std=c99", "-Wall", "-Werror", "-Wno-unused-function", "-o"])
+            .arg(&exe_path)
+            .arg(&c_path)
+            .status()
+            .expect("run cc");
+        assert!(status.success(), "cc rejected the C source:\n{c_source}");
+        Command::new(&exe_path)
+            .status()
+            .expect("run program")
+            .code()
+            .expect("exit code")
+    }
+
+    #[test]
+    fn add_program_compiles_and_returns_its_sum() {
+        let hir = lower(
+            r#"
+            func add(a: i32, b: i32) -> i32 { return a + b }
+            func main() -> i32 { return add(40, 2) }
+            "#,
+        );
+        let c_source = compile(&hir, "add.nr").expect("C emission failed");
+
+        assert!(c_source.contains("nr_add(40, 2)"), "{c_source}");
+        assert_eq!(run_c(&c_source), 42);
+    }
+
+    #[test]
+    fn control_flow_runs_as_in_neuro() {
+        let hir = lower(
+            r#"
+            func fib(n: i32) -> i32 {
+                if n < 2 { n } else { fib(n - 1) + fib(n - 2) }
+            }
+
+            func main() -> i32 {
+                mut total = 0
+                for i in 0..=5 {
+                    if i == 3 { continue }
+                    total = total + i
+                }
+                mut n = 0
+                while true {
+                    n = n + 1
+                    if n >= 4 { break }
+                }
+                val x = 10
+                val x = x + n
+                val found = loop {
+                    if total > 0 { break fib(x) }
+                }
+                return found % 100
+            }
+            "#,
+        );
+        let c_source = compile(&hir, "flow.nr").expect("C emission failed");
+
+        // total = 0 + 1 + 2 + 4 + 5 = 12, n = 4, fib(14) = 377
+        assert_eq!(run_c(&c_source), 77);
+    }
+
+    #[test]
+    fn signed_overflow_wraps() {
+        let hir = lower(
+            r#"
+            func grow(x: i8) -> i8 { return x + 100i8 }
+            func main() -> i32 { return grow(100i8) as i32 + 100 }
+            "#,
+        );
+        let c_source = compile(&hir, "wrap.nr").expect("C emission failed");
+
+        // 100 + 100 wraps to -56 in i8.
+        assert_eq!(run_c(&c_source), 44);
+    }
+
+    #[test]
+    fn strings_are_unsupported() {
+        let hir = lower(r#"func greet() -> string { return "hi" }"#);
+
+        assert!(matches!(
+            compile(&hir, "greet.nr"),
+            Err(CodegenError::UnsupportedType(_))
+        ));
+    }
+}
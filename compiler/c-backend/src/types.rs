@@ -0,0 +1,41 @@
+// Neuro type → C type mapping
+
+use neuro_hir::HirType;
+
+use crate::errors::{CodegenError, CodegenResult};
+
+/// The C spelling of a Neuro type. Fixed-width integers come from `<stdint.h>` and
+/// `bool` from `<stdbool.h>`; a `char` is its Unicode scalar value.
+pub(crate) fn c_type(ty: &HirType) -> CodegenResult<&'static str> {
+    Ok(match ty {
+        HirType::I8 => "int8_t",
+        HirType::I16 => "int16_t",
+        HirType::I32 => "int32_t",
+        HirType::I64 => "int64_t",
+        HirType::U8 => "uint8_t",
+        HirType::U16 => "uint16_t",
+        HirType::U32 | HirType::Char => "uint32_t",
+        HirType::U64 => "uint64_t",
+        HirType::F32 => "float",
+        HirType::F64 => "double",
+        HirType::Bool => "bool",
+        HirType::Void => "void",
+        other => return Err(CodegenError::UnsupportedType(other.to_string())),
+    })
+}
+
+/// The unsigned type integer `+`, `-`, `*`, negation, and `<<` are computed in, or
+/// `None` for a non-integer type.
+///
+/// Signed overflow is undefined in C, and a narrow unsigned operand promotes to a
+/// signed `int`, so the arithmetic runs on an unsigned type at least as wide as
+/// `int` and the result converts back, wrapping as two's complement.
+pub(crate) fn wrapping_type(ty: &HirType) -> Option<&'static str> {
+    match ty {
+        HirType::I8 | HirType::I16 | HirType::I32 | HirType::U8 | HirType::U16 | HirType::U32 => {
+            Some("uint32_t")
+        }
+        HirType::I64 | HirType::U64 => Some("uint64_t"),
+        _ => None,
+    }
+}
@@ -0,0 +1,206 @@
+//! Typed local bindings read back from the HIR, for tooling that shows the
+//! type the checker gave each `val`/`mut` (`neurc check --explain-types`).
+
+use neuro_hir::{HirExpr, HirExprKind, HirItem, HirProgram, HirStmt, HirType};
+use shared_types::Span;
+
+/// A local binding declared in a function, method, or closure body.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeclaredBinding {
+    pub name: String,
+    pub ty: HirType,
+    /// The span of the whole declaration statement.
+    pub span: Span,
+}
+
+/// Every local binding in `program`, in source order.
+///
+/// Bindings lowering or parsing synthesizes under a reserved `__`-prefixed name,
+/// such as a destructured value's temporary, are left out; a user name with `__`
+/// inside it, like `a__b`, is kept. A generic function lowered once per
+/// instantiation lists its bindings once per distinct type.
+pub fn declared_bindings(program: &HirProgram) -> Vec<DeclaredBinding> {
+    let mut bindings = Vec::new();
+    for item in &program.items {
+        match item {
+            HirItem::Function(func) => collect_block(&func.body, &mut bindings),
+            HirItem::Impl(imp) => {
+                for method in &imp.methods {
+                    collect_block(&method.body, &mut bindings);
+                }
+            }
+            HirItem::Closure(closure) => collect_block(&closure.body, &mut bindings),
+            HirItem::Struct(_) | HirItem::Enum(_) | HirItem::Const(_) | HirItem::Trait(_) => {}
+        }
+    }
+    bindings.retain(|binding| !binding.name.starts_with(RESERVED_PREFIX));
+    bindings.sort_by_key(|binding| (binding.span.start, binding.span.end));
+    bindings.dedup();
+    bindings
+}
+
+/// The prefix of names the compiler generates, which user items may not use.
+const RESERVED_PREFIX: &str = "__";
+
+fn collect_block(stmts: &[HirStmt], out: &mut Vec<DeclaredBinding>) {
+    for stmt in stmts {
+        collect_stmt(stmt, out);
+    }
+}
+
+fn collect_stmt(stmt: &HirStmt, out: &mut Vec<DeclaredBinding>) {
+    match stmt {
+        HirStmt::VarDecl {
+            name,
+            ty,
+            init,
+            span,
+            ..
+        } => {
+            out.push(DeclaredBinding {
+                name: name.clone(),
+                ty: ty.clone(),
+                span: *span,
+            });
+            if let Some(init) = init {
+                collect_expr(init, out);
+            }
+        }
+        HirStmt::If {
+            condition,
+            then_block,
+            else_if_blocks,
+            else_block,
+            ..
+        } => collect_if(condition, then_block, else_if_blocks, else_block, out),
+        HirStmt::While {
+            condition, body, ..
+        } => {
+            collect_expr(condition, out);
+            collect_block(body, out);
+        }
+        HirStmt::ForRange {
+            start, end, body, ..
+        } => {
+            collect_expr(start, out);
+            collect_expr(end, out);
+            collect_block(body, out);
+        }
+        HirStmt::ForEach { iterable, body, .. } => {
+            collect_expr(iterable, out);
+            collect_block(body, out);
+        }
+        HirStmt::Loop { body, .. } => collect_block(body, out),
+        HirStmt::Assignment { value, .. }
+        | HirStmt::FieldAssignment { value, .. }
+        | HirStmt::Const { value, .. } => collect_expr(value, out),
+        HirStmt::DerefAssignment { pointer, value, .. } => {
+            collect_expr(pointer, out);
+            collect_expr(value, out);
+        }
+        HirStmt::IndexAssignment { index, value, .. } => {
+            collect_expr(index, out);
+            collect_expr(value, out);
+        }
+        HirStmt::Return { value, .. } | HirStmt::Break { value, .. } => {
+            if let Some(value) = value {
+                collect_expr(value, out);
+            }
+        }
+        HirStmt::Continue { .. } => {}
+        HirStmt::Expr(expr) => collect_expr(expr, out),
+    }
+}
+
+fn collect_if(
+    condition: &HirExpr,
+    then_block: &[HirStmt],
+    else_if_blocks: &[(HirExpr, Vec<HirStmt>)],
+    else_block: &Option<Vec<HirStmt>>,
+    out: &mut Vec<DeclaredBinding>,
+) {
+    collect_expr(condition, out);
+    collect_block(then_block, out);
+    for (condition, block) in else_if_blocks {
+        collect_expr(condition, out);
+        collect_block(block, out);
+    }
+    if let Some(block) = else_block {
+        collect_block(block, out);
+    }
+}
+
+/// Bindings inside an expression: the blocks of `if`, `match`, `loop`, and block
+/// expressions, wherever they nest. Closure bodies are lifted to their own items.
+fn collect_expr(expr: &HirExpr, out: &mut Vec<DeclaredBinding>) {
+    match &expr.kind {
+        HirExprKind::If {
+            condition,
+            then_block,
+            else_if_blocks,
+            else_block,
+        } => collect_if(condition, then_block, else_if_blocks, else_block, out),
+        HirExprKind::Block { stmts }
+        | HirExprKind::Unsafe { stmts }
+        | HirExprKind::Loop { body: stmts, .. } => collect_block(stmts, out),
+        HirExprKind::Match { scrutinee, arms } => {
+            collect_expr(scrutinee, out);
+            for arm in arms {
+                if let Some(guard) = &arm.guard {
+                    collect_expr(guard, out);
+                }
+                collect_expr(&arm.body, out);
+            }
+        }
+        HirExprKind::Binary { left, right, .. }
+        | HirExprKind::Range {
+            start: left,
+            end: right,
+            ..
+        }
+        | HirExprKind::Index {
+            object: left,
+            index: right,
+        } => {
+            collect_expr(left, out);
+            collect_expr(right, out);
+        }
+        HirExprKind::Unary { operand: inner, .. }
+        | HirExprKind::FieldAccess { object: inner, .. }
+        | HirExprKind::Cast { value: inner }
+        | HirExprKind::Reference { operand: inner, .. }
+        | HirExprKind::Deref { operand: inner }
+        | HirExprKind::DynCoerce { value: inner }
+        | HirExprKind::TupleIndex { object: inner, .. }
+        | HirExprKind::NewtypeConstruct { value: inner, .. }
+        | HirExprKind::NewtypeAccess { object: inner }
+        | HirExprKind::ArrayRest { array: inner, .. } => collect_expr(inner, out),
+        HirExprKind::Call { callee, args } => {
+            collect_expr(callee, out);
+            for arg in args {
+                collect_expr(arg, out);
+            }
+        }
+        HirExprKind::StructLiteral { fields, base, .. } => {
+            for field in fields {
+                collect_expr(&field.value, out);
+            }
+            if let Some(base) = base {
+                collect_expr(base, out);
+            }
+        }
+        HirExprKind::ArrayLiteral { elements }
+        | HirExprKind::TupleLiteral { elements }
+        | HirExprKind::EnumConstruct {
+            payload: elements, ..
+        } => {
+            for element in elements {
+                collect_expr(element, out);
+            }
+        }
+        HirExprKind::Literal(_)
+        | HirExprKind::Variable(_)
+        | HirExprKind::Path { .. }
+        | HirExprKind::Closure { .. } => {}
+    }
+}
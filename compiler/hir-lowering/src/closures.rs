@@ -8,7 +8,7 @@
 
 use std::collections::HashSet;
 
-use ast_types::{ClosureParam, EnumPatternPayload, Expr, Pattern, Stmt};
+use ast_types::{ClosureParam, EnumPatternPayload, Expr, FunctionDef, Pattern, Stmt};
 use neuro_hir::{
     HirCapture, HirClosure, HirExpr, HirExprKind, HirItem, HirParam, HirStmt, HirType,
 };
@@ -98,6 +98,190 @@ impl Lowerer {
         ))
     }
 
+    /// Lower a nested `func` to a capture-free closure item and a binding of its name
+    /// to that closure, so the rest of the block calls it like any function-typed
+    /// local.
+    ///
+    /// The body is lowered with the enclosing scopes and loops set aside: it sees only
+    /// its parameters, itself, and the nested functions visible where it is declared.
+    /// Recursion and calls to those functions work through bindings of their names
+    /// at the top of the lifted body; being capture-free, they need no environment.
+    pub(crate) fn lower_nested_function(
+        &mut self,
+        func: &FunctionDef,
+    ) -> Result<HirStmt, LoweringError> {
+        let mut params = Vec::with_capacity(func.params.len());
+        for param in &func.params {
+            params.push(HirParam {
+                name: param.name.name.clone(),
+                ty: self.resolve_type(&param.ty)?,
+                span: param.span,
+            });
+        }
+        let return_type = match &func.return_type {
+            Some(t) => self.resolve_type(t)?,
+            None => HirType::Void,
+        };
+        let fn_ty = HirType::Function {
+            params: params.iter().map(|p| p.ty.clone()).collect(),
+            ret: Box::new(return_type.clone()),
+        };
+
+        let closure_name = format!("__closure_{}", self.closure_counter);
+        self.closure_counter += 1;
+        let value = HirExpr::new(
+            HirExprKind::Closure {
+                name: closure_name.clone(),
+                captures: Vec::new(),
+            },
+            fn_ty.clone(),
+            func.span,
+        );
+        let mut bound = vec![(func.name.name.clone(), closure_name.clone(), fn_ty.clone())];
+        bound.extend(
+            self.visible_nested_functions()
+                .into_iter()
+                .filter(|(name, ..)| {
+                    *name != func.name.name && params.iter().all(|p| p.name != *name)
+                }),
+        );
+        let mut body: Vec<HirStmt> = bound
+            .iter()
+            .map(|(name, closure, ty)| HirStmt::VarDecl {
+                name: name.clone(),
+                ty: ty.clone(),
+                init: Some(HirExpr::new(
+                    HirExprKind::Closure {
+                        name: closure.clone(),
+                        captures: Vec::new(),
+                    },
+                    ty.clone(),
+                    func.name.span,
+                )),
+                mutable: false,
+                span: func.name.span,
+            })
+            .collect();
+
+        let enclosing_scopes = std::mem::take(&mut self.scopes);
+        let enclosing_nested = std::mem::take(&mut self.nested_functions);
+        let enclosing_loops = std::mem::take(&mut self.loop_stack);
+        self.push_scope();
+        for (name, closure, ty) in bound {
+            self.define_nested_function(name, ty, closure);
+        }
+        for p in &params {
+            self.define(p.name.clone(), p.ty.clone());
+        }
+        let lowered = self.lower_body(&func.body, &return_type);
+        self.scopes = enclosing_scopes;
+        self.nested_functions = enclosing_nested;
+        self.loop_stack = enclosing_loops;
+
+        body.extend(lowered?);
+        self.closure_items.push(HirItem::Closure(HirClosure {
+            name: closure_name.clone(),
+            captures: Vec::new(),
+            params,
+            return_type,
+            body,
+            span: func.span,
+        }));
+
+        self.define_nested_function(func.name.name.clone(), fn_ty.clone(), closure_name);
+        Ok(HirStmt::VarDecl {
+            name: func.name.name.clone(),
+            ty: fn_ty,
+            init: Some(value),
+            mutable: false,
+            span: func.span,
+        })
+    }
+
+    /// Lower a top-level function named as a value to a capture-free closure whose
+    /// body forwards its parameters to the function, so it has the same fat-pointer
+    /// representation and calling convention as any other function value.
+    pub(crate) fn lower_function_value(&mut self, name: &str, span: Span) -> HirExpr {
+        let (params, ret) = self.functions[name].clone();
+        let fn_ty = HirType::Function {
+            params: params.clone(),
+            ret: Box::new(ret.clone()),
+        };
+
+        let closure_name = match self.function_values.get(name) {
+            Some(existing) => existing.clone(),
+            None => {
+                let closure_name = format!("__closure_{}", self.closure_counter);
+                self.closure_counter += 1;
+                let hir_params: Vec<HirParam> = params
+                    .iter()
+                    .enumerate()
+                    .map(|(i, ty)| HirParam {
+                        name: format!("__arg{}", i),
+                        ty: ty.clone(),
+                        span,
+                    })
+                    .collect();
+                let args = hir_params
+                    .iter()
+                    .map(|p| {
+                        HirExpr::new(HirExprKind::Variable(p.name.clone()), p.ty.clone(), span)
+                    })
+                    .collect();
+                let callee =
+                    HirExpr::new(HirExprKind::Variable(name.to_string()), fn_ty.clone(), span);
+                let call = HirExpr::new(
+                    HirExprKind::Call {
+                        callee: Box::new(callee),
+                        args,
+                    },
+                    ret.clone(),
+                    span,
+                );
+                self.closure_items.push(HirItem::Closure(HirClosure {
+                    name: closure_name.clone(),
+                    captures: Vec::new(),
+                    params: hir_params,
+                    return_type: ret,
+                    body: vec![HirStmt::Expr(call)],
+                    span,
+                }));
+                self.function_values
+                    .insert(name.to_string(), closure_name.clone());
+                closure_name
+            }
+        };
+
+        HirExpr::new(
+            HirExprKind::Closure {
+                name: closure_name,
+                captures: Vec::new(),
+            },
+            fn_ty,
+            span,
+        )
+    }
+
+    /// The nested functions visible from the current scope as `(name, lifted closure,
+    /// type)`, sorted by name. A name whose innermost binding is not a nested
+    /// function is left out.
+    fn visible_nested_functions(&self) -> Vec<(String, String, HirType)> {
+        let mut seen = HashSet::new();
+        let mut visible = Vec::new();
+        for (scope, nested) in self.scopes.iter().zip(&self.nested_functions).rev() {
+            for (name, ty) in scope {
+                if !seen.insert(name.as_str()) {
+                    continue;
+                }
+                if let Some(closure) = nested.get(name) {
+                    visible.push((name.clone(), closure.clone(), ty.clone()));
+                }
+            }
+        }
+        visible.sort_by(|a, b| a.0.cmp(&b.0));
+        visible
+    }
+
     /// Compute the ordered, de-duplicated capture list: free variables of the body
     /// (excluding names bound inside it or by the parameters) that resolve to an
     /// enclosing local binding, paired with that binding's type.
@@ -167,12 +351,36 @@ fn collect_stmt(stmt: &Stmt, fv: &mut FreeVars) {
                 collect_block(block, fv);
             }
         }
+        Stmt::IfLet {
+            pattern,
+            scrutinee,
+            then_block,
+            else_block,
+            ..
+        } => {
+            collect_expr(scrutinee, fv);
+            collect_pattern_bindings(pattern, fv);
+            collect_block(then_block, fv);
+            if let Some(block) = else_block {
+                collect_block(block, fv);
+            }
+        }
         Stmt::While {
             condition, body, ..
         } => {
             collect_expr(condition, fv);
             collect_block(body, fv);
         }
+        Stmt::WhileLet {
+            pattern,
+            scrutinee,
+            body,
+            ..
+        } => {
+            collect_expr(scrutinee, fv);
+            collect_pattern_bindings(pattern, fv);
+            collect_block(body, fv);
+        }
         Stmt::ForRange {
             iterator,
             start,
@@ -195,7 +403,7 @@ fn collect_stmt(stmt: &Stmt, fv: &mut FreeVars) {
             fv.bound.insert(iterator.name.clone());
             collect_block(body, fv);
         }
-        Stmt::Loop { body, .. } => collect_block(body, fv),
+        Stmt::Loop { body, .. } | Stmt::Block(body, _) => collect_block(body, fv),
         Stmt::Break { value, .. } => {
             if let Some(value) = value {
                 collect_expr(value, fv);
@@ -224,6 +432,11 @@ fn collect_stmt(stmt: &Stmt, fv: &mut FreeVars) {
             collect_expr(value, fv);
             fv.bound.insert(name.name.clone());
         }
+        // A nested function captures nothing, so its body contributes no reads; it
+        // only binds its name.
+        Stmt::Func(func) => {
+            fv.bound.insert(func.name.name.clone());
+        }
         Stmt::Expr(expr) => collect_expr(expr, fv),
     }
 }
@@ -11,6 +11,9 @@ use crate::{is_full_float, is_integer, peels_to_string, LoopCtx, Lowerer, Loweri
 /// call takes on whatever type its context demands.
 const PANIC_BUILTINS: &[&str] = &["panic", "assert", "unreachable"];
 
+/// The identity builtin whose argument the optimizer must treat as observed.
+const BLACK_BOX_BUILTIN: &str = "black_box";
+
 /// The deep-copy method shared by `string` and `Clone`-deriving structs.
 const CLONE_METHOD: &str = "clone";
 
@@ -70,6 +73,9 @@ impl Lowerer {
                     ty,
                     ident.span,
                 )),
+                None if self.free_functions.contains(&ident.name) => {
+                    Ok(self.lower_function_value(&ident.name, ident.span))
+                }
                 None => Err(LoweringError::UnresolvedBinding {
                     name: ident.name.clone(),
                 }),
@@ -396,17 +402,26 @@ impl Lowerer {
             } => {
                 let condition = self.lower_expr(condition, Some(&HirType::Bool))?;
                 let (then_stmts, then_ty) = self.lower_block_value(then_block)?;
+                let mut arm_types = vec![then_ty];
                 let mut elifs = Vec::with_capacity(else_if_blocks.len());
                 for (cond, block) in else_if_blocks {
                     let cond = self.lower_expr(cond, Some(&HirType::Bool))?;
-                    let (block, _) = self.lower_block_value(block)?;
+                    let (block, block_ty) = self.lower_block_value(block)?;
+                    arm_types.push(block_ty);
                     elifs.push((cond, block));
                 }
-                // An `if` is a value only with an `else`; otherwise it yields unit.
+                // An `if` is a value only with an `else`; otherwise it yields unit. A
+                // diverging arm lowers as `void`, so the value type is that of the first
+                // arm that produces one (the checker made every such arm agree).
                 let (else_block, ty) = match else_block {
                     Some(block) => {
-                        let (block, _) = self.lower_block_value(block)?;
-                        (Some(block), then_ty)
+                        let (block, block_ty) = self.lower_block_value(block)?;
+                        arm_types.push(block_ty);
+                        let ty = arm_types
+                            .into_iter()
+                            .find(|ty| !matches!(ty, HirType::Void))
+                            .unwrap_or(HirType::Void);
+                        (Some(block), ty)
                     }
                     None => (None, HirType::Void),
                 };
@@ -466,7 +481,7 @@ impl Lowerer {
     /// Lower a `match` expression into the fully-resolved HIR node: each arm's
     /// patterns become refutable tests, its bindings resolve to payload slots or the
     /// whole scrutinee, and the guard/body lower with the bindings in scope. The match
-    /// type is the first arm's body type, mirroring the checker.
+    /// type is the first value-producing arm's body type, mirroring the checker.
     fn lower_match(
         &mut self,
         scrutinee: &Expr,
@@ -482,7 +497,7 @@ impl Lowerer {
         let mut hint: Option<HirType> = expected.cloned();
         let mut hir_arms = Vec::with_capacity(arms.len());
         let mut result_ty = HirType::Void;
-        for (i, arm) in arms.iter().enumerate() {
+        for arm in arms {
             let mut tests = Vec::with_capacity(arm.patterns.len());
             for pat in &arm.patterns {
                 tests.push(self.pattern_test(pat)?);
@@ -505,11 +520,12 @@ impl Lowerer {
             let body = self.lower_expr(&arm.body, hint.as_ref())?;
             self.pop_scope();
 
-            if hint.is_none() {
-                hint = Some(body.ty.clone());
-            }
-            if i == 0 {
-                result_ty = body.ty.clone();
+            // A diverging arm lowers as `void`; the first arm with a value sets the type.
+            if !matches!(body.ty, HirType::Void) {
+                hint.get_or_insert_with(|| body.ty.clone());
+                if matches!(result_ty, HirType::Void) {
+                    result_ty = body.ty.clone();
+                }
             }
             hir_arms.push(neuro_hir::HirMatchArm {
                 tests,
@@ -529,6 +545,63 @@ impl Lowerer {
         ))
     }
 
+    /// Lower the `let pattern = scrutinee` head of an `if let` / `while let` to
+    /// a unit `match` with two arms: `pattern` runs `then_block` with its
+    /// bindings in scope, and a wildcard runs the statements `else_arm` lowers.
+    pub(crate) fn lower_let_match(
+        &mut self,
+        pattern: &ast_types::Pattern,
+        scrutinee: &Expr,
+        then_block: &[ast_types::Stmt],
+        else_arm: impl FnOnce(&mut Self) -> Result<Vec<HirStmt>, LoweringError>,
+        span: shared_types::Span,
+    ) -> Result<HirExpr, LoweringError> {
+        let scrutinee = self.lower_expr(scrutinee, None)?;
+        let test = self.pattern_test(pattern)?;
+        let bindings = self.pattern_bindings(pattern, &scrutinee.ty)?;
+
+        self.push_scope();
+        for b in &bindings {
+            self.define(b.name.clone(), b.ty.clone());
+        }
+        let then_stmts = self.lower_stmt_block(then_block);
+        self.pop_scope();
+        let then_stmts = then_stmts?;
+        let else_stmts = else_arm(self)?;
+
+        let arms = vec![
+            neuro_hir::HirMatchArm {
+                tests: vec![test],
+                bindings,
+                guard: None,
+                body: HirExpr::new(
+                    HirExprKind::Block { stmts: then_stmts },
+                    HirType::Void,
+                    span,
+                ),
+            },
+            neuro_hir::HirMatchArm {
+                tests: vec![neuro_hir::HirMatchTest::Wildcard],
+                bindings: Vec::new(),
+                guard: None,
+                body: HirExpr::new(
+                    HirExprKind::Block { stmts: else_stmts },
+                    HirType::Void,
+                    span,
+                ),
+            },
+        ];
+
+        Ok(HirExpr::new(
+            HirExprKind::Match {
+                scrutinee: Box::new(scrutinee),
+                arms,
+            },
+            HirType::Void,
+            span,
+        ))
+    }
+
     /// Build the refutable [`HirMatchTest`] for one pattern.
     fn pattern_test(
         &self,
@@ -662,12 +735,25 @@ impl Lowerer {
             Expr::Path {
                 type_name, member, ..
             } => self.lower_assoc_call(&type_name.name, &member.name, args, span),
-            other => Err(LoweringError::Malformed {
-                detail: format!(
-                    "call of non-callable expression {:?}",
-                    std::mem::discriminant(other)
-                ),
-            }),
+            // Any other callee evaluates to a function value and is called
+            // indirectly through it, e.g. `make_adder()(1)`.
+            other => {
+                let callee = self.lower_expr(other, None)?;
+                let HirType::Function { params, ret } = callee.ty.clone() else {
+                    return Err(LoweringError::Malformed {
+                        detail: format!("call of non-function value of type {}", callee.ty),
+                    });
+                };
+                let args = self.lower_args(args, &params)?;
+                Ok(HirExpr::new(
+                    HirExprKind::Call {
+                        callee: Box::new(callee),
+                        args,
+                    },
+                    *ret,
+                    span,
+                ))
+            }
         }
     }
 
@@ -765,6 +851,28 @@ impl Lowerer {
             ));
         }
 
+        if name == BLACK_BOX_BUILTIN {
+            // Identity: the call takes its argument's type.
+            let args = self.lower_args(args, &[])?;
+            let ty = args.first().map_or(HirType::Void, |arg| arg.ty.clone());
+            let callee = HirExpr::new(
+                HirExprKind::Variable(name.to_string()),
+                HirType::Function {
+                    params: vec![ty.clone()],
+                    ret: Box::new(ty.clone()),
+                },
+                span,
+            );
+            return Ok(HirExpr::new(
+                HirExprKind::Call {
+                    callee: Box::new(callee),
+                    args,
+                },
+                ty,
+                span,
+            ));
+        }
+
         if PANIC_BUILTINS.contains(&name) {
             let param = match name {
                 "panic" => vec![HirType::String],
@@ -1487,7 +1595,7 @@ impl Lowerer {
     /// returning the lowered statements and the block's value type — the type of the
     /// trailing expression, or `void`. The tail is typed with no contextual hint,
     /// matching the checker's `check_block_expr_type`.
-    fn lower_block_value(
+    pub(crate) fn lower_block_value(
         &mut self,
         stmts: &[ast_types::Stmt],
     ) -> Result<(Vec<HirStmt>, HirType), LoweringError> {
@@ -14,6 +14,18 @@ use crate::{EnumVariantData, Lowerer, LoweringError, MonoInstance};
 const DERIVE_ATTRIBUTE: &str = "derive";
 const COPY_TRAIT: &str = "Copy";
 const CLONE_TRAIT: &str = "Clone";
+/// The `@optimize(N)` attribute name; its level is handed to the backend.
+const OPTIMIZE_ATTRIBUTE: &str = "optimize";
+
+/// The level of a function's `@optimize(N)` attribute. The checker has validated
+/// that there is at most one and that `N` is 0 to 3.
+fn optimize_level(func: &FunctionDef) -> Option<u8> {
+    func.attributes
+        .iter()
+        .find(|attr| attr.name.name == OPTIMIZE_ATTRIBUTE)
+        .and_then(|attr| attr.args.first())
+        .and_then(|level| level.name.parse().ok())
+}
 
 impl Lowerer {
     /// Build the global symbol tables (structs, methods, functions, constants) in a
@@ -467,6 +479,7 @@ impl Lowerer {
             params.push(self.resolve_type(&param.ty)?);
         }
         let ret = self.declared_return_type(&func.return_type, &func.body)?;
+        self.free_functions.insert(func.name.name.clone());
         self.functions.insert(func.name.name.clone(), (params, ret));
         Ok(())
     }
@@ -658,6 +671,7 @@ impl Lowerer {
             params,
             return_type,
             body,
+            optimize: optimize_level(&template),
             span: template.span,
         })
     }
@@ -751,6 +765,7 @@ impl Lowerer {
             params,
             return_type,
             body,
+            optimize: optimize_level(func),
             span: func.span,
         })
     }
@@ -21,13 +21,17 @@
 //!
 //! # Entry point
 //!
-//! [`lower_program`] is the only public function. It returns a [`neuro_hir::HirProgram`].
+//! [`lower_program`] is the main public function. It returns a [`neuro_hir::HirProgram`].
+//! [`lower_program_with_consts`] also takes the folded values of the module
+//! constants that array lengths name.
+//! [`declared_bindings`] reads the typed local bindings back out of one.
 
 use std::collections::{HashMap, HashSet};
 
 use ast_types::Item;
 use neuro_hir::{HirProgram, HirType};
 
+mod bindings;
 mod closures;
 mod expressions;
 mod items;
@@ -38,6 +42,8 @@ mod types;
 #[cfg(test)]
 mod tests;
 
+pub use bindings::{declared_bindings, DeclaredBinding};
+
 /// A failure encountered while lowering a type-checked program to HIR.
 ///
 /// Every variant denotes a state the type checker should have already rejected;
@@ -93,6 +99,9 @@ struct LoopCtx {
 struct Lowerer {
     /// Free functions and mangled methods → (parameter types, return type).
     functions: HashMap<String, (Vec<HirType>, HirType)>,
+    /// Names of the non-generic top-level functions in `functions` — the only ones
+    /// that may be lowered as function values.
+    free_functions: HashSet<String>,
     /// Struct name → ordered `(field_name, field_type)` list.
     structs: HashMap<String, Vec<(String, HirType)>>,
     /// Enum name → ordered variants. Each variant carries its name and ordered
@@ -120,6 +129,10 @@ struct Lowerer {
     constants: HashMap<String, HirType>,
     /// Lexical scope stack of `binding name → type`, innermost last.
     scopes: Vec<HashMap<String, HirType>>,
+    /// Parallel to [`Self::scopes`]: the bindings at each level that are nested
+    /// functions, mapped to their lifted closure. A later binding of the same name
+    /// at that level removes the entry.
+    nested_functions: Vec<HashMap<String, String>>,
     /// Active loops, innermost last.
     loop_stack: Vec<LoopCtx>,
     /// The current function/method's resolved return type (for `return` typing).
@@ -152,6 +165,9 @@ struct Lowerer {
     /// reference to one lowers to a correctly-typed integer literal. Parallel to
     /// [`Self::const_subst`].
     const_types: HashMap<String, HirType>,
+    /// Folded values of the module-level integer constants, for array lengths that
+    /// name one. A const parameter in [`Self::const_subst`] shadows an entry.
+    const_lengths: HashMap<String, u64>,
     /// Monomorphization worklist: instances discovered but not yet lowered.
     mono_pending: Vec<MonoInstance>,
     /// Mangled names already queued or emitted, so each instance is produced once.
@@ -159,13 +175,16 @@ struct Lowerer {
     /// Concrete instance functions produced by monomorphization, appended to the
     /// program after the ordinary items.
     mono_items: Vec<neuro_hir::HirItem>,
-    /// Lifted closure bodies discovered while lowering expressions, appended to the
+    /// Lifted closure bodies and nested functions discovered while lowering, appended to the
     /// program as top-level [`neuro_hir::HirItem::Closure`] items.
     closure_items: Vec<neuro_hir::HirItem>,
     /// Monotonic counter that names each lifted closure uniquely (`__closure_N`).
     /// The `__` prefix is a reserved generated-symbol marker the checker forbids in
     /// user names, so a lifted closure can never collide with a user function.
     closure_counter: usize,
+    /// Top-level functions named as values, mapped to the lifted closure that
+    /// forwards to each, so a function used as a value many times is wrapped once.
+    function_values: HashMap<String, String>,
 }
 
 /// One trait method's lowering-visible signature, in declaration order.
@@ -221,14 +240,38 @@ struct MonoStruct {
 ///
 /// The `items` must come from a program that passed `semantic_analysis::type_check`;
 /// lowering assumes well-typedness and re-derives each expression's resolved type.
+/// No module-level constant values are supplied, so an array length naming one
+/// (`[i32; SIZE]`) cannot resolve: lower such a program with
+/// [`lower_program_with_consts`].
 ///
 /// # Errors
 ///
 /// Returns a [`LoweringError`] if an AST shape the type checker should have rejected
-/// reaches lowering (an unresolved type, binding, or call target). A well-typed
-/// program never triggers these.
+/// reaches lowering (an unresolved type, binding, or call target), or
+/// [`LoweringError::UnresolvedType`] for an array length naming a module constant.
+/// A well-typed program whose array lengths are literals or const generic
+/// parameters never triggers these.
 pub fn lower_program(items: &[Item]) -> Result<HirProgram, LoweringError> {
+    lower_program_with_consts(items, &HashMap::new())
+}
+
+/// Lower a type-checked program whose array lengths may name module-level
+/// constants. `consts` holds each such constant's value, as
+/// `semantic_analysis::const_array_lengths` folds them; lowering does not
+/// evaluate constant expressions itself. This is the entry point for any
+/// well-typed program.
+///
+/// # Errors
+///
+/// Returns a [`LoweringError`] if an AST shape the type checker should have rejected
+/// reaches lowering. A well-typed program, lowered with the values
+/// `const_array_lengths` returns for it, never triggers these.
+pub fn lower_program_with_consts(
+    items: &[Item],
+    consts: &HashMap<String, u64>,
+) -> Result<HirProgram, LoweringError> {
     let mut lowerer = Lowerer::new();
+    lowerer.const_lengths = consts.clone();
     lowerer.register_items(items)?;
     lowerer.lower_program(items)
 }
@@ -237,6 +280,7 @@ impl Lowerer {
     fn new() -> Self {
         Self {
             functions: HashMap::new(),
+            free_functions: HashSet::new(),
             structs: HashMap::new(),
             enums: HashMap::new(),
             newtypes: HashMap::new(),
@@ -247,6 +291,7 @@ impl Lowerer {
             operator_unary_impls: HashMap::new(),
             constants: HashMap::new(),
             scopes: Vec::new(),
+            nested_functions: Vec::new(),
             loop_stack: Vec::new(),
             current_return: HirType::Void,
             generic_templates: HashMap::new(),
@@ -257,31 +302,47 @@ impl Lowerer {
             type_subst: HashMap::new(),
             const_subst: HashMap::new(),
             const_types: HashMap::new(),
+            const_lengths: HashMap::new(),
             mono_pending: Vec::new(),
             mono_seen: HashSet::new(),
             mono_items: Vec::new(),
             closure_items: Vec::new(),
             closure_counter: 0,
+            function_values: HashMap::new(),
         }
     }
 
     fn push_scope(&mut self) {
         self.scopes.push(HashMap::new());
+        self.nested_functions.push(HashMap::new());
     }
 
     fn pop_scope(&mut self) {
         self.scopes.pop();
+        self.nested_functions.pop();
     }
 
     /// Define a binding in the innermost scope. A missing scope is a lowering bug
     /// (every body opens a scope first), so the define is silently dropped rather
     /// than panicking — the subsequent lookup would surface it as an error.
     fn define(&mut self, name: String, ty: HirType) {
+        if let Some(nested) = self.nested_functions.last_mut() {
+            nested.remove(&name);
+        }
         if let Some(scope) = self.scopes.last_mut() {
             scope.insert(name, ty);
         }
     }
 
+    /// Define a nested function's binding in the innermost scope, recording the
+    /// lifted closure it names so a later nested function can bind it too.
+    fn define_nested_function(&mut self, name: String, ty: HirType, closure: String) {
+        self.define(name.clone(), ty);
+        if let Some(nested) = self.nested_functions.last_mut() {
+            nested.insert(name, closure);
+        }
+    }
+
     /// Resolve a binding's type: innermost scope outward, then module constants —
     /// matching the checker's "locals shadow constants" precedence.
     fn lookup(&self, name: &str) -> Option<HirType> {
@@ -383,16 +444,18 @@ fn unify_ast_hir(
 }
 
 /// Resolve a fixed-size array length annotation to a concrete value. A
-/// literal is taken as-is; a `const`-parameter length is looked up in `const_subst`
-/// (populated while a monomorphized instance body is lowered).
+/// literal is taken as-is; a named length is looked up in `const_subst` (populated
+/// while a monomorphized instance body is lowered), then in `const_lengths`.
 fn resolve_array_size(
     size: &ast_types::ArraySize,
     const_subst: &HashMap<String, u64>,
+    const_lengths: &HashMap<String, u64>,
 ) -> Result<usize, LoweringError> {
     match size {
         ast_types::ArraySize::Literal(n) => Ok(*n as usize),
         ast_types::ArraySize::Const(id) => const_subst
             .get(&id.name)
+            .or_else(|| const_lengths.get(&id.name))
             .map(|v| *v as usize)
             .ok_or_else(|| LoweringError::UnresolvedType {
                 name: format!("const array length '{}'", id.name),
@@ -1,7 +1,7 @@
 //! Statement lowering.
 
 use ast_types::Stmt;
-use neuro_hir::{HirStmt, HirType};
+use neuro_hir::{HirExpr, HirExprKind, HirStmt, HirType};
 
 use crate::{LoopCtx, Lowerer, LoweringError};
 
@@ -63,6 +63,8 @@ impl Lowerer {
                 })
             }
 
+            Stmt::Func(func) => self.lower_nested_function(func),
+
             Stmt::Return { value, span } => {
                 let value = match value {
                     Some(expr) => {
@@ -101,6 +103,59 @@ impl Lowerer {
                 })
             }
 
+            // `if let P = e { A } else { B }` is a unit `match e { P => A, _ => B }`.
+            Stmt::IfLet {
+                pattern,
+                scrutinee,
+                then_block,
+                else_block,
+                span,
+            } => {
+                let matched = self.lower_let_match(
+                    pattern,
+                    scrutinee,
+                    then_block,
+                    |lo| match else_block {
+                        Some(block) => lo.lower_stmt_block(block),
+                        None => Ok(Vec::new()),
+                    },
+                    *span,
+                )?;
+                Ok(HirStmt::Expr(matched))
+            }
+
+            // `while let P = e { body }` is `loop { match e { P => body, _ => break } }`.
+            // The synthesized `break` is unlabeled, so it exits this loop.
+            Stmt::WhileLet {
+                label,
+                pattern,
+                scrutinee,
+                body,
+                span,
+            } => {
+                let body = self.lower_loop_body_with(label, false, |lo| {
+                    let matched = lo.lower_let_match(
+                        pattern,
+                        scrutinee,
+                        body,
+                        |_| {
+                            Ok(vec![HirStmt::Break {
+                                label: None,
+                                value: None,
+                                span: *span,
+                            }])
+                        },
+                        *span,
+                    )?;
+                    Ok(vec![HirStmt::Expr(matched)])
+                })?;
+                Ok(HirStmt::Loop {
+                    label: label.as_ref().map(|l| l.name.clone()),
+                    body,
+                    span: *span,
+                })
+            }
+
             Stmt::While {
                 label,
                 condition,
@@ -274,6 +329,16 @@ impl Lowerer {
                 })
             }
 
+            // Lowered like a bare block expression whose value is discarded.
+            Stmt::Block(stmts, span) => {
+                let (stmts, ty) = self.lower_block_value(stmts)?;
+                Ok(HirStmt::Expr(HirExpr::new(
+                    HirExprKind::Block { stmts },
+                    ty,
+                    *span,
+                )))
+            }
+
             Stmt::Expr(expr) => Ok(HirStmt::Expr(self.lower_expr(expr, None)?)),
         }
     }
@@ -1,6 +1,6 @@
 //! Unit tests: lower representative programs and assert on the re-derived HIR types.
 
-use crate::{lower_program, LoweringError};
+use crate::{lower_program, lower_program_with_consts, LoweringError};
 use neuro_hir::{HirExpr, HirExprKind, HirItem, HirProgram, HirStmt, HirType};
 
 /// Parse and lower `src`, expecting success.
@@ -153,6 +153,54 @@ fn array_literal_index_and_len() {
     assert_eq!(binding_init(body, "n").ty, HirType::U64);
 }
 
+#[test]
+fn array_length_naming_a_const_uses_its_folded_value() {
+    let ast = syntax_parsing::parse(
+        "const SIZE: i32 = 1 + 2\nfunc main() -> i32 { val arr: [i32; SIZE] = [1, 2, 3]\n 0 }",
+    )
+    .expect("source should parse");
+    let consts = std::collections::HashMap::from([("SIZE".to_string(), 3)]);
+    let program = lower_program_with_consts(&ast, &consts).expect("program should lower");
+    assert_eq!(
+        binding_init(function_body(&program, "main"), "arr").ty,
+        HirType::Array {
+            element: Box::new(HirType::I32),
+            size: 3,
+        }
+    );
+}
+
+#[test]
+fn array_length_naming_a_const_needs_its_value() {
+    // `lower_program` supplies no constant values, so the length cannot resolve.
+    let ast = syntax_parsing::parse(
+        "const SIZE: i32 = 3\nfunc main() -> i32 { val arr: [i32; SIZE] = [1, 2, 3]\n 0 }",
+    )
+    .expect("source should parse");
+    assert!(matches!(
+        lower_program(&ast),
+        Err(LoweringError::UnresolvedType { .. })
+    ));
+}
+
+#[test]
+fn black_box_call_takes_its_argument_type() {
+    let program = lower(
+        "func work() -> i64 { return 9 }\n\
+         func main() -> i32 {\n\
+           val kept = black_box(work())\n\
+           0\n\
+         }",
+    );
+    let init = binding_init(function_body(&program, "main"), "kept");
+    assert_eq!(init.ty, HirType::I64);
+    assert!(matches!(
+        &init.kind,
+        HirExprKind::Call { callee, .. }
+            if matches!(&callee.kind, HirExprKind::Variable(name) if name == "black_box")
+    ));
+}
+
 #[test]
 fn if_expression_and_loop_value_types() {
     let program = lower(
@@ -168,6 +216,21 @@ fn if_expression_and_loop_value_types() {
     assert_eq!(binding_init(body, "looped").ty, HirType::I64);
 }
 
+#[test]
+fn diverging_arm_defers_to_the_value_arm() {
+    let program = lower(
+        "func main() -> i32 {\n\
+           val a: i64 = 1\n\
+           val early = if a < 0i64 { return 1 } else { 2i64 }\n\
+           val matched = match a { 0 => { return 2 }, _ => 3u8 }\n\
+           0\n\
+         }",
+    );
+    let body = function_body(&program, "main");
+    assert_eq!(binding_init(body, "early").ty, HirType::I64);
+    assert_eq!(binding_init(body, "matched").ty, HirType::U8);
+}
+
 #[test]
 fn trailing_expression_typed_against_return_type() {
     // The implicit return `42` is typed as the declared i64, not the default i32.
@@ -190,6 +253,18 @@ fn unresolved_binding_is_an_error_not_a_panic() {
     ));
 }
 
+#[test]
+fn mangled_method_name_does_not_lower_as_a_function_value() {
+    let ast = syntax_parsing::parse(
+        "struct P { x: i32 }\n\nimpl P {\n    func get(&self) -> i32 { self.x }\n}\n\nfunc main() -> i32 { val f = P__get\n 0 }",
+    )
+    .expect("source parses");
+    assert!(matches!(
+        lower_program(&ast),
+        Err(LoweringError::UnresolvedBinding { name }) if name == "P__get"
+    ));
+}
+
 #[test]
 fn array_rest_remainder_is_sized_subarray() {
     // `val [a, ..rest] = arr` lowers `rest` to an ArrayRest holding the tail.
@@ -323,6 +398,67 @@ func main() -> i32 { area(Shape::Unit) + classify(1) }
     assert!(matches!(arms[2].tests[0], HirMatchTest::Wildcard));
 }
 
+#[test]
+fn if_let_and_while_let_lower_to_unit_matches() {
+    use neuro_hir::HirMatchTest;
+
+    let program = lower(
+        r#"
+enum Step { Next(i32), Done }
+func step(n: i32) -> Step { Step::Done }
+func main() -> i32 {
+    mut last: i32 = 0
+    if let Step::Next(n) = step(0) {
+        last = n
+    }
+    while let Step::Next(n) = step(last) {
+        last = n
+    }
+    last
+}
+"#,
+    );
+
+    let body = function_body(&program, "main");
+    // `if let` is a unit match: the pattern arm binds `n`, a wildcard arm follows.
+    let HirStmt::Expr(m) = &body[1] else {
+        panic!("if let should lower to a match expression statement");
+    };
+    let HirExprKind::Match { arms, .. } = &m.kind else {
+        panic!("expected a match expression");
+    };
+    assert_eq!(m.ty, HirType::Void);
+    assert_eq!(arms.len(), 2);
+    assert!(matches!(arms[0].tests[0], HirMatchTest::Tag { tag: 0 }));
+    assert_eq!(arms[0].bindings[0].name, "n");
+    assert!(matches!(arms[1].tests[0], HirMatchTest::Wildcard));
+
+    // `while let` is a `loop` whose match falls back to an unlabeled `break`.
+    let HirStmt::Loop {
+        body: loop_body, ..
+    } = &body[2]
+    else {
+        panic!("while let should lower to a loop");
+    };
+    let [HirStmt::Expr(m)] = loop_body.as_slice() else {
+        panic!("loop body should be a single match statement");
+    };
+    let HirExprKind::Match { arms, .. } = &m.kind else {
+        panic!("expected a match expression");
+    };
+    let HirExprKind::Block { stmts } = &arms[1].body.kind else {
+        panic!("fallback arm should be a block");
+    };
+    assert!(matches!(
+        stmts.as_slice(),
+        [HirStmt::Break {
+            label: None,
+            value: None,
+            ..
+        }]
+    ));
+}
+
 #[test]
 fn newtype_construction_lowers_to_transparent_wrapper() {
     // `Meters(7)` becomes a NewtypeConstruct whose type is the newtype and whose
@@ -891,3 +1027,201 @@ fn closure_lowers_to_value_and_lifted_item() {
     assert_eq!(closure.captures.len(), 1);
     assert_eq!(closure.captures[0].name, "base");
 }
+
+#[test]
+fn nested_function_lowers_to_capture_free_closure() {
+    let program = lower(
+        "func main() -> i32 {\n    func fact(n: i32) -> i32 {\n        if n <= 1 { return 1 }\n        return n * fact(n - 1)\n    }\n    return fact(5)\n}",
+    );
+    let body = function_body(&program, "main");
+
+    // The nested function becomes an immutable function-typed binding of its name.
+    let init = binding_init(body, "fact");
+    let (name, captures) = match &init.kind {
+        HirExprKind::Closure { name, captures } => (name, captures),
+        other => panic!("expected a closure value, got {:?}", other),
+    };
+    assert!(captures.is_empty());
+
+    let closure = program
+        .items
+        .iter()
+        .find_map(|item| match item {
+            HirItem::Closure(c) if &c.name == name => Some(c),
+            _ => None,
+        })
+        .expect("the nested function should be lifted");
+    assert!(closure.captures.is_empty());
+    assert_eq!(closure.return_type, HirType::I32);
+    // Its body re-binds its own name first so the recursive call resolves.
+    assert!(matches!(
+        closure.body.first(),
+        Some(HirStmt::VarDecl { name, .. }) if name == "fact"
+    ));
+}
+
+#[test]
+fn nested_function_binds_the_siblings_it_can_call() {
+    let program = lower(
+        "func main() -> i32 {\n    func one() -> i32 { 1 }\n    func two() -> i32 { one() + one() }\n    two()\n}",
+    );
+    let body = function_body(&program, "main");
+    let closure_of = |binding: &str| match &binding_init(body, binding).kind {
+        HirExprKind::Closure { name, .. } => name.clone(),
+        other => panic!("expected a closure value, got {:?}", other),
+    };
+    let (one, two) = (closure_of("one"), closure_of("two"));
+
+    let lifted = program
+        .items
+        .iter()
+        .find_map(|item| match item {
+            HirItem::Closure(c) if c.name == two => Some(c),
+            _ => None,
+        })
+        .expect("`two` should be lifted");
+    // `two` binds itself, then `one` to the same capture-free closure as `main` does.
+    let bound: Vec<(&str, &str)> = lifted
+        .body
+        .iter()
+        .map_while(|stmt| match stmt {
+            HirStmt::VarDecl {
+                name,
+                init: Some(init),
+                ..
+            } => match &init.kind {
+                HirExprKind::Closure { name: closure, .. } => {
+                    Some((name.as_str(), closure.as_str()))
+                }
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect();
+    assert_eq!(bound, [("two", two.as_str()), ("one", one.as_str())]);
+}
+
+#[test]
+fn function_value_lowers_to_forwarding_closure() {
+    let program = lower(
+        "func add(a: i32, b: i32) -> i32 { a + b }\n\nfunc main() -> i32 {\n    val f = add\n    val g = add\n    f(1, 2) + g(3, 4)\n}",
+    );
+    let body = function_body(&program, "main");
+
+    // Both uses of `add` as a value share one capture-free forwarding closure.
+    let closure_name = |binding: &str| match &binding_init(body, binding).kind {
+        HirExprKind::Closure { name, captures } => {
+            assert!(captures.is_empty());
+            name.clone()
+        }
+        other => panic!("expected a closure value, got {:?}", other),
+    };
+    let name = closure_name("f");
+    assert_eq!(closure_name("g"), name);
+
+    let forwarders: Vec<_> = program
+        .items
+        .iter()
+        .filter_map(|item| match item {
+            HirItem::Closure(c) if c.name == name => Some(c),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(forwarders.len(), 1);
+    let closure = forwarders[0];
+    assert_eq!(closure.params.len(), 2);
+    assert_eq!(closure.return_type, HirType::I32);
+    match closure.body.as_slice() {
+        [HirStmt::Expr(HirExpr {
+            kind: HirExprKind::Call { callee, args },
+            ..
+        })] => {
+            assert!(matches!(&callee.kind, HirExprKind::Variable(n) if n == "add"));
+            assert_eq!(args.len(), 2);
+        }
+        other => panic!("expected a forwarding call, got {:?}", other),
+    }
+}
+
+#[test]
+fn call_result_is_called_indirectly() {
+    let program = lower(
+        "func inc(x: i32) -> i32 { x + 1 }\n\nfunc pick() -> (i32) -> i32 { inc }\n\nfunc main() -> i32 { pick()(41) }",
+    );
+    let body = function_body(&program, "main");
+    let call = match body.last() {
+        Some(HirStmt::Expr(expr))
+        | Some(HirStmt::Return {
+            value: Some(expr), ..
+        }) => expr,
+        other => panic!("expected a trailing call, got {:?}", other),
+    };
+    match &call.kind {
+        HirExprKind::Call { callee, args } => {
+            assert!(matches!(callee.kind, HirExprKind::Call { .. }));
+            assert!(matches!(callee.ty, HirType::Function { .. }));
+            assert_eq!(args.len(), 1);
+        }
+        other => panic!("expected a call, got {:?}", other),
+    }
+    assert_eq!(call.ty, HirType::I32);
+}
+
+#[test]
+fn cfg_gated_function_is_lowered_only_when_enabled() {
+    let src = "@cfg(target = \"cuda\")\nfunc kernel() -> i32 { 1 }\n\nfunc main() -> i32 { 0 }";
+    let has_kernel = |program: &HirProgram| {
+        program
+            .items
+            .iter()
+            .any(|item| matches!(item, HirItem::Function(f) if f.name == "kernel"))
+    };
+    assert!(!has_kernel(&lower(src)));
+
+    let mut cfg = syntax_parsing::CfgSet::new();
+    cfg.set_value("target", "cuda");
+    let ast = syntax_parsing::parse_with_cfg(src, &cfg).expect("source should parse");
+    let program = lower_program(&ast).expect("well-typed program should lower");
+    assert!(has_kernel(&program));
+}
+
+#[test]
+fn optimize_attribute_is_carried_on_the_function() {
+    let program = lower(
+        r#"
+@optimize(3)
+func hot() -> i32 { 1 }
+
+func main() -> i32 { hot() }
+"#,
+    );
+    let levels: Vec<(&str, Option<u8>)> = program
+        .items
+        .iter()
+        .filter_map(|item| match item {
+            HirItem::Function(f) => Some((f.name.as_str(), f.optimize)),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(levels, vec![("hot", Some(3)), ("main", None)]);
+}
+
+#[test]
+fn declared_bindings_keep_inner_double_underscores_and_drop_reserved_names() {
+    // The destructuring temporary is `__destructure_N`; `a__b` is the user's own.
+    let program = lower(
+        "func main() -> i32 {\n    val a__b = 1\n    val (x, y) = (2, true)\n    return a__b + x\n}\n",
+    );
+    let bindings: Vec<(String, String)> = crate::declared_bindings(&program)
+        .into_iter()
+        .map(|binding| (binding.name, binding.ty.to_string()))
+        .collect();
+    assert_eq!(
+        bindings,
+        vec![
+            ("a__b".to_string(), "i32".to_string()),
+            ("x".to_string(), "i32".to_string()),
+            ("y".to_string(), "bool".to_string()),
+        ]
+    );
+}
@@ -85,7 +85,7 @@ impl Lowerer {
             }),
             ast_types::Type::Array { element, size, .. } => Ok(HirType::Array {
                 element: Box::new(self.resolve_type(element)?),
-                size: crate::resolve_array_size(size, &self.const_subst)?,
+                size: crate::resolve_array_size(size, &self.const_subst, &self.const_lengths)?,
             }),
             ast_types::Type::Tuple { elements, .. } => {
                 let mut resolved = Vec::with_capacity(elements.len());
@@ -2,8 +2,9 @@ use std::fmt;
 
 use shared_types::{Identifier, Literal, Span};
 
-use super::statements::Stmt;
+use super::statements::{fmt_block, fmt_if, fmt_label, Stmt};
 use super::types::GenericArg;
+use super::{fmt_list, Spanned};
 
 /// A single field initializer in a struct literal: `field_name: expr`
 #[derive(Debug, Clone, PartialEq)]
@@ -296,9 +297,8 @@ impl Pattern {
     }
 }
 
-impl Expr {
-    /// Get the span of this expression
-    pub fn span(&self) -> Span {
+impl Spanned for Expr {
+    fn span(&self) -> Span {
         match self {
             Expr::Literal(_, span) => *span,
             Expr::Identifier(ident) => ident.span,
@@ -329,6 +329,233 @@ impl Expr {
     }
 }
 
+/// The expression as source on one line, exactly as parsed: `1 + 2`, `p.x`,
+/// `f::<i32>(a, b)`. Grouping shows only where the source had parentheses, and
+/// blocks print inline (`if c { 1 } else { 2 }`). A compact debugging view for logs
+/// and messages, not the formatter's layout.
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expr::Literal(literal, _) => write!(f, "{literal}"),
+            Expr::Identifier(ident) => f.write_str(&ident.name),
+            Expr::Binary {
+                left, op, right, ..
+            } => write!(f, "{left} {op} {right}"),
+            Expr::Call {
+                func,
+                type_args,
+                args,
+                ..
+            } => {
+                write!(f, "{func}")?;
+                if !type_args.is_empty() {
+                    f.write_str("::<")?;
+                    fmt_list(f, type_args)?;
+                    f.write_str(">")?;
+                }
+                f.write_str("(")?;
+                fmt_list(f, args)?;
+                f.write_str(")")
+            }
+            Expr::Unary { op, operand, .. } => write!(f, "{op}{operand}"),
+            Expr::Paren(inner, _) => write!(f, "({inner})"),
+            Expr::StructLiteral {
+                name, fields, base, ..
+            } => {
+                write!(f, "{} ", name.name)?;
+                fmt_fields(f, fields, base.as_deref())
+            }
+            Expr::FieldAccess { object, field, .. } => write!(f, "{object}.{}", field.name),
+            Expr::EnumStructLiteral {
+                enum_name,
+                variant,
+                fields,
+                ..
+            } => {
+                write!(f, "{}::{} ", enum_name.name, variant.name)?;
+                fmt_fields(f, fields, None)
+            }
+            Expr::Path {
+                type_name, member, ..
+            } => write!(f, "{}::{}", type_name.name, member.name),
+            Expr::Cast {
+                expr, target_type, ..
+            } => write!(f, "{expr} as {target_type}"),
+            Expr::If {
+                condition,
+                then_block,
+                else_if_blocks,
+                else_block,
+                ..
+            } => fmt_if(f, condition, then_block, else_if_blocks, else_block),
+            Expr::Block { stmts, .. } => fmt_block(f, stmts),
+            Expr::Loop { label, body, .. } => {
+                fmt_label(f, label)?;
+                f.write_str("loop ")?;
+                fmt_block(f, body)
+            }
+            Expr::Unsafe { stmts, .. } => {
+                f.write_str("unsafe ")?;
+                fmt_block(f, stmts)
+            }
+            Expr::Reference {
+                operand, mutable, ..
+            } => {
+                let prefix = if *mutable { "&mut " } else { "&" };
+                write!(f, "{prefix}{operand}")
+            }
+            Expr::Deref { operand, .. } => write!(f, "*{operand}"),
+            Expr::Range {
+                start,
+                end,
+                inclusive,
+                ..
+            } => {
+                let range = if *inclusive { "..=" } else { ".." };
+                write!(f, "{start}{range}{end}")
+            }
+            Expr::ArrayLiteral { elements, .. } => {
+                f.write_str("[")?;
+                fmt_list(f, elements)?;
+                f.write_str("]")
+            }
+            Expr::Index { object, index, .. } => write!(f, "{object}[{index}]"),
+            Expr::TupleLiteral { elements, .. } => {
+                f.write_str("(")?;
+                fmt_list(f, elements)?;
+                f.write_str(")")
+            }
+            Expr::TupleIndex { object, index, .. } => write!(f, "{object}.{index}"),
+            // Never written in source; shown as the slice it produces.
+            Expr::ArrayRest { array, start, .. } => write!(f, "{array}[{start}..]"),
+            Expr::Match {
+                scrutinee, arms, ..
+            } => {
+                write!(f, "match {scrutinee} {{ ")?;
+                fmt_list(f, arms)?;
+                f.write_str(" }")
+            }
+            Expr::Closure {
+                params,
+                ret,
+                body,
+                is_move,
+                ..
+            } => {
+                if *is_move {
+                    f.write_str("move ")?;
+                }
+                f.write_str("|")?;
+                fmt_list(f, params)?;
+                f.write_str("| ")?;
+                if let Some(ret) = ret {
+                    write!(f, "-> {ret} ")?;
+                }
+                write!(f, "{body}")
+            }
+        }
+    }
+}
+
+/// Write a braced field list, `{ x: 1, y: 2 }`, with a trailing `..base` for a
+/// functional update.
+fn fmt_fields(
+    f: &mut fmt::Formatter<'_>,
+    fields: &[FieldInit],
+    base: Option<&Expr>,
+) -> fmt::Result {
+    f.write_str("{ ")?;
+    for (i, field) in fields.iter().enumerate() {
+        if i > 0 {
+            f.write_str(", ")?;
+        }
+        write!(f, "{}: {}", field.name.name, field.value)?;
+    }
+    if let Some(base) = base {
+        if !fields.is_empty() {
+            f.write_str(", ")?;
+        }
+        write!(f, "..{base}")?;
+    }
+    f.write_str(" }")
+}
+
+impl fmt::Display for ClosureParam {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.name.name)?;
+        match &self.ty {
+            Some(ty) => write!(f, ": {ty}"),
+            None => Ok(()),
+        }
+    }
+}
+
+impl fmt::Display for MatchArm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, pattern) in self.patterns.iter().enumerate() {
+            if i > 0 {
+                f.write_str(" | ")?;
+            }
+            write!(f, "{pattern}")?;
+        }
+        if let Some(guard) = &self.guard {
+            write!(f, " if {guard}")?;
+        }
+        write!(f, " => {}", self.body)
+    }
+}
+
+impl fmt::Display for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Pattern::Wildcard(_) => f.write_str("_"),
+            Pattern::Binding(name) => f.write_str(&name.name),
+            Pattern::Literal(literal, _) => write!(f, "{literal}"),
+            Pattern::Range {
+                start,
+                end,
+                inclusive,
+                ..
+            } => {
+                let range = if *inclusive { "..=" } else { ".." };
+                write!(f, "{start}{range}{end}")
+            }
+            Pattern::Enum {
+                enum_name,
+                variant,
+                payload,
+                ..
+            } => {
+                write!(f, "{}::{}", enum_name.name, variant.name)?;
+                match payload {
+                    EnumPatternPayload::Unit => Ok(()),
+                    EnumPatternPayload::Tuple(patterns) => {
+                        f.write_str("(")?;
+                        fmt_list(f, patterns)?;
+                        f.write_str(")")
+                    }
+                    EnumPatternPayload::Struct(fields) => {
+                        f.write_str(" { ")?;
+                        fmt_list(f, fields)?;
+                        f.write_str(" }")
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// `field: pattern`, or the shorthand `field` when the pattern binds the field's
+/// own name.
+impl fmt::Display for FieldPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.pattern {
+            Pattern::Binding(name) if name.name == self.field.name => f.write_str(&name.name),
+            pattern => write!(f, "{}: {pattern}", self.field.name),
+        }
+    }
+}
+
 /// Binary operators
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum BinaryOp {
@@ -5,6 +5,7 @@ use shared_types::{Identifier, Span};
 use super::expressions::Expr;
 use super::statements::Stmt;
 use super::types::Type;
+use super::Spanned;
 
 /// What a generic parameter binds.
 ///
@@ -68,12 +69,16 @@ pub struct FunctionDef {
 ///
 /// The semantics of an attribute are interpreted by later passes (e.g. the
 /// `@allow(prefer_loop_over_while_true)` lint suppression in semantic analysis).
+/// An integer argument, as in `@optimize(3)`, is stored as its decimal text.
 /// Unknown attributes are accepted by the parser to keep the surface forward
 /// compatible with future passes such as `@grad`, `@gpu`, and `@no_prelude`.
 #[derive(Debug, Clone, PartialEq)]
 pub struct Attribute {
     pub name: Identifier,
     pub args: Vec<Identifier>,
+    /// `key = value` arguments, as in `@cfg(target = "cuda")`, in source order.
+    /// The value is a string literal's contents or an identifier's name.
+    pub named_args: Vec<(Identifier, String)>,
     pub span: Span,
 }
 
@@ -279,3 +284,17 @@ pub enum Item {
     Const(ConstDef),
     Newtype(NewtypeDef),
 }
+
+impl Spanned for Item {
+    fn span(&self) -> Span {
+        match self {
+            Item::Function(def) => def.span,
+            Item::Struct(def) => def.span,
+            Item::Enum(def) => def.span,
+            Item::Trait(def) => def.span,
+            Item::Impl(def) => def.span,
+            Item::Const(def) => def.span,
+            Item::Newtype(def) => def.span,
+        }
+    }
+}
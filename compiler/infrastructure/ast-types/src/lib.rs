@@ -7,6 +7,10 @@ pub mod items;
 pub mod statements;
 pub mod types;
 
+use std::fmt;
+
+use shared_types::Span;
+
 pub use expressions::{
     BinaryOp, ClosureParam, EnumPatternPayload, Expr, FieldInit, FieldPattern, MatchArm, Pattern,
     UnaryOp,
@@ -18,3 +22,27 @@ pub use items::{
 };
 pub use statements::Stmt;
 pub use types::{ArraySize, GenericArg, Type};
+
+/// An AST node that knows the source range it was parsed from.
+///
+/// Implemented by every node kind whose span lives in per-variant fields, so a
+/// caller never matches on the variants to find it.
+pub trait Spanned {
+    /// The source span of this node.
+    fn span(&self) -> Span;
+}
+
+/// Write `items` separated by `, `, for the list positions of the one-line
+/// `Display` views of AST nodes.
+pub(crate) fn fmt_list<T: fmt::Display>(f: &mut fmt::Formatter<'_>, items: &[T]) -> fmt::Result {
+    for (i, item) in items.iter().enumerate() {
+        if i > 0 {
+            f.write_str(", ")?;
+        }
+        write!(f, "{item}")?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests;
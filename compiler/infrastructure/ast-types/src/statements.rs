@@ -1,9 +1,13 @@
 // Statement AST nodes
 
+use std::fmt;
+
 use shared_types::{Identifier, Span};
 
-use super::expressions::Expr;
+use super::expressions::{Expr, Pattern};
+use super::items::FunctionDef;
 use super::types::Type;
+use super::Spanned;
 
 /// Statement AST nodes
 #[derive(Debug, Clone, PartialEq)]
@@ -31,6 +35,19 @@ pub enum Stmt {
         else_block: Option<Vec<Stmt>>,
         span: Span,
     },
+    /// Pattern-conditional statement `if let pattern = scrutinee { ... }`.
+    ///
+    /// Sugar over a two-arm `match`: `then_block` runs with the pattern's
+    /// bindings in scope when `scrutinee` matches, `else_block` otherwise. An
+    /// `else if` / `else if let` chain is nested as the sole statement of
+    /// `else_block`.
+    IfLet {
+        pattern: Pattern,
+        scrutinee: Expr,
+        then_block: Vec<Stmt>,
+        else_block: Option<Vec<Stmt>>,
+        span: Span,
+    },
     /// While loop statement.
     ///
     /// Executes `body` repeatedly while `condition` evaluates to `true`. An
@@ -42,6 +59,18 @@ pub enum Stmt {
         body: Vec<Stmt>,
         span: Span,
     },
+    /// Pattern-conditional loop `while let pattern = scrutinee { ... }`.
+    ///
+    /// Re-evaluates `scrutinee` before each iteration and runs `body` with the
+    /// pattern's bindings in scope until it no longer matches. Labeled like
+    /// `While`.
+    WhileLet {
+        label: Option<Identifier>,
+        pattern: Pattern,
+        scrutinee: Expr,
+        body: Vec<Stmt>,
+        span: Span,
+    },
     /// For loop over a numeric range.
     ///
     /// Executes `body` for each value of `iterator` from `start` up to
@@ -134,5 +163,235 @@ pub enum Stmt {
         value: Expr,
         span: Span,
     },
+    /// A function declared inside a function body. It is in scope for the rest of
+    /// the enclosing block and in its own body. Unlike a closure it captures
+    /// nothing, so its body sees only its parameters and top-level items.
+    Func(Box<FunctionDef>),
+    /// A bare block `{ ... }` in statement position. Its statements run in a new
+    /// scope, so bindings declared inside end with it. A block that ends its
+    /// enclosing body is its value and stays a [`Stmt::Expr`] of [`Expr::Block`].
+    Block(Vec<Stmt>, Span),
     Expr(Expr),
 }
+
+impl Spanned for Stmt {
+    fn span(&self) -> Span {
+        match self {
+            Stmt::VarDecl { span, .. } => *span,
+            Stmt::Const { span, .. } => *span,
+            Stmt::Assignment { span, .. } => *span,
+            Stmt::Return { span, .. } => *span,
+            Stmt::If { span, .. } => *span,
+            Stmt::IfLet { span, .. } => *span,
+            Stmt::While { span, .. } => *span,
+            Stmt::WhileLet { span, .. } => *span,
+            Stmt::Loop { span, .. } => *span,
+            Stmt::ForRange { span, .. } => *span,
+            Stmt::ForEach { span, .. } => *span,
+            Stmt::Break { span, .. } => *span,
+            Stmt::Continue { span, .. } => *span,
+            Stmt::FieldAssignment { span, .. } => *span,
+            Stmt::IndexAssignment { span, .. } => *span,
+            Stmt::DerefAssignment { span, .. } => *span,
+            Stmt::Func(func) => func.span,
+            Stmt::Block(_, span) => *span,
+            Stmt::Expr(e) => e.span(),
+        }
+    }
+}
+
+/// The statement as source on one line, nested blocks included: `val x: i32 = 1`,
+/// `while i < n { i = i + 1 }`. A compact debugging view for logs and messages, not
+/// the formatter's layout.
+impl fmt::Display for Stmt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Stmt::VarDecl {
+                name,
+                ty,
+                init,
+                mutable,
+                ..
+            } => {
+                let keyword = if *mutable { "mut" } else { "val" };
+                write!(f, "{keyword} {}", name.name)?;
+                if let Some(ty) = ty {
+                    write!(f, ": {ty}")?;
+                }
+                if let Some(init) = init {
+                    write!(f, " = {init}")?;
+                }
+                Ok(())
+            }
+            Stmt::Assignment { target, value, .. } => write!(f, "{} = {value}", target.name),
+            Stmt::Return { value: None, .. } => f.write_str("return"),
+            Stmt::Return {
+                value: Some(value), ..
+            } => write!(f, "return {value}"),
+            Stmt::If {
+                condition,
+                then_block,
+                else_if_blocks,
+                else_block,
+                ..
+            } => fmt_if(f, condition, then_block, else_if_blocks, else_block),
+            Stmt::IfLet {
+                pattern,
+                scrutinee,
+                then_block,
+                else_block,
+                ..
+            } => {
+                write!(f, "if let {pattern} = {scrutinee} ")?;
+                fmt_block(f, then_block)?;
+                if let Some(block) = else_block {
+                    f.write_str(" else ")?;
+                    fmt_block(f, block)?;
+                }
+                Ok(())
+            }
+            Stmt::While {
+                label,
+                condition,
+                body,
+                ..
+            } => {
+                fmt_label(f, label)?;
+                write!(f, "while {condition} ")?;
+                fmt_block(f, body)
+            }
+            Stmt::WhileLet {
+                label,
+                pattern,
+                scrutinee,
+                body,
+                ..
+            } => {
+                fmt_label(f, label)?;
+                write!(f, "while let {pattern} = {scrutinee} ")?;
+                fmt_block(f, body)
+            }
+            Stmt::ForRange {
+                label,
+                iterator,
+                start,
+                end,
+                inclusive,
+                body,
+                ..
+            } => {
+                fmt_label(f, label)?;
+                let range = if *inclusive { "..=" } else { ".." };
+                write!(f, "for {} in {start}{range}{end} ", iterator.name)?;
+                fmt_block(f, body)
+            }
+            Stmt::ForEach {
+                label,
+                iterator,
+                iterable,
+                body,
+                ..
+            } => {
+                fmt_label(f, label)?;
+                write!(f, "for {} in {iterable} ", iterator.name)?;
+                fmt_block(f, body)
+            }
+            Stmt::Loop { label, body, .. } => {
+                fmt_label(f, label)?;
+                f.write_str("loop ")?;
+                fmt_block(f, body)
+            }
+            Stmt::Break { label, value, .. } => {
+                f.write_str("break")?;
+                if let Some(label) = label {
+                    write!(f, " {}", label.name)?;
+                }
+                if let Some(value) = value {
+                    write!(f, " {value}")?;
+                }
+                Ok(())
+            }
+            Stmt::Continue { label: None, .. } => f.write_str("continue"),
+            Stmt::Continue {
+                label: Some(label), ..
+            } => write!(f, "continue {}", label.name),
+            Stmt::FieldAssignment {
+                object,
+                field,
+                value,
+                ..
+            } => write!(f, "{}.{} = {value}", object.name, field.name),
+            Stmt::DerefAssignment { pointer, value, .. } => write!(f, "*{pointer} = {value}"),
+            Stmt::IndexAssignment {
+                target,
+                index,
+                value,
+                ..
+            } => write!(f, "{}[{index}] = {value}", target.name),
+            Stmt::Const {
+                name, ty, value, ..
+            } => write!(f, "const {}: {ty} = {value}", name.name),
+            Stmt::Func(func) => {
+                write!(f, "func {}(", func.name.name)?;
+                for (i, param) in func.params.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{}: {}", param.name.name, param.ty)?;
+                }
+                f.write_str(") ")?;
+                if let Some(ret) = &func.return_type {
+                    write!(f, "-> {ret} ")?;
+                }
+                fmt_block(f, &func.body)
+            }
+            Stmt::Block(stmts, _) => fmt_block(f, stmts),
+            Stmt::Expr(expr) => write!(f, "{expr}"),
+        }
+    }
+}
+
+/// Write a block on one line: `{ a; b }`, or `{}` when it is empty.
+pub(crate) fn fmt_block(f: &mut fmt::Formatter<'_>, stmts: &[Stmt]) -> fmt::Result {
+    if stmts.is_empty() {
+        return f.write_str("{}");
+    }
+    f.write_str("{ ")?;
+    for (i, stmt) in stmts.iter().enumerate() {
+        if i > 0 {
+            f.write_str("; ")?;
+        }
+        write!(f, "{stmt}")?;
+    }
+    f.write_str(" }")
+}
+
+/// Write an `if` / `else if` / `else` chain, shared by the statement and the
+/// expression form.
+pub(crate) fn fmt_if(
+    f: &mut fmt::Formatter<'_>,
+    condition: &Expr,
+    then_block: &[Stmt],
+    else_if_blocks: &[(Expr, Vec<Stmt>)],
+    else_block: &Option<Vec<Stmt>>,
+) -> fmt::Result {
+    write!(f, "if {condition} ")?;
+    fmt_block(f, then_block)?;
+    for (condition, block) in else_if_blocks {
+        write!(f, " else if {condition} ")?;
+        fmt_block(f, block)?;
+    }
+    if let Some(block) = else_block {
+        f.write_str(" else ")?;
+        fmt_block(f, block)?;
+    }
+    Ok(())
+}
+
+/// Write a loop label as it precedes the loop: `outer: `.
+pub(crate) fn fmt_label(f: &mut fmt::Formatter<'_>, label: &Option<Identifier>) -> fmt::Result {
+    match label {
+        Some(label) => write!(f, "{}: ", label.name),
+        None => Ok(()),
+    }
+}
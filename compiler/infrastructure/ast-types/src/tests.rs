@@ -0,0 +1,89 @@
+// Unit tests for the one-line `Display` views of AST nodes.
+
+use super::*;
+use shared_types::{Identifier, Literal};
+
+fn span() -> Span {
+    Span::new(0, 0)
+}
+
+fn ident(name: &str) -> Identifier {
+    Identifier::new(name.to_string(), span())
+}
+
+fn var(name: &str) -> Expr {
+    Expr::Identifier(ident(name))
+}
+
+fn int(value: i64) -> Expr {
+    Expr::Literal(Literal::Integer(value, None), span())
+}
+
+fn named(name: &str) -> Type {
+    Type::Named(ident(name))
+}
+
+#[test]
+fn binary_expression_reads_like_source() {
+    let expr = Expr::Binary {
+        left: Box::new(int(1)),
+        op: BinaryOp::Add,
+        right: Box::new(int(2)),
+        span: span(),
+    };
+    assert_eq!(expr.to_string(), "1 + 2");
+}
+
+#[test]
+fn function_type_lists_params_and_return() {
+    let ty = Type::Function {
+        params: vec![named("i32"), named("i32")],
+        ret: Box::new(named("i32")),
+        span: span(),
+    };
+    assert_eq!(ty.to_string(), "(i32, i32) -> i32");
+}
+
+#[test]
+fn reference_to_array_type() {
+    let ty = Type::Reference {
+        inner: Box::new(Type::Array {
+            element: Box::new(named("i32")),
+            size: ArraySize::Literal(4),
+            span: span(),
+        }),
+        mutable: true,
+        lifetime: None,
+        span: span(),
+    };
+    assert_eq!(ty.to_string(), "&mut [i32; 4]");
+}
+
+#[test]
+fn var_decl_with_call_initializer() {
+    let stmt = Stmt::VarDecl {
+        name: ident("x"),
+        ty: Some(named("i32")),
+        init: Some(Expr::Call {
+            func: Box::new(var("f")),
+            type_args: vec![],
+            args: vec![int(1), int(2)],
+            span: span(),
+        }),
+        mutable: false,
+        span: span(),
+    };
+    assert_eq!(stmt.to_string(), "val x: i32 = f(1, 2)");
+}
+
+#[test]
+fn if_else_statement_prints_blocks_inline() {
+    let stmt = Stmt::If {
+        condition: var("c"),
+        then_block: vec![Stmt::Expr(int(1))],
+        else_if_blocks: vec![],
+        else_block: Some(vec![]),
+        span: span(),
+    };
+    assert_eq!(stmt.to_string(), "if c { 1 } else {}");
+}
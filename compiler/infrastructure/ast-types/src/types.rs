@@ -1,7 +1,11 @@
 // Type AST nodes
 
+use std::fmt;
+
 use shared_types::{Identifier, Span};
 
+use super::fmt_list;
+
 /// One generic argument in a type application `Name<...>` or a call-site turbofish
 /// `f::<...>(x)`. An argument is either a type (for a type parameter) or an
 /// integer value (for a const parameter). Positional: matched to the callee's or
@@ -95,10 +99,11 @@ pub enum Type {
     /// and any non-object-safe trait.
     DynTrait { trait_name: Identifier, span: Span },
 
-    /// Closure / function type `(T1, T2, ...) -> R`: the type of a callable value
-    /// (a closure literal or a function-typed parameter). Parentheses around the
-    /// parameter types distinguish it from a closure *literal* `|p| body`. `span`
-    /// covers the leading `(` through the return type.
+    /// Closure / function type `(T1, T2, ...) -> R`, also spelled `func(T1, ...) -> R`:
+    /// the type of a callable value (a closure literal or a function-typed parameter).
+    /// Parentheses around the parameter types distinguish it from a closure *literal*
+    /// `|p| body`. `func(...)` without `->` returns `void`. `span` covers the leading
+    /// `(` or `func` through the return type.
     Function {
         params: Vec<Type>,
         ret: Box<Type>,
@@ -133,3 +138,73 @@ impl Type {
         }
     }
 }
+
+/// The type as written in source, on one line: `&'a mut [i32; 4]`,
+/// `(i32, i32) -> bool`, `Pair<T, 4>`.
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Type::Named(name) => f.write_str(&name.name),
+            Type::Reference {
+                inner,
+                mutable,
+                lifetime,
+                ..
+            } => {
+                f.write_str("&")?;
+                if let Some(lifetime) = lifetime {
+                    write!(f, "'{} ", lifetime.name)?;
+                }
+                if *mutable {
+                    f.write_str("mut ")?;
+                }
+                write!(f, "{inner}")
+            }
+            Type::Array { element, size, .. } => write!(f, "[{element}; {size}]"),
+            Type::Tuple { elements, .. } => {
+                f.write_str("(")?;
+                fmt_list(f, elements)?;
+                f.write_str(")")
+            }
+            Type::Generic { name, args, .. } => {
+                write!(f, "{}<", name.name)?;
+                fmt_list(f, args)?;
+                f.write_str(">")
+            }
+            Type::ImplTrait { trait_name, .. } => write!(f, "impl {}", trait_name.name),
+            Type::DynTrait { trait_name, .. } => write!(f, "dyn {}", trait_name.name),
+            Type::Function { params, ret, .. } => {
+                f.write_str("(")?;
+                fmt_list(f, params)?;
+                write!(f, ") -> {ret}")
+            }
+            Type::Tensor {
+                element_type,
+                shape,
+                ..
+            } => {
+                write!(f, "Tensor<{element_type}, [")?;
+                fmt_list(f, shape)?;
+                f.write_str("]>")
+            }
+        }
+    }
+}
+
+impl fmt::Display for GenericArg {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GenericArg::Type(ty) => write!(f, "{ty}"),
+            GenericArg::Const { value, .. } => write!(f, "{value}"),
+        }
+    }
+}
+
+impl fmt::Display for ArraySize {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ArraySize::Literal(len) => write!(f, "{len}"),
+            ArraySize::Const(name) => f.write_str(&name.name),
+        }
+    }
+}
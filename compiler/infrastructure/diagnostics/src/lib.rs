@@ -1,7 +1,7 @@
 //! Collecting and formatting compiler diagnostics (errors, warnings, hints).
 //!
 //! Severity levels, error codes for categorization, a builder for attaching
-//! spans and notes, and a collector that accumulates multiple diagnostics.
+//! spans, notes, and suggested fixes, and a collector that accumulates multiple diagnostics.
 //! Pure infrastructure with no business logic.
 //!
 //! # Examples
@@ -25,6 +25,10 @@
 //! }
 //! ```
 
+mod wrap;
+
+pub use wrap::wrap_text;
+
 use shared_types::Span;
 use thiserror::Error;
 
@@ -68,6 +72,16 @@ impl std::fmt::Display for DiagnosticCode {
     }
 }
 
+/// A machine-applicable fix: replacing the source text at `span` with
+/// `replacement` resolves the diagnostic. `label` says what the fix does and is
+/// what a human reads.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Suggestion {
+    pub span: Span,
+    pub replacement: String,
+    pub label: String,
+}
+
 /// A diagnostic message
 #[derive(Debug, Clone)]
 pub struct Diagnostic {
@@ -76,6 +90,7 @@ pub struct Diagnostic {
     pub message: String,
     pub span: Option<Span>,
     pub notes: Vec<String>,
+    pub suggestions: Vec<Suggestion>,
 }
 
 impl Diagnostic {
@@ -86,6 +101,7 @@ impl Diagnostic {
             message,
             span: None,
             notes: Vec::new(),
+            suggestions: Vec::new(),
         }
     }
 
@@ -96,6 +112,7 @@ impl Diagnostic {
             message,
             span: None,
             notes: Vec::new(),
+            suggestions: Vec::new(),
         }
     }
 
@@ -108,23 +125,67 @@ impl Diagnostic {
         self.notes.push(note);
         self
     }
-}
 
-impl std::fmt::Display for Diagnostic {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.severity)?;
-        write!(f, "[{}]", self.code)?;
+    pub fn with_suggestion(mut self, suggestion: Suggestion) -> Self {
+        self.suggestions.push(suggestion);
+        self
+    }
+
+    /// The diagnostic as [`Display`](std::fmt::Display) prints it, with the message,
+    /// each note, and each suggestion label word-wrapped to `max_width` columns. A
+    /// wrapped message continues under a two-space indent and a wrapped note or
+    /// label under its own text, past `note: ` or `help: `.
+    pub fn render_wrapped(&self, max_width: usize) -> String {
+        let mut out = wrap_text(&self.message, max_width, &self.header(), "  ");
+        for note in &self.notes {
+            out.push('\n');
+            out.push_str(&wrap_text(note, max_width, "  note: ", "        "));
+        }
+        for suggestion in &self.suggestions {
+            out.push('\n');
+            out.push_str(&wrap_text(
+                &suggestion.label,
+                max_width,
+                "  help: ",
+                "        ",
+            ));
+        }
+        out
+    }
 
-        if let Some(span) = self.span {
-            write!(f, " at {}..{}", span.start, span.end)?;
+    /// `severity[code] at start..end: `, the text before the message.
+    fn header(&self) -> String {
+        match self.span {
+            Some(span) => format!(
+                "{}[{}] at {}..{}: ",
+                self.severity, self.code, span.start, span.end
+            ),
+            None => format!("{}[{}]: ", self.severity, self.code),
         }
+    }
 
-        write!(f, ": {}", self.message)?;
+    /// Whether `other` reports the same problem: same severity, code, message, and
+    /// span. Notes and suggestions are not compared.
+    fn same_report(&self, other: &Diagnostic) -> bool {
+        self.severity == other.severity
+            && self.code == other.code
+            && self.message == other.message
+            && self.span == other.span
+    }
+}
+
+impl std::fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", self.header(), self.message)?;
 
         for note in &self.notes {
             write!(f, "\n  note: {}", note)?;
         }
 
+        for suggestion in &self.suggestions {
+            write!(f, "\n  help: {}", suggestion.label)?;
+        }
+
         Ok(())
     }
 }
@@ -142,8 +203,13 @@ impl DiagnosticCollector {
         }
     }
 
+    /// Add a diagnostic unless one reporting the same problem is already collected.
+    /// Multi-pass checking can reach the same node twice; the first report, with its
+    /// notes, is the one kept.
     pub fn add(&mut self, diagnostic: Diagnostic) {
-        self.diagnostics.push(diagnostic);
+        if !self.diagnostics.iter().any(|d| d.same_report(&diagnostic)) {
+            self.diagnostics.push(diagnostic);
+        }
     }
 
     pub fn has_errors(&self) -> bool {
@@ -189,6 +255,30 @@ mod tests {
         assert_eq!(collector.diagnostics().len(), 1);
     }
 
+    #[test]
+    fn diagnostic_collector_drops_identical_diagnostics() {
+        let mut collector = DiagnosticCollector::new();
+        let mismatch = || {
+            Diagnostic::error(DiagnosticCode::TypeError, "type mismatch".to_string())
+                .with_span(Span::new(10, 15))
+        };
+
+        collector.add(mismatch());
+        collector.add(mismatch().with_note("seen again".to_string()));
+        assert_eq!(collector.diagnostics().len(), 1);
+        assert!(collector.diagnostics()[0].notes.is_empty());
+
+        collector.add(
+            Diagnostic::error(DiagnosticCode::TypeError, "type mismatch".to_string())
+                .with_span(Span::new(20, 25)),
+        );
+        collector.add(Diagnostic::warning(
+            DiagnosticCode::TypeError,
+            "type mismatch".to_string(),
+        ));
+        assert_eq!(collector.diagnostics().len(), 3);
+    }
+
     #[test]
     fn diagnostic_display_without_span() {
         let diag = Diagnostic::error(DiagnosticCode::TypeError, "type mismatch".to_string());
@@ -216,6 +306,61 @@ mod tests {
         );
     }
 
+    #[test]
+    fn diagnostic_display_with_suggestion() {
+        let diag = Diagnostic::error(DiagnosticCode::NameError, "undefined variable".to_string())
+            .with_span(Span::new(4, 9))
+            .with_note("not found in this scope".to_string())
+            .with_suggestion(Suggestion {
+                span: Span::new(4, 9),
+                replacement: "count".to_string(),
+                label: "a binding with a similar name exists: `count`".to_string(),
+            });
+        assert_eq!(
+            diag.to_string(),
+            "error[E0003] at 4..9: undefined variable\n  \
+             note: not found in this scope\n  \
+             help: a binding with a similar name exists: `count`"
+        );
+        assert_eq!(diag.render_wrapped(80), diag.to_string());
+    }
+
+    #[test]
+    fn render_wrapped_breaks_long_message_at_width() {
+        let diag = Diagnostic::error(
+            DiagnosticCode::TypeError,
+            "expected an integer operand for the shift but found a floating-point value"
+                .to_string(),
+        )
+        .with_note("convert the right-hand side with an explicit cast first".to_string());
+        let output = diag.render_wrapped(40);
+        assert_eq!(
+            output,
+            "error[E0002]: expected an integer\n  \
+             operand for the shift but found a\n  \
+             floating-point value\n  \
+             note: convert the right-hand side with\n        \
+             an explicit cast first"
+        );
+        assert!(output.lines().all(|line| line.chars().count() <= 40));
+    }
+
+    #[test]
+    fn render_wrapped_leaves_short_message_unchanged() {
+        let diag = Diagnostic::warning(DiagnosticCode::Unknown, "unused variable".to_string())
+            .with_span(Span::new(3, 4))
+            .with_note("prefix it with `_`".to_string());
+        assert_eq!(diag.render_wrapped(60), diag.to_string());
+    }
+
+    #[test]
+    fn wrap_text_keeps_an_overlong_word_whole() {
+        assert_eq!(
+            wrap_text("see path/to/a/very/long/file.nr now", 12, "", "  "),
+            "see\n  path/to/a/very/long/file.nr\n  now"
+        );
+    }
+
     #[test]
     fn severity_display() {
         assert_eq!(format!("{}", Severity::Error), "error");
@@ -0,0 +1,53 @@
+//! Word wrapping for diagnostic message text on narrow terminals.
+
+/// Wrap `text` so no line runs past `max_width` columns, breaking only between
+/// words. The first line starts with `prefix`, every later line with `indent`,
+/// so a numbered or `note:` continuation stays aligned under its text.
+///
+/// Lines already within the width are kept exactly as written, and a line break
+/// in `text` is kept and indented like a wrapped one. A word wider than the room
+/// left is placed on a line of its own rather than split.
+pub fn wrap_text(text: &str, max_width: usize, prefix: &str, indent: &str) -> String {
+    let mut out = String::new();
+    for (i, line) in text.split('\n').enumerate() {
+        let lead = if i == 0 {
+            prefix
+        } else {
+            out.push('\n');
+            indent
+        };
+        out.push_str(lead);
+        if columns(lead) + columns(line) <= max_width {
+            out.push_str(line);
+            continue;
+        }
+
+        let words = line.trim_start();
+        let leading = &line[..line.len() - words.len()];
+        out.push_str(leading);
+        let mut column = columns(lead) + columns(leading);
+        let mut line_empty = true;
+        for word in words.split_whitespace() {
+            let width = columns(word);
+            if !line_empty && column + 1 + width > max_width {
+                out.push('\n');
+                out.push_str(indent);
+                column = columns(indent);
+                line_empty = true;
+            }
+            if !line_empty {
+                out.push(' ');
+                column += 1;
+            }
+            out.push_str(word);
+            column += width;
+            line_empty = false;
+        }
+    }
+    out
+}
+
+/// Display columns of `text`, counted as one per character.
+fn columns(text: &str) -> usize {
+    text.chars().count()
+}
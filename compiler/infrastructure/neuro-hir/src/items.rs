@@ -103,6 +103,9 @@ pub struct HirFunction {
     pub params: Vec<HirParam>,
     pub return_type: HirType,
     pub body: Vec<HirStmt>,
+    /// Optimization level from `@optimize(N)` (0-3), overriding the module-wide
+    /// level for this function only. `None` follows the module.
+    pub optimize: Option<u8>,
     pub span: Span,
 }
 
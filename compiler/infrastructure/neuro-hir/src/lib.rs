@@ -141,6 +141,7 @@ mod tests {
                 params: vec![],
                 return_type: HirType::I32,
                 body,
+                optimize: None,
                 span: span(),
             })],
         };
@@ -19,7 +19,7 @@
 //! ```
 
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use thiserror::Error;
 
 /// Project configuration from neuro.toml
@@ -72,6 +72,18 @@ pub enum OptimizationLevel {
     O3,
 }
 
+impl OptimizationLevel {
+    /// The level as the number `-O` takes.
+    pub fn as_u8(self) -> u8 {
+        match self {
+            OptimizationLevel::O0 => 0,
+            OptimizationLevel::O1 => 1,
+            OptimizationLevel::O2 => 2,
+            OptimizationLevel::O3 => 3,
+        }
+    }
+}
+
 #[derive(Debug, Error)]
 pub enum ConfigError {
     #[error("failed to read config file: {0}")]
@@ -84,12 +96,22 @@ pub enum ConfigError {
     NotFound,
 }
 
+/// The name of a project's configuration file.
+pub const CONFIG_FILE_NAME: &str = "neuro.toml";
+
 impl ProjectConfig {
     pub fn load(path: PathBuf) -> Result<Self, ConfigError> {
         let content = std::fs::read_to_string(path)?;
         let config: ProjectConfig = toml::from_str(&content)?;
         Ok(config)
     }
+
+    /// The `neuro.toml` in `dir` or in the nearest of its ancestors that has one.
+    pub fn find(dir: &Path) -> Option<PathBuf> {
+        dir.ancestors()
+            .map(|ancestor| ancestor.join(CONFIG_FILE_NAME))
+            .find(|candidate| candidate.is_file())
+    }
 }
 
 #[cfg(test)]
@@ -190,4 +212,27 @@ mod tests {
         let level = OptimizationLevel::default();
         assert!(matches!(level, OptimizationLevel::O0));
     }
+
+    #[test]
+    fn optimization_level_as_u8() {
+        assert_eq!(OptimizationLevel::O0.as_u8(), 0);
+        assert_eq!(OptimizationLevel::O3.as_u8(), 3);
+    }
+
+    #[test]
+    fn find_walks_up_to_the_nearest_config() {
+        let root = tempfile::TempDir::new().unwrap();
+        let nested = root.path().join("src").join("deep");
+        std::fs::create_dir_all(&nested).unwrap();
+        assert_eq!(ProjectConfig::find(&nested), None);
+
+        let config = root.path().join(CONFIG_FILE_NAME);
+        std::fs::write(&config, "[package]\nname = \"p\"\nversion = \"1.0.0\"\n").unwrap();
+        assert_eq!(ProjectConfig::find(&nested), Some(config.clone()));
+        assert_eq!(ProjectConfig::find(root.path()), Some(config));
+
+        let inner = root.path().join("src").join(CONFIG_FILE_NAME);
+        std::fs::write(&inner, "[package]\nname = \"q\"\nversion = \"1.0.0\"\n").unwrap();
+        assert_eq!(ProjectConfig::find(&nested), Some(inner));
+    }
 }
@@ -1,5 +1,12 @@
 //! Common type definitions shared across compiler slices: source locations,
-//! identifiers, and literal values. Pure infrastructure with no business logic.
+//! identifiers, literal values, and a lexically scoped name map. Pure
+//! infrastructure with no business logic.
+
+mod scoped_map;
+
+use std::fmt;
+
+pub use scoped_map::ScopedMap;
 
 /// Source code span representing a location in the source file.
 ///
@@ -104,6 +111,61 @@ pub enum IntSuffix {
     U64,
 }
 
+impl IntSuffix {
+    /// The suffix as written in source, e.g. `"i64"`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            IntSuffix::I8 => "i8",
+            IntSuffix::I16 => "i16",
+            IntSuffix::I32 => "i32",
+            IntSuffix::I64 => "i64",
+            IntSuffix::U8 => "u8",
+            IntSuffix::U16 => "u16",
+            IntSuffix::U32 => "u32",
+            IntSuffix::U64 => "u64",
+        }
+    }
+}
+
+/// The base an integer literal was written in (`255`, `0b1111_1111`, `0o377`,
+/// `0xFF`).
+///
+/// It is not part of [`Literal`], which holds only the value, so `0x10` and `16`
+/// compare equal. Tools that reproduce a literal as written read its base back
+/// from the source text under the literal's span with [`IntRadix::of_literal`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum IntRadix {
+    #[default]
+    Decimal,
+    Binary,
+    Octal,
+    Hex,
+}
+
+impl IntRadix {
+    /// The base of the integer literal whose source text is `text`, from its
+    /// prefix. Text without a `0b`, `0o`, or `0x` prefix is decimal.
+    pub fn of_literal(text: &str) -> IntRadix {
+        let text = text.strip_prefix('-').unwrap_or(text);
+        match text.get(..2) {
+            Some("0b" | "0B") => IntRadix::Binary,
+            Some("0o" | "0O") => IntRadix::Octal,
+            Some("0x" | "0X") => IntRadix::Hex,
+            _ => IntRadix::Decimal,
+        }
+    }
+
+    /// The prefix that introduces a literal in this base; empty for decimal.
+    pub fn prefix(self) -> &'static str {
+        match self {
+            IntRadix::Decimal => "",
+            IntRadix::Binary => "0b",
+            IntRadix::Octal => "0o",
+            IntRadix::Hex => "0x",
+        }
+    }
+}
+
 /// Type suffix on a float literal (e.g., the `f32` in `1.5f32`).
 ///
 /// `F16`/`BF16` are the half-precision suffixes (`1.5f16`, `0.02bf16`). The
@@ -118,6 +180,18 @@ pub enum FloatSuffix {
     F64,
 }
 
+impl FloatSuffix {
+    /// The suffix as written in source, e.g. `"f32"`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            FloatSuffix::F16 => "f16",
+            FloatSuffix::BF16 => "bf16",
+            FloatSuffix::F32 => "f32",
+            FloatSuffix::F64 => "f64",
+        }
+    }
+}
+
 /// Literal value types supported in the language.
 ///
 /// These represent constant values that appear directly in the source code.
@@ -139,6 +213,64 @@ pub enum Literal {
     Char(char),
 }
 
+impl Literal {
+    /// The literal as source text, with an integer written in `radix` (upper-case
+    /// hex digits). Other literals ignore `radix`. Separators (`1_000`) are not
+    /// preserved.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use shared_types::{IntRadix, IntSuffix, Literal};
+    ///
+    /// let byte = Literal::Integer(255, Some(IntSuffix::U8));
+    /// assert_eq!(byte.to_source(IntRadix::Hex), "0xFFu8");
+    /// assert_eq!(Literal::Integer(-5, None).to_source(IntRadix::Binary), "-0b101");
+    /// ```
+    pub fn to_source(&self, radix: IntRadix) -> String {
+        let Literal::Integer(value, suffix) = self else {
+            return self.to_string();
+        };
+        let sign = if *value < 0 { "-" } else { "" };
+        let magnitude = value.unsigned_abs();
+        let digits = match radix {
+            IntRadix::Decimal => magnitude.to_string(),
+            IntRadix::Binary => format!("{magnitude:b}"),
+            IntRadix::Octal => format!("{magnitude:o}"),
+            IntRadix::Hex => format!("{magnitude:X}"),
+        };
+        let suffix = suffix.map_or("", IntSuffix::as_str);
+        format!("{sign}{}{digits}{suffix}", radix.prefix())
+    }
+}
+
+/// Renders the literal as source text, with integers in decimal; see
+/// [`Literal::to_source`] for another base.
+impl fmt::Display for Literal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Literal::Integer(value, suffix) => {
+                write!(f, "{value}")?;
+                if let Some(suffix) = suffix {
+                    f.write_str(suffix.as_str())?;
+                }
+                Ok(())
+            }
+            Literal::Float(value, suffix) => {
+                // `{:?}` keeps the `.0` that `{}` drops from whole numbers.
+                write!(f, "{value:?}")?;
+                if let Some(suffix) = suffix {
+                    f.write_str(suffix.as_str())?;
+                }
+                Ok(())
+            }
+            Literal::String(value) => write!(f, "{value:?}"),
+            Literal::Boolean(value) => write!(f, "{value}"),
+            Literal::Char(value) => write!(f, "{value:?}"),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -199,6 +331,36 @@ mod tests {
         assert_ne!(lit, Literal::Integer(43, None));
     }
 
+    #[test]
+    fn radix_is_read_from_literal_text() {
+        assert_eq!(IntRadix::of_literal("0xFF"), IntRadix::Hex);
+        assert_eq!(IntRadix::of_literal("0B1010i64"), IntRadix::Binary);
+        assert_eq!(IntRadix::of_literal("-0o17"), IntRadix::Octal);
+        assert_eq!(IntRadix::of_literal("255"), IntRadix::Decimal);
+        assert_eq!(IntRadix::of_literal("0"), IntRadix::Decimal);
+    }
+
+    #[test]
+    fn integer_source_text_keeps_its_base() {
+        let lit = Literal::Integer(0xFF, None);
+        assert_eq!(lit.to_source(IntRadix::Hex), "0xFF");
+        assert_eq!(lit.to_source(IntRadix::Decimal), "255");
+        assert_eq!(lit.to_string(), "255");
+        assert_eq!(
+            Literal::Integer(0o755, Some(IntSuffix::I64)).to_source(IntRadix::Octal),
+            "0o755i64"
+        );
+    }
+
+    #[test]
+    fn float_literal_keeps_its_point() {
+        assert_eq!(Literal::Float(2.0, None).to_string(), "2.0");
+        assert_eq!(
+            Literal::Float(1.5, Some(FloatSuffix::F32)).to_string(),
+            "1.5f32"
+        );
+    }
+
     #[test]
     fn literal_float() {
         let lit = Literal::Float(2.5, None);
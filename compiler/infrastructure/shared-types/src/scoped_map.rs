@@ -0,0 +1,193 @@
+//! Lexically scoped name → value map.
+
+use std::collections::HashMap;
+
+/// A stack of name → value scopes with innermost-first lookup.
+///
+/// The map always holds at least one (root) scope: [`pop_scope`] never removes
+/// it, so definitions made before any `push_scope` live for the map's lifetime.
+/// Redefining a name in the *same* scope is rejected; defining it in an inner
+/// scope shadows the outer binding until that scope is popped.
+///
+/// Passes that need scoped names — the type checker's symbol table, constant
+/// folding environments, interactive session state — wrap this type and keep
+/// their own per-binding payload in `V`.
+///
+/// # Examples
+///
+/// ```
+/// use shared_types::ScopedMap;
+///
+/// let mut env = ScopedMap::new();
+/// env.define("x".to_string(), 1).unwrap();
+///
+/// env.push_scope();
+/// env.define("x".to_string(), 2).unwrap();
+/// assert_eq!(env.lookup("x"), Some(&2));
+///
+/// env.pop_scope();
+/// assert_eq!(env.lookup("x"), Some(&1));
+/// ```
+///
+/// [`pop_scope`]: ScopedMap::pop_scope
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScopedMap<V> {
+    /// Stack of scopes (innermost scope is last). Never empty.
+    scopes: Vec<HashMap<String, V>>,
+}
+
+impl<V> ScopedMap<V> {
+    /// Creates a map holding only the root scope.
+    pub fn new() -> Self {
+        Self {
+            scopes: vec![HashMap::new()],
+        }
+    }
+
+    /// Enters a new innermost scope.
+    pub fn push_scope(&mut self) {
+        self.scopes.push(HashMap::new());
+    }
+
+    /// Exits the innermost scope and returns its bindings so the caller can
+    /// release anything they hold. Returns `None` (and keeps the scope) when only
+    /// the root scope remains.
+    pub fn pop_scope(&mut self) -> Option<HashMap<String, V>> {
+        if self.scopes.len() <= 1 {
+            return None;
+        }
+        self.scopes.pop()
+    }
+
+    /// Number of live scopes, including the root scope.
+    pub fn depth(&self) -> usize {
+        self.scopes.len()
+    }
+
+    /// Defines `name` in the innermost scope. Fails with the rejected name when
+    /// that scope already binds it; outer bindings of the same name are shadowed.
+    pub fn define(&mut self, name: String, value: V) -> Result<(), String> {
+        let Some(current) = self.scopes.last_mut() else {
+            return Err(name);
+        };
+        if current.contains_key(&name) {
+            return Err(name);
+        }
+        current.insert(name, value);
+        Ok(())
+    }
+
+    /// Looks `name` up from the innermost scope outwards.
+    pub fn lookup(&self, name: &str) -> Option<&V> {
+        self.scopes.iter().rev().find_map(|scope| scope.get(name))
+    }
+
+    /// Mutable counterpart of [`lookup`](ScopedMap::lookup).
+    pub fn lookup_mut(&mut self, name: &str) -> Option<&mut V> {
+        self.scopes
+            .iter_mut()
+            .rev()
+            .find_map(|scope| scope.get_mut(name))
+    }
+
+    /// Every live binding, outermost scope first. Within a scope the order is
+    /// unspecified but stable while the map is not modified.
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.scopes.iter().flat_map(|scope| scope.values())
+    }
+
+    /// Mutable counterpart of [`values`](ScopedMap::values), visiting bindings
+    /// in the same order.
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut V> {
+        self.scopes.iter_mut().flat_map(|scope| scope.values_mut())
+    }
+
+    /// The name of every live binding, in the order of [`values`](ScopedMap::values).
+    /// A shadowed name appears once per scope that binds it.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.scopes
+            .iter()
+            .flat_map(|scope| scope.keys().map(String::as_str))
+    }
+}
+
+impl<V> Default for ScopedMap<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scoped_map_scoping() {
+        let mut map = ScopedMap::new();
+
+        // Define in the root scope
+        assert!(map.define("x".to_string(), 1).is_ok());
+        assert_eq!(map.lookup("x"), Some(&1));
+
+        // Define in a nested scope
+        map.push_scope();
+        assert!(map.define("y".to_string(), 2).is_ok());
+        assert_eq!(map.lookup("y"), Some(&2));
+        assert_eq!(map.lookup("x"), Some(&1)); // Outer scope still visible
+
+        // Shadow
+        assert!(map.define("x".to_string(), 3).is_ok());
+        assert_eq!(map.lookup("x"), Some(&3));
+
+        // Pop returns the dying bindings
+        let dying = map.pop_scope().expect("nested scope should pop");
+        assert_eq!(dying.len(), 2);
+        assert_eq!(map.lookup("x"), Some(&1)); // Back to outer definition
+        assert_eq!(map.lookup("y"), None); // Inner binding gone
+    }
+
+    #[test]
+    fn scoped_map_duplicate_definition() {
+        let mut map = ScopedMap::new();
+        assert!(map.define("x".to_string(), 1).is_ok());
+        assert_eq!(map.define("x".to_string(), 2), Err("x".to_string()));
+        assert_eq!(map.lookup("x"), Some(&1));
+    }
+
+    #[test]
+    fn scoped_map_root_scope_is_never_popped() {
+        let mut map = ScopedMap::new();
+        assert!(map.define("x".to_string(), 1).is_ok());
+        assert!(map.pop_scope().is_none());
+        assert_eq!(map.depth(), 1);
+        assert_eq!(map.lookup("x"), Some(&1));
+    }
+
+    #[test]
+    fn scoped_map_lookup_mut_hits_innermost() {
+        let mut map = ScopedMap::new();
+        assert!(map.define("x".to_string(), 1).is_ok());
+        map.push_scope();
+        assert!(map.define("x".to_string(), 2).is_ok());
+        if let Some(value) = map.lookup_mut("x") {
+            *value = 20;
+        }
+        map.pop_scope();
+        assert_eq!(map.lookup("x"), Some(&1));
+    }
+
+    #[test]
+    fn scoped_map_values_visit_every_scope() {
+        let mut map = ScopedMap::new();
+        assert!(map.define("a".to_string(), 1).is_ok());
+        map.push_scope();
+        assert!(map.define("b".to_string(), 2).is_ok());
+
+        for value in map.values_mut() {
+            *value *= 10;
+        }
+        let mut seen: Vec<i32> = map.values().copied().collect();
+        seen.sort_unstable();
+        assert_eq!(seen, vec![10, 20]);
+    }
+}
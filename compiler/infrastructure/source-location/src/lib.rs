@@ -3,6 +3,7 @@
 //! Pure infrastructure with no business logic.
 
 use shared_types::Span;
+use thiserror::Error;
 
 /// Human-readable position in source code (line and column).
 ///
@@ -35,6 +36,26 @@ impl Position {
     }
 }
 
+/// Why [`SourceFile::try_snippet`] could not extract a span's text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum SnippetError {
+    /// The span ends before it starts.
+    #[error("span {start}..{end} is reversed: it ends before it starts")]
+    Reversed { start: usize, end: usize },
+
+    /// The span reaches past the end of the source.
+    #[error("span {start}..{end} is out of bounds for a {len}-byte source")]
+    OutOfBounds {
+        start: usize,
+        end: usize,
+        len: usize,
+    },
+
+    /// A span endpoint falls inside a multi-byte UTF-8 character.
+    #[error("byte offset {offset} falls inside a multi-byte UTF-8 character")]
+    NotCharBoundary { offset: usize },
+}
+
 /// Source file with cached line start positions for efficient position lookups.
 ///
 /// This structure precomputes the byte offset of each line start during construction,
@@ -138,12 +159,53 @@ impl SourceFile {
         Position::new(line as u32 + 1, column as u32 + 1)
     }
 
+    /// Number of lines in the source, from the cached line starts.
+    ///
+    /// Every line ends at a `\n` except the last, so a trailing newline starts one
+    /// more, empty line: the line [`position_at`](Self::position_at) reports for the
+    /// end of the file. An empty source has one line.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use source_location::SourceFile;
+    ///
+    /// let source = SourceFile::new("test.nr".to_string(), "a\nb\nc".to_string());
+    /// assert_eq!(source.line_count(), 3);
+    /// ```
+    pub fn line_count(&self) -> usize {
+        self.line_starts.len()
+    }
+
+    /// Text of the 1-indexed `line`, without its `\n` or `\r\n` ending.
+    ///
+    /// Returns `None` for line 0 or a line past [`line_count`](Self::line_count).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use source_location::SourceFile;
+    ///
+    /// let source = SourceFile::new("test.nr".to_string(), "a\nb\r\nc".to_string());
+    /// assert_eq!(source.line_text(2), Some("b"));
+    /// assert_eq!(source.line_text(4), None);
+    /// ```
+    pub fn line_text(&self, line: u32) -> Option<&str> {
+        let index = usize::try_from(line).ok()?.checked_sub(1)?;
+        let start = *self.line_starts.get(index)?;
+        let end = self
+            .line_starts
+            .get(index + 1)
+            .map_or(self.content.len(), |next| next - 1);
+        let text = &self.content[start..end];
+        Some(text.strip_suffix('\r').unwrap_or(text))
+    }
+
     /// Extracts a source code snippet for the given span.
     ///
-    /// Returns `None` if:
-    /// - The span is invalid (start > end)
-    /// - The span is out of bounds
-    /// - The span doesn't align with UTF-8 character boundaries
+    /// Returns `None` if the span is reversed, out of bounds, or does not align with
+    /// UTF-8 character boundaries. Use [`try_snippet`](Self::try_snippet) to learn
+    /// which.
     ///
     /// # Examples
     ///
@@ -161,10 +223,43 @@ impl SourceFile {
     /// assert_eq!(source.snippet(Span::new(5, 0)), None);  // Invalid: start > end
     /// ```
     pub fn snippet(&self, span: Span) -> Option<&str> {
-        if span.start > span.end {
-            return None;
+        self.try_snippet(span).ok()
+    }
+
+    /// Extracts a source code snippet for the given span, reporting why it could not.
+    ///
+    /// Checks are made in order: a reversed span first, then one past the end of the
+    /// source, then an endpoint inside a multi-byte character (start before end).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use source_location::{SnippetError, SourceFile};
+    /// use shared_types::Span;
+    ///
+    /// let source = SourceFile::new("test.nr".to_string(), "val π = 3".to_string());
+    ///
+    /// assert_eq!(source.try_snippet(Span::new(4, 6)), Ok("π"));
+    /// assert_eq!(
+    ///     source.try_snippet(Span::new(4, 5)),
+    ///     Err(SnippetError::NotCharBoundary { offset: 5 })
+    /// );
+    /// ```
+    pub fn try_snippet(&self, span: Span) -> Result<&str, SnippetError> {
+        let Span { start, end } = span;
+        if start > end {
+            return Err(SnippetError::Reversed { start, end });
         }
-        self.content.get(span.start..span.end)
+        let len = self.content.len();
+        if end > len {
+            return Err(SnippetError::OutOfBounds { start, end, len });
+        }
+        for offset in [start, end] {
+            if !self.content.is_char_boundary(offset) {
+                return Err(SnippetError::NotCharBoundary { offset });
+            }
+        }
+        Ok(&self.content[start..end])
     }
 }
 
@@ -219,6 +314,93 @@ mod tests {
         assert_eq!(source.snippet(span), Some("Hello "));
     }
 
+    #[test]
+    fn try_snippet_multibyte_source() {
+        // "val " is 4 bytes; "π" spans bytes 4..6 and "世界" spans 10..16.
+        let source = SourceFile::new("test.nr".to_string(), "val π = \"世界\"".to_string());
+        assert_eq!(source.try_snippet(Span::new(4, 6)), Ok("π"));
+        assert_eq!(source.try_snippet(Span::new(10, 16)), Ok("世界"));
+    }
+
+    #[test]
+    fn try_snippet_reports_reversed_span() {
+        let source = SourceFile::new("test.nr".to_string(), "val π = 3".to_string());
+        assert_eq!(
+            source.try_snippet(Span::new(6, 4)),
+            Err(SnippetError::Reversed { start: 6, end: 4 })
+        );
+    }
+
+    #[test]
+    fn try_snippet_reports_out_of_bounds() {
+        // 10 bytes: the `π` counts twice.
+        let source = SourceFile::new("test.nr".to_string(), "val π = 3".to_string());
+        assert_eq!(
+            source.try_snippet(Span::new(4, 11)),
+            Err(SnippetError::OutOfBounds {
+                start: 4,
+                end: 11,
+                len: 10
+            })
+        );
+    }
+
+    #[test]
+    fn try_snippet_reports_non_char_boundary() {
+        let source = SourceFile::new("test.nr".to_string(), "val π = 3".to_string());
+        // End inside `π`.
+        assert_eq!(
+            source.try_snippet(Span::new(4, 5)),
+            Err(SnippetError::NotCharBoundary { offset: 5 })
+        );
+        // Start inside `π`; the start is reported first.
+        assert_eq!(
+            source.try_snippet(Span::new(5, 5)),
+            Err(SnippetError::NotCharBoundary { offset: 5 })
+        );
+        assert_eq!(source.snippet(Span::new(4, 5)), None);
+    }
+
+    #[test]
+    fn snippet_error_messages_name_the_cause() {
+        let message = SnippetError::NotCharBoundary { offset: 5 }.to_string();
+        assert!(message.contains("offset 5"), "{message}");
+        assert!(message.contains("UTF-8"), "{message}");
+    }
+
+    #[test]
+    fn line_count_of_multiline_file() {
+        let source = SourceFile::new("test.nr".to_string(), "line1\nline2\nline3".to_string());
+        assert_eq!(source.line_count(), 3);
+
+        let trailing = SourceFile::new("test.nr".to_string(), "line1\n".to_string());
+        assert_eq!(trailing.line_count(), 2);
+        assert_eq!(trailing.line_text(2), Some(""));
+
+        let empty = SourceFile::new("test.nr".to_string(), String::new());
+        assert_eq!(empty.line_count(), 1);
+        assert_eq!(empty.line_text(1), Some(""));
+    }
+
+    #[test]
+    fn line_text_of_middle_line() {
+        let source = SourceFile::new(
+            "test.nr".to_string(),
+            "func main() {\r\n  val π = 3\r\n}".to_string(),
+        );
+        assert_eq!(source.line_text(1), Some("func main() {"));
+        assert_eq!(source.line_text(2), Some("  val π = 3"));
+        assert_eq!(source.line_text(3), Some("}"));
+    }
+
+    #[test]
+    fn line_text_out_of_range_is_none() {
+        let source = SourceFile::new("test.nr".to_string(), "line1\nline2".to_string());
+        assert_eq!(source.line_text(0), None);
+        assert_eq!(source.line_text(3), None);
+        assert_eq!(source.line_text(u32::MAX), None);
+    }
+
     #[test]
     fn position_at_empty_file() {
         let source = SourceFile::new("test.nr".to_string(), String::new());
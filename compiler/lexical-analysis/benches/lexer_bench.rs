@@ -0,0 +1,54 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use lexical_analysis::tokenize;
+
+/// Build a synthetic program of `functions` functions exercising every hot token
+/// class: keywords, repeated identifiers, decimal / underscored / suffixed
+/// numbers, floats, plain and escaped strings, operators, and comments.
+fn build_source(functions: usize) -> String {
+    let mut source = String::with_capacity(functions * 320);
+    for i in 0..functions {
+        source.push_str(&format!(
+            r#"// helper number {i}
+func compute_{i}(count: i32, scale: f64) -> i32 {{
+    mut total: i32 = 0
+    val limit = 1_000_000
+    val mask = 0xFF_u32
+    while total < count {{
+        total = total + {i} * 2 - (count / 3)
+    }}
+    val label = "compute step"
+    val escaped = "line\tbreak\n"
+    if scale > 1.5 && total >= 42i64 {{
+        return total
+    }}
+    return total + limit
+}}
+"#
+        ));
+    }
+    source
+}
+
+fn bench_tokenize(c: &mut Criterion) {
+    let mut group = c.benchmark_group("tokenize");
+
+    for functions in [100, 1_000] {
+        let source = build_source(functions);
+        group.throughput(Throughput::Bytes(source.len() as u64));
+        group.bench_with_input(
+            BenchmarkId::new("functions", functions),
+            &source,
+            |b, source| {
+                b.iter(|| {
+                    let tokens = tokenize(source).expect("benchmark source must tokenize");
+                    assert!(!tokens.is_empty());
+                })
+            },
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_tokenize);
+criterion_main!(benches);
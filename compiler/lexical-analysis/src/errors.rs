@@ -1,5 +1,6 @@
 // Lexical error definitions
 
+use diagnostics::{Diagnostic, DiagnosticCode};
 use shared_types::Span;
 use thiserror::Error;
 
@@ -25,6 +26,38 @@ pub enum LexError {
     UnterminatedBlockComment { span: Span },
 }
 
+impl LexError {
+    /// The source span the error points at.
+    pub fn span(&self) -> Span {
+        match self {
+            Self::UnexpectedChar { span, .. }
+            | Self::UnterminatedString { span }
+            | Self::InvalidNumber { span, .. }
+            | Self::InvalidEscape { span, .. }
+            | Self::InvalidCharLiteral { span, .. }
+            | Self::UnterminatedBlockComment { span } => *span,
+        }
+    }
+
+    /// The error as a `SyntaxError` diagnostic at its span. The message leaves out
+    /// the byte position the `Display` text gives, which the span carries instead.
+    pub fn to_diagnostic(&self) -> Diagnostic {
+        let message = match self {
+            Self::UnexpectedChar { character, .. } => {
+                format!("unexpected character '{character}'")
+            }
+            Self::UnterminatedString { .. } => "unterminated string literal".to_string(),
+            Self::InvalidNumber { text, .. } => format!("invalid number literal '{text}'"),
+            Self::InvalidEscape { escape, .. } => format!("invalid escape sequence '{escape}'"),
+            Self::InvalidCharLiteral { literal, .. } => {
+                format!("invalid character literal {literal}")
+            }
+            Self::UnterminatedBlockComment { .. } => "unterminated block comment".to_string(),
+        };
+        Diagnostic::error(DiagnosticCode::SyntaxError, message).with_span(self.span())
+    }
+}
+
 impl Default for LexError {
     fn default() -> Self {
         LexError::UnexpectedChar {
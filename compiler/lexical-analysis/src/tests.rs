@@ -193,6 +193,35 @@ fn tokenize_floats() {
     }
 }
 
+#[test]
+fn prefixed_integers_do_not_lex_as_floats() {
+    let result = tokenize("0x1e5 1e5 0b101 0.5e3").unwrap();
+    assert_eq!(result.len(), 5); // 4 literals + EOF
+
+    // `e` is a hex digit, not an exponent marker, after `0x`.
+    assert_eq!(result[0].kind, TokenKind::Integer(0x1e5));
+    assert_eq!(result[1].kind, TokenKind::Float(1e5));
+    assert_eq!(result[2].kind, TokenKind::Integer(0b101));
+    assert_eq!(result[3].kind, TokenKind::Float(0.5e3));
+}
+
+#[test]
+fn hex_digit_e_is_not_an_exponent() {
+    // A sign after a hex `e` is an operator, and a trailing `f32` is more hex digits.
+    let result = tokenize("0x1E+5 0x1e5f32 0x1e5i32").unwrap();
+    assert_eq!(result[0].kind, TokenKind::Integer(0x1e));
+    assert_eq!(result[1].kind, TokenKind::Plus);
+    assert!(matches!(result[2].kind, TokenKind::Integer(5)));
+    assert_eq!(result[3].kind, TokenKind::Integer(0x1e5f32));
+    match &result[4].kind {
+        TokenKind::IntegerSuffix(tok) => {
+            assert_eq!(tok.value, 0x1e5);
+            assert_eq!(tok.suffix, IntSuffix::I32);
+        }
+        _ => panic!("expected IntegerSuffix"),
+    }
+}
+
 #[test]
 fn tokenize_float_suffixes() {
     let result = tokenize("1.5f32 2.0f64 1e10f32 1.5e-5f64").unwrap();
@@ -384,6 +413,21 @@ fn tokenize_string_unicode_escape() {
     }
 }
 
+#[test]
+fn tokenize_escaped_single_quote() {
+    // `\'` is a no-op escape in strings and the only way to write a quote char.
+    let result = tokenize(r#""it\'s" "it's" '\''"#).unwrap();
+    match &result[0].kind {
+        TokenKind::String(s) => assert_eq!(s, "it's"),
+        _ => panic!("Expected string"),
+    }
+    match &result[1].kind {
+        TokenKind::String(s) => assert_eq!(s, "it's"),
+        _ => panic!("Expected string"),
+    }
+    assert!(matches!(result[2].kind, TokenKind::Char('\'')));
+}
+
 #[test]
 fn tokenize_operators() {
     let result = tokenize("+ - * / % = == != < > <= >=").unwrap();
@@ -403,6 +447,22 @@ fn tokenize_operators() {
     assert!(matches!(result[11].kind, TokenKind::GreaterEqual));
 }
 
+#[test]
+fn tokenize_compound_assignment_operators() {
+    let result = tokenize("+= -= *= /= %= x+=1").unwrap();
+    assert_eq!(result.len(), 9); // 5 operators + `x`, `+=`, `1` + EOF
+
+    assert!(matches!(result[0].kind, TokenKind::PlusEqual));
+    assert!(matches!(result[1].kind, TokenKind::MinusEqual));
+    assert!(matches!(result[2].kind, TokenKind::StarEqual));
+    assert!(matches!(result[3].kind, TokenKind::SlashEqual));
+    assert!(matches!(result[4].kind, TokenKind::PercentEqual));
+    // Longest match keeps `+=` whole even with no surrounding spaces.
+    assert!(matches!(result[5].kind, TokenKind::Identifier(_)));
+    assert!(matches!(result[6].kind, TokenKind::PlusEqual));
+    assert!(matches!(result[7].kind, TokenKind::Integer(1)));
+}
+
 #[test]
 fn tokenize_logical_operators() {
     let result = tokenize("&& || !").unwrap();
@@ -421,6 +481,15 @@ fn tokenize_move_keyword() {
     assert!(matches!(result[1].kind, TokenKind::Pipe));
 }
 
+#[test]
+fn tokenize_let_keyword() {
+    // `let` introduces a pattern condition in `if let` / `while let`.
+    let result = tokenize("if let Some(x) = v").unwrap();
+    assert!(matches!(result[0].kind, TokenKind::If));
+    assert!(matches!(result[1].kind, TokenKind::Let));
+    assert!(matches!(result[2].kind, TokenKind::Identifier(ref s) if s == "Some"));
+}
+
 #[test]
 fn tokenize_delimiters() {
     let result = tokenize("( ) { } [ ] , : ;").unwrap();
@@ -478,6 +547,74 @@ fn tokenize_multiline_block_comments() {
     assert!(matches!(result[1].kind, TokenKind::Val));
 }
 
+#[test]
+fn block_comment_ends_at_first_close_after_stars() {
+    let result = tokenize("func /* a **/ val /*/ x */").unwrap();
+    assert_eq!(result.len(), 3); // func, val, EOF
+    assert!(matches!(result[1].kind, TokenKind::Val));
+}
+
+#[test]
+fn error_on_unterminated_block_comment() {
+    let source = "val x = 1 /* never closed";
+    match tokenize(source) {
+        Err(LexError::UnterminatedBlockComment { span }) => {
+            assert_eq!(span, Span::new(10, source.len()));
+        }
+        other => panic!("Expected UnterminatedBlockComment, got: {:?}", other),
+    }
+
+    match tokenize("/* never closed") {
+        Err(LexError::UnterminatedBlockComment { span }) => assert_eq!(span.start, 0),
+        other => panic!("Expected UnterminatedBlockComment, got: {:?}", other),
+    }
+}
+
+#[test]
+fn tokenize_all_collects_every_error_and_keeps_the_tokens_around_them() {
+    let source = "val a = $\nval s = \"open\nval b = 1 $";
+    let (tokens, errors) = tokenize_all(source);
+
+    assert_eq!(errors.len(), 3, "{errors:?}");
+    assert!(matches!(
+        errors[0],
+        LexError::UnexpectedChar { character: '$', .. }
+    ));
+    // The unterminated string covers the rest of its line; lexing resumes after it.
+    assert_eq!(
+        errors[1],
+        LexError::UnterminatedString {
+            span: Span::new(18, 23)
+        }
+    );
+    assert!(matches!(
+        errors[2],
+        LexError::UnexpectedChar { character: '$', .. }
+    ));
+
+    let vals = tokens
+        .iter()
+        .filter(|token| token.kind == TokenKind::Val)
+        .count();
+    assert_eq!(vals, 3);
+    assert_eq!(
+        tokens.last().map(|token| &token.kind),
+        Some(&TokenKind::Eof)
+    );
+    assert_eq!(tokenize_all("val a = 1").1, Vec::new());
+}
+
+#[test]
+fn lex_error_diagnostic_drops_the_byte_position() {
+    let error = LexError::UnterminatedString {
+        span: Span::new(4, 9),
+    };
+    let diagnostic = error.to_diagnostic();
+    assert_eq!(diagnostic.message, "unterminated string literal");
+    assert_eq!(diagnostic.span, Some(Span::new(4, 9)));
+    assert_eq!(diagnostic.code, diagnostics::DiagnosticCode::SyntaxError);
+}
+
 #[test]
 fn tokenize_simple_function() {
     let source = r#"
@@ -494,6 +631,50 @@ func add(a: i32, b: i32) -> i32 {
     // More detailed checks would go here
 }
 
+/// Line and column (both 0-based) of byte `offset`, counting only `\n` as a line break.
+fn line_col(source: &str, offset: usize) -> (usize, usize) {
+    let before = &source[..offset];
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    (before.matches('\n').count(), offset - line_start)
+}
+
+#[test]
+fn crlf_newlines_tokenize_like_lf() {
+    let lf = "val a = 1 \nval b = 2\n\n  val c = a\n";
+    let crlf = lf.replace('\n', "\r\n");
+    let lf_tokens = tokenize(lf).unwrap();
+    let crlf_tokens = tokenize(&crlf).unwrap();
+
+    let kinds = |tokens: &[Token]| tokens.iter().map(|t| t.kind.clone()).collect::<Vec<_>>();
+    assert_eq!(kinds(&crlf_tokens), kinds(&lf_tokens));
+
+    for (lf_token, crlf_token) in lf_tokens.iter().zip(&crlf_tokens) {
+        if lf_token.kind == TokenKind::Newline {
+            // A newline token covers every `\r\n` pair of its run, `\r` included.
+            let text = &crlf[crlf_token.span.start..crlf_token.span.end];
+            assert!(text.starts_with('\r'), "newline span {:?}", crlf_token.span);
+            assert_eq!(
+                text.replace("\r\n", "\n"),
+                &lf[lf_token.span.start..lf_token.span.end]
+            );
+        } else {
+            assert_eq!(
+                line_col(&crlf, crlf_token.span.start),
+                line_col(lf, lf_token.span.start),
+                "{:?}",
+                crlf_token.kind
+            );
+        }
+    }
+}
+
+#[test]
+fn lone_carriage_return_is_whitespace() {
+    let result = tokenize("a\rb").unwrap();
+    assert!(matches!(&result[0].kind, TokenKind::Identifier(n) if n == "a"));
+    assert!(matches!(&result[1].kind, TokenKind::Identifier(n) if n == "b"));
+}
+
 #[test]
 fn tokenize_complex_expression() {
     let result = tokenize("val x = (a + b) * c - d / e").unwrap();
@@ -621,6 +802,92 @@ fn char_literal_wins_over_lifetime() {
     assert!(matches!(result[0].kind, TokenKind::Char('a')));
 }
 
+#[test]
+fn comment_kinds_have_display_text() {
+    // The lexer skips comments, but `as_str` must still cover every kind
+    // without panicking.
+    let line = Token::new(TokenKind::_LineComment, Span::new(0, 2));
+    let block = Token::new(TokenKind::_BlockComment, Span::new(0, 4));
+    assert_eq!(line.as_str(), "<line comment>");
+    assert_eq!(block.as_str(), "<block comment>");
+}
+
+#[test]
+fn oversized_integer_literals_are_errors() {
+    for source in [
+        "9223372036854775808",
+        "99999999999999999999i8",
+        "0xfffffffffffffffffffu8",
+        "0b11111111111111111111111111111111111111111111111111111111111111111u64",
+        "0o7777777777777777777777777i32",
+    ] {
+        match tokenize(source) {
+            Err(LexError::InvalidNumber { text, .. }) => assert_eq!(text, source),
+            other => panic!("expected InvalidNumber for {source}, got {other:?}"),
+        }
+    }
+}
+
+#[test]
+fn malformed_literals_are_errors() {
+    // Each is rejected with an `Err`, never a panic.
+    for source in [
+        "'",
+        "''",
+        "'\\u{110000}'",
+        "\"\\u{ffffffffff}\"",
+        "\"\\",
+        "/* open",
+    ] {
+        assert!(tokenize(source).is_err(), "{source:?} should not tokenize");
+    }
+}
+
+/// The categories `kind` falls in, in `[keyword, literal, operator, delimiter]` order.
+fn categories(kind: &TokenKind) -> [bool; 4] {
+    [
+        kind.is_keyword(),
+        kind.is_literal(),
+        kind.is_operator(),
+        kind.is_delimiter(),
+    ]
+}
+
+#[test]
+fn token_kinds_classify_into_categories() {
+    let kinds = |source: &str| -> Vec<TokenKind> {
+        tokenize(source)
+            .unwrap()
+            .into_iter()
+            .map(|token| token.kind)
+            .filter(|kind| !matches!(kind, TokenKind::Eof))
+            .collect()
+    };
+
+    let keywords = "func val mut const as if else return while loop for in break continue \
+                    struct enum impl trait dyn import export module match let where type \
+                    newtype unsafe move self Self";
+    for kind in kinds(keywords) {
+        assert_eq!(categories(&kind), [true, false, false, false], "{kind:?}");
+    }
+    for kind in kinds("42 7u8 1.5 2.0f32 \"s\" 'c'") {
+        assert_eq!(categories(&kind), [false, true, false, false], "{kind:?}");
+    }
+    for kind in kinds("+ += == < << && | ! = -> => :: . .. ..= ??") {
+        assert_eq!(categories(&kind), [false, false, true, false], "{kind:?}");
+    }
+    for kind in kinds("( ) { } [ ] , : ;") {
+        assert_eq!(categories(&kind), [false, false, false, true], "{kind:?}");
+    }
+    // `true` and `false` are reserved words and boolean literals.
+    for kind in kinds("true false") {
+        assert_eq!(categories(&kind), [true, true, false, false], "{kind:?}");
+    }
+    for kind in kinds("name 'a @") {
+        assert_eq!(categories(&kind), [false; 4], "{kind:?}");
+    }
+}
+
 #[test]
 fn stress_test_large_input() {
     let mut source = String::new();
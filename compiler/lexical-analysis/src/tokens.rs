@@ -6,7 +6,9 @@
 // keyword is missing there; rules with no one-to-one token (string bodies, escapes) are
 // not covered and must be updated by hand.
 
-use logos::Logos;
+use std::borrow::Cow;
+
+use logos::{FilterResult, Logos};
 use shared_types::{FloatSuffix, IntSuffix, Span};
 
 use crate::errors::LexError;
@@ -29,7 +31,9 @@ pub struct FloatSuffixToken {
 
 /// Token types in the Neuro language
 #[derive(Debug, Clone, PartialEq, Logos)]
-#[logos(skip r"[ \t\r]+")]
+// `\r` is skipped on its own so a whitespace run never swallows the `\r` of a
+// `\r\n` line ending, which belongs to the newline token.
+#[logos(skip r"[ \t]+|\r")]
 #[logos(error = LexError)]
 pub enum TokenKind {
     // Phase 1 Keywords
@@ -85,6 +89,8 @@ pub enum TokenKind {
     Module,
     #[token("match")]
     Match,
+    #[token("let")]
+    Let,
     #[token("where")]
     Where,
     #[token("type")]
@@ -104,7 +110,10 @@ pub enum TokenKind {
     #[regex(r"[_\p{XID_Start}]\p{XID_Continue}*", |lex| lex.slice().to_string())]
     Identifier(String),
 
-    // Number literals
+    // Number literals. A float needs `.` or an exponent straight after its leading
+    // decimal digits, so it never competes with a prefixed integer: in `0x1e5` the `x`
+    // ends the float's digit run, and the hex pattern's longer match wins, reading `e`
+    // as a hex digit.
     #[regex(r"[0-9][0-9_]*\.[0-9][0-9_]*([eE][+-]?[0-9][0-9_]*)?", parse_float)]
     #[regex(r"[0-9][0-9_]*[eE][+-]?[0-9][0-9_]*", parse_float)]
     Float(f64),
@@ -159,7 +168,7 @@ pub enum TokenKind {
 
     // String literals (including potentially malformed ones for better error messages)
     #[regex(
-        r#""([^"\\\n]|\\[nrt\\"0xu]|\\u\{[0-9a-fA-F]+\}|\\x[0-9a-fA-F]{2})*""#,
+        r#""([^"\\\n]|\\[nrt\\"'0xu]|\\u\{[0-9a-fA-F]+\}|\\x[0-9a-fA-F]{2})*""#,
         parse_string,
         priority = 2
     )]
@@ -290,15 +299,135 @@ pub enum TokenKind {
     // Comments and whitespace
     #[regex(r"//[^\n]*", logos::skip)]
     _LineComment,
-    #[regex(r"/\*([^*]|\*[^/])*\*/", logos::skip)]
+    #[token("/*", skip_block_comment)]
     _BlockComment,
-    #[regex(r"\n+")]
+    // A run of line endings, LF or CRLF, is one token spanning all of them.
+    #[regex(r"(\r?\n)+")]
     Newline,
 
     // End of file
     Eof,
 }
 
+// Token categories, shared by the parser and by tooling such as syntax highlighters.
+// A kind belongs to at most one category, except `true` and `false`, which are both
+// reserved words and boolean literals. Identifiers, lifetimes, `@` (the attribute
+// sigil), newlines, and end of file belong to none.
+impl TokenKind {
+    /// Whether this is a reserved word, such as `func`, `while`, or `self`.
+    pub fn is_keyword(&self) -> bool {
+        matches!(
+            self,
+            TokenKind::Func
+                | TokenKind::Val
+                | TokenKind::Mut
+                | TokenKind::Const
+                | TokenKind::As
+                | TokenKind::If
+                | TokenKind::Else
+                | TokenKind::Return
+                | TokenKind::True
+                | TokenKind::False
+                | TokenKind::While
+                | TokenKind::Loop
+                | TokenKind::For
+                | TokenKind::In
+                | TokenKind::Break
+                | TokenKind::Continue
+                | TokenKind::Struct
+                | TokenKind::Enum
+                | TokenKind::Impl
+                | TokenKind::Trait
+                | TokenKind::Dyn
+                | TokenKind::Import
+                | TokenKind::Export
+                | TokenKind::Module
+                | TokenKind::Match
+                | TokenKind::Let
+                | TokenKind::Where
+                | TokenKind::Type
+                | TokenKind::Newtype
+                | TokenKind::Unsafe
+                | TokenKind::Move
+                | TokenKind::SelfLower
+                | TokenKind::SelfUpper
+        )
+    }
+
+    /// Whether this is a literal value: a number, string, or char literal, or a
+    /// boolean (`true` / `false`).
+    pub fn is_literal(&self) -> bool {
+        matches!(
+            self,
+            TokenKind::Integer(_)
+                | TokenKind::IntegerSuffix(_)
+                | TokenKind::Float(_)
+                | TokenKind::FloatSuffix(_)
+                | TokenKind::String(_)
+                | TokenKind::Char(_)
+                | TokenKind::True
+                | TokenKind::False
+        )
+    }
+
+    /// Whether this is an operator: arithmetic, comparison, logical, bitwise,
+    /// assignment (plain or compound), or one of `.`, `..`, `..=`, `::`, `->`, `=>`,
+    /// and `??`. The `as` cast is a keyword.
+    pub fn is_operator(&self) -> bool {
+        matches!(
+            self,
+            TokenKind::Plus
+                | TokenKind::Minus
+                | TokenKind::Star
+                | TokenKind::Slash
+                | TokenKind::Percent
+                | TokenKind::PlusEqual
+                | TokenKind::MinusEqual
+                | TokenKind::StarEqual
+                | TokenKind::SlashEqual
+                | TokenKind::PercentEqual
+                | TokenKind::EqualEqual
+                | TokenKind::NotEqual
+                | TokenKind::LessEqual
+                | TokenKind::GreaterEqual
+                | TokenKind::LeftShift
+                | TokenKind::Less
+                | TokenKind::Greater
+                | TokenKind::AmpAmp
+                | TokenKind::Amp
+                | TokenKind::PipePipe
+                | TokenKind::Pipe
+                | TokenKind::Caret
+                | TokenKind::Tilde
+                | TokenKind::Bang
+                | TokenKind::Equal
+                | TokenKind::Arrow
+                | TokenKind::FatArrow
+                | TokenKind::ColonColon
+                | TokenKind::DotDotEqual
+                | TokenKind::DotDot
+                | TokenKind::Dot
+                | TokenKind::QuestionQuestion
+        )
+    }
+
+    /// Whether this is a delimiter: a bracket of any kind, `,`, `:`, or `;`.
+    pub fn is_delimiter(&self) -> bool {
+        matches!(
+            self,
+            TokenKind::LeftParen
+                | TokenKind::RightParen
+                | TokenKind::LeftBrace
+                | TokenKind::RightBrace
+                | TokenKind::LeftBracket
+                | TokenKind::RightBracket
+                | TokenKind::Comma
+                | TokenKind::Colon
+                | TokenKind::Semicolon
+        )
+    }
+}
+
 /// A token with its kind and location
 #[derive(Debug, Clone, PartialEq)]
 pub struct Token {
@@ -339,6 +468,7 @@ impl Token {
             TokenKind::Export => "export",
             TokenKind::Module => "module",
             TokenKind::Match => "match",
+            TokenKind::Let => "let",
             TokenKind::Where => "where",
             TokenKind::Type => "type",
             TokenKind::Newtype => "newtype",
@@ -398,16 +528,28 @@ impl Token {
             TokenKind::Semicolon => ";",
             TokenKind::Newline => "<newline>",
             TokenKind::Eof => "<eof>",
-            TokenKind::_LineComment | TokenKind::_BlockComment => unreachable!(),
+            // Skipped by the lexer, so never in a token stream; named for completeness.
+            TokenKind::_LineComment => "<line comment>",
+            TokenKind::_BlockComment => "<block comment>",
         }
     }
 }
 
 // Literal parsing helper functions (tightly coupled to TokenKind)
 
+/// Strip `_` digit separators, borrowing the slice unchanged in the common case
+/// where it has none so plain literals parse without an allocation.
+fn strip_separators(digits: &str) -> Cow<'_, str> {
+    if digits.contains('_') {
+        Cow::Owned(digits.replace('_', ""))
+    } else {
+        Cow::Borrowed(digits)
+    }
+}
+
 /// Helper function to parse float literals
 fn parse_float(lex: &mut logos::Lexer<TokenKind>) -> Result<f64, LexError> {
-    let slice = lex.slice().replace('_', "");
+    let slice = strip_separators(lex.slice());
     slice.parse::<f64>().map_err(|_| LexError::InvalidNumber {
         text: lex.slice().to_string(),
         span: Span::new(lex.span().start, lex.span().end),
@@ -416,7 +558,7 @@ fn parse_float(lex: &mut logos::Lexer<TokenKind>) -> Result<f64, LexError> {
 
 /// Helper function to parse decimal integer literals
 fn parse_decimal(lex: &mut logos::Lexer<TokenKind>) -> Result<i64, LexError> {
-    let slice = lex.slice().replace('_', "");
+    let slice = strip_separators(lex.slice());
     slice.parse::<i64>().map_err(|_| LexError::InvalidNumber {
         text: lex.slice().to_string(),
         span: Span::new(lex.span().start, lex.span().end),
@@ -425,7 +567,7 @@ fn parse_decimal(lex: &mut logos::Lexer<TokenKind>) -> Result<i64, LexError> {
 
 /// Helper function to parse binary integer literals
 fn parse_binary(lex: &mut logos::Lexer<TokenKind>) -> Result<i64, LexError> {
-    let slice = lex.slice()[2..].replace('_', ""); // Skip "0b" prefix
+    let slice = strip_separators(&lex.slice()[2..]); // Skip "0b" prefix
     i64::from_str_radix(&slice, 2).map_err(|_| LexError::InvalidNumber {
         text: lex.slice().to_string(),
         span: Span::new(lex.span().start, lex.span().end),
@@ -434,7 +576,7 @@ fn parse_binary(lex: &mut logos::Lexer<TokenKind>) -> Result<i64, LexError> {
 
 /// Helper function to parse octal integer literals
 fn parse_octal(lex: &mut logos::Lexer<TokenKind>) -> Result<i64, LexError> {
-    let slice = lex.slice()[2..].replace('_', ""); // Skip "0o" prefix
+    let slice = strip_separators(&lex.slice()[2..]); // Skip "0o" prefix
     i64::from_str_radix(&slice, 8).map_err(|_| LexError::InvalidNumber {
         text: lex.slice().to_string(),
         span: Span::new(lex.span().start, lex.span().end),
@@ -443,7 +585,7 @@ fn parse_octal(lex: &mut logos::Lexer<TokenKind>) -> Result<i64, LexError> {
 
 /// Helper function to parse hexadecimal integer literals
 fn parse_hex(lex: &mut logos::Lexer<TokenKind>) -> Result<i64, LexError> {
-    let slice = lex.slice()[2..].replace('_', ""); // Skip "0x" prefix
+    let slice = strip_separators(&lex.slice()[2..]); // Skip "0x" prefix
     i64::from_str_radix(&slice, 16).map_err(|_| LexError::InvalidNumber {
         text: lex.slice().to_string(),
         span: Span::new(lex.span().start, lex.span().end),
@@ -455,7 +597,12 @@ fn parse_string(lex: &mut logos::Lexer<TokenKind>) -> Result<String, LexError> {
     let slice = lex.slice();
     let content = &slice[1..slice.len() - 1]; // Strip quotes
 
-    let mut result = String::new();
+    // Most literals carry no escapes: copy them in one exact-size allocation.
+    if !content.contains('\\') {
+        return Ok(content.to_string());
+    }
+
+    let mut result = String::with_capacity(content.len());
     let mut chars = content.chars().peekable();
 
     while let Some(ch) = chars.next() {
@@ -466,6 +613,9 @@ fn parse_string(lex: &mut logos::Lexer<TokenKind>) -> Result<String, LexError> {
                 Some('t') => result.push('\t'),
                 Some('\\') => result.push('\\'),
                 Some('"') => result.push('"'),
+                // Needed only in char literals; accepted here so both quote
+                // characters escape the same way everywhere.
+                Some('\'') => result.push('\''),
                 Some('0') => result.push('\0'),
                 Some('x') => {
                     // Hex escape: \xNN
@@ -536,6 +686,25 @@ fn parse_string(lex: &mut logos::Lexer<TokenKind>) -> Result<String, LexError> {
     Ok(result)
 }
 
+/// Skip a block comment from its `/*` through the first `*/`; comments do not
+/// nest. Without a closing `*/` the rest of the source is consumed and reported as
+/// `UnterminatedBlockComment`, spanning from the `/*` to the end of the input.
+fn skip_block_comment(lex: &mut logos::Lexer<TokenKind>) -> FilterResult<(), LexError> {
+    let remainder = lex.remainder();
+    match remainder.find("*/") {
+        Some(close) => {
+            lex.bump(close + 2);
+            FilterResult::Skip
+        }
+        None => {
+            lex.bump(remainder.len());
+            FilterResult::Error(LexError::UnterminatedBlockComment {
+                span: Span::new(lex.span().start, lex.span().end),
+            })
+        }
+    }
+}
+
 /// Catch-all parser for strings that may have invalid escape sequences
 /// This pattern only matches strings with closing quotes
 fn parse_string_catch_all(lex: &mut logos::Lexer<TokenKind>) -> Result<String, LexError> {
@@ -589,68 +758,71 @@ fn parse_char(lex: &mut logos::Lexer<TokenKind>) -> Result<char, LexError> {
 
 // ── Suffixed integer helpers ──────────────────────────────────────────────────
 
-/// Maps the suffix string (e.g. "i64") to `IntSuffix`. Panics for unexpected
-/// inputs — the logos regex guarantees the suffix is one of the eight variants.
-fn parse_int_suffix(suffix: &str) -> IntSuffix {
+/// Maps the suffix string (e.g. "i64") to `IntSuffix`. The logos regex only
+/// admits the eight suffixes, but a mismatch is still `None` rather than a panic
+/// so the callers can report it as an invalid number.
+fn parse_int_suffix(suffix: &str) -> Option<IntSuffix> {
     match suffix {
-        "i8" => IntSuffix::I8,
-        "i16" => IntSuffix::I16,
-        "i32" => IntSuffix::I32,
-        "i64" => IntSuffix::I64,
-        "u8" => IntSuffix::U8,
-        "u16" => IntSuffix::U16,
-        "u32" => IntSuffix::U32,
-        "u64" => IntSuffix::U64,
-        // Safety: the regex only admits the eight suffixes above.
-        _ => unreachable!("unexpected suffix '{}'", suffix),
+        "i8" => Some(IntSuffix::I8),
+        "i16" => Some(IntSuffix::I16),
+        "i32" => Some(IntSuffix::I32),
+        "i64" => Some(IntSuffix::I64),
+        "u8" => Some(IntSuffix::U8),
+        "u16" => Some(IntSuffix::U16),
+        "u32" => Some(IntSuffix::U32),
+        "u64" => Some(IntSuffix::U64),
+        _ => None,
     }
 }
 
 fn parse_decimal_suffix(lex: &mut logos::Lexer<TokenKind>) -> Result<IntegerSuffixToken, LexError> {
     let raw = lex.slice();
-    let suffix_start = raw.find(|c: char| c.is_alphabetic()).unwrap_or(raw.len());
-    let digits = raw[..suffix_start].replace('_', "");
-    let value = digits.parse::<i64>().map_err(|_| LexError::InvalidNumber {
+    let invalid = || LexError::InvalidNumber {
         text: raw.to_string(),
         span: Span::new(lex.span().start, lex.span().end),
-    })?;
+    };
+    let suffix_start = raw.find(|c: char| c.is_alphabetic()).unwrap_or(raw.len());
+    let digits = strip_separators(&raw[..suffix_start]);
+    let value = digits.parse::<i64>().map_err(|_| invalid())?;
     Ok(IntegerSuffixToken {
         value,
-        suffix: parse_int_suffix(&raw[suffix_start..]),
+        suffix: parse_int_suffix(&raw[suffix_start..]).ok_or_else(invalid)?,
     })
 }
 
 fn parse_binary_suffix(lex: &mut logos::Lexer<TokenKind>) -> Result<IntegerSuffixToken, LexError> {
     let raw = lex.slice();
+    let invalid = || LexError::InvalidNumber {
+        text: raw.to_string(),
+        span: Span::new(lex.span().start, lex.span().end),
+    };
     let suffix_start = raw[2..]
         .find(|c: char| c.is_alphabetic())
         .map(|i| i + 2)
         .unwrap_or(raw.len());
-    let digits = raw[2..suffix_start].replace('_', "");
-    let value = i64::from_str_radix(&digits, 2).map_err(|_| LexError::InvalidNumber {
-        text: raw.to_string(),
-        span: Span::new(lex.span().start, lex.span().end),
-    })?;
+    let digits = strip_separators(&raw[2..suffix_start]);
+    let value = i64::from_str_radix(&digits, 2).map_err(|_| invalid())?;
     Ok(IntegerSuffixToken {
         value,
-        suffix: parse_int_suffix(&raw[suffix_start..]),
+        suffix: parse_int_suffix(&raw[suffix_start..]).ok_or_else(invalid)?,
     })
 }
 
 fn parse_octal_suffix(lex: &mut logos::Lexer<TokenKind>) -> Result<IntegerSuffixToken, LexError> {
     let raw = lex.slice();
+    let invalid = || LexError::InvalidNumber {
+        text: raw.to_string(),
+        span: Span::new(lex.span().start, lex.span().end),
+    };
     let suffix_start = raw[2..]
         .find(|c: char| c.is_alphabetic())
         .map(|i| i + 2)
         .unwrap_or(raw.len());
-    let digits = raw[2..suffix_start].replace('_', "");
-    let value = i64::from_str_radix(&digits, 8).map_err(|_| LexError::InvalidNumber {
-        text: raw.to_string(),
-        span: Span::new(lex.span().start, lex.span().end),
-    })?;
+    let digits = strip_separators(&raw[2..suffix_start]);
+    let value = i64::from_str_radix(&digits, 8).map_err(|_| invalid())?;
     Ok(IntegerSuffixToken {
         value,
-        suffix: parse_int_suffix(&raw[suffix_start..]),
+        suffix: parse_int_suffix(&raw[suffix_start..]).ok_or_else(invalid)?,
     })
 }
 
@@ -686,8 +858,7 @@ fn parse_fractional_float_suffix(
     };
     // Safety: the regex only admits the four recognized suffixes.
     let (digits, suffix) = split_float_suffix(raw).ok_or_else(invalid)?;
-    let value = digits
-        .replace('_', "")
+    let value = strip_separators(digits)
         .parse::<f64>()
         .map_err(|_| invalid())?;
     Ok(FloatSuffixToken { value, suffix })
@@ -695,19 +866,20 @@ fn parse_fractional_float_suffix(
 
 fn parse_hex_suffix(lex: &mut logos::Lexer<TokenKind>) -> Result<IntegerSuffixToken, LexError> {
     let raw = lex.slice();
+    let invalid = || LexError::InvalidNumber {
+        text: raw.to_string(),
+        span: Span::new(lex.span().start, lex.span().end),
+    };
     // Skip "0x" prefix; find first alphabetic that is NOT a hex digit (a-f/A-F)
     let after_prefix = &raw[2..];
     let suffix_start = after_prefix
         .find(|c: char| c.is_alphabetic() && !matches!(c, 'a'..='f' | 'A'..='F'))
         .map(|i| i + 2)
         .unwrap_or(raw.len());
-    let digits = raw[2..suffix_start].replace('_', "");
-    let value = i64::from_str_radix(&digits, 16).map_err(|_| LexError::InvalidNumber {
-        text: raw.to_string(),
-        span: Span::new(lex.span().start, lex.span().end),
-    })?;
+    let digits = strip_separators(&raw[2..suffix_start]);
+    let value = i64::from_str_radix(&digits, 16).map_err(|_| invalid())?;
     Ok(IntegerSuffixToken {
         value,
-        suffix: parse_int_suffix(&raw[suffix_start..]),
+        suffix: parse_int_suffix(&raw[suffix_start..]).ok_or_else(invalid)?,
     })
 }
@@ -0,0 +1,172 @@
+//! Guards the lexer's literal fast paths against the straightforward decoding they
+//! replaced.
+//!
+//! Number callbacks borrow the source slice when it has no `_` separators, and string
+//! callbacks copy an escape-free body in one allocation instead of decoding it char by
+//! char. Both are pure optimizations: every literal token must carry exactly the value
+//! the reference decoders below derive from its source text — the allocate-and-strip /
+//! decode-every-char logic the lexer used before. The corpus is every example program
+//! plus an edge-case block covering each literal form.
+
+use std::path::{Path, PathBuf};
+
+use lexical_analysis::{tokenize, TokenKind};
+
+/// Literal forms the example programs may not exercise: separators in every radix,
+/// every suffix, and every escape.
+const EDGE_CASES: &str = r#"
+val a = 1_000_000
+val b = 0xFF_FF
+val c = 0o7_7
+val d = 0b1010_1010
+val e = 255u8
+val f = 1_000i64
+val g = 0xFF_u32
+val h = 0b1_0i8
+val i = 0o1_7u16
+val j = 3.141_592
+val k = 1_0e1_0
+val l = 2.5f32
+val m = 1_0.0_1f64
+val n = 1e3bf16
+val o = 0.5f16
+val p = "plain text"
+val q = "tab\there\nnewline \"quoted\" it\'s back\\slash \0 \x41 \u{1F600}"
+val r = ""
+"#;
+
+fn workspace_root() -> PathBuf {
+    // CARGO_MANIFEST_DIR = <root>/compiler/lexical-analysis
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../..")
+}
+
+fn collect_sources(dir: &Path, out: &mut Vec<(PathBuf, String)>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_sources(&path, out);
+        } else if path.extension().is_some_and(|ext| ext == "nr") {
+            let source = std::fs::read_to_string(&path)
+                .unwrap_or_else(|e| panic!("cannot read {}: {e}", path.display()));
+            out.push((path, source));
+        }
+    }
+}
+
+fn reference_int(text: &str) -> i64 {
+    let digits = text.replace('_', "");
+    let (body, radix) = match digits.get(..2) {
+        Some("0x" | "0X") => (&digits[2..], 16),
+        Some("0o" | "0O") => (&digits[2..], 8),
+        Some("0b" | "0B") => (&digits[2..], 2),
+        _ => (digits.as_str(), 10),
+    };
+    i64::from_str_radix(body, radix).unwrap_or_else(|e| panic!("bad integer `{text}`: {e}"))
+}
+
+fn strip_suffix<'a>(text: &'a str, suffixes: &[&str]) -> &'a str {
+    suffixes
+        .iter()
+        .find_map(|suffix| text.strip_suffix(suffix))
+        .unwrap_or_else(|| panic!("`{text}` has no expected suffix"))
+}
+
+fn reference_float(text: &str) -> f64 {
+    text.replace('_', "")
+        .parse()
+        .unwrap_or_else(|e| panic!("bad float `{text}`: {e}"))
+}
+
+/// Decode a string literal body (quotes stripped) one character at a time.
+fn reference_string(body: &str) -> String {
+    let mut out = String::new();
+    let mut chars = body.chars();
+    while let Some(ch) = chars.next() {
+        if ch != '\\' {
+            out.push(ch);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some('r') => out.push('\r'),
+            Some('t') => out.push('\t'),
+            Some('\\') => out.push('\\'),
+            Some('"') => out.push('"'),
+            Some('\'') => out.push('\''),
+            Some('0') => out.push('\0'),
+            Some('x') => {
+                let hex: String = chars.by_ref().take(2).collect();
+                let code = u8::from_str_radix(&hex, 16).expect("lexer accepted a bad \\x escape");
+                out.push(code as char);
+            }
+            Some('u') => {
+                let hex: String = chars.by_ref().take_while(|&c| c != '}').skip(1).collect();
+                let code = u32::from_str_radix(&hex, 16).expect("lexer accepted a bad \\u escape");
+                out.push(char::from_u32(code).expect("lexer accepted a non-scalar \\u escape"));
+            }
+            other => panic!("lexer accepted an invalid escape {other:?}"),
+        }
+    }
+    out
+}
+
+const INT_SUFFIXES: &[&str] = &["i16", "i32", "i64", "u16", "u32", "u64", "i8", "u8"];
+const FLOAT_SUFFIXES: &[&str] = &["bf16", "f16", "f32", "f64"];
+
+/// Tokenize `source` and check every literal token against the reference decoders.
+/// Returns how many literal tokens were checked.
+fn check_literals(name: &str, source: &str) -> usize {
+    let tokens = tokenize(source).unwrap_or_else(|e| panic!("{name} failed to tokenize: {e:?}"));
+    let mut checked = 0;
+    for token in &tokens {
+        let text = &source[token.span.start..token.span.end];
+        match &token.kind {
+            TokenKind::Integer(value) => {
+                assert_eq!(*value, reference_int(text), "{name}: integer `{text}`");
+            }
+            TokenKind::IntegerSuffix(lit) => {
+                let digits = strip_suffix(text, INT_SUFFIXES);
+                assert_eq!(lit.value, reference_int(digits), "{name}: integer `{text}`");
+            }
+            TokenKind::Float(value) => {
+                assert_eq!(*value, reference_float(text), "{name}: float `{text}`");
+            }
+            TokenKind::FloatSuffix(lit) => {
+                let digits = strip_suffix(text, FLOAT_SUFFIXES);
+                assert_eq!(lit.value, reference_float(digits), "{name}: float `{text}`");
+            }
+            TokenKind::String(value) => {
+                let body = &text[1..text.len() - 1];
+                assert_eq!(*value, reference_string(body), "{name}: string {text}");
+            }
+            _ => continue,
+        }
+        checked += 1;
+    }
+    checked
+}
+
+#[test]
+fn edge_case_literals_match_reference_decoding() {
+    // One literal per `val` line.
+    assert_eq!(check_literals("edge cases", EDGE_CASES), 18);
+}
+
+#[test]
+fn example_corpus_literals_match_reference_decoding() {
+    let mut sources = Vec::new();
+    collect_sources(&workspace_root().join("examples"), &mut sources);
+    assert!(
+        !sources.is_empty(),
+        "no .nr files found under examples/ — the corpus moved and this test no longer sees it"
+    );
+
+    let checked: usize = sources
+        .iter()
+        .map(|(path, source)| check_literals(&path.display().to_string(), source))
+        .sum();
+    assert!(checked > 0, "the example corpus contains no literal tokens");
+}
@@ -0,0 +1,72 @@
+// Codegen for the `black_box(value)` builtin: an identity the optimizer cannot see
+// through.
+//
+// The value round-trips through a stack slot with a volatile store and a volatile
+// load. LLVM may neither drop a volatile access nor assume what it reads, so the
+// computation that produced the value stays live and the result is opaque to later
+// folding. The slot lives in the function's entry block, so a call inside a loop
+// does not grow the stack per iteration.
+
+use inkwell::values::{BasicValue, BasicValueEnum};
+use neuro_hir::HirExpr;
+
+use crate::codegen::context::CodegenContext;
+use crate::errors::{CodegenError, CodegenResult};
+use crate::types::Type;
+
+impl<'ctx> CodegenContext<'ctx> {
+    /// True when `name` is the `black_box` builtin. Mirrors the resolver in
+    /// `semantic-analysis`, as [`CodegenContext::is_panic_builtin`] does.
+    pub(crate) fn is_black_box_builtin(name: &str) -> bool {
+        name == "black_box"
+    }
+
+    /// Lower `black_box(value)`. A `void` argument has no value to hide; it is
+    /// evaluated for its effects and the call yields `None`.
+    pub(crate) fn codegen_black_box(
+        &mut self,
+        args: &[HirExpr],
+    ) -> CodegenResult<Option<BasicValueEnum<'ctx>>> {
+        let value = args.first().ok_or_else(|| {
+            CodegenError::InternalError("black_box() reached codegen without a value".into())
+        })?;
+        let ty = Type::from_hir(&value.ty);
+        let computed = self.codegen_expr(value)?;
+        if matches!(ty, Type::Void) {
+            return Ok(None);
+        }
+
+        let llvm_ty = self.get_any_llvm_type(&ty)?;
+        let function = self
+            .current_function
+            .ok_or_else(|| CodegenError::InternalError("black_box() outside a function".into()))?;
+        let entry = function.get_first_basic_block().ok_or_else(|| {
+            CodegenError::InternalError("black_box() in a function with no entry block".into())
+        })?;
+        let entry_builder = self.context.create_builder();
+        match entry.get_first_instruction() {
+            Some(first) => entry_builder.position_before(&first),
+            None => entry_builder.position_at_end(entry),
+        }
+        let slot = entry_builder
+            .build_alloca(llvm_ty, "black_box.slot")
+            .map_err(|e| CodegenError::LlvmError(e.to_string()))?;
+
+        let store = self
+            .builder
+            .build_store(slot, computed)
+            .map_err(|e| CodegenError::LlvmError(e.to_string()))?;
+        store
+            .set_volatile(true)
+            .map_err(|e| CodegenError::LlvmError(e.to_string()))?;
+        let loaded = self
+            .builder
+            .build_load(llvm_ty, slot, "black_box")
+            .map_err(|e| CodegenError::LlvmError(e.to_string()))?;
+        if let Some(load) = loaded.as_instruction_value() {
+            load.set_volatile(true)
+                .map_err(|e| CodegenError::LlvmError(e.to_string()))?;
+        }
+        Ok(Some(loaded))
+    }
+}
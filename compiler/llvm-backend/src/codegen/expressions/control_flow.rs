@@ -2,7 +2,7 @@
 
 use inkwell::basic_block::BasicBlock;
 use inkwell::values::*;
-use neuro_hir::{HirExpr, HirStmt};
+use neuro_hir::{HirExpr, HirStmt, HirType};
 
 use crate::codegen::context::CodegenContext;
 use crate::errors::{CodegenError, CodegenResult};
@@ -173,16 +173,21 @@ impl<'ctx> CodegenContext<'ctx> {
             }
             self.codegen_stmt(stmt)?;
         }
-        let result = if let HirStmt::Expr(expr) = last {
-            let val = self.codegen_expr(expr)?;
-            // The yielded place escapes the block, so it is moved out, not dropped here.
-            self.mark_moved_for_drop(expr);
-            val
-        } else {
-            if !self.current_block_terminated() {
-                self.codegen_stmt(last)?;
+        let result = match last {
+            // A unit-typed tail (e.g. a `void` call in an `if let` body) yields no
+            // value; it falls through to statement codegen below.
+            HirStmt::Expr(expr) if !matches!(expr.ty, HirType::Void) => {
+                let val = self.codegen_expr(expr)?;
+                // The yielded place escapes the block, so it is moved out, not dropped here.
+                self.mark_moved_for_drop(expr);
+                val
+            }
+            _ => {
+                if !self.current_block_terminated() {
+                    self.codegen_stmt(last)?;
+                }
+                self.context.i32_type().const_int(0, false).into()
             }
-            self.context.i32_type().const_int(0, false).into()
         };
         if !self.current_block_terminated() {
             self.emit_top_scope_drops()?;
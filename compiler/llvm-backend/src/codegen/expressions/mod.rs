@@ -44,15 +44,20 @@ impl<'ctx> CodegenContext<'ctx> {
                 self.codegen_unary(*op, operand, &operand_ty)
             }
             HirExprKind::Call { callee, args } => {
-                // In value position a unit-returning call is an error — there is no
-                // value to bind. Statement position discards the result instead
-                // (see `codegen_call_dispatch` callers in `codegen_stmt`).
-                self.codegen_call_dispatch(callee, args, &expr.span)?
-                    .ok_or_else(|| {
-                        CodegenError::InternalError(
-                            "function call returned void when value expected".to_string(),
-                        )
-                    })
+                // The checker accepts a unit-returning call in value position only when
+                // the callee never returns (its type is `never`), so control cannot get
+                // past it. Close the block with abort + `unreachable` and hand back a
+                // placeholder the caller discards, exactly as for `panic`. Statement
+                // position discards the result instead (see `codegen_stmt`).
+                match self.codegen_call_dispatch(callee, args, &expr.span)? {
+                    Some(value) => Ok(value),
+                    None => {
+                        if !self.current_block_terminated() {
+                            self.emit_abort_unreachable()?;
+                        }
+                        Ok(self.context.i32_type().const_int(0, false).into())
+                    }
+                }
             }
 
             HirExprKind::Path { .. } => {
@@ -209,6 +214,10 @@ impl<'ctx> CodegenContext<'ctx> {
                 if CodegenContext::is_panic_builtin(name) && !self.functions.contains_key(name) {
                     return Ok(Some(self.codegen_panic_builtin(name, args, *span)?));
                 }
+                if CodegenContext::is_black_box_builtin(name) && !self.functions.contains_key(name)
+                {
+                    return self.codegen_black_box(args);
+                }
                 self.codegen_call(name, args)
             }
 
@@ -262,6 +271,12 @@ impl<'ctx> CodegenContext<'ctx> {
                 self.codegen_call(&mangled, args)
             }
 
+            // Any other function-typed callee, such as the result of another call
+            // (`make_adder()(1)`), is a fat pointer called indirectly.
+            _ if matches!(callee.ty, neuro_hir::HirType::Function { .. }) => {
+                self.codegen_indirect_call(callee, args)
+            }
+
             _ => Err(CodegenError::UnsupportedType(
                 "unsupported call expression".to_string(),
             )),
@@ -1,3 +1,4 @@
+use inkwell::attributes::{Attribute, AttributeLoc};
 use inkwell::types::*;
 use inkwell::values::*;
 use neuro_hir::{HirExpr, HirFunction, HirImpl, HirMethod, HirSelfParam, HirStmt};
@@ -301,6 +302,50 @@ impl<'ctx> CodegenContext<'ctx> {
         self.codegen_body(&method.body, return_type)
     }
 
+    /// Mark every defined function for which `unoptimized(name)` holds as
+    /// `optnone noinline`, so the module pass pipeline leaves it exactly as emitted.
+    /// LLVM requires `noinline` alongside `optnone`; declarations are skipped.
+    /// Returns the functions marked, for [`Self::restore_optimization`].
+    pub(crate) fn exclude_from_optimization(
+        &self,
+        unoptimized: impl Fn(&str) -> bool,
+    ) -> Vec<FunctionValue<'ctx>> {
+        let optnone = self
+            .context
+            .create_enum_attribute(Attribute::get_named_enum_kind_id("optnone"), 0);
+        let noinline = self
+            .context
+            .create_enum_attribute(Attribute::get_named_enum_kind_id("noinline"), 0);
+        let mut excluded = Vec::new();
+        for function in self.module.get_functions() {
+            if function.count_basic_blocks() == 0 {
+                continue;
+            }
+            let Ok(name) = function.get_name().to_str() else {
+                continue;
+            };
+            if unoptimized(name) {
+                function.add_attribute(AttributeLoc::Function, optnone);
+                function.add_attribute(AttributeLoc::Function, noinline);
+                excluded.push(function);
+            }
+        }
+        excluded
+    }
+
+    /// Undo [`Self::exclude_from_optimization`] for `functions`, so the next pass
+    /// pipeline run optimizes them again.
+    pub(crate) fn restore_optimization(&self, functions: &[FunctionValue<'ctx>]) {
+        for function in functions {
+            for kind in ["optnone", "noinline"] {
+                function.remove_enum_attribute(
+                    AttributeLoc::Function,
+                    Attribute::get_named_enum_kind_id(kind),
+                );
+            }
+        }
+    }
+
     /// Declare a function's LLVM signature (no body) and record it so call sites can
     /// resolve it. Run in a pre-pass over every item before any body is generated, so
     /// that a call resolves regardless of definition order — required because a
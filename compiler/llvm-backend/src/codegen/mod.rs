@@ -1,3 +1,4 @@
+pub(crate) mod black_box;
 pub(crate) mod closures;
 pub(crate) mod context;
 pub(crate) mod dispatch;
@@ -178,7 +178,7 @@ impl<'ctx> CodegenContext<'ctx> {
     }
 
     /// Emit `abort()` followed by an `unreachable` terminator, ending the basic block.
-    fn emit_abort_unreachable(&self) -> CodegenResult<()> {
+    pub(crate) fn emit_abort_unreachable(&self) -> CodegenResult<()> {
         let abort_fn = self.get_or_declare_abort();
         self.builder
             .build_call(abort_fn, &[], "panic.abort")
@@ -34,6 +34,9 @@ pub enum CodegenError {
 
     #[error("invalid optimization level: {0} (expected 0..=3)")]
     InvalidOptimizationLevel(u8),
+
+    #[error("target {0} is not available in this build (enable the `wasm` feature)")]
+    UnsupportedTarget(String),
 }
 
 /// Result type for code generation operations
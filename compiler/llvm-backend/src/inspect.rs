@@ -0,0 +1,139 @@
+// Read-only inspection of emitted code: the function symbols of an object file and
+// per-function slices of the assembly listing. Backs `neurc disasm`.
+
+use object::{Object, ObjectSection, ObjectSymbol, SectionIndex, SymbolKind};
+
+use crate::errors::{CodegenError, CodegenResult};
+
+/// A function defined in an emitted object file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FunctionSymbol {
+    /// Symbol name exactly as the object records it (Mach-O keeps its leading `_`).
+    pub name: String,
+    /// Offset of the function within its section.
+    pub address: u64,
+    /// Size of the function body in bytes.
+    pub size: u64,
+}
+
+/// List the functions an object file defines, ordered by section and address.
+///
+/// ELF records symbol sizes directly. Mach-O and COFF do not, so a zero size is
+/// filled in as the distance to the next function in the same section (or to the
+/// section end for the last one).
+pub fn function_symbols(object_code: &[u8]) -> CodegenResult<Vec<FunctionSymbol>> {
+    let file = object::File::parse(object_code)
+        .map_err(|e| CodegenError::LlvmError(format!("failed to read object file: {}", e)))?;
+
+    let mut found: Vec<(SectionIndex, FunctionSymbol)> = Vec::new();
+    for symbol in file.symbols() {
+        if symbol.kind() != SymbolKind::Text || !symbol.is_definition() {
+            continue;
+        }
+        let (Some(section), Ok(name)) = (symbol.section_index(), symbol.name()) else {
+            continue;
+        };
+        found.push((
+            section,
+            FunctionSymbol {
+                name: name.to_string(),
+                address: symbol.address(),
+                size: symbol.size(),
+            },
+        ));
+    }
+    found.sort_by_key(|(section, symbol)| (section.0, symbol.address));
+
+    for i in 0..found.len() {
+        if found[i].1.size != 0 {
+            continue;
+        }
+        let (section, address) = (found[i].0, found[i].1.address);
+        let end = match found.get(i + 1) {
+            Some((next_section, next)) if *next_section == section => next.address,
+            _ => file
+                .section_by_index(section)
+                .map(|s| s.address() + s.size())
+                .unwrap_or(address),
+        };
+        found[i].1.size = end.saturating_sub(address);
+    }
+
+    Ok(found.into_iter().map(|(_, symbol)| symbol).collect())
+}
+
+/// Slice one function's body out of an assembly listing produced by
+/// [`compile_assembly`](crate::compile_assembly): from its `name:` label up to the
+/// end-of-function marker LLVM emits after it. Returns `None` when the listing has
+/// no label for `name`.
+pub fn function_assembly<'a>(assembly: &'a str, name: &str) -> Option<&'a str> {
+    let label = format!("{}:", name);
+    let mut offset = 0;
+    let mut start = None;
+    for line in assembly.split_inclusive('\n') {
+        let trimmed = line.trim();
+        match start {
+            // LLVM follows the label with a `# @name` comment.
+            None if trimmed.split_whitespace().next() == Some(label.as_str()) => {
+                start = Some(offset)
+            }
+            Some(begin) if is_function_end(trimmed) => return Some(&assembly[begin..offset]),
+            _ => {}
+        }
+        offset += line.len();
+    }
+    start.map(|begin| &assembly[begin..])
+}
+
+/// The first line LLVM prints after a function's last instruction: the
+/// `.Lfunc_endN` label on ELF, `Lfunc_endN` on Mach-O, or the unwind-info close.
+fn is_function_end(line: &str) -> bool {
+    line.starts_with(".Lfunc_end")
+        || line.starts_with("Lfunc_end")
+        || line.starts_with(".cfi_endproc")
+        || line.starts_with(".seh_endproc")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LISTING: &str = "\t.text\n\
+        \t.globl\tadd\n\
+        add:                                    # @add\n\
+        \t.cfi_startproc\n\
+        \tleal\t(%rdi,%rsi), %eax\n\
+        \tretq\n\
+        .Lfunc_end0:\n\
+        \t.size\tadd, .Lfunc_end0-add\n\
+        \t.cfi_endproc\n\
+        \t.globl\tmain\n\
+        main:\n\
+        \tmovl\t$42, %eax\n\
+        \tretq\n\
+        .Lfunc_end1:\n";
+
+    #[test]
+    fn function_assembly_slices_label_to_function_end() {
+        let body = function_assembly(LISTING, "add").expect("add is listed");
+        assert!(body.starts_with("add:"));
+        assert!(body.contains("leal"));
+        assert!(!body.contains(".Lfunc_end0"));
+        assert!(!body.contains("main"));
+
+        let body = function_assembly(LISTING, "main").expect("main is listed");
+        assert!(body.contains("movl\t$42, %eax"));
+    }
+
+    #[test]
+    fn function_assembly_ignores_label_prefixes() {
+        // `ma:` must not match the start of `main:`, and an unknown name finds nothing.
+        assert_eq!(function_assembly(LISTING, "ma"), None);
+        assert_eq!(function_assembly(LISTING, "missing"), None);
+    }
+
+    #[test]
+    fn function_symbols_rejects_non_object_bytes() {
+        assert!(function_symbols(b"not an object file").is_err());
+    }
+}
@@ -1,13 +1,16 @@
 // Feature slice for LLVM IR generation and optimization.
-// Public API: the `compile()` entry point.
+// Public API: the `compile()` entry point, plus `compile_assembly()` and the
+// object/assembly inspection helpers behind `neurc disasm`.
 
 mod codegen;
 mod errors;
+mod inspect;
 mod softfloat;
 mod type_mapping;
 mod types;
 
 pub use errors::{CodegenError, CodegenResult};
+pub use inspect::{function_assembly, function_symbols, FunctionSymbol};
 
 use inkwell::context::Context as LLVMContext;
 use inkwell::OptimizationLevel as LlvmOptimizationLevel;
@@ -17,7 +20,7 @@ use types::Type;
 
 use codegen::CodegenContext;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum OptimizationLevelSetting {
     O0,
     O1,
@@ -44,6 +47,56 @@ impl OptimizationLevelSetting {
             Self::O3 => LlvmOptimizationLevel::Aggressive,
         }
     }
+
+    /// The new-pass-manager pipeline for this level; `None` at `-O0`.
+    fn pass_pipeline(self) -> Option<&'static str> {
+        match self {
+            Self::O0 => None,
+            Self::O1 => Some("default<O1>"),
+            Self::O2 => Some("default<O2>"),
+            Self::O3 => Some("default<O3>"),
+        }
+    }
+}
+
+/// The machine `compile()` generates code for.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CodegenTarget {
+    /// The host: an object file for the platform linker.
+    #[default]
+    Native,
+    /// `wasm32-unknown-unknown`: a WebAssembly object file for `wasm-ld`, with every
+    /// top-level function exported under its Neuro name. Requires the `wasm` feature.
+    Wasm32,
+}
+
+impl CodegenTarget {
+    /// The target triple `wasm32` compiles for.
+    pub const WASM32_TRIPLE: &'static str = "wasm32-unknown-unknown";
+}
+
+/// Backend settings for one `compile()` call.
+///
+/// Built from an [`OptimizationLevelSetting`] via `From`, which applies the
+/// level's defaults; individual knobs can then be overridden (`neurc -C ...`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CodegenOptions {
+    pub optimization: OptimizationLevelSetting,
+    /// Route integer `+`/`-`/`*` through the `with.overflow` intrinsics and trap
+    /// on overflow. Defaults to on at `-O0` and off (wrapping) otherwise.
+    pub overflow_checks: bool,
+    /// The machine to generate code for. Defaults to the host.
+    pub target: CodegenTarget,
+}
+
+impl From<OptimizationLevelSetting> for CodegenOptions {
+    fn from(optimization: OptimizationLevelSetting) -> Self {
+        Self {
+            optimization,
+            overflow_checks: optimization == OptimizationLevelSetting::O0,
+            target: CodegenTarget::Native,
+        }
+    }
 }
 
 /// Compile a typed HIR program to linkable LLVM object code.
@@ -54,10 +107,15 @@ impl OptimizationLevelSetting {
 ///
 /// # Arguments
 ///
-/// * `optimization` - Optimization level (also selects overflow trapping at -O0)
+/// * `options` - Optimization level plus codegen knobs; a bare
+///   [`OptimizationLevelSetting`] converts with that level's defaults (overflow
+///   trapping at -O0 only)
 /// * `source` / `source_path` - Original module text and path, used only to render
 ///   `file:line:col` in panic-family runtime diagnostics
 ///
+/// With [`CodegenTarget::Wasm32`] the result is a WebAssembly object file rather
+/// than a host one; `wasm-ld` links it into a module.
+///
 /// # Examples
 ///
 /// ```
@@ -74,10 +132,61 @@ impl OptimizationLevelSetting {
 /// ```
 pub fn compile(
     program: &HirProgram,
-    optimization: OptimizationLevelSetting,
+    options: impl Into<CodegenOptions>,
     source: &str,
     source_path: &str,
 ) -> CodegenResult<Vec<u8>> {
+    emit(
+        program,
+        options.into(),
+        source,
+        source_path,
+        inkwell::targets::FileType::Object,
+    )
+}
+
+/// Compile a typed HIR program to a native assembly listing.
+///
+/// Runs the same pipeline and target machine as [`compile`], printing assembly
+/// instead of writing an object file, so the listing matches the object code
+/// instruction for instruction. [`function_assembly`] slices one function out of it.
+pub fn compile_assembly(
+    program: &HirProgram,
+    options: impl Into<CodegenOptions>,
+    source: &str,
+    source_path: &str,
+) -> CodegenResult<String> {
+    let listing = emit(
+        program,
+        options.into(),
+        source,
+        source_path,
+        inkwell::targets::FileType::Assembly,
+    )?;
+    String::from_utf8(listing)
+        .map_err(|e| CodegenError::LlvmError(format!("assembly listing is not UTF-8: {}", e)))
+}
+
+/// The version of the LLVM library the backend runs on, as `major.minor.patch`.
+pub fn llvm_version() -> String {
+    let (major, minor, patch) = inkwell::support::get_llvm_version();
+    format!("{major}.{minor}.{patch}")
+}
+
+/// Generate code for `program` and emit it from the target machine for
+/// `options.target` as `file_type`.
+fn emit(
+    program: &HirProgram,
+    options: CodegenOptions,
+    source: &str,
+    source_path: &str,
+    file_type: inkwell::targets::FileType,
+) -> CodegenResult<Vec<u8>> {
+    let CodegenOptions {
+        optimization,
+        overflow_checks,
+        target: codegen_target,
+    } = options;
     let items = &program.items;
 
     // Collect struct definitions first so struct field/parameter types resolve below.
@@ -199,8 +308,9 @@ pub fn compile(
         source.to_string(),
     ));
 
-    // Debug builds (-O0) trap on integer overflow; release builds wrap.
-    codegen_ctx.set_overflow_checks(optimization == OptimizationLevelSetting::O0);
+    // Debug builds (-O0) trap on integer overflow and release builds wrap, unless
+    // the caller overrode the default.
+    codegen_ctx.set_overflow_checks(overflow_checks);
 
     // Emit module-level constants as LLVM global constants before any function.
     // This ensures all globals are defined before function bodies reference them.
@@ -259,6 +369,20 @@ pub fn compile(
             .map_err(CodegenError::LlvmError)?;
     }
 
+    // A WebAssembly module exposes only what it exports, so every top-level function
+    // is exported under its own name for the host (a browser, a wasm runtime) to call.
+    if codegen_target == CodegenTarget::Wasm32 {
+        for item in items {
+            if let HirItem::Function(func_def) = item {
+                if let Some(function) = codegen_ctx.module.get_function(&func_def.name) {
+                    let export =
+                        context.create_string_attribute("wasm-export-name", &func_def.name);
+                    function.add_attribute(inkwell::attributes::AttributeLoc::Function, export);
+                }
+            }
+        }
+    }
+
     // Verify the module
     if let Err(err) = codegen_ctx.module.verify() {
         return Err(CodegenError::LlvmError(format!(
@@ -267,10 +391,64 @@ pub fn compile(
         )));
     }
 
+    // `@optimize(N)` overrides the module level for one function. The module level
+    // itself only configures the target machine, so a pass pipeline runs just for the
+    // override levels that differ from it: once per such level, each time with every
+    // function at another level marked `optnone noinline`. The level-0 functions stay
+    // marked throughout. The target machine uses the highest level any function asks
+    // for, since instruction selection skips `optnone` functions either way.
+    let mut overrides: HashMap<&str, OptimizationLevelSetting> = HashMap::new();
+    for item in items {
+        if let HirItem::Function(func_def) = item {
+            if let Some(level) = func_def.optimize {
+                overrides.insert(
+                    func_def.name.as_str(),
+                    OptimizationLevelSetting::from_u8(level)?,
+                );
+            }
+        }
+    }
+    let level_of = |name: &str| overrides.get(name).copied().unwrap_or(optimization);
+    let mut pass_levels: Vec<OptimizationLevelSetting> = overrides
+        .values()
+        .copied()
+        .filter(|&level| level != optimization)
+        .collect();
+    pass_levels.sort();
+    pass_levels.dedup();
+    let machine_level = overrides
+        .values()
+        .copied()
+        .fold(optimization, OptimizationLevelSetting::max);
+    if machine_level != OptimizationLevelSetting::O0 {
+        codegen_ctx
+            .exclude_from_optimization(|name| level_of(name) == OptimizationLevelSetting::O0);
+    }
+
     // Generate object code
-    let target_triple = inkwell::targets::TargetMachine::get_default_triple();
-    inkwell::targets::Target::initialize_native(&inkwell::targets::InitializationConfig::default())
-        .map_err(|e| CodegenError::InitializationFailed(e.to_string()))?;
+    let (target_triple, reloc_mode) = match codegen_target {
+        CodegenTarget::Native => {
+            inkwell::targets::Target::initialize_native(
+                &inkwell::targets::InitializationConfig::default(),
+            )
+            .map_err(|e| CodegenError::InitializationFailed(e.to_string()))?;
+            // PIC relocation model is required so the emitted object can be linked into
+            // a PIE executable (the default on modern Linux distributions).
+            // RelocMode::Default maps to Static on some targets, which emits
+            // R_X86_64_32 relocations that ld rejects with -pie.
+            (
+                inkwell::targets::TargetMachine::get_default_triple(),
+                inkwell::targets::RelocMode::PIC,
+            )
+        }
+        CodegenTarget::Wasm32 => {
+            initialize_wasm()?;
+            (
+                inkwell::targets::TargetTriple::create(CodegenTarget::WASM32_TRIPLE),
+                inkwell::targets::RelocMode::Static,
+            )
+        }
+    };
 
     let target = inkwell::targets::Target::from_triple(&target_triple)
         .map_err(|e| CodegenError::InitializationFailed(format!("failed to get target: {}", e)))?;
@@ -280,23 +458,62 @@ pub fn compile(
             &target_triple,
             "generic",
             "",
-            optimization.to_llvm(),
-            // PIC relocation model is required so the emitted object can be linked into
-            // a PIE executable (the default on modern Linux distributions). RelocMode::Default
-            // maps to Static on some targets, which emits R_X86_64_32 relocations that ld
-            // rejects with -pie.
-            inkwell::targets::RelocMode::PIC,
+            machine_level.to_llvm(),
+            reloc_mode,
             inkwell::targets::CodeModel::Default,
         )
         .ok_or_else(|| {
             CodegenError::InitializationFailed("failed to create target machine".to_string())
         })?;
 
-    let object_code = target_machine
-        .write_to_memory_buffer(&codegen_ctx.module, inkwell::targets::FileType::Object)
+    // The pass pipeline must see the target's triple and data layout rather than
+    // the module's empty (host-sized) default; wasm32, for one, has 32-bit pointers.
+    codegen_ctx.module.set_triple(&target_triple);
+    codegen_ctx
+        .module
+        .set_data_layout(&target_machine.get_target_data().get_data_layout());
+
+    for level in pass_levels {
+        let Some(pipeline) = level.pass_pipeline() else {
+            continue;
+        };
+        let other_levels = codegen_ctx.exclude_from_optimization(|name| {
+            let own = level_of(name);
+            own != level && own != OptimizationLevelSetting::O0
+        });
+        codegen_ctx
+            .module
+            .run_passes(
+                pipeline,
+                &target_machine,
+                inkwell::passes::PassBuilderOptions::create(),
+            )
+            .map_err(|e| CodegenError::LlvmError(format!("optimization pipeline failed: {}", e)))?;
+        codegen_ctx.restore_optimization(&other_levels);
+    }
+
+    let emitted = target_machine
+        .write_to_memory_buffer(&codegen_ctx.module, file_type)
         .map_err(|e| CodegenError::LlvmError(format!("failed to generate object code: {}", e)))?;
 
-    Ok(object_code.as_slice().to_vec())
+    Ok(emitted.as_slice().to_vec())
+}
+
+/// Register the WebAssembly target with LLVM, which only builds with the `wasm`
+/// feature: the prebuilt LLVM on some platforms ships no WebAssembly target libraries.
+#[cfg(feature = "wasm")]
+fn initialize_wasm() -> CodegenResult<()> {
+    inkwell::targets::Target::initialize_webassembly(
+        &inkwell::targets::InitializationConfig::default(),
+    );
+    Ok(())
+}
+
+#[cfg(not(feature = "wasm"))]
+fn initialize_wasm() -> CodegenResult<()> {
+    Err(CodegenError::UnsupportedTarget(
+        CodegenTarget::WASM32_TRIPLE.to_string(),
+    ))
 }
 
 #[cfg(test)]
@@ -306,6 +523,7 @@ mod tests {
 
     /// Parse and lower `source` to typed HIR for the backend smoke tests. Mirrors the
     /// `parse → lower → compile` pipeline `neurc` runs (lowering assumes well-typedness).
+    /// No module constants are folded, so array lengths in `source` must be literals.
     fn lower(source: &str) -> neuro_hir::HirProgram {
         let ast = syntax_parsing::parse(source).expect("parsing failed");
         hir_lowering::lower_program(&ast).expect("HIR lowering failed")
@@ -324,6 +542,46 @@ mod tests {
         assert!(mapper.map_type(&Type::Void).is_err());
     }
 
+    #[test]
+    fn test_type_mapper_half_precision() {
+        let context = LLVMContext::create();
+        let mapper = TypeMapper::new(&context);
+
+        let half = mapper.map_type(&Type::F16).expect("f16 maps");
+        let bfloat = mapper.map_type(&Type::BF16).expect("bf16 maps");
+        assert_eq!(half.into_float_type().print_to_string().to_string(), "half");
+        assert_eq!(
+            bfloat.into_float_type().print_to_string().to_string(),
+            "bfloat"
+        );
+    }
+
+    #[test]
+    fn test_compile_half_precision_arithmetic() {
+        // Half arithmetic is plain `fadd`/`fmul`/... on `half`/`bfloat`; the
+        // widening it needs on targets without half ALUs links the soft-float builtins.
+        let source = r#"
+            func main() -> i32 {
+                val a: f16 = 1.5f16
+                val b: f16 = -a * 2.0f16 + a
+                val g: bf16 = 4.0bf16
+                val h: bf16 = g / 2.0bf16 - g
+                return (b as f32 + h as f32) as i32
+            }
+        "#;
+
+        let hir = lower(source);
+        let result = compile(&hir, OptimizationLevelSetting::O0, source, "test.nr");
+
+        assert!(result.is_ok(), "compilation failed: {:?}", result.err());
+    }
+
+    #[test]
+    fn test_llvm_version_is_the_linked_major() {
+        // The workspace builds against LLVM 20 (inkwell's `llvm20-1` feature).
+        assert!(llvm_version().starts_with("20."), "got {}", llvm_version());
+    }
+
     #[test]
     fn test_type_predicates() {
         assert!(TypeMapper::is_float_type(&Type::F32));
@@ -372,6 +630,70 @@ mod tests {
         assert!(!object_code.is_empty(), "object code should not be empty");
     }
 
+    #[test]
+    fn test_compile_while_loop_sum() {
+        // `codegen_while` leaves the builder on `while.exit`, so the `return`
+        // after the loop lands in a reachable block and the module verifies.
+        let source = r#"
+            func sum_below(n: i32) -> i32 {
+                mut total: i32 = 0
+                mut i: i32 = 0
+                while i < n {
+                    total = total + i
+                    i = i + 1
+                }
+                return total
+            }
+
+            func main() -> i32 {
+                return sum_below(10)
+            }
+        "#;
+
+        let hir = lower(source);
+        let result = compile(&hir, OptimizationLevelSetting::O0, source, "test.nr");
+
+        assert!(result.is_ok(), "compilation failed: {:?}", result.err());
+        assert!(
+            !result.unwrap().is_empty(),
+            "object code should not be empty"
+        );
+    }
+
+    #[test]
+    fn test_compile_loop_with_early_break_and_continue() {
+        // `break` and `continue` branch to the innermost entry of `loop_targets`;
+        // the blocks after them must still verify.
+        let source = r#"
+            func count_until(limit: i32) -> i32 {
+                mut count: i32 = 0
+                while count < 100 {
+                    count = count + 1
+                    if count % 2 == 0 {
+                        continue
+                    }
+                    if count >= limit {
+                        break
+                    }
+                }
+                return count
+            }
+
+            func main() -> i32 {
+                return count_until(7)
+            }
+        "#;
+
+        let hir = lower(source);
+        let result = compile(&hir, OptimizationLevelSetting::O0, source, "test.nr");
+
+        assert!(result.is_ok(), "compilation failed: {:?}", result.err());
+        assert!(
+            !result.unwrap().is_empty(),
+            "object code should not be empty"
+        );
+    }
+
     #[test]
     fn test_overflow_checks_emit_valid_ir_at_o0() {
         // -O0 routes integer +/-/* through the with-overflow intrinsics and a
@@ -417,6 +739,216 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_overflow_checks_override_emits_valid_ir() {
+        // Forcing the checks on at -O2 (and off at -O0) must still verify.
+        let source = r#"
+            func main() -> i32 {
+                mut x: i32 = 2147483647
+                val y: i32 = 1
+                return x + y
+            }
+        "#;
+
+        let hir = lower(source);
+        for (optimization, overflow_checks) in [
+            (OptimizationLevelSetting::O2, true),
+            (OptimizationLevelSetting::O0, false),
+        ] {
+            let options = CodegenOptions {
+                optimization,
+                overflow_checks,
+                target: CodegenTarget::Native,
+            };
+            let result = compile(&hir, options, source, "test.nr");
+            assert!(result.is_ok(), "compilation failed: {:?}", result.err());
+        }
+    }
+
+    #[test]
+    fn test_codegen_options_defaults_follow_optimization_level() {
+        assert!(CodegenOptions::from(OptimizationLevelSetting::O0).overflow_checks);
+        assert!(!CodegenOptions::from(OptimizationLevelSetting::O1).overflow_checks);
+        assert!(!CodegenOptions::from(OptimizationLevelSetting::O3).overflow_checks);
+    }
+
+    /// The names in a WebAssembly binary's export section (id 7).
+    #[cfg(feature = "wasm")]
+    fn wasm_exports(module: &[u8]) -> Vec<String> {
+        fn leb(bytes: &[u8], pos: &mut usize) -> usize {
+            let (mut value, mut shift) = (0usize, 0);
+            loop {
+                let byte = bytes[*pos];
+                *pos += 1;
+                value |= usize::from(byte & 0x7f) << shift;
+                if byte & 0x80 == 0 {
+                    return value;
+                }
+                shift += 7;
+            }
+        }
+
+        let mut exports = Vec::new();
+        let mut pos = 8; // `\0asm` magic and version
+        while pos < module.len() {
+            let id = module[pos];
+            pos += 1;
+            let size = leb(module, &mut pos);
+            let end = pos + size;
+            if id == 7 {
+                for _ in 0..leb(module, &mut pos) {
+                    let len = leb(module, &mut pos);
+                    exports.push(String::from_utf8_lossy(&module[pos..pos + len]).into_owned());
+                    pos += len + 1; // name, then the export kind byte
+                    leb(module, &mut pos); // index
+                }
+            }
+            pos = end;
+        }
+        exports
+    }
+
+    #[cfg(feature = "wasm")]
+    #[test]
+    fn test_wasm_target_exports_functions_by_name() {
+        inkwell::targets::Target::initialize_webassembly(
+            &inkwell::targets::InitializationConfig::default(),
+        );
+        // An LLVM built without the WebAssembly target registers nothing to use.
+        if inkwell::targets::Target::from_name("wasm32").is_none() {
+            return;
+        }
+
+        let source = "func add(a: i32, b: i32) -> i32 { return a + b }";
+        let options = CodegenOptions {
+            target: CodegenTarget::Wasm32,
+            ..CodegenOptions::from(OptimizationLevelSetting::O0)
+        };
+        let module =
+            compile(&lower(source), options, source, "add.nr").expect("wasm compilation failed");
+
+        assert!(module.starts_with(b"\0asm"), "not a wasm binary");
+        assert!(
+            wasm_exports(&module).contains(&"add".to_string()),
+            "exports: {:?}",
+            wasm_exports(&module)
+        );
+    }
+
+    #[test]
+    fn test_object_symbols_and_assembly_list_each_function() {
+        let source = r#"
+            func add(a: i32, b: i32) -> i32 {
+                return a + b
+            }
+
+            func main() -> i32 {
+                return add(20, 22)
+            }
+        "#;
+        let program = lower(source);
+
+        let object = compile(&program, OptimizationLevelSetting::O0, source, "test.nr")
+            .expect("compile failed");
+        let symbols = function_symbols(&object).expect("object should be readable");
+        let main = symbols
+            .iter()
+            .find(|s| s.name.trim_start_matches('_') == "main")
+            .expect("main should be a defined function symbol");
+        assert!(main.size > 0);
+
+        let listing = compile_assembly(&program, OptimizationLevelSetting::O0, source, "test.nr")
+            .expect("assembly emission failed");
+        assert!(function_assembly(&listing, &main.name).is_some());
+    }
+
+    #[test]
+    fn test_optimize_attribute_overrides_module_level_per_function() {
+        // Same body twice in an -O0 module: only the `@optimize(3)` copy is run through
+        // the pass pipeline, which promotes the locals and folds the product to 42.
+        let source = r#"
+            @optimize(3)
+            func folded() -> i32 {
+                val x = 6
+                val y = 7
+                return x * y
+            }
+
+            func unfolded() -> i32 {
+                val x = 6
+                val y = 7
+                return x * y
+            }
+
+            func main() -> i32 {
+                return folded() - unfolded()
+            }
+        "#;
+        let program = lower(source);
+
+        let object = compile(&program, OptimizationLevelSetting::O0, source, "test.nr")
+            .expect("compile failed");
+        let symbols = function_symbols(&object).expect("object should be readable");
+        let listing = compile_assembly(&program, OptimizationLevelSetting::O0, source, "test.nr")
+            .expect("assembly emission failed");
+        let body = |name: &str| {
+            let symbol = symbols
+                .iter()
+                .find(|s| s.name.trim_start_matches('_') == name)
+                .unwrap_or_else(|| panic!("{} should be a defined function symbol", name));
+            function_assembly(&listing, &symbol.name)
+                .unwrap_or_else(|| panic!("{} should be in the listing", name))
+        };
+
+        assert!(body("folded").contains("42"), "{}", body("folded"));
+        assert!(!body("unfolded").contains("42"), "{}", body("unfolded"));
+    }
+
+    #[test]
+    fn test_optimize_attribute_runs_each_level_as_its_own_pipeline() {
+        // `wrapped` is optimized at -O1 apart from the -O3 function it calls, so it
+        // keeps its call to `seven`; `inlined`, also at -O3, inlines it. The -O0
+        // module level runs no pipeline at all.
+        let source = r#"
+            @optimize(3)
+            func seven() -> i32 {
+                return 7
+            }
+
+            @optimize(1)
+            func wrapped() -> i32 {
+                return seven()
+            }
+
+            @optimize(3)
+            func inlined() -> i32 {
+                return seven()
+            }
+
+            func main() -> i32 {
+                return wrapped() - inlined()
+            }
+        "#;
+        let program = lower(source);
+
+        let object = compile(&program, OptimizationLevelSetting::O0, source, "test.nr")
+            .expect("compile failed");
+        let symbols = function_symbols(&object).expect("object should be readable");
+        let listing = compile_assembly(&program, OptimizationLevelSetting::O0, source, "test.nr")
+            .expect("assembly emission failed");
+        let body = |name: &str| {
+            let symbol = symbols
+                .iter()
+                .find(|s| s.name.trim_start_matches('_') == name)
+                .unwrap_or_else(|| panic!("{} should be a defined function symbol", name));
+            function_assembly(&listing, &symbol.name)
+                .unwrap_or_else(|| panic!("{} should be in the listing", name))
+        };
+
+        assert!(body("wrapped").contains("seven"), "{}", body("wrapped"));
+        assert!(!body("inlined").contains("seven"), "{}", body("inlined"));
+    }
+
     #[test]
     fn test_optimization_level_parsing() {
         assert_eq!(
@@ -133,9 +133,10 @@ impl Type {
 
     /// Whether this type lowers to an LLVM floating-point value. Unlike the
     /// semantic predicate, this **includes** `f16`/`bf16`: at the LLVM level they
-    /// are floats (`half`/`bfloat`), so equality and `as`-cast lowering route
-    /// through the float instructions. Arithmetic never reaches here — semantic
-    /// analysis rejects it for half-precision.
+    /// are floats (`half`/`bfloat`), so arithmetic, comparison, and `as`-cast
+    /// lowering route through the float instructions. Where a target has no half
+    /// ALU, LLVM widens half arithmetic through the soft-float conversion builtins
+    /// (see `softfloat`).
     pub(crate) fn is_float(&self) -> bool {
         matches!(self, Type::F16 | Type::BF16 | Type::F32 | Type::F64)
     }
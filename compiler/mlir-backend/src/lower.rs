@@ -212,6 +212,7 @@ mod tests {
                 params: vec![param("a", HirType::I32), param("b", HirType::I32)],
                 return_type: HirType::I32,
                 body: vec![],
+                optimize: None,
                 span: span(),
             })],
         };
@@ -268,6 +269,7 @@ mod tests {
                 ],
                 return_type: HirType::F32,
                 body: vec![],
+                optimize: None,
                 span: span(),
             })],
         };
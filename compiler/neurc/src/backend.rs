@@ -0,0 +1,236 @@
+//! Code generators `compile` can drive, selected with `--backend`.
+//!
+//! Every backend consumes the same typed HIR. LLVM is the default and the only one
+//! that produces native code; the C backend emits portable C source for targets
+//! without LLVM. `--target wasm32-unknown-unknown --emit wasm` keeps LLVM but
+//! retargets it to a WebAssembly module. A further alternative plugs in as another
+//! [`Backend`] implementor and a [`BackendKind`] variant.
+
+use std::fs;
+use std::process::Command;
+
+use anyhow::{bail, Context, Result};
+use clap::ValueEnum;
+use llvm_backend::{CodegenOptions, CodegenTarget};
+use neuro_hir::HirProgram;
+use syntax_parsing::Item;
+
+/// The backend named on the command line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub(crate) enum BackendKind {
+    /// Native object code through LLVM, linked into an executable.
+    Llvm,
+    /// Emit a fixed marker instead of code. Exercises the backend plumbing
+    /// without a code generator.
+    Null,
+    /// Portable C source, written to a `.c` file for a C compiler to build.
+    C,
+}
+
+impl BackendKind {
+    pub(crate) fn backend(self) -> &'static dyn Backend {
+        match self {
+            Self::Llvm => &LlvmBackend,
+            Self::Null => &NullBackend,
+            Self::C => &CBackend,
+        }
+    }
+}
+
+/// The machine named by `--target`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub(crate) enum TargetKind {
+    /// The host `neurc` runs on.
+    Native,
+    /// WebAssembly for browsers and wasm runtimes, through LLVM.
+    #[value(name = "wasm32-unknown-unknown")]
+    Wasm32,
+}
+
+/// The artifact named by `--emit`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub(crate) enum EmitKind {
+    /// What the backend produces for the host: an executable, or the C backend's source.
+    Exe,
+    /// A linked `.wasm` module exporting every top-level function by name.
+    Wasm,
+    /// JSON listing every function's signature, for editors and other tooling.
+    Metadata,
+}
+
+/// The backend for a `--backend`, `--target`, and `--emit` combination, or why the
+/// combination is invalid. A wasm module comes only from the LLVM backend targeting
+/// `wasm32-unknown-unknown`, and that target emits nothing else. Metadata generates
+/// no code, so it ignores the backend and target.
+pub(crate) fn select(
+    kind: BackendKind,
+    target: TargetKind,
+    emit: EmitKind,
+) -> Result<&'static dyn Backend> {
+    match (kind, target, emit) {
+        (_, _, EmitKind::Metadata) => Ok(&MetadataBackend),
+        (_, TargetKind::Native, EmitKind::Exe) => Ok(kind.backend()),
+        (BackendKind::Llvm, TargetKind::Wasm32, EmitKind::Wasm) => Ok(&WasmBackend),
+        (BackendKind::Llvm, TargetKind::Wasm32, EmitKind::Exe) => bail!(
+            "the {} target produces a module, not an executable; pass `--emit wasm`",
+            CodegenTarget::WASM32_TRIPLE
+        ),
+        (_, TargetKind::Native, EmitKind::Wasm) => bail!(
+            "`--emit wasm` requires `--target {}`",
+            CodegenTarget::WASM32_TRIPLE
+        ),
+        (_, TargetKind::Wasm32, _) => bail!(
+            "`--target {}` requires the llvm backend",
+            CodegenTarget::WASM32_TRIPLE
+        ),
+    }
+}
+
+/// What a backend's bytes are, and so how `compile` finishes the build.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum BackendOutput {
+    /// Object code for the platform linker to turn into an executable.
+    Object,
+    /// The finished artifact, written to the output path as is.
+    Final,
+}
+
+/// Everything a backend is given besides the program.
+pub(crate) struct BackendOptions<'a> {
+    pub(crate) codegen: CodegenOptions,
+    /// The source text and its path, for the runtime's panic locations.
+    pub(crate) source: &'a str,
+    pub(crate) source_name: &'a str,
+    /// The type-checked AST the program was lowered from, for output that
+    /// describes the source rather than generated code.
+    pub(crate) items: &'a [Item],
+}
+
+/// A code generator from typed HIR to bytes.
+pub(crate) trait Backend {
+    fn output(&self) -> BackendOutput;
+
+    /// The extension of the output file when `-o` does not name one, or `None`
+    /// for a native executable.
+    fn extension(&self) -> Option<&'static str> {
+        None
+    }
+
+    fn compile(&self, program: &HirProgram, options: &BackendOptions<'_>) -> Result<Vec<u8>>;
+}
+
+struct LlvmBackend;
+
+impl Backend for LlvmBackend {
+    fn output(&self) -> BackendOutput {
+        BackendOutput::Object
+    }
+
+    fn compile(&self, program: &HirProgram, options: &BackendOptions<'_>) -> Result<Vec<u8>> {
+        llvm_backend::compile(
+            program,
+            options.codegen,
+            options.source,
+            options.source_name,
+        )
+        .map_err(|e| anyhow::anyhow!("Code generation error: {}", e))
+    }
+}
+
+/// What the null backend writes for every program.
+const NULL_BACKEND_MARKER: &[u8] = b"neurc null backend\n";
+
+struct NullBackend;
+
+impl Backend for NullBackend {
+    fn output(&self) -> BackendOutput {
+        BackendOutput::Final
+    }
+
+    fn compile(&self, _program: &HirProgram, _options: &BackendOptions<'_>) -> Result<Vec<u8>> {
+        Ok(NULL_BACKEND_MARKER.to_vec())
+    }
+}
+
+struct CBackend;
+
+impl Backend for CBackend {
+    fn output(&self) -> BackendOutput {
+        BackendOutput::Final
+    }
+
+    fn extension(&self) -> Option<&'static str> {
+        Some("c")
+    }
+
+    fn compile(&self, program: &HirProgram, options: &BackendOptions<'_>) -> Result<Vec<u8>> {
+        c_backend::compile(program, options.source_name)
+            .map(String::into_bytes)
+            .map_err(|e| anyhow::anyhow!("Code generation error: {}", e))
+    }
+}
+
+/// Function signatures as JSON instead of code; see [`crate::metadata`].
+struct MetadataBackend;
+
+impl Backend for MetadataBackend {
+    fn output(&self) -> BackendOutput {
+        BackendOutput::Final
+    }
+
+    fn extension(&self) -> Option<&'static str> {
+        Some("json")
+    }
+
+    fn compile(&self, _program: &HirProgram, options: &BackendOptions<'_>) -> Result<Vec<u8>> {
+        crate::metadata::metadata_json(options.items, options.source, options.source_name)
+            .map(String::into_bytes)
+    }
+}
+
+/// LLVM retargeted to `wasm32-unknown-unknown`, linked into a module by `wasm-ld`.
+struct WasmBackend;
+
+impl Backend for WasmBackend {
+    fn output(&self) -> BackendOutput {
+        BackendOutput::Final
+    }
+
+    fn extension(&self) -> Option<&'static str> {
+        Some("wasm")
+    }
+
+    fn compile(&self, program: &HirProgram, options: &BackendOptions<'_>) -> Result<Vec<u8>> {
+        let codegen = CodegenOptions {
+            target: CodegenTarget::Wasm32,
+            ..options.codegen
+        };
+        let object = llvm_backend::compile(program, codegen, options.source, options.source_name)
+            .map_err(|e| anyhow::anyhow!("Code generation error: {}", e))?;
+        link_wasm(&object).context("Failed to link the wasm module")
+    }
+}
+
+/// Link a wasm32 object file into a module with `wasm-ld`. The module has no entry
+/// point, since the host calls its exports, and the functions it calls without
+/// defining (the runtime's) become imports from `env`.
+fn link_wasm(object: &[u8]) -> Result<Vec<u8>> {
+    let dir = tempfile::tempdir().context("Failed to create temporary directory")?;
+    let object_path = dir.path().join("module.o");
+    let module_path = dir.path().join("module.wasm");
+    fs::write(&object_path, object).context("Failed to write wasm object file")?;
+
+    let output = Command::new("wasm-ld")
+        .args(["--no-entry", "--allow-undefined", "-o"])
+        .arg(&module_path)
+        .arg(&object_path)
+        .output()
+        .context("Failed to execute wasm-ld - install LLVM's lld to link wasm modules")?;
+    if !output.status.success() {
+        bail!(
+            "wasm-ld failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    fs::read(&module_path).context("Failed to read the linked wasm module")
+}
@@ -0,0 +1,172 @@
+//! `neurc bench`: time every `@bench` function and report nanoseconds per
+//! iteration.
+//!
+//! Each benchmark is built twice as a standalone executable whose `main` calls
+//! the function in a loop: once for the requested iteration count and once for
+//! zero iterations. The zero-iteration baseline absorbs process start-up, so the
+//! difference between the two, divided by the iteration count, is the cost of
+//! one call. The driver hands each result to the `black_box` builtin, so even
+//! at `-O3` the calls are really made.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, Instant};
+
+use anyhow::Context;
+use syntax_parsing::{FunctionDef, Item};
+
+use crate::backend::BackendKind;
+use crate::report::{Failure, Rendering, Reporter};
+use crate::{
+    build_executable, codegen_options, lower_driver, lower_items, parse_source, read_source,
+    CodegenFlag, Frontend,
+};
+
+/// The attribute that marks a function as a benchmark.
+const BENCH_ATTRIBUTE: &str = "bench";
+
+/// Timed runs of each benchmark executable. The report gives their median and
+/// spread, criterion-style.
+const SAMPLES: usize = 5;
+
+/// Build, run, and report every `@bench` function in `input`.
+pub(crate) fn bench_file(
+    input: &Path,
+    optimization: u8,
+    codegen: &[CodegenFlag],
+    iterations: u32,
+    frontend: &Frontend,
+    rendering: Rendering,
+) -> Result<(), Failure> {
+    let source = read_source(input)?;
+    let reporter = Reporter::new(rendering, input, &source);
+
+    // Check the program as written once, so its diagnostics print once rather
+    // than once per generated driver.
+    let ast = parse_source(&source, &frontend.cfg, &reporter)?;
+    lower_items(&ast, &frontend.type_check, &reporter)?;
+
+    let benches = bench_functions(&ast)?;
+    if benches.is_empty() {
+        println!("No benchmarks found in {}", input.display());
+        return Ok(());
+    }
+
+    let options = codegen_options(optimization, codegen)?;
+    let build_dir = tempfile::tempdir().context("Failed to create benchmark build directory")?;
+    let source_name = input.display().to_string();
+    let width = benches.iter().map(|name| name.len()).max().unwrap_or(0);
+
+    for name in &benches {
+        let mut executables = Vec::with_capacity(2);
+        for count in [0, iterations] {
+            let driver_source = driver_source(&source, name, count);
+            let (driver_ast, hir) =
+                lower_driver(&driver_source, source.len(), &frontend.cfg, "benchmark")?;
+            let path = executable_path(build_dir.path(), name, count);
+            build_executable(
+                &hir,
+                &driver_ast,
+                BackendKind::Llvm.backend(),
+                options,
+                &driver_source,
+                &source_name,
+                &path,
+                None,
+            )?;
+            executables.push(path);
+        }
+
+        let baseline = time_runs(&executables[0], name)?
+            .into_iter()
+            .min()
+            .unwrap_or_default();
+        let mut per_iteration: Vec<f64> = time_runs(&executables[1], name)?
+            .into_iter()
+            .map(|elapsed| {
+                elapsed.saturating_sub(baseline).as_nanos() as f64 / f64::from(iterations)
+            })
+            .collect();
+        per_iteration.sort_by(f64::total_cmp);
+
+        println!(
+            "{:<width$}  {:>12.1} ns/iter (min {:.1}, max {:.1}; {} iterations)",
+            name,
+            per_iteration[SAMPLES / 2],
+            per_iteration[0],
+            per_iteration[SAMPLES - 1],
+            iterations,
+        );
+    }
+
+    Ok(())
+}
+
+/// Names of the free functions marked `@bench`, in source order. A benchmark is
+/// called with no arguments, so one that takes parameters or generics is an error.
+fn bench_functions(ast: &[Item]) -> anyhow::Result<Vec<String>> {
+    let mut names = Vec::new();
+    for func in ast.iter().filter_map(|item| match item {
+        Item::Function(func) if is_bench(func) => Some(func),
+        _ => None,
+    }) {
+        if !func.params.is_empty() || !func.generics.is_empty() {
+            anyhow::bail!(
+                "benchmark '{}' must take no parameters and no generic arguments",
+                func.name.name
+            );
+        }
+        names.push(func.name.name.clone());
+    }
+    Ok(names)
+}
+
+fn is_bench(func: &FunctionDef) -> bool {
+    func.attributes
+        .iter()
+        .any(|attr| attr.name.name == BENCH_ATTRIBUTE)
+}
+
+/// The program's source followed by a `main` that calls `bench` `count` times.
+/// Appending keeps every user span pointing at the user's own text, so runtime
+/// panic locations stay accurate. Each result passes through `black_box`, so an
+/// optimized build cannot delete a call whose value is otherwise unused.
+fn driver_source(source: &str, bench: &str, count: u32) -> String {
+    format!(
+        "{source}\n\
+         func main() -> i32 {{\n    \
+             mut iteration: i32 = 0\n    \
+             while iteration < {count} {{\n        \
+                 black_box({bench}())\n        \
+                 iteration += 1\n    \
+             }}\n    \
+             return 0\n\
+         }}\n"
+    )
+}
+
+fn executable_path(dir: &Path, bench: &str, count: u32) -> PathBuf {
+    let path = dir.join(format!("{bench}_{count}"));
+    if cfg!(target_os = "windows") {
+        path.with_extension("exe")
+    } else {
+        path
+    }
+}
+
+/// Wall-clock time of `SAMPLES` runs of a benchmark executable.
+fn time_runs(executable: &Path, bench: &str) -> anyhow::Result<Vec<Duration>> {
+    let mut samples = Vec::with_capacity(SAMPLES);
+    for _ in 0..SAMPLES {
+        let start = Instant::now();
+        let status = Command::new(executable)
+            .status()
+            .with_context(|| format!("Failed to run benchmark '{bench}'"))?;
+        let elapsed = start.elapsed();
+        if !status.success() {
+            anyhow::bail!("benchmark '{bench}' exited with {status}");
+        }
+        samples.push(elapsed);
+    }
+    Ok(samples)
+}
@@ -0,0 +1,100 @@
+//! `neurc doctor`: check that this machine can build Neuro programs.
+//!
+//! Prints one line per check, `[ok]` or `[FAIL]` with what it found, then the
+//! overall result:
+//!
+//! ```text
+//! [ok]   linker: cc
+//! [ok]   llvm: LLVM 20.1.2
+//! [ok]   build: compiled, linked, and ran a test program
+//! all checks passed
+//! ```
+//!
+//! The linker check looks for the programs `compile` links with, in the order it
+//! tries them. The build check sends a program with a known exit code through the
+//! same LLVM pipeline and linker as `compile`, then runs it.
+
+use std::process::Command;
+
+use anyhow::{anyhow, bail, Context, Result};
+
+use crate::backend::BackendKind;
+use crate::report::Failure;
+use crate::{build_executable, codegen_options, LINKERS};
+
+/// The program the build check compiles, and the exit code it must produce.
+const PROBE_SOURCE: &str = "func main() -> i32 {\n    return 42\n}\n";
+const PROBE_EXIT_CODE: i32 = 42;
+
+/// Run every check and print the checklist. Any failed check fails the command.
+pub(crate) fn doctor() -> Result<(), Failure> {
+    let checks = [
+        ("linker", find_linker()),
+        ("llvm", Ok(format!("LLVM {}", llvm_backend::llvm_version()))),
+        ("build", build_probe()),
+    ];
+
+    let mut failed = 0;
+    for (name, outcome) in &checks {
+        match outcome {
+            Ok(found) => println!("[ok]   {name}: {found}"),
+            Err(error) => {
+                failed += 1;
+                println!("[FAIL] {name}: {error:#}");
+            }
+        }
+    }
+    if failed > 0 {
+        return Err(Failure::from(anyhow!(
+            "{failed} of {} checks failed",
+            checks.len()
+        )));
+    }
+    println!("all checks passed");
+    Ok(())
+}
+
+/// The first linker in `LINKERS` that can be started. A program that starts is on
+/// the `PATH`, whatever it makes of `--version`.
+fn find_linker() -> Result<String> {
+    LINKERS
+        .iter()
+        .find(|linker| Command::new(linker).arg("--version").output().is_ok())
+        .map(|linker| linker.to_string())
+        .ok_or_else(|| anyhow!("none of {} found on PATH", LINKERS.join(", ")))
+}
+
+/// Compile, link, and run `PROBE_SOURCE`, checking its exit code.
+fn build_probe() -> Result<String> {
+    let ast = syntax_parsing::parse(PROBE_SOURCE).map_err(|e| anyhow!("Parse error: {e}"))?;
+    semantic_analysis::type_check(&ast)
+        .map_err(|errors| anyhow!("{} type error(s) found", errors.len()))?;
+    let consts = semantic_analysis::const_array_lengths(&ast);
+    let hir = hir_lowering::lower_program_with_consts(&ast, &consts)
+        .map_err(|e| anyhow!("HIR lowering error: {e}"))?;
+
+    let build_dir = tempfile::tempdir().context("Failed to create build directory")?;
+    let executable = build_dir.path().join(if cfg!(target_os = "windows") {
+        "doctor.exe"
+    } else {
+        "doctor"
+    });
+    build_executable(
+        &hir,
+        &ast,
+        BackendKind::Llvm.backend(),
+        codegen_options(0, &[])?,
+        PROBE_SOURCE,
+        "doctor.nr",
+        &executable,
+        None,
+    )?;
+
+    let status = Command::new(&executable)
+        .status()
+        .context("Failed to run the test program")?;
+    if status.code() != Some(PROBE_EXIT_CODE) {
+        bail!("the test program exited with {status}, expected code {PROBE_EXIT_CODE}");
+    }
+    Ok("compiled, linked, and ran a test program".to_string())
+}
@@ -0,0 +1,37 @@
+//! `neurc check --explain-types`: every `val`/`mut` binding with the type the
+//! checker gave it, read back from the typed HIR.
+
+use std::path::Path;
+
+use hir_lowering::declared_bindings;
+use neuro_hir::HirProgram;
+use shared_types::Span;
+use source_location::SourceFile;
+
+/// One line per `val`/`mut` declaration in `hir`, in source order:
+/// `file:line:col: val x = 1 + 2  // x: i32`.
+///
+/// The source shown runs from the declaration to the end of its line. The
+/// binding lowering declares behind a nested function has no `val`/`mut` text and
+/// is left out.
+pub(crate) fn explain_types(hir: &HirProgram, path: &Path, source: &str) -> Vec<String> {
+    let file = SourceFile::new(path.display().to_string(), source.to_string());
+    declared_bindings(hir)
+        .into_iter()
+        .filter_map(|binding| {
+            // A destructuring pattern's own spans stop short, so show the rest of
+            // the line from where the declaration starts.
+            let start = binding.span.start;
+            let rest = file.snippet(Span::new(start, file.content.len()))?;
+            let text = rest.lines().next()?.trim_end();
+            if !(text.starts_with("val") || text.starts_with("mut")) {
+                return None;
+            }
+            let position = file.position_at(start);
+            Some(format!(
+                "{}:{}:{}: {}  // {}: {}",
+                file.path, position.line, position.column, text, binding.name, binding.ty
+            ))
+        })
+        .collect()
+}
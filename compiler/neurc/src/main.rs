@@ -1,18 +1,75 @@
 use anyhow::{Context, Result};
-use clap::{Parser, Subcommand};
-use llvm_backend::OptimizationLevelSetting;
+use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
+use llvm_backend::{CodegenOptions, OptimizationLevelSetting};
+use neuro_hir::HirProgram;
+use project_config::{BuildConfig, ProjectConfig};
+use semantic_analysis::TypeCheckOptions;
+use std::ffi::OsString;
 use std::fs;
 use std::io::Write;
 use std::path::{Path, PathBuf};
-use std::process::{self, Command};
+use std::process::Command;
+use syntax_parsing::{CfgSet, Item};
+
+mod backend;
+mod bench;
+mod doctor;
+mod explain;
+mod metadata;
+mod report;
+mod test_runner;
+
+use backend::{Backend, BackendKind, BackendOptions, BackendOutput, EmitKind, TargetKind};
+use report::{ErrorFormat, Failure, Rendering, Reporter};
 
 #[derive(Parser)]
 #[command(name = "neurc")]
 #[command(about = "Neuro Programming Language Compiler", long_about = None)]
 #[command(version)]
+#[command(arg_required_else_help = true)]
 struct Cli {
     #[command(subcommand)]
-    command: Commands,
+    command: Option<Commands>,
+
+    /// Print information about the compiler instead of running a command:
+    /// `config` lists the build settings in effect in the current directory
+    #[arg(long, value_enum, value_name = "WHAT")]
+    print: Option<PrintKind>,
+
+    /// How to print diagnostics: `human`, or `short` for one
+    /// `file:line:col: severity: message` line each
+    #[arg(long, global = true, value_enum, default_value_t = ErrorFormat::Human)]
+    error_format: ErrorFormat,
+
+    /// Log progress to stderr: `-v` info, `-vv` debug, `-vvv` trace. Overrides
+    /// the level set by `RUST_LOG`
+    #[arg(short, long, global = true, action = clap::ArgAction::Count)]
+    verbose: u8,
+
+    /// Enable items gated with `@cfg`: `NAME` sets a flag, `KEY=VALUE` sets a
+    /// value; may be repeated
+    #[arg(long = "cfg", global = true, value_name = "NAME[=VALUE]", value_parser = parse_cfg_option)]
+    cfg: Vec<CfgOption>,
+
+    /// Reject reliance on inference defaults: unannotated bindings typed by a
+    /// bare numeric literal and call statements that discard a non-void result
+    #[arg(long, global = true)]
+    pedantic: bool,
+
+    /// Enable an opt-in lint warning; may be repeated
+    #[arg(
+        short = 'W',
+        long = "warn",
+        global = true,
+        value_enum,
+        value_name = "LINT"
+    )]
+    warn: Vec<OptInLint>,
+
+    /// Wrap human-format diagnostic messages at this many columns; `0` turns
+    /// wrapping off. Defaults to the terminal width
+    #[arg(long, global = true, value_name = "COLUMNS")]
+    max_width: Option<usize>,
 }
 
 #[derive(Subcommand)]
@@ -27,9 +84,86 @@ enum Commands {
         #[arg(short, long, value_name = "FILE")]
         output: Option<PathBuf>,
 
+        /// Directory for the executable and any kept intermediates, named after the
+        /// input file; created if missing
+        #[arg(long, value_name = "DIR", conflicts_with = "output")]
+        out_dir: Option<PathBuf>,
+
+        /// Optimization level (0-3). Defaults to `build.optimization_level` from the
+        /// nearest `neuro.toml`, else 0
+        #[arg(short = 'O', long, value_parser = clap::value_parser!(u8).range(0..=3))]
+        optimization: Option<u8>,
+
+        /// Codegen option overriding an optimization-level default
+        /// (`overflow-checks=on|off`); may be repeated
+        #[arg(short = 'C', value_name = "OPT=VALUE", value_parser = parse_codegen_flag)]
+        codegen: Vec<CodegenFlag>,
+
+        /// Keep the intermediate object file next to the output instead of deleting it
+        #[arg(long)]
+        keep_temps: bool,
+
+        /// Code generator to compile with
+        #[arg(long, value_enum, default_value_t = BackendKind::Llvm)]
+        backend: BackendKind,
+
+        /// Machine to compile for. Defaults to `build.target` from the nearest
+        /// `neuro.toml`, else native
+        #[arg(long, value_enum)]
+        target: Option<TargetKind>,
+
+        /// Kind of output to produce; `wasm` needs `--target wasm32-unknown-unknown`,
+        /// `metadata` writes function signatures as JSON
+        #[arg(long, value_enum, default_value_t = EmitKind::Exe)]
+        emit: EmitKind,
+
+        /// Instead of writing an executable, build every `test_*` function with
+        /// `--cfg test` set, run each, and report which pass
+        #[arg(
+            long,
+            conflicts_with_all = ["output", "out_dir", "keep_temps", "backend", "target", "emit"]
+        )]
+        test: bool,
+    },
+
+    /// List the function symbols of the compiled object code
+    Disasm {
+        /// Input source file
+        #[arg(value_name = "FILE")]
+        input: PathBuf,
+
         /// Optimization level (0-3)
         #[arg(short = 'O', long, default_value_t = 0, value_parser = clap::value_parser!(u8).range(0..=3))]
         optimization: u8,
+
+        /// Codegen option overriding an optimization-level default
+        /// (`overflow-checks=on|off`); may be repeated
+        #[arg(short = 'C', value_name = "OPT=VALUE", value_parser = parse_codegen_flag)]
+        codegen: Vec<CodegenFlag>,
+
+        /// Also print each function's machine code as assembly
+        #[arg(short, long)]
+        disassemble: bool,
+    },
+
+    /// Time every `@bench` function and report nanoseconds per iteration
+    Bench {
+        /// Input source file
+        #[arg(value_name = "FILE")]
+        input: PathBuf,
+
+        /// Optimization level (0-3)
+        #[arg(short = 'O', long, default_value_t = 0, value_parser = clap::value_parser!(u8).range(0..=3))]
+        optimization: u8,
+
+        /// Codegen option overriding an optimization-level default
+        /// (`overflow-checks=on|off`); may be repeated
+        #[arg(short = 'C', value_name = "OPT=VALUE", value_parser = parse_codegen_flag)]
+        codegen: Vec<CodegenFlag>,
+
+        /// Calls per timed run of each benchmark
+        #[arg(long, default_value_t = 100_000, value_parser = clap::value_parser!(u32).range(1..=i64::from(i32::MAX)))]
+        iterations: u32,
     },
 
     /// Check syntax and types without generating code
@@ -37,41 +171,294 @@ enum Commands {
         /// Input source file
         #[arg(value_name = "FILE")]
         input: PathBuf,
+
+        /// Print each `val`/`mut` declaration with its inferred type
+        #[arg(long)]
+        explain_types: bool,
     },
 
+    /// Check for a linker and LLVM, and build a test program, printing a
+    /// pass/fail checklist
+    Doctor,
+
     /// Display version information
     Version,
 }
 
+/// What `--print` shows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum PrintKind {
+    /// The effective build settings and the `neuro.toml` they came from, as
+    /// `key=value` lines
+    Config,
+}
+
+/// A lint warning that is off unless `-W` names it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum OptInLint {
+    /// `==`/`!=` between `f32`/`f64` values
+    FloatEquality,
+    /// Parentheses that change nothing, such as `return (x)`; reported as hints
+    RedundantParens,
+}
+
+/// One `-C key=value` codegen override.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CodegenFlag {
+    /// `overflow-checks=on|off` — trap on integer overflow instead of wrapping.
+    OverflowChecks(bool),
+}
+
+fn parse_codegen_flag(raw: &str) -> Result<CodegenFlag, String> {
+    let (key, value) = raw
+        .split_once('=')
+        .ok_or_else(|| format!("expected OPT=VALUE, got '{}'", raw))?;
+    let switch = |value: &str| match value {
+        "on" => Ok(true),
+        "off" => Ok(false),
+        other => Err(format!(
+            "invalid value '{}' for '{}' (expected on or off)",
+            other, key
+        )),
+    };
+    match key {
+        "overflow-checks" => switch(value).map(CodegenFlag::OverflowChecks),
+        other => Err(format!(
+            "unknown codegen option '{}' (supported: overflow-checks)",
+            other
+        )),
+    }
+}
+
+/// One `--cfg` setting: a bare flag or a `key=value` pair.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum CfgOption {
+    Flag(String),
+    Value(String, String),
+}
+
+fn parse_cfg_option(raw: &str) -> Result<CfgOption, String> {
+    match raw.split_once('=') {
+        // A shell-quoted `target="cuda"` means the same as `target=cuda`.
+        Some((key, value)) if !key.is_empty() => Ok(CfgOption::Value(
+            key.to_string(),
+            value.trim_matches('"').to_string(),
+        )),
+        None if !raw.is_empty() => Ok(CfgOption::Flag(raw.to_string())),
+        _ => Err(format!("expected NAME or KEY=VALUE, got '{}'", raw)),
+    }
+}
+
+/// Collect the `--cfg` settings into the configuration `@cfg` conditions test.
+fn cfg_set(options: &[CfgOption]) -> CfgSet {
+    let mut cfg = CfgSet::new();
+    for option in options {
+        match option {
+            CfgOption::Flag(name) => cfg.set_flag(name.clone()),
+            CfgOption::Value(key, value) => cfg.set_value(key.clone(), value.clone()),
+        }
+    }
+    cfg
+}
+
+/// Settings for the front end every command runs: the `--cfg` set `@cfg` conditions
+/// test while parsing, and the type checker's options.
+pub(crate) struct Frontend {
+    pub(crate) cfg: CfgSet,
+    pub(crate) type_check: TypeCheckOptions,
+}
+
+/// Replace each `@path` argument with the whitespace-separated arguments in the file
+/// at `path`, so a long command line can live in a response file. Expansion is one
+/// level deep: an `@` argument inside a response file is passed on unchanged. The
+/// program name and a bare `@` are never expanded.
+fn expand_response_files(args: impl IntoIterator<Item = OsString>) -> Result<Vec<OsString>> {
+    let mut expanded = Vec::new();
+    for (index, arg) in args.into_iter().enumerate() {
+        let path = arg
+            .to_str()
+            .and_then(|arg| arg.strip_prefix('@'))
+            .filter(|path| index > 0 && !path.is_empty());
+        match path {
+            Some(path) => {
+                let contents = fs::read_to_string(path)
+                    .context(format!("Failed to read response file: {}", path))?;
+                expanded.extend(contents.split_whitespace().map(OsString::from));
+            }
+            None => expanded.push(arg),
+        }
+    }
+    Ok(expanded)
+}
+
+/// Start the logger from `RUST_LOG`, with `-v` occurrences raising the default
+/// level: one for info, two for debug, three or more for trace. Without `-v`,
+/// `RUST_LOG` alone decides, and nothing is logged when it is unset.
+fn init_logging(verbose: u8) {
+    let mut builder = env_logger::Builder::from_default_env();
+    let level = match verbose {
+        0 => None,
+        1 => Some(log::LevelFilter::Info),
+        2 => Some(log::LevelFilter::Debug),
+        _ => Some(log::LevelFilter::Trace),
+    };
+    if let Some(level) = level {
+        builder.filter_level(level);
+    }
+    builder.init();
+}
+
 fn main() {
-    env_logger::init();
+    // A panic is an internal compiler error; it keeps Rust's exit code 101.
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        default_hook(info);
+        eprintln!("error: internal compiler error: neurc panicked; please report this");
+    }));
 
-    let cli = Cli::parse();
+    let args = expand_response_files(std::env::args_os())
+        .unwrap_or_else(|error| Failure::usage(error).exit(ErrorFormat::Human, "Error"));
+    let cli = Cli::parse_from(args);
+    init_logging(cli.verbose);
+    let rendering = Rendering {
+        format: cli.error_format,
+        max_width: cli
+            .max_width
+            .or_else(report::terminal_width)
+            .filter(|&width| width > 0),
+    };
+    let frontend = Frontend {
+        cfg: cfg_set(&cli.cfg),
+        type_check: TypeCheckOptions {
+            pedantic: cli.pedantic,
+            float_equality: cli.warn.contains(&OptInLint::FloatEquality),
+            redundant_parens: cli.warn.contains(&OptInLint::RedundantParens),
+        },
+    };
+
+    let command = match (cli.print, cli.command) {
+        (Some(PrintKind::Config), None) => {
+            if let Err(error) = print_config() {
+                Failure::usage(error).exit(rendering.format, "Error");
+            }
+            return;
+        }
+        (Some(_), Some(_)) => Cli::command()
+            .error(
+                clap::error::ErrorKind::ArgumentConflict,
+                "--print cannot be combined with a command",
+            )
+            .exit(),
+        (None, None) => Cli::command()
+            .error(
+                clap::error::ErrorKind::MissingSubcommand,
+                "a command or --print is required",
+            )
+            .exit(),
+        (None, Some(command)) => command,
+    };
+
+    match command {
+        Commands::Compile {
+            input,
+            optimization,
+            codegen,
+            test: true,
+            ..
+        } => {
+            let tested = build_settings(&input, optimization, None)
+                .map_err(Failure::usage)
+                .and_then(|(optimization, _)| {
+                    test_runner::test_file(&input, optimization, &codegen, &frontend, rendering)
+                });
+            if let Err(failure) = tested {
+                failure.exit(rendering.format, "Tests failed");
+            }
+        }
 
-    match cli.command {
         Commands::Compile {
             input,
             output,
+            out_dir,
             optimization,
+            codegen,
+            keep_temps,
+            backend,
+            target,
+            emit,
+            test: false,
         } => {
-            if let Err(e) = compile_file(&input, output.as_deref(), optimization) {
-                eprintln!("Compilation failed: {}", e);
+            let compiled = build_settings(&input, optimization, target)
+                .and_then(|(optimization, target)| {
+                    backend::select(backend, target, emit).map(|backend| (optimization, backend))
+                })
+                .map_err(Failure::usage)
+                .and_then(|(optimization, backend)| {
+                    compile_file(
+                        &input,
+                        &output_path(&input, output, out_dir.as_deref(), backend),
+                        out_dir.as_deref(),
+                        optimization,
+                        &codegen,
+                        backend,
+                        keep_temps,
+                        &frontend,
+                        rendering,
+                    )
+                });
+            if let Err(failure) = compiled {
+                failure.exit(rendering.format, "Compilation failed");
+            }
+        }
 
-                // Print error chain for detailed context
-                let mut chain = e.chain();
-                chain.next(); // Skip the root error (already printed)
-                for (i, cause) in chain.enumerate() {
-                    eprintln!("  Caused by ({}): {}", i + 1, cause);
-                }
+        Commands::Disasm {
+            input,
+            optimization,
+            codegen,
+            disassemble,
+        } => {
+            if let Err(failure) = disasm_file(
+                &input,
+                optimization,
+                &codegen,
+                disassemble,
+                &frontend,
+                rendering,
+            ) {
+                failure.exit(rendering.format, "Error");
+            }
+        }
+
+        Commands::Bench {
+            input,
+            optimization,
+            codegen,
+            iterations,
+        } => {
+            if let Err(failure) = bench::bench_file(
+                &input,
+                optimization,
+                &codegen,
+                iterations,
+                &frontend,
+                rendering,
+            ) {
+                failure.exit(rendering.format, "Benchmark failed");
+            }
+        }
 
-                process::exit(1);
+        Commands::Check {
+            input,
+            explain_types,
+        } => {
+            if let Err(failure) = check_file(&input, explain_types, &frontend, rendering) {
+                failure.exit(rendering.format, "Error");
             }
         }
 
-        Commands::Check { input } => {
-            if let Err(e) = check_file(&input) {
-                eprintln!("Error: {}", e);
-                process::exit(1);
+        Commands::Doctor => {
+            if let Err(failure) = doctor::doctor() {
+                failure.exit(rendering.format, "Doctor failed");
             }
         }
 
@@ -97,147 +484,468 @@ fn validate_source_file(path: &Path) -> Result<()> {
     }
 }
 
-/// Check a Neuro source file for syntax and type errors
-fn check_file(path: &PathBuf) -> anyhow::Result<()> {
-    validate_source_file(path)?;
-
-    let source = fs::read_to_string(path)
-        .map_err(|e| anyhow::anyhow!("Failed to read file {:?}: {}", path, e))?;
+/// Read a `.nr` source file. A wrong extension or an unreadable file is a usage
+/// error.
+fn read_source(path: &Path) -> Result<String, Failure> {
+    validate_source_file(path).map_err(Failure::usage)?;
+    fs::read_to_string(path)
+        .context(format!("Failed to read source file: {}", path.display()))
+        .map_err(Failure::usage)
+}
 
-    let ast = syntax_parsing::parse(&source).map_err(|e| anyhow::anyhow!("Parse error: {}", e))?;
+/// Check a Neuro source file for syntax and type errors. With `explain_types`,
+/// first list every `val`/`mut` declaration with its inferred type.
+fn check_file(
+    path: &Path,
+    explain_types: bool,
+    frontend: &Frontend,
+    rendering: Rendering,
+) -> Result<(), Failure> {
+    let source = read_source(path)?;
+    let reporter = Reporter::new(rendering, path, &source);
 
-    match semantic_analysis::type_check(&ast) {
-        Ok(warnings) => {
-            print_warnings(&warnings);
-            // Lower the type-checked AST to typed HIR (Phase 1.8). The result is the
-            // backend-agnostic contract every backend will consume; building it here
-            // exercises the lowering end-to-end on every checked program.
-            let hir = hir_lowering::lower_program(&ast)
-                .map_err(|e| anyhow::anyhow!("HIR lowering error: {}", e))?;
-            println!(
-                "Type checking passed for {:?} ({} HIR items)",
-                path,
-                hir.items.len()
-            );
-            Ok(())
-        }
-        Err(errors) => {
-            eprintln!("Type errors found in {:?}:", path);
-            for (i, error) in errors.iter().enumerate() {
-                eprintln!("  {}. {}", i + 1, error);
-            }
-            Err(anyhow::anyhow!("{} type error(s) found", errors.len()))
+    // Lowering to typed HIR (Phase 1.8) runs here too: the result is the
+    // backend-agnostic contract every backend will consume, and building it
+    // exercises the lowering end-to-end on every checked program.
+    let (_, hir) = lower_source(&source, frontend, &reporter)?;
+    if explain_types {
+        for line in explain::explain_types(&hir, path, &source) {
+            println!("{line}");
         }
     }
+    println!(
+        "Type checking passed for {:?} ({} HIR items)",
+        path,
+        hir.items.len()
+    );
+    Ok(())
+}
+
+/// The optimization level and target `compile` builds with. An explicit `-O` or
+/// `--target` wins; a missing one comes from the `[build]` table of the `neuro.toml`
+/// in the input's directory or its nearest ancestor that has one, and otherwise
+/// defaults to `-O0` and the native target. A config file that cannot be read or
+/// parsed, or that names an unknown target, is an error.
+fn build_settings(
+    input: &Path,
+    optimization: Option<u8>,
+    target: Option<TargetKind>,
+) -> Result<(u8, TargetKind)> {
+    if let (Some(optimization), Some(target)) = (optimization, target) {
+        return Ok((optimization, target));
+    }
+
+    // A missing input has no project; reading it reports the error.
+    let config = match fs::canonicalize(input)
+        .ok()
+        .and_then(|input| input.parent().map(Path::to_path_buf))
+    {
+        Some(dir) => project_build_config(&dir)?,
+        None => None,
+    };
+    let build = config.map(|(_, build)| build).unwrap_or_default();
+    resolve_build_settings(&build, optimization, target)
 }
 
-/// Render lint warnings to stderr. Warnings never block compilation; they are
-/// informational guidance for the author.
-fn print_warnings(warnings: &[semantic_analysis::Warning]) {
-    for warning in warnings {
-        eprintln!("{}", warning);
+/// The `[build]` table of the `neuro.toml` in `dir` or its nearest ancestor that
+/// has one, with that file's path; `None` when there is no such file.
+fn project_build_config(dir: &Path) -> Result<Option<(PathBuf, BuildConfig)>> {
+    let Some(path) = ProjectConfig::find(dir) else {
+        return Ok(None);
+    };
+    log::info!("Using build settings from {}", path.display());
+    let config = ProjectConfig::load(path.clone())
+        .context(format!("Failed to load project config: {}", path.display()))?;
+    Ok(Some((path, config.build)))
+}
+
+/// Merge explicit `-O` / `--target` values over the `[build]` table `build`.
+fn resolve_build_settings(
+    build: &BuildConfig,
+    optimization: Option<u8>,
+    target: Option<TargetKind>,
+) -> Result<(u8, TargetKind)> {
+    let optimization = optimization.unwrap_or_else(|| build.optimization_level.as_u8());
+    let target = match (target, build.target.as_deref()) {
+        (Some(target), _) => target,
+        (None, Some(name)) => TargetKind::from_str(name, false)
+            .map_err(|_| anyhow::anyhow!("unknown target '{}' in project config", name))?,
+        (None, None) => TargetKind::Native,
+    };
+    Ok((optimization, target))
+}
+
+/// `neurc --print config`: the settings `compile` would use for a source file in
+/// the current directory when given no `-O` or `--target`, and the `neuro.toml`
+/// they come from (`none` without one).
+fn print_config() -> Result<()> {
+    let dir = std::env::current_dir().context("Failed to read the current directory")?;
+    let (config_file, build) = match project_build_config(&dir)? {
+        Some((path, build)) => (path.display().to_string(), build),
+        None => ("none".to_string(), BuildConfig::default()),
+    };
+    let (optimization, target) = resolve_build_settings(&build, None, None)?;
+
+    println!("config_file={}", config_file);
+    println!("optimization_level={}", optimization);
+    if let Some(target) = target.to_possible_value() {
+        println!("target={}", target.get_name());
     }
+    Ok(())
 }
 
-/// Compile a Neuro source file to a native executable.
+/// Where `compile` writes the executable.
 ///
-/// Pipeline: read source → parse → type-check → lower to HIR → LLVM object
-/// code → link. `output` defaults to the input name without its extension
-/// (plus `.exe` on Windows).
-fn compile_file(input: &Path, output: Option<&Path>, optimization: u8) -> Result<()> {
-    validate_source_file(input)?;
+/// An explicit `-o` path is used as given. Otherwise the executable is named
+/// after the input without its extension (plus `.exe` on Windows, or the
+/// backend's own extension for a source-emitting backend) and placed in
+/// `out_dir` or next to the input. `compile_file` creates a missing `out_dir`
+/// once the program compiles, so a failed run leaves nothing behind.
+fn output_path(
+    input: &Path,
+    output: Option<PathBuf>,
+    out_dir: Option<&Path>,
+    backend: &dyn Backend,
+) -> PathBuf {
+    if let Some(output) = output {
+        return output;
+    }
 
-    let source = fs::read_to_string(input)
-        .context(format!("Failed to read source file: {}", input.display()))?;
+    let mut default_output = match out_dir {
+        Some(dir) => dir.join(input.file_stem().unwrap_or(input.as_os_str())),
+        None => input.with_extension(""),
+    };
+    match backend.extension() {
+        Some(extension) => {
+            default_output.set_extension(extension);
+        }
+        None if cfg!(target_os = "windows") => {
+            default_output.set_extension("exe");
+        }
+        None => {}
+    }
+    default_output
+}
+
+/// Compile a Neuro source file to a native executable at `output_path`.
+/// `out_dir`, when given, is created after the program compiles and before the
+/// executable is written into it.
+///
+/// Pipeline: read source → parse → type-check → lower to HIR → `backend` (LLVM
+/// object code by default) → link. `codegen` flags are applied in order on top of
+/// the optimization level's defaults, so the last occurrence of a key wins.
+#[allow(clippy::too_many_arguments)]
+fn compile_file(
+    input: &Path,
+    output_path: &Path,
+    out_dir: Option<&Path>,
+    optimization: u8,
+    codegen: &[CodegenFlag],
+    backend: &dyn Backend,
+    keep_temps: bool,
+    frontend: &Frontend,
+    rendering: Rendering,
+) -> Result<(), Failure> {
+    let source = read_source(input)?;
+    let reporter = Reporter::new(rendering, input, &source);
 
     log::info!("Compiling {}", input.display());
     log::info!("Using optimization level -O{}", optimization);
 
+    let (ast, hir) = lower_source(&source, frontend, &reporter)?;
+    let options = codegen_options(optimization, codegen)?;
+    if let Some(dir) = out_dir {
+        fs::create_dir_all(dir).context(format!(
+            "Failed to create output directory: {}",
+            dir.display()
+        ))?;
+    }
+
+    // `--keep-temps` writes the object file next to the executable. A backend
+    // that emits the final artifact has no intermediate to keep.
+    let kept_object = (keep_temps && backend.output() == BackendOutput::Object)
+        .then(|| output_path.with_extension(object_extension()));
+    build_executable(
+        &hir,
+        &ast,
+        backend,
+        options,
+        &source,
+        &input.display().to_string(),
+        output_path,
+        kept_object.as_deref(),
+    )?;
+
+    println!(
+        "Successfully compiled {} -> {}",
+        input.display(),
+        output_path.display()
+    );
+    if let Some(object_path) = &kept_object {
+        println!("Kept object file: {}", object_path.display());
+    }
+
+    Ok(())
+}
+
+/// Generate code for `hir`, lowered from `ast`, with `backend` and link it into an
+/// executable at `output_path`, or write it there as is when the backend emits the
+/// final artifact.
+///
+/// `source` and `source_name` feed the runtime's panic locations. The object file
+/// goes to a temporary path that is removed after linking, unless `keep_object`
+/// names where to write it instead.
+#[allow(clippy::too_many_arguments)]
+fn build_executable(
+    hir: &HirProgram,
+    ast: &[Item],
+    backend: &dyn Backend,
+    options: CodegenOptions,
+    source: &str,
+    source_name: &str,
+    output_path: &Path,
+    keep_object: Option<&Path>,
+) -> Result<()> {
+    log::debug!("Generating object code...");
+    let options = BackendOptions {
+        codegen: options,
+        source,
+        source_name,
+        items: ast,
+    };
+    let object_code = backend
+        .compile(hir, &options)
+        .context("Failed to generate object code")?;
+    if backend.output() == BackendOutput::Final {
+        return fs::write(output_path, &object_code)
+            .context(format!("Failed to write output: {}", output_path.display()));
+    }
+
+    log::debug!("Writing object file...");
+    let object_path = if let Some(path) = keep_object {
+        fs::write(path, &object_code)
+            .context(format!("Failed to write object file: {}", path.display()))?;
+        path.to_path_buf()
+    } else {
+        let mut object_file = tempfile::Builder::new()
+            .suffix(&format!(".{}", object_extension()))
+            .tempfile()
+            .context("Failed to create temporary object file")?;
+
+        object_file
+            .write_all(&object_code)
+            .context("Failed to write object code to temporary file")?;
+
+        object_file.flush().context("Failed to flush object file")?;
+
+        // Persist past the TempFile guard so the file survives until the linker reads it.
+        let (_, object_path) = object_file
+            .keep()
+            .context("Failed to persist temporary object file")?;
+        object_path
+    };
+
+    log::debug!("Linking to create executable: {}", output_path.display());
+    let linked = link_object_to_executable(&object_path, output_path)
+        .context("Failed to link object file to executable");
+
+    if keep_object.is_none() {
+        let _ = fs::remove_file(&object_path);
+    }
+    linked
+}
+
+/// MSVC expects .obj on Windows; .o is conventional on Unix.
+fn object_extension() -> &'static str {
+    if cfg!(target_os = "windows") {
+        "obj"
+    } else {
+        "o"
+    }
+}
+
+/// Parse, type-check, and lower `source` to typed HIR, returning the checked AST
+/// alongside it and reporting parse errors,
+/// type errors, and lint warnings along the way and closing with their count.
+/// Items whose `@cfg` conditions `frontend.cfg` does not satisfy are dropped before
+/// type checking.
+///
+/// A lowering failure is an internal error: lowering only sees programs the checker
+/// accepted.
+fn lower_source(
+    source: &str,
+    frontend: &Frontend,
+    reporter: &Reporter,
+) -> Result<(Vec<Item>, HirProgram), Failure> {
+    let lowered = parse_source(source, &frontend.cfg, reporter).and_then(|ast| {
+        let hir = lower_items(&ast, &frontend.type_check, reporter)?;
+        Ok((ast, hir))
+    });
+    reporter.summary();
+    lowered
+}
+
+/// Parse `source`, dropping items gated by an unmet `@cfg`, and report every
+/// lexical error and a parse error. Parsing runs on the tokens around any lexical
+/// errors, so one run shows them all, but the source is only accepted without any.
+fn parse_source(source: &str, cfg: &CfgSet, reporter: &Reporter) -> Result<Vec<Item>, Failure> {
     log::debug!("Parsing source...");
-    let ast = syntax_parsing::parse(&source)
-        .map_err(|e| anyhow::anyhow!("Parse error: {}", e))
-        .context("Failed to parse source file")?;
+    let (tokens, lex_errors) = lexical_analysis::tokenize_all(source);
+    reporter.lex_errors(&lex_errors);
+    let items = syntax_parsing::parse_tokens(tokens, cfg).map_err(|e| {
+        reporter.parse_error(&e);
+        Failure::diagnostics(
+            anyhow::anyhow!("Parse error: {}", e).context("Failed to parse source file"),
+        )
+    })?;
+    if !lex_errors.is_empty() {
+        return Err(Failure::diagnostics(
+            anyhow::anyhow!("{} lexical error(s) found", lex_errors.len())
+                .context("Failed to parse source file"),
+        ));
+    }
+    Ok(items)
+}
 
+/// Parse, check, and lower a generated `kind` driver: a program's source with a
+/// `main` appended, returning the driver's AST alongside its HIR. The program's
+/// own `main`, which starts before `user_len`, gives way to the driver's.
+///
+/// The program already passed checking, so a failure here is a bug in the
+/// driver, not in the user's source.
+fn lower_driver(
+    driver_source: &str,
+    user_len: usize,
+    cfg: &CfgSet,
+    kind: &str,
+) -> Result<(Vec<Item>, HirProgram), Failure> {
+    let internal = |error: anyhow::Error| {
+        Failure::internal(error.context(format!("Failed to build the {kind} driver")))
+    };
+
+    let mut ast = syntax_parsing::parse_with_cfg(driver_source, cfg)
+        .map_err(|e| internal(anyhow::anyhow!("Parse error: {}", e)))?;
+    ast.retain(|item| {
+        !matches!(item, Item::Function(func)
+            if func.name.name == "main" && func.span.start < user_len)
+    });
+
+    semantic_analysis::type_check(&ast)
+        .map_err(|errors| internal(anyhow::anyhow!("{} type error(s) found", errors.len())))?;
+    let consts = semantic_analysis::const_array_lengths(&ast);
+    let hir = hir_lowering::lower_program_with_consts(&ast, &consts)
+        .map_err(|e| internal(anyhow::anyhow!("HIR lowering error: {}", e)))?;
+    Ok((ast, hir))
+}
+
+/// Type-check and lower parsed items to typed HIR, reporting type errors and lint
+/// warnings.
+fn lower_items(
+    ast: &[Item],
+    options: &TypeCheckOptions,
+    reporter: &Reporter,
+) -> Result<HirProgram, Failure> {
     log::debug!("Type checking...");
-    let warnings = semantic_analysis::type_check(&ast)
-        .map_err(|errors| {
-            eprintln!("Type errors found:");
-            for (i, error) in errors.iter().enumerate() {
-                eprintln!("  {}. {}", i + 1, error);
-            }
-            anyhow::anyhow!("{} type error(s) found", errors.len())
-        })
-        .context("Type checking failed")?;
-    print_warnings(&warnings);
+    let (errors, warnings) = semantic_analysis::type_check_diagnostics(ast, options);
+    reporter.warnings(&warnings);
+    if !errors.is_empty() {
+        reporter.type_errors(&errors);
+        return Err(Failure::diagnostics(
+            anyhow::anyhow!("{} type error(s) found", errors.len()).context("Type checking failed"),
+        ));
+    }
 
     // Lower to typed HIR (Phase 1.8). The LLVM backend consumes this HIR directly —
     // every node carries its resolved type, so the backend no longer re-derives types
     // from the AST.
     log::debug!("Lowering to typed HIR...");
-    let hir = hir_lowering::lower_program(&ast)
-        .map_err(|e| anyhow::anyhow!("HIR lowering error: {}", e))
-        .context("Failed to lower to HIR")?;
+    let consts = semantic_analysis::const_array_lengths(ast);
+    let hir = hir_lowering::lower_program_with_consts(ast, &consts).map_err(|e| {
+        Failure::internal(
+            anyhow::anyhow!("HIR lowering error: {}", e).context("Failed to lower to HIR"),
+        )
+    })?;
     log::debug!("Lowered {} HIR items", hir.items.len());
+    Ok(hir)
+}
 
-    log::debug!("Generating LLVM IR and object code...");
+/// Resolve `-O` and the `-C` overrides into backend options.
+fn codegen_options(optimization: u8, codegen: &[CodegenFlag]) -> Result<CodegenOptions> {
     let optimization =
         OptimizationLevelSetting::from_u8(optimization).context("Invalid optimization level")?;
+    let mut options = CodegenOptions::from(optimization);
+    for flag in codegen {
+        match *flag {
+            CodegenFlag::OverflowChecks(enabled) => options.overflow_checks = enabled,
+        }
+    }
+    log::debug!("Overflow checks: {}", options.overflow_checks);
+    Ok(options)
+}
 
-    let object_code =
-        llvm_backend::compile(&hir, optimization, &source, &input.display().to_string())
-            .map_err(|e| anyhow::anyhow!("Code generation error: {}", e))
-            .context("Failed to generate object code")?;
-
-    // MSVC expects .obj on Windows; .o is conventional on Unix.
-    log::debug!("Writing object file...");
-    let object_extension = if cfg!(target_os = "windows") {
-        "obj"
-    } else {
-        "o"
-    };
-
-    let mut object_file = tempfile::Builder::new()
-        .suffix(&format!(".{}", object_extension))
-        .tempfile()
-        .context("Failed to create temporary object file")?;
-
-    object_file
-        .write_all(&object_code)
-        .context("Failed to write object code to temporary file")?;
+/// List the function symbols of the object `compile` would link, without linking.
+///
+/// The object is built in memory by the same emission path as `compile`. With
+/// `disassemble`, each function is followed by its machine code as printed by
+/// LLVM's assembly printer for the same module and target.
+fn disasm_file(
+    input: &Path,
+    optimization: u8,
+    codegen: &[CodegenFlag],
+    disassemble: bool,
+    frontend: &Frontend,
+    rendering: Rendering,
+) -> Result<(), Failure> {
+    let source = read_source(input)?;
+    let source_path = input.display().to_string();
+    let reporter = Reporter::new(rendering, input, &source);
 
-    object_file.flush().context("Failed to flush object file")?;
+    let (_, hir) = lower_source(&source, frontend, &reporter)?;
+    let options = codegen_options(optimization, codegen)?;
 
-    // Persist past the TempFile guard so the file survives until the linker reads it.
-    let (_, object_path) = object_file
-        .keep()
-        .context("Failed to persist temporary object file")?;
+    let object_code = llvm_backend::compile(&hir, options, &source, &source_path)
+        .map_err(|e| anyhow::anyhow!("Code generation error: {}", e))
+        .context("Failed to generate object code")?;
+    let symbols = llvm_backend::function_symbols(&object_code)
+        .map_err(|e| anyhow::anyhow!("{}", e))
+        .context("Failed to read emitted object code")?;
 
-    let output_path = if let Some(out) = output {
-        out.to_path_buf()
+    let listing = if disassemble {
+        let assembly = llvm_backend::compile_assembly(&hir, options, &source, &source_path)
+            .map_err(|e| anyhow::anyhow!("Code generation error: {}", e))
+            .context("Failed to generate assembly")?;
+        Some(assembly)
     } else {
-        // Default: input name with the extension stripped (`.exe` on Windows).
-        let mut default_output = input.with_extension("");
-        if cfg!(target_os = "windows") {
-            default_output.set_extension("exe");
-        }
-        default_output
+        None
     };
 
-    log::debug!("Linking to create executable: {}", output_path.display());
-    link_object_to_executable(&object_path, &output_path)
-        .context("Failed to link object file to executable")?;
-
-    let _ = fs::remove_file(&object_path);
+    println!("{:>18}  {:>8}  NAME", "ADDRESS", "SIZE");
+    for symbol in &symbols {
+        println!(
+            "{:#018x}  {:>8}  {}",
+            symbol.address, symbol.size, symbol.name
+        );
+    }
 
-    println!(
-        "Successfully compiled {} -> {}",
-        input.display(),
-        output_path.display()
-    );
+    if let Some(listing) = &listing {
+        for symbol in &symbols {
+            println!();
+            match llvm_backend::function_assembly(listing, &symbol.name) {
+                Some(body) => print!("{}", body),
+                None => println!("{}: <no assembly listing>", symbol.name),
+            }
+        }
+    }
 
     Ok(())
 }
 
+/// The programs `link_object_to_executable` links with, in the order it tries them.
+#[cfg(target_os = "windows")]
+const LINKERS: &[&str] = &["clang", "lld-link", "cl"];
+#[cfg(not(target_os = "windows"))]
+const LINKERS: &[&str] = &["cc"];
+
 /// Link an object file to a native executable via the platform's C compiler,
 /// which acts as a linker driver (C runtime, startup code, etc.).
 ///
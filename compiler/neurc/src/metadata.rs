@@ -0,0 +1,169 @@
+//! `neurc compile --emit metadata`: the signature of every function in a program
+//! that passed type checking, as JSON, for editors and other tooling that need
+//! less than a full AST.
+//!
+//! ```json
+//! {
+//!   "source": "add.nr",
+//!   "functions": [
+//!     {
+//!       "name": "add",
+//!       "params": [{ "name": "a", "type": "i32" }, { "name": "b", "type": "i32" }],
+//!       "return_type": "i32",
+//!       "span": { "start": 0, "end": 41, "line": 1, "column": 1 }
+//!     }
+//!   ]
+//! }
+//! ```
+//!
+//! Methods are named `Type::method` and carry their receiver (`&self`, `&mut self`,
+//! `self`, or `null` for an associated function); a trait's own methods are listed
+//! the same way under the trait's name. The signatures come from the type-checked
+//! AST, not the lowered program, so a generic function appears once under its own
+//! name with its parameters in `generics`, whether or not anything instantiates it.
+//! Types are printed as written, with aliases already expanded by the parser, and a
+//! missing return type is `void`. Closures and nested functions are not listed.
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use shared_types::Span;
+use source_location::SourceFile;
+use syntax_parsing::{GenericParam, GenericParamKind, Item, Parameter, SelfParam, Type};
+
+#[derive(Serialize)]
+struct Metadata<'a> {
+    source: &'a str,
+    functions: Vec<FunctionMetadata>,
+}
+
+#[derive(Serialize)]
+struct FunctionMetadata {
+    name: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    generics: Vec<String>,
+    #[serde(rename = "self", skip_serializing_if = "Option::is_none")]
+    receiver: Option<Option<&'static str>>,
+    params: Vec<ParamMetadata>,
+    return_type: String,
+    span: SpanMetadata,
+}
+
+#[derive(Serialize)]
+struct ParamMetadata {
+    name: String,
+    #[serde(rename = "type")]
+    ty: String,
+}
+
+/// A byte range with the 1-based line and column where it starts.
+#[derive(Serialize)]
+struct SpanMetadata {
+    start: usize,
+    end: usize,
+    line: u32,
+    column: u32,
+}
+
+/// The metadata document for the type-checked `items`, parsed from `source` at
+/// `source_name`.
+pub(crate) fn metadata_json(items: &[Item], source: &str, source_name: &str) -> Result<String> {
+    let file = SourceFile::new(source_name.to_string(), source.to_string());
+    let mut functions = Vec::new();
+    for item in items {
+        match item {
+            Item::Function(func) => functions.push(function_metadata(
+                func.name.name.clone(),
+                generics(&func.generics),
+                None,
+                &func.params,
+                func.return_type.as_ref(),
+                func.span,
+                &file,
+            )),
+            Item::Impl(imp) => {
+                for method in &imp.methods {
+                    functions.push(function_metadata(
+                        format!("{}::{}", imp.type_name.name, method.name.name),
+                        generics(&imp.generics),
+                        Some(method.self_param.as_ref().map(receiver)),
+                        &method.params,
+                        method.return_type.as_ref(),
+                        method.span,
+                        &file,
+                    ));
+                }
+            }
+            Item::Trait(def) => {
+                for method in &def.methods {
+                    functions.push(function_metadata(
+                        format!("{}::{}", def.name.name, method.name.name),
+                        Vec::new(),
+                        Some(method.self_param.as_ref().map(receiver)),
+                        &method.params,
+                        method.return_type.as_ref(),
+                        method.span,
+                        &file,
+                    ));
+                }
+            }
+            Item::Struct(_) | Item::Enum(_) | Item::Const(_) | Item::Newtype(_) => {}
+        }
+    }
+    functions.sort_by_key(|func| (func.span.start, func.span.end));
+
+    let metadata = Metadata {
+        source: source_name,
+        functions,
+    };
+    serde_json::to_string_pretty(&metadata).context("Failed to serialize metadata")
+}
+
+fn function_metadata(
+    name: String,
+    generics: Vec<String>,
+    receiver: Option<Option<&'static str>>,
+    params: &[Parameter],
+    return_type: Option<&Type>,
+    span: Span,
+    file: &SourceFile,
+) -> FunctionMetadata {
+    let position = file.position_at(span.start);
+    FunctionMetadata {
+        name,
+        generics,
+        receiver,
+        params: params
+            .iter()
+            .map(|param| ParamMetadata {
+                name: param.name.name.clone(),
+                ty: param.ty.to_string(),
+            })
+            .collect(),
+        return_type: return_type.map_or_else(|| "void".to_string(), Type::to_string),
+        span: SpanMetadata {
+            start: span.start,
+            end: span.end,
+            line: position.line,
+            column: position.column,
+        },
+    }
+}
+
+/// Each generic parameter as declared: `T`, or `const N: u32`. Bounds are left out.
+fn generics(params: &[GenericParam]) -> Vec<String> {
+    params
+        .iter()
+        .map(|param| match &param.kind {
+            GenericParamKind::Type => param.name.name.clone(),
+            GenericParamKind::Const(ty) => format!("const {}: {ty}", param.name.name),
+        })
+        .collect()
+}
+
+fn receiver(self_param: &SelfParam) -> &'static str {
+    match self_param {
+        SelfParam::Ref => "&self",
+        SelfParam::RefMut => "&mut self",
+        SelfParam::Owned => "self",
+    }
+}
@@ -0,0 +1,289 @@
+//! How `neurc` reports failure: the exit-code convention and the two
+//! `--error-format` renderings of source diagnostics.
+
+use std::cell::Cell;
+use std::path::Path;
+use std::process;
+
+use clap::ValueEnum;
+use diagnostics::wrap_text;
+use lexical_analysis::LexError;
+use semantic_analysis::{TypeError, Warning};
+use source_location::SourceFile;
+use syntax_parsing::ParseError;
+
+/// Rendering of source diagnostics, selected with `--error-format`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub(crate) enum ErrorFormat {
+    /// Numbered diagnostics under a header, followed by the failure and its causes.
+    Human,
+    /// One `file:line:col: severity: message` line per diagnostic, for editor
+    /// quickfix lists.
+    Short,
+}
+
+/// How source diagnostics print: the `--error-format` rendering and the column
+/// width human messages wrap at (`None` leaves them unwrapped). The short
+/// rendering keeps one line per diagnostic whatever the width.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Rendering {
+    pub(crate) format: ErrorFormat,
+    pub(crate) max_width: Option<usize>,
+}
+
+/// The column width of the terminal stderr writes to, where diagnostics go, or
+/// `None` when stderr is not a terminal.
+pub(crate) fn terminal_width() -> Option<usize> {
+    terminal_size::terminal_size_of(std::io::stderr()).map(|(width, _)| usize::from(width.0))
+}
+
+/// Why a command failed. Each kind maps to one exit code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum FailureKind {
+    /// The source has errors, already printed as diagnostics. Exits 1.
+    Diagnostics,
+    /// A step after checking failed: code generation, linking, writing output. Exits 1.
+    Compile,
+    /// The command line is wrong: an unreadable input or one without the `.nr`
+    /// extension. Exits 2, the code clap uses for malformed arguments.
+    Usage,
+    /// The compiler broke on a program it had accepted. Exits 101, the code of a
+    /// Rust panic, which is the other way an internal error surfaces.
+    Internal,
+}
+
+impl FailureKind {
+    pub(crate) fn exit_code(self) -> i32 {
+        match self {
+            Self::Diagnostics | Self::Compile => 1,
+            Self::Usage => 2,
+            Self::Internal => 101,
+        }
+    }
+}
+
+/// A failed command: its kind and the error to show for it.
+#[derive(Debug)]
+pub(crate) struct Failure {
+    pub(crate) kind: FailureKind,
+    pub(crate) error: anyhow::Error,
+}
+
+impl Failure {
+    pub(crate) fn diagnostics(error: anyhow::Error) -> Self {
+        Self {
+            kind: FailureKind::Diagnostics,
+            error,
+        }
+    }
+
+    pub(crate) fn usage(error: anyhow::Error) -> Self {
+        Self {
+            kind: FailureKind::Usage,
+            error,
+        }
+    }
+
+    pub(crate) fn internal(error: anyhow::Error) -> Self {
+        Self {
+            kind: FailureKind::Internal,
+            error,
+        }
+    }
+
+    /// Print the failure and exit with its code.
+    ///
+    /// The human rendering leads with `headline` and lists the error's causes. The
+    /// short rendering adds nothing after a `Diagnostics` failure, whose lines are
+    /// already out, and prints any other failure as a single `error:` line.
+    pub(crate) fn exit(self, format: ErrorFormat, headline: &str) -> ! {
+        match format {
+            ErrorFormat::Human => {
+                eprintln!("{}: {}", headline, self.error);
+                let mut chain = self.error.chain();
+                chain.next(); // Skip the root error (already printed)
+                for (i, cause) in chain.enumerate() {
+                    eprintln!("  Caused by ({}): {}", i + 1, cause);
+                }
+            }
+            ErrorFormat::Short => {
+                if self.kind != FailureKind::Diagnostics {
+                    eprintln!("error: {:#}", self.error);
+                }
+            }
+        }
+        process::exit(self.kind.exit_code())
+    }
+}
+
+/// A plain `anyhow` error is a compile failure: code generation, linking, and file
+/// output all report through `anyhow`.
+impl From<anyhow::Error> for Failure {
+    fn from(error: anyhow::Error) -> Self {
+        Self {
+            kind: FailureKind::Compile,
+            error,
+        }
+    }
+}
+
+/// Prints the diagnostics of one source file in the selected format, counting
+/// them by severity for the closing summary.
+pub(crate) struct Reporter {
+    format: ErrorFormat,
+    max_width: Option<usize>,
+    file: SourceFile,
+    errors: Cell<usize>,
+    warnings: Cell<usize>,
+}
+
+impl Reporter {
+    pub(crate) fn new(rendering: Rendering, path: &Path, source: &str) -> Self {
+        Self {
+            format: rendering.format,
+            max_width: rendering.max_width,
+            file: SourceFile::new(path.display().to_string(), source.to_string()),
+            errors: Cell::new(0),
+            warnings: Cell::new(0),
+        }
+    }
+
+    /// Report every lexical error, each at the line and column it starts.
+    pub(crate) fn lex_errors(&self, errors: &[LexError]) {
+        if errors.is_empty() {
+            return;
+        }
+        self.errors.set(self.errors.get() + errors.len());
+        if self.format == ErrorFormat::Human {
+            eprintln!("Lexical errors found in {}:", self.file.path);
+        }
+        for (i, error) in errors.iter().enumerate() {
+            let diagnostic = error.to_diagnostic();
+            let offset = diagnostic.span.map_or(0, |span| span.start);
+            match self.format {
+                ErrorFormat::Human => {
+                    let position = self.file.position_at(offset);
+                    let number = format!("  {}. ", i + 1);
+                    let indent = " ".repeat(number.len());
+                    let message = format!(
+                        "{} at line {}, column {}",
+                        diagnostic.message, position.line, position.column
+                    );
+                    eprintln!("{}", self.wrap(&message, &number, &indent));
+                }
+                ErrorFormat::Short => self.short_line(offset, "error", &diagnostic.message),
+            }
+        }
+    }
+
+    /// Report a parse error. The human rendering leaves it to the failure message;
+    /// the short rendering points at the error, or at the end of the file when the
+    /// error has no single location.
+    pub(crate) fn parse_error(&self, error: &ParseError) {
+        self.errors.set(self.errors.get() + 1);
+        if self.format == ErrorFormat::Short {
+            let offset = error
+                .span()
+                .map_or(self.file.content.len(), |span| span.start);
+            self.short_line(offset, "error", &error.to_string());
+        }
+    }
+
+    pub(crate) fn type_errors(&self, errors: &[TypeError]) {
+        self.errors.set(self.errors.get() + errors.len());
+        match self.format {
+            ErrorFormat::Human => {
+                eprintln!("Type errors found in {}:", self.file.path);
+                for (i, error) in errors.iter().enumerate() {
+                    let number = format!("  {}. ", i + 1);
+                    let indent = " ".repeat(number.len());
+                    let position = self.file.position_at(error.span().start);
+                    let message = format!(
+                        "{} at line {}, column {}",
+                        error, position.line, position.column
+                    );
+                    eprintln!("{}", self.wrap(&message, &number, &indent));
+                    for fix in error.to_diagnostic(&self.file.content).suggestions {
+                        let help = format!("{indent}help: ");
+                        let help_indent = " ".repeat(help.len());
+                        eprintln!("{}", self.wrap(&fix.label, &help, &help_indent));
+                    }
+                }
+            }
+            ErrorFormat::Short => {
+                for error in errors {
+                    self.short_line(error.span().start, "error", &error.to_string());
+                }
+            }
+        }
+    }
+
+    /// Report lint warnings. Warnings never block compilation; they are
+    /// informational guidance for the author.
+    pub(crate) fn warnings(&self, warnings: &[Warning]) {
+        self.warnings.set(self.warnings.get() + warnings.len());
+        for warning in warnings {
+            match self.format {
+                ErrorFormat::Human => {
+                    eprintln!("{}", self.wrap(&warning.to_string(), "", "  "));
+                    for fix in warning.to_diagnostic(&self.file.content).suggestions {
+                        eprintln!("{}", self.wrap(&fix.label, "  help: ", "        "));
+                    }
+                }
+                ErrorFormat::Short => self.short_line(
+                    warning.span.start,
+                    &warning.code.severity().to_string(),
+                    &format!("{} [{}]", warning.message, warning.code.name()),
+                ),
+            }
+        }
+    }
+
+    /// Close the human rendering with a count of what was reported, as in
+    /// `error: aborting due to 3 previous errors; 2 warnings emitted`. Nothing is
+    /// printed when there were no diagnostics, or in the short rendering, which
+    /// keeps one line per diagnostic.
+    pub(crate) fn summary(&self) {
+        if self.format == ErrorFormat::Human {
+            if let Some(line) = summary_line(self.errors.get(), self.warnings.get()) {
+                eprintln!("{line}");
+            }
+        }
+    }
+
+    /// `prefix` then `message`, wrapped to `max_width` with later lines under
+    /// `indent`.
+    fn wrap(&self, message: &str, prefix: &str, indent: &str) -> String {
+        match self.max_width {
+            Some(width) => wrap_text(message, width, prefix, indent),
+            None => format!("{prefix}{message}"),
+        }
+    }
+
+    fn short_line(&self, offset: usize, severity: &str, message: &str) {
+        let position = self.file.position_at(offset);
+        eprintln!(
+            "{}:{}:{}: {}: {}",
+            self.file.path, position.line, position.column, severity, message
+        );
+    }
+}
+
+/// The closing summary for `errors` errors and `warnings` warnings, worded as
+/// rustc words it.
+fn summary_line(errors: usize, warnings: usize) -> Option<String> {
+    let plural = |n: usize| if n == 1 { "" } else { "s" };
+    let emitted = format!("{warnings} warning{} emitted", plural(warnings));
+    match (errors, warnings) {
+        (0, 0) => None,
+        (0, _) => Some(format!("warning: {emitted}")),
+        (_, 0) => Some(format!(
+            "error: aborting due to {errors} previous error{}",
+            plural(errors)
+        )),
+        _ => Some(format!(
+            "error: aborting due to {errors} previous error{}; {emitted}",
+            plural(errors)
+        )),
+    }
+}
@@ -0,0 +1,167 @@
+//! `neurc compile --test`: build and run every `test_*` function, reporting
+//! whether each passed.
+//!
+//! A Neuro program cannot print, and a failed `assert` aborts its process, so
+//! one executable cannot run several tests and report on them all. As with
+//! `bench`, each test is instead built as its own executable: the program's
+//! source followed by a `main` that calls the test. A test passes when that
+//! executable exits with code 0. What a failed test wrote to stderr, normally
+//! its panic message, is printed under `failures:`.
+//!
+//! ```text
+//! running 2 tests
+//! test test_add ... ok
+//! test test_sub ... FAILED
+//!
+//! failures:
+//! ---- test_sub ----
+//! assertion failed at math.nr:9:5
+//!
+//! test result: FAILED. 1 passed; 1 failed
+//! ```
+
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::{anyhow, Context};
+use syntax_parsing::Item;
+
+use crate::backend::BackendKind;
+use crate::report::{Failure, Rendering, Reporter};
+use crate::{
+    build_executable, codegen_options, lower_driver, lower_items, parse_source, read_source,
+    CodegenFlag, Frontend,
+};
+
+/// Functions whose names start with this are tests.
+const TEST_PREFIX: &str = "test_";
+
+/// The `--cfg` flag set while building tests, so `@cfg(test)` items are kept.
+const TEST_CFG: &str = "test";
+
+/// Build, run, and report every `test_*` function in `input`.
+pub(crate) fn test_file(
+    input: &Path,
+    optimization: u8,
+    codegen: &[CodegenFlag],
+    frontend: &Frontend,
+    rendering: Rendering,
+) -> Result<(), Failure> {
+    let source = read_source(input)?;
+    let reporter = Reporter::new(rendering, input, &source);
+    let mut cfg = frontend.cfg.clone();
+    cfg.set_flag(TEST_CFG);
+
+    // Check the program as written once, so its diagnostics print once rather
+    // than once per generated driver.
+    let ast = parse_source(&source, &cfg, &reporter)?;
+    lower_items(&ast, &frontend.type_check, &reporter)?;
+
+    let tests = test_functions(&ast)?;
+    if tests.is_empty() {
+        println!("No tests found in {}", input.display());
+        return Ok(());
+    }
+
+    let options = codegen_options(optimization, codegen)?;
+    let build_dir = tempfile::tempdir().context("Failed to create test build directory")?;
+    let source_name = input.display().to_string();
+
+    println!(
+        "running {} test{}",
+        tests.len(),
+        if tests.len() == 1 { "" } else { "s" }
+    );
+    let mut failures = Vec::new();
+    for name in &tests {
+        let driver_source = driver_source(&source, name);
+        let (driver_ast, hir) = lower_driver(&driver_source, source.len(), &cfg, "test")?;
+        let path = build_dir.path().join(if cfg!(target_os = "windows") {
+            format!("{name}.exe")
+        } else {
+            name.clone()
+        });
+        build_executable(
+            &hir,
+            &driver_ast,
+            BackendKind::Llvm.backend(),
+            options,
+            &driver_source,
+            &source_name,
+            &path,
+            None,
+        )?;
+
+        let output = Command::new(&path)
+            .output()
+            .with_context(|| format!("Failed to run test '{name}'"))?;
+        if output.status.success() {
+            println!("test {name} ... ok");
+        } else {
+            println!("test {name} ... FAILED");
+            failures.push((name, output));
+        }
+    }
+
+    if !failures.is_empty() {
+        println!("\nfailures:");
+        for (name, output) in &failures {
+            println!("---- {name} ----");
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            if stderr.trim().is_empty() {
+                println!("exited with {}", output.status);
+            } else {
+                print!("{stderr}");
+            }
+        }
+    }
+    println!(
+        "\ntest result: {}. {} passed; {} failed",
+        if failures.is_empty() { "ok" } else { "FAILED" },
+        tests.len() - failures.len(),
+        failures.len()
+    );
+
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        Err(Failure::from(anyhow!(
+            "{} of {} tests failed",
+            failures.len(),
+            tests.len()
+        )))
+    }
+}
+
+/// Names of the free functions whose names start with `test_`, in source order.
+/// A test is called with no arguments, so one that takes parameters or generics
+/// is an error.
+fn test_functions(ast: &[Item]) -> anyhow::Result<Vec<String>> {
+    let mut names = Vec::new();
+    for func in ast.iter().filter_map(|item| match item {
+        Item::Function(func) if func.name.name.starts_with(TEST_PREFIX) => Some(func),
+        _ => None,
+    }) {
+        if !func.params.is_empty() || !func.generics.is_empty() {
+            anyhow::bail!(
+                "test '{}' must take no parameters and no generic arguments",
+                func.name.name
+            );
+        }
+        names.push(func.name.name.clone());
+    }
+    Ok(names)
+}
+
+/// The program's source followed by a `main` that calls `test` once. Appending
+/// keeps every user span pointing at the user's own text, so a failed assertion
+/// reports its real location.
+fn driver_source(source: &str, test: &str) -> String {
+    format!(
+        "{source}\n\
+         func main() -> i32 {{\n    \
+             {test}()\n    \
+             return 0\n\
+         }}\n"
+    )
+}
@@ -54,6 +54,7 @@ fn test_no_cross_slice_dependencies() {
         "compiler/hir-lowering",
         "compiler/control-flow",
         "compiler/llvm-backend",
+        "compiler/c-backend",
     ];
 
     for slice_path in &feature_slices {
@@ -114,6 +115,7 @@ fn test_infrastructure_no_slice_dependencies() {
         "semantic-analysis",
         "control-flow",
         "llvm-backend",
+        "c-backend",
     ];
 
     for infra_path in &infrastructure_crates {
@@ -147,6 +149,7 @@ fn test_all_slices_have_context_md() {
         "compiler/hir-lowering",
         "compiler/control-flow",
         "compiler/llvm-backend",
+        "compiler/c-backend",
         "compiler/neurc",
         // Infrastructure slices also require CONTEXT.md (VSA 4.3 AC-011)
         "compiler/infrastructure/shared-types",
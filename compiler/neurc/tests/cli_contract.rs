@@ -98,6 +98,33 @@ func main() -> i32 {
     );
 }
 
+#[test]
+fn check_suggests_a_close_name_for_an_undefined_variable() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let source = r#"
+func main() -> i32 {
+    val count = 1
+    return cont
+}
+"#;
+
+    let source_path = write_source(&temp_dir, "typo.nr", source);
+
+    let output = Command::new(neurc_path())
+        .arg("check")
+        .arg(&source_path)
+        .output()
+        .expect("Failed to execute neurc check");
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    assert!(!output.status.success());
+    assert!(
+        stderr.contains("     help: a variable with a similar name exists: `count`"),
+        "Expected a help line under the error, got: {stderr}"
+    );
+}
+
 #[test]
 fn compile_command_error_is_nonzero_and_stderr() {
     let temp_dir = TempDir::new().expect("Failed to create temp directory");
@@ -139,3 +166,1089 @@ func main() -> i32 {
         "Expected empty stdout on compile failure, got: {stdout}"
     );
 }
+
+#[test]
+fn disasm_command_lists_main_symbol() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let source = r#"
+func add(a: i32, b: i32) -> i32 {
+    return a + b
+}
+
+func main() -> i32 {
+    return add(40, 2)
+}
+"#;
+
+    let source_path = write_source(&temp_dir, "disasm.nr", source);
+
+    let output = Command::new(neurc_path())
+        .arg("disasm")
+        .arg("--disassemble")
+        .arg(&source_path)
+        .output()
+        .expect("Failed to execute neurc disasm");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    assert!(
+        output.status.success(),
+        "Expected success, stderr: {stderr}"
+    );
+    // Mach-O prefixes symbols with `_`, so match the name column loosely.
+    let listed = |name: &str| {
+        stdout.lines().any(|line| {
+            line.split_whitespace()
+                .nth(2)
+                .map(|n| n.trim_start_matches('_'))
+                == Some(name)
+        })
+    };
+    assert!(
+        listed("main"),
+        "Expected `main` in symbol table, got: {stdout}"
+    );
+    assert!(
+        listed("add"),
+        "Expected `add` in symbol table, got: {stdout}"
+    );
+    assert!(
+        stdout.contains("main:") || stdout.contains("_main:"),
+        "Expected a disassembly section for main, got: {stdout}"
+    );
+}
+
+#[test]
+fn type_error_exits_1_with_short_format_line() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let source = "func main() -> i32 {\n    val x: i32 = true\n    return x\n}\n";
+    let source_path = write_source(&temp_dir, "short_format.nr", source);
+
+    let output = Command::new(neurc_path())
+        .arg("check")
+        .arg("--error-format")
+        .arg("short")
+        .arg(&source_path)
+        .output()
+        .expect("Failed to execute neurc check");
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    assert_eq!(output.status.code(), Some(1), "stderr: {stderr}");
+    let expected = format!(
+        "{}:2:5: error: type mismatch: expected i32, found bool",
+        source_path.display()
+    );
+    assert_eq!(
+        stderr.lines().collect::<Vec<_>>(),
+        vec![expected.as_str()],
+        "short format prints exactly one line per diagnostic"
+    );
+}
+
+#[test]
+fn failed_check_ends_with_a_diagnostics_summary() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let source = "func main() -> i32 {\n    val x: i32 = true\n    val y: i32 = false\n    while true {\n        return 0\n    }\n    return 1\n}\n";
+    let source_path = write_source(&temp_dir, "summary.nr", source);
+
+    let output = Command::new(neurc_path())
+        .arg("check")
+        .arg(&source_path)
+        .output()
+        .expect("Failed to execute neurc check");
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(output.status.code(), Some(1), "stderr: {stderr}");
+    let summary = "error: aborting due to 2 previous errors; 1 warning emitted";
+    assert_eq!(
+        stderr.lines().filter(|line| *line == summary).count(),
+        1,
+        "expected the summary once, got: {stderr}"
+    );
+    let before_failure: Vec<&str> = stderr
+        .lines()
+        .take_while(|line| !line.starts_with("Error:"))
+        .collect();
+    assert_eq!(
+        before_failure.last(),
+        Some(&summary),
+        "the summary closes the diagnostics, got: {stderr}"
+    );
+}
+
+#[test]
+fn clean_check_prints_no_diagnostics_summary() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let source_path = write_source(
+        &temp_dir,
+        "clean.nr",
+        "func main() -> i32 {\n    return 0\n}\n",
+    );
+
+    let output = Command::new(neurc_path())
+        .arg("check")
+        .arg(&source_path)
+        .output()
+        .expect("Failed to execute neurc check");
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "stderr: {stderr}");
+    assert!(
+        !stderr.contains("aborting due to") && !stderr.contains("emitted"),
+        "a clean run has no summary, got: {stderr}"
+    );
+}
+
+#[test]
+fn compile_short_format_reports_parse_error_position() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let source = "func main( -> i32 {\n}\n";
+    let source_path = write_source(&temp_dir, "short_parse.nr", source);
+
+    let output = Command::new(neurc_path())
+        .arg("compile")
+        .arg(&source_path)
+        .arg("--error-format=short")
+        .output()
+        .expect("Failed to execute neurc compile");
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    assert_eq!(output.status.code(), Some(1), "stderr: {stderr}");
+    assert!(
+        stderr.starts_with(&format!("{}:1:12: error: ", source_path.display())),
+        "Expected a file:line:col parse error, got: {stderr}"
+    );
+}
+
+#[test]
+fn check_reports_every_lexical_error_with_its_position() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let source = "func main() -> i32 {\n    val a = 1 $\n    val b = a + #2\n    return b $\n}\n";
+    let source_path = write_source(&temp_dir, "lex_errors.nr", source);
+
+    let check = |format: &str| {
+        Command::new(neurc_path())
+            .arg("check")
+            .arg(&source_path)
+            .arg(format!("--error-format={format}"))
+            .output()
+            .expect("Failed to execute neurc check")
+    };
+
+    let short = check("short");
+    let stderr = String::from_utf8_lossy(&short.stderr);
+    assert_eq!(short.status.code(), Some(1), "stderr: {stderr}");
+    let path = source_path.display();
+    let lines: Vec<&str> = stderr.lines().collect();
+    assert_eq!(
+        lines,
+        [
+            format!("{path}:2:15: error: unexpected character '$'"),
+            format!("{path}:3:17: error: unexpected character '#'"),
+            format!("{path}:4:14: error: unexpected character '$'"),
+        ],
+        "got: {stderr}"
+    );
+
+    let human = check("human");
+    let stderr = String::from_utf8_lossy(&human.stderr);
+    assert_eq!(human.status.code(), Some(1), "stderr: {stderr}");
+    for expected in [
+        "1. unexpected character '$' at line 2, column 15",
+        "2. unexpected character '#' at line 3, column 17",
+        "3. unexpected character '$' at line 4, column 14",
+        "error: aborting due to 3 previous errors",
+    ] {
+        assert!(
+            stderr.contains(expected),
+            "missing {expected:?} in: {stderr}"
+        );
+    }
+}
+
+#[test]
+fn usage_errors_exit_2() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let wrong_extension = write_source(&temp_dir, "program.txt", "func main() -> i32 { 0 }\n");
+
+    for args in [
+        vec!["check".into(), wrong_extension.into_os_string()],
+        vec!["check".into(), temp_dir.path().join("missing.nr").into()],
+        vec!["check".into()],
+        vec!["compile".into(), "--error-format".into(), "verbose".into()],
+        vec![
+            "compile".into(),
+            "--test".into(),
+            "-o".into(),
+            "out".into(),
+            temp_dir.path().join("program.nr").into(),
+        ],
+    ] {
+        let output = Command::new(neurc_path())
+            .args(&args)
+            .output()
+            .expect("Failed to execute neurc");
+        assert_eq!(
+            output.status.code(),
+            Some(2),
+            "{args:?}: stderr: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+}
+
+#[test]
+fn verbose_flag_enables_debug_logging() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let source_path = write_source(&temp_dir, "verbose.nr", "func main() -> i32 { 0 }\n");
+
+    let run = |flag: Option<&str>| {
+        let mut command = Command::new(neurc_path());
+        command.env_remove("RUST_LOG");
+        if let Some(flag) = flag {
+            command.arg(flag);
+        }
+        command
+            .arg("check")
+            .arg(&source_path)
+            .output()
+            .expect("Failed to execute neurc check")
+    };
+
+    let quiet = run(None);
+    let stderr = String::from_utf8_lossy(&quiet.stderr);
+    assert!(quiet.status.success(), "stderr: {stderr}");
+    assert!(
+        stderr.trim().is_empty(),
+        "no logging without -v, got: {stderr}"
+    );
+
+    let info = run(Some("-v"));
+    let stderr = String::from_utf8_lossy(&info.stderr);
+    assert!(info.status.success(), "stderr: {stderr}");
+    assert!(!stderr.contains("DEBUG"), "-v stops at info, got: {stderr}");
+
+    let debug = run(Some("-vv"));
+    let stderr = String::from_utf8_lossy(&debug.stderr);
+    assert!(debug.status.success(), "stderr: {stderr}");
+    assert!(
+        stderr.contains("DEBUG") && stderr.contains("Parsing source"),
+        "-vv must log debug messages, got: {stderr}"
+    );
+}
+
+#[test]
+fn cfg_flag_enables_gated_items() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    // `kernel` is ill-typed, so `check` fails exactly when it is compiled in.
+    let source = r#"
+@cfg(target = "cuda")
+func kernel() -> i32 {
+    return true
+}
+
+func main() -> i32 {
+    return 0
+}
+"#;
+    let source_path = write_source(&temp_dir, "cfg.nr", source);
+
+    let run = |cfg: &[&str]| {
+        Command::new(neurc_path())
+            .args(cfg)
+            .arg("check")
+            .arg(&source_path)
+            .output()
+            .expect("Failed to execute neurc check")
+    };
+
+    let unset = run(&[]);
+    assert!(
+        unset.status.success(),
+        "gated item should be excluded, stderr: {}",
+        String::from_utf8_lossy(&unset.stderr)
+    );
+
+    let other = run(&["--cfg", "target=cpu"]);
+    assert!(other.status.success());
+
+    let set = run(&["--cfg", "target=cuda"]);
+    assert_eq!(set.status.code(), Some(1));
+
+    let empty = run(&["--cfg", "=cuda"]);
+    assert_eq!(empty.status.code(), Some(2));
+}
+
+#[test]
+fn response_file_arguments_apply_like_direct_ones() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let source = r#"
+@cfg(target = "cuda")
+func kernel() -> i32 {
+    return true
+}
+
+func main() -> i32 {
+    return 0
+}
+"#;
+    let source_path = write_source(&temp_dir, "gated.nr", source);
+    let args_path = temp_dir.path().join("args.txt");
+    fs::write(
+        &args_path,
+        format!(
+            "--error-format short\n--cfg target=cuda\n  check {}\n",
+            source_path.display()
+        ),
+    )
+    .expect("Failed to write response file");
+    let flags_path = temp_dir.path().join("flags.txt");
+    fs::write(&flags_path, "--error-format short --cfg target=cuda").expect("write flags");
+
+    let run = |args: &[&std::ffi::OsStr]| {
+        Command::new(neurc_path())
+            .args(args)
+            .output()
+            .expect("Failed to execute neurc")
+    };
+    let direct = run(&[
+        "--error-format".as_ref(),
+        "short".as_ref(),
+        "--cfg".as_ref(),
+        "target=cuda".as_ref(),
+        "check".as_ref(),
+        source_path.as_os_str(),
+    ]);
+    assert_eq!(direct.status.code(), Some(1));
+
+    let at = |path: &PathBuf| format!("@{}", path.display());
+    let whole = at(&args_path);
+    let flags = at(&flags_path);
+    for response in [
+        run(&[whole.as_ref()]),
+        run(&[flags.as_ref(), "check".as_ref(), source_path.as_os_str()]),
+    ] {
+        assert_eq!(response.status.code(), direct.status.code());
+        assert_eq!(response.stderr, direct.stderr);
+    }
+
+    let missing = at(&temp_dir.path().join("missing.txt"));
+    let unreadable = run(&[missing.as_ref()]);
+    assert_eq!(unreadable.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&unreadable.stderr).contains("response file"));
+}
+
+#[test]
+fn pedantic_flag_rejects_default_literal_types() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let write = |name: &str, binding: &str| {
+        let source = format!("func main() -> i32 {{\n    {binding}\n    return x\n}}\n");
+        write_source(&temp_dir, name, &source)
+    };
+    let unannotated = write("unannotated.nr", "val x = 1");
+    let annotated = write("annotated.nr", "val x: i32 = 1");
+
+    let check = |path: &PathBuf, pedantic: bool| {
+        let mut command = Command::new(neurc_path());
+        if pedantic {
+            command.arg("--pedantic");
+        }
+        command
+            .arg("check")
+            .arg(path)
+            .output()
+            .expect("Failed to execute neurc check")
+    };
+
+    assert!(check(&unannotated, false).status.success());
+
+    let strict = check(&unannotated, true);
+    assert_eq!(strict.status.code(), Some(1));
+    let stderr = String::from_utf8_lossy(&strict.stderr);
+    assert!(
+        stderr.contains("pedantic mode requires an annotation"),
+        "got: {stderr}"
+    );
+
+    let annotated = check(&annotated, true);
+    assert!(
+        annotated.status.success(),
+        "annotated binding should pass, stderr: {}",
+        String::from_utf8_lossy(&annotated.stderr)
+    );
+}
+
+#[test]
+fn explain_types_lists_inferred_binding_types() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let source = "func main() -> i32 {\n    val x = 1 + 2\n    mut ratio: f32 = 0.5\n    val big = 7i64\n    return x\n}\n";
+    let source_path = write_source(&temp_dir, "explain.nr", source);
+
+    let output = Command::new(neurc_path())
+        .arg("check")
+        .arg("--explain-types")
+        .arg(&source_path)
+        .output()
+        .expect("Failed to execute neurc check");
+    assert!(
+        output.status.success(),
+        "Expected success, stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let path = source_path.display();
+    for expected in [
+        format!("{path}:2:5: val x = 1 + 2  // x: i32"),
+        format!("{path}:3:5: mut ratio: f32 = 0.5  // ratio: f32"),
+        format!("{path}:4:5: val big = 7i64  // big: i64"),
+    ] {
+        assert!(
+            stdout.contains(&expected),
+            "missing {expected:?} in: {stdout}"
+        );
+    }
+
+    let plain = Command::new(neurc_path())
+        .arg("check")
+        .arg(&source_path)
+        .output()
+        .expect("Failed to execute neurc check");
+    assert!(!String::from_utf8_lossy(&plain.stdout).contains("// x:"));
+}
+
+#[test]
+fn max_width_wraps_human_diagnostic_messages() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let source = "func main() -> i32 {\n    val flag: bool = 1\n    return 0\n}\n";
+    let source_path = write_source(&temp_dir, "narrow.nr", source);
+
+    let check = |width: &str| {
+        let output = Command::new(neurc_path())
+            .args(["--max-width", width, "check"])
+            .arg(&source_path)
+            .output()
+            .expect("Failed to execute neurc check");
+        assert_eq!(output.status.code(), Some(1));
+        String::from_utf8_lossy(&output.stderr).into_owned()
+    };
+
+    // The numbered message breaks between words and continues under its text.
+    let narrow = check("30");
+    let message: Vec<&str> = narrow
+        .lines()
+        .skip_while(|line| !line.starts_with("  1. "))
+        .take_while(|line| line.starts_with("  "))
+        .collect();
+    assert!(
+        message.len() > 1,
+        "expected a wrapped message, got: {narrow}"
+    );
+    assert!(
+        message.iter().all(|line| line.chars().count() <= 30),
+        "got: {narrow}"
+    );
+    assert!(
+        message[1..].iter().all(|line| line.starts_with("     ")),
+        "got: {narrow}"
+    );
+
+    // A message that fits is printed on one line, as without the flag.
+    let wide = check("200");
+    assert!(
+        wide.lines()
+            .any(|line| line.starts_with("  1. type mismatch")
+                && line.contains("found i32 at line 2, column ")),
+        "got: {wide}"
+    );
+}
+
+#[test]
+fn bench_command_reports_time_per_iteration() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let source = r#"
+@bench
+func sum_to_hundred() -> i32 {
+    mut total: i32 = 0
+    mut i: i32 = 0
+    while i < 100 {
+        total += i
+        i += 1
+    }
+    return total
+}
+
+func main() -> i32 {
+    return 0
+}
+"#;
+    let source_path = write_source(&temp_dir, "bench.nr", source);
+
+    let output = Command::new(neurc_path())
+        .arg("bench")
+        .arg(&source_path)
+        .args(["--iterations", "1000"])
+        .output()
+        .expect("Failed to execute neurc bench");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        output.status.success(),
+        "Expected success, stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let line = stdout
+        .lines()
+        .find(|line| line.starts_with("sum_to_hundred"))
+        .unwrap_or_else(|| panic!("Expected a timing line for the benchmark, got: {stdout}"));
+    assert!(line.contains("ns/iter"), "got: {line}");
+    assert!(line.contains("1000 iterations"), "got: {line}");
+}
+
+#[test]
+fn compile_test_runs_each_test_function_and_reports_its_result() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let source = r#"
+func add(a: i32, b: i32) -> i32 {
+    return a + b
+}
+
+@cfg(test)
+func five() -> i32 {
+    return 5
+}
+
+func test_add() {
+    assert(add(2, 3) == five())
+}
+
+func test_add_is_wrong() {
+    assert(add(2, 2) == five())
+}
+
+func main() -> i32 {
+    return add(1, 1)
+}
+"#;
+    let source_path = write_source(&temp_dir, "math.nr", source);
+
+    let output = Command::new(neurc_path())
+        .arg("compile")
+        .arg("--test")
+        .arg(&source_path)
+        .output()
+        .expect("Failed to execute neurc compile --test");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(
+        output.status.code(),
+        Some(1),
+        "stdout: {stdout}, stderr: {stderr}"
+    );
+    for expected in [
+        "running 2 tests",
+        "test test_add ... ok",
+        "test test_add_is_wrong ... FAILED",
+        "---- test_add_is_wrong ----",
+        "test result: FAILED. 1 passed; 1 failed",
+    ] {
+        assert!(
+            stdout.lines().any(|line| line == expected),
+            "missing {expected:?} in: {stdout}"
+        );
+    }
+    assert!(stdout.contains("assertion failed at"), "got: {stdout}");
+    assert!(stderr.contains("1 of 2 tests failed"), "got: {stderr}");
+}
+
+#[test]
+fn doctor_reports_linker_status_and_overall_result() {
+    let output = Command::new(neurc_path())
+        .arg("doctor")
+        .output()
+        .expect("Failed to execute neurc doctor");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        output.status.success(),
+        "Expected success, stdout: {stdout}, stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    for check in ["linker", "llvm", "build"] {
+        assert!(
+            stdout
+                .lines()
+                .any(|line| line.starts_with("[ok]") && line.contains(&format!("{check}: "))),
+            "Expected a passing {check} check, got: {stdout}"
+        );
+    }
+    assert!(stdout.ends_with("all checks passed\n"), "got: {stdout}");
+}
+
+#[test]
+fn bench_with_parameters_is_rejected() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let source = r#"
+@bench
+func square(x: i32) -> i32 {
+    return x * x
+}
+"#;
+    let source_path = write_source(&temp_dir, "bench_params.nr", source);
+
+    let output = Command::new(neurc_path())
+        .arg("bench")
+        .arg(&source_path)
+        .output()
+        .expect("Failed to execute neurc bench");
+
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("benchmark 'square' must take no parameters"),
+        "got: {stderr}"
+    );
+}
+
+#[test]
+fn backend_flag_selects_the_null_backend() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let source_path = write_source(
+        &temp_dir,
+        "null.nr",
+        "func main() -> i32 {\n    return 0\n}\n",
+    );
+    let output_path = temp_dir.path().join("null.out");
+
+    let output = Command::new(neurc_path())
+        .args(["compile", "--backend", "null"])
+        .arg(&source_path)
+        .arg("-o")
+        .arg(&output_path)
+        .output()
+        .expect("Failed to execute neurc compile");
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "stderr: {stderr}");
+    let written = fs::read(&output_path).expect("the backend output is written");
+    assert_eq!(written, b"neurc null backend\n");
+}
+
+#[test]
+fn project_config_sets_optimization_level() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    fs::write(
+        temp_dir.path().join("neuro.toml"),
+        "[package]\nname = \"opt\"\nversion = \"0.1.0\"\n\n[build]\noptimization_level = \"O2\"\n",
+    )
+    .expect("Failed to write neuro.toml");
+    fs::create_dir(temp_dir.path().join("src")).expect("Failed to create src directory");
+    let source_path = temp_dir.path().join("src").join("opt.nr");
+    fs::write(&source_path, "func main() -> i32 { 0 }\n").expect("Failed to write source file");
+    let output_path = temp_dir.path().join("opt.out");
+
+    let compile = |flag: Option<&str>| {
+        let mut command = Command::new(neurc_path());
+        command.env_remove("RUST_LOG");
+        command.args(["-v", "compile", "--backend", "null"]);
+        if let Some(flag) = flag {
+            command.arg(flag);
+        }
+        command
+            .arg(&source_path)
+            .arg("-o")
+            .arg(&output_path)
+            .output()
+            .expect("Failed to execute neurc compile")
+    };
+
+    let configured = compile(None);
+    let stderr = String::from_utf8_lossy(&configured.stderr);
+    assert!(configured.status.success(), "stderr: {stderr}");
+    assert!(
+        stderr.contains("Using optimization level -O2"),
+        "the nearest neuro.toml sets the level, got: {stderr}"
+    );
+
+    let explicit = compile(Some("-O1"));
+    let stderr = String::from_utf8_lossy(&explicit.stderr);
+    assert!(explicit.status.success(), "stderr: {stderr}");
+    assert!(
+        stderr.contains("Using optimization level -O1"),
+        "an explicit -O overrides the config, got: {stderr}"
+    );
+}
+
+#[test]
+fn print_config_shows_the_project_settings() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let config_path = temp_dir.path().join("neuro.toml");
+    fs::write(
+        &config_path,
+        "[package]\nname = \"opt\"\nversion = \"0.1.0\"\n\n[build]\noptimization_level = \"O3\"\n",
+    )
+    .expect("Failed to write neuro.toml");
+    let src_dir = temp_dir.path().join("src");
+    fs::create_dir(&src_dir).expect("Failed to create src directory");
+
+    let output = Command::new(neurc_path())
+        .args(["--print", "config"])
+        .current_dir(&src_dir)
+        .output()
+        .expect("Failed to execute neurc --print config");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let config_path = fs::canonicalize(&config_path).expect("neuro.toml exists");
+    assert!(
+        stdout.contains(&format!("config_file={}\n", config_path.display())),
+        "got: {stdout}"
+    );
+    assert!(stdout.contains("optimization_level=3\n"), "got: {stdout}");
+    assert!(stdout.contains("target=native\n"), "got: {stdout}");
+}
+
+#[test]
+fn emit_metadata_lists_function_signatures() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let source_path = write_source(
+        &temp_dir,
+        "sigs.nr",
+        "func scale(value: i32, factor: f64) -> f64 {\n    return factor * 2.0\n}\n\nfunc main() -> i32 {\n    return 0\n}\n",
+    );
+
+    let output = Command::new(neurc_path())
+        .args(["compile", "--emit", "metadata"])
+        .arg(&source_path)
+        .output()
+        .expect("Failed to execute neurc compile");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "stderr: {stderr}");
+
+    let written = fs::read_to_string(temp_dir.path().join("sigs.json"))
+        .expect("metadata is written next to the input");
+    let metadata: serde_json::Value =
+        serde_json::from_str(&written).expect("metadata is valid JSON");
+    let functions = metadata["functions"]
+        .as_array()
+        .expect("functions is an array");
+    assert_eq!(functions.len(), 2, "got: {written}");
+
+    assert_eq!(functions[0]["name"], "scale");
+    assert_eq!(
+        functions[0]["params"],
+        serde_json::json!([
+            { "name": "value", "type": "i32" },
+            { "name": "factor", "type": "f64" },
+        ])
+    );
+    assert_eq!(functions[0]["return_type"], "f64");
+    assert_eq!(functions[0]["span"]["line"], 1);
+
+    assert_eq!(functions[1]["name"], "main");
+    assert_eq!(functions[1]["params"], serde_json::json!([]));
+    assert_eq!(functions[1]["return_type"], "i32");
+    assert_eq!(functions[1]["span"]["line"], 5);
+}
+
+#[test]
+fn emit_metadata_lists_generic_functions_once_by_name() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let source_path = write_source(
+        &temp_dir,
+        "generic.nr",
+        "func identity<T>(value: T) -> T {\n    return value\n}\n\n\
+         func unused<T>(value: T) -> T {\n    return value\n}\n\n\
+         func main() -> i32 {\n    return identity(0) + identity(1)\n}\n",
+    );
+
+    let output = Command::new(neurc_path())
+        .args(["compile", "--emit", "metadata"])
+        .arg(&source_path)
+        .output()
+        .expect("Failed to execute neurc compile");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "stderr: {stderr}");
+
+    let written = fs::read_to_string(temp_dir.path().join("generic.json"))
+        .expect("metadata is written next to the input");
+    let metadata: serde_json::Value =
+        serde_json::from_str(&written).expect("metadata is valid JSON");
+    let functions = metadata["functions"]
+        .as_array()
+        .expect("functions is an array");
+    let names: Vec<_> = functions.iter().map(|func| &func["name"]).collect();
+    assert_eq!(names, ["identity", "unused", "main"], "got: {written}");
+
+    for func in &functions[..2] {
+        assert_eq!(func["generics"], serde_json::json!(["T"]));
+        assert_eq!(
+            func["params"],
+            serde_json::json!([{ "name": "value", "type": "T" }])
+        );
+        assert_eq!(func["return_type"], "T");
+    }
+    assert!(functions[2].get("generics").is_none(), "got: {written}");
+}
+
+#[test]
+fn c_backend_emits_source_that_cc_builds() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let source_path = write_source(
+        &temp_dir,
+        "add.nr",
+        "func add(a: i32, b: i32) -> i32 {\n    return a + b\n}\n\n\
+         func main() -> i32 {\n    return add(40, 2)\n}\n",
+    );
+
+    let output = Command::new(neurc_path())
+        .args(["compile", "--backend", "c"])
+        .arg(&source_path)
+        .output()
+        .expect("Failed to execute neurc compile");
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "stderr: {stderr}");
+    // Without `-o`, the C file is named after the input.
+    let c_path = temp_dir.path().join("add.c");
+    let c_source = fs::read_to_string(&c_path).expect("the C source is written");
+    assert!(c_source.contains("nr_add("), "C source:\n{c_source}");
+
+    let exe_path = temp_dir.path().join("add");
+    let cc = Command::new("cc")
+        .arg("-o")
+        .arg(&exe_path)
+        .arg(&c_path)
+        .output()
+        .expect("Failed to execute cc");
+    assert!(
+        cc.status.success(),
+        "cc: {}",
+        String::from_utf8_lossy(&cc.stderr)
+    );
+    let status = Command::new(&exe_path)
+        .status()
+        .expect("Failed to run the C build");
+    assert_eq!(status.code(), Some(42));
+}
+
+#[test]
+fn wasm_target_and_emit_must_be_paired() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let source_path = write_source(
+        &temp_dir,
+        "lib.nr",
+        "func add(a: i32, b: i32) -> i32 {\n    return a + b\n}\n",
+    );
+
+    for (flags, expected) in [
+        (
+            &["--emit", "wasm"][..],
+            "`--emit wasm` requires `--target wasm32-unknown-unknown`",
+        ),
+        (
+            &["--target", "wasm32-unknown-unknown"][..],
+            "pass `--emit wasm`",
+        ),
+        (
+            &[
+                "--target",
+                "wasm32-unknown-unknown",
+                "--emit",
+                "wasm",
+                "--backend",
+                "c",
+            ][..],
+            "requires the llvm backend",
+        ),
+    ] {
+        let output = Command::new(neurc_path())
+            .arg("compile")
+            .arg(&source_path)
+            .args(flags)
+            .output()
+            .expect("Failed to execute neurc compile");
+
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert_eq!(output.status.code(), Some(2), "{flags:?}: {stderr}");
+        assert!(stderr.contains(expected), "{flags:?}: {stderr}");
+    }
+}
+
+#[test]
+fn keep_temps_preserves_object_file() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let source_path = write_source(
+        &temp_dir,
+        "keep.nr",
+        "func main() -> i32 {\n    return 0\n}\n",
+    );
+    let object_extension = if cfg!(target_os = "windows") {
+        "obj"
+    } else {
+        "o"
+    };
+
+    // Without `--keep-temps` the object file goes to the system temp directory,
+    // so point that at a directory of our own to check it is cleaned up.
+    let scratch = temp_dir.path().join("scratch");
+    fs::create_dir(&scratch).expect("Failed to create scratch directory");
+
+    let compile = |name: &str, keep_temps: bool| {
+        let output_path = temp_dir.path().join(name);
+        let mut command = Command::new(neurc_path());
+        command
+            .arg("compile")
+            .arg(&source_path)
+            .arg("-o")
+            .arg(&output_path);
+        for var in ["TMPDIR", "TMP", "TEMP"] {
+            command.env(var, &scratch);
+        }
+        if keep_temps {
+            command.arg("--keep-temps");
+        }
+        let output = command.output().expect("Failed to execute neurc compile");
+        assert!(
+            output.status.success(),
+            "Expected success, stderr: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        (
+            output_path.with_extension(object_extension),
+            String::from_utf8_lossy(&output.stdout).into_owned(),
+        )
+    };
+
+    let (kept, stdout) = compile("kept", true);
+    assert!(
+        kept.exists(),
+        "object file should be kept at {}",
+        kept.display()
+    );
+    assert!(
+        stdout.contains(&format!("Kept object file: {}", kept.display())),
+        "got: {stdout}"
+    );
+
+    let (removed, stdout) = compile("removed", false);
+    assert!(
+        !removed.exists(),
+        "no object file should be written next to the output"
+    );
+    let leftovers: Vec<_> = fs::read_dir(&scratch)
+        .expect("Failed to read scratch directory")
+        .map(|entry| entry.expect("Failed to read scratch entry").path())
+        .collect();
+    assert!(
+        leftovers.is_empty(),
+        "temporary object file should be removed, found {leftovers:?}"
+    );
+    assert!(!stdout.contains("Kept object file"), "got: {stdout}");
+}
+
+#[test]
+fn out_dir_places_outputs_under_input_stem() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let source_path = write_source(
+        &temp_dir,
+        "program.nr",
+        "func main() -> i32 {\n    return 0\n}\n",
+    );
+    let out_dir = temp_dir.path().join("build").join("bin");
+
+    let output = Command::new(neurc_path())
+        .arg("compile")
+        .arg(&source_path)
+        .arg("--out-dir")
+        .arg(&out_dir)
+        .arg("--keep-temps")
+        .output()
+        .expect("Failed to execute neurc compile");
+    assert!(
+        output.status.success(),
+        "Expected success, stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let executable = if cfg!(target_os = "windows") {
+        out_dir.join("program.exe")
+    } else {
+        out_dir.join("program")
+    };
+    assert!(
+        executable.exists(),
+        "executable should land in the out-dir at {}",
+        executable.display()
+    );
+    let object = executable.with_extension(if cfg!(target_os = "windows") {
+        "obj"
+    } else {
+        "o"
+    });
+    assert!(
+        object.exists(),
+        "kept object should land in the out-dir at {}",
+        object.display()
+    );
+    assert!(
+        !temp_dir.path().join("program").exists(),
+        "nothing should be written next to the input"
+    );
+
+    let conflicting = Command::new(neurc_path())
+        .arg("compile")
+        .arg(&source_path)
+        .arg("--out-dir")
+        .arg(&out_dir)
+        .arg("-o")
+        .arg(temp_dir.path().join("other"))
+        .output()
+        .expect("Failed to execute neurc compile");
+    assert_eq!(conflicting.status.code(), Some(2));
+}
+
+#[test]
+fn failed_compile_does_not_create_out_dir() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let source_path = write_source(
+        &temp_dir,
+        "broken.nr",
+        "func main() -> i32 {\n    return true\n}\n",
+    );
+    let out_dir = temp_dir.path().join("build");
+
+    let type_error = Command::new(neurc_path())
+        .arg("compile")
+        .arg(&source_path)
+        .arg("--out-dir")
+        .arg(&out_dir)
+        .output()
+        .expect("Failed to execute neurc compile");
+    assert_eq!(type_error.status.code(), Some(1));
+    assert!(
+        !out_dir.exists(),
+        "a type error should not create the out-dir"
+    );
+
+    let usage_error = Command::new(neurc_path())
+        .arg("compile")
+        .arg(temp_dir.path().join("missing.nr"))
+        .arg("--out-dir")
+        .arg(&out_dir)
+        .output()
+        .expect("Failed to execute neurc compile");
+    assert_eq!(usage_error.status.code(), Some(2));
+    assert!(
+        !out_dir.exists(),
+        "a usage error should not create the out-dir"
+    );
+}
@@ -124,6 +124,47 @@ func main() -> i32 {
     assert_eq!(exit, 15);
 }
 
+#[test]
+fn function_stored_in_variable_is_called_indirectly() {
+    let test = CompileTest::new();
+    let source = r#"
+func add(a: i32, b: i32) -> i32 {
+    a + b
+}
+
+func main() -> i32 {
+    val f = add
+    f(20, 22)
+}
+"#;
+    let exit = test
+        .compile_and_run("function_value.nr", source)
+        .expect("compile/run failed");
+    assert_eq!(exit, 42);
+}
+
+#[test]
+fn result_of_a_call_is_called() {
+    let test = CompileTest::new();
+    let source = r#"
+func inc(x: i32) -> i32 {
+    x + 1
+}
+
+func pick() -> (i32) -> i32 {
+    inc
+}
+
+func main() -> i32 {
+    pick()(41)
+}
+"#;
+    let exit = test
+        .compile_and_run("call_result_call.nr", source)
+        .expect("compile/run failed");
+    assert_eq!(exit, 42);
+}
+
 #[test]
 fn capturing_non_copy_value_is_rejected() {
     let test = CompileTest::new();
@@ -240,3 +240,26 @@ func main() -> i32 {
         "duplicate function-body const should be rejected"
     );
 }
+
+// ── Array length naming a const ──────────────────────────────────────────────
+
+#[test]
+fn array_length_names_module_const() {
+    let test = CompileTest::new();
+    let source = r#"
+const SIZE: i32 = 1 + 2
+
+func total(xs: [i32; SIZE]) -> i32 {
+    return xs[0] + xs[1] + xs[2]
+}
+
+func main() -> i32 {
+    val xs: [i32; SIZE] = [4, 5, 6]
+    return total(xs)
+}
+"#;
+    let exit_code = test
+        .compile_and_run("array_length_const.nr", source)
+        .expect("array length naming a const should compile and run");
+    assert_eq!(exit_code, 15, "4 + 5 + 6 should be 15");
+}
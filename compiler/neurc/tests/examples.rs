@@ -1,5 +1,8 @@
 // Integration test that compiles and runs *every* program under examples/.
 //
+// Every example is built at each optimization level, since the `-O1`..`-O3`
+// pass pipelines must not change what a program does.
+//
 // Discovery is automatic: the test walks examples/ recursively, collects each
 // `.nr` file, and checks its exit code against examples/expected.txt (the
 // single source of truth for expected codes). Adding a new example therefore
@@ -90,14 +93,22 @@ fn parse_manifest(path: &Path) -> BTreeMap<String, i32> {
     map
 }
 
-/// Compile `examples/<rel>` to a temp binary, returning its path or an error.
-fn compile_example(examples_dir: &Path, rel: &str) -> Result<PathBuf, String> {
+/// The `-O` levels every example is built and run at.
+const OPTIMIZATION_LEVELS: [u8; 4] = [0, 1, 2, 3];
+
+/// Compile `examples/<rel>` at `-O<level>` to a temp binary, returning its path
+/// or an error.
+fn compile_example(examples_dir: &Path, rel: &str, level: u8) -> Result<PathBuf, String> {
     let src = examples_dir.join(rel);
-    let out = std::env::temp_dir().join(format!("neuro_example_{}", rel.replace(['/', '.'], "_")));
+    let out = std::env::temp_dir().join(format!(
+        "neuro_example_O{level}_{}",
+        rel.replace(['/', '.'], "_")
+    ));
 
     let output = Command::new(neurc_path())
         .arg("compile")
         .arg(&src)
+        .arg(format!("-O{level}"))
         .arg("-o")
         .arg(&out)
         .output()
@@ -139,13 +150,17 @@ fn all_examples_compile_run_and_match_manifest() {
             ));
             continue;
         };
-        match compile_example(&examples_dir, rel) {
-            Ok(exe) => match run_exit_code(&exe) {
-                Ok(code) if code == expected => {}
-                Ok(code) => failures.push(format!("{rel}: exit code {code}, expected {expected}")),
-                Err(e) => failures.push(format!("{rel}: {e}")),
-            },
-            Err(e) => failures.push(format!("{rel}: {e}")),
+        for level in OPTIMIZATION_LEVELS {
+            match compile_example(&examples_dir, rel, level) {
+                Ok(exe) => match run_exit_code(&exe) {
+                    Ok(code) if code == expected => {}
+                    Ok(code) => failures.push(format!(
+                        "{rel} at -O{level}: exit code {code}, expected {expected}"
+                    )),
+                    Err(e) => failures.push(format!("{rel} at -O{level}: {e}")),
+                },
+                Err(e) => failures.push(format!("{rel} at -O{level}: {e}")),
+            }
         }
     }
 
@@ -87,3 +87,28 @@ func main() -> i32 {
         .expect("Compilation or execution failed");
     assert_eq!(exit_code, 8, "Expected exit code 8");
 }
+
+#[test]
+fn test_black_box_returns_its_argument() {
+    let test = CompileTest::new();
+    let source = r#"
+func square(x: i32) -> i32 {
+    return x * x
+}
+
+func main() -> i32 {
+    mut total: i32 = 0
+    mut i: i32 = 0
+    while i < 3 {
+        total += black_box(square(i + 1))
+        i += 1
+    }
+    return total
+}
+"#;
+
+    let exit_code = test
+        .compile_and_run("black_box.nr", source)
+        .expect("Compilation or execution failed");
+    assert_eq!(exit_code, 14, "Expected exit code 14");
+}
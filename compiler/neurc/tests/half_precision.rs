@@ -1,7 +1,7 @@
 // End-to-end tests for the `f16` / `bf16` half-precision primitives.
 //
-// The scalar contract is deliberately narrow: binding, copy, `==`/`!=`, and
-// `as`-cast to/from any numeric type — but no arithmetic (compute in `f32`).
+// Halves bind, copy, compare with `==`/`!=`, `as`-cast to/from any numeric
+// type, and support `+ - * / %` and negation between operands of the same type.
 
 mod common;
 use common::CompileTest;
@@ -113,20 +113,39 @@ func main() -> i32 {
 }
 
 #[test]
-fn half_precision_arithmetic_is_rejected() {
-    // Half-precision scalars have no arithmetic operators.
+fn half_precision_arithmetic() {
     let test = CompileTest::new();
     let source = r#"
 func main() -> i32 {
-    val a: f16 = 1.0f16
+    val a: f16 = 1.5f16
     val b: f16 = 2.0f16
-    val c: f16 = a + b
+    val c: f16 = a * b + -a
+    val g: bf16 = 10.0bf16
+    val h: bf16 = g / 4.0bf16
+    return (c as f32 * 2.0f32) as i32 + h as i32
+}
+"#;
+    let exit_code = test
+        .compile_and_run("half_arith.nr", source)
+        .expect("Compilation or execution failed");
+    assert_eq!(exit_code, 5);
+}
+
+#[test]
+fn mixed_half_precision_arithmetic_is_rejected() {
+    // Halves never widen implicitly: `f16 + bf16` needs an explicit cast.
+    let test = CompileTest::new();
+    let source = r#"
+func main() -> i32 {
+    val a: f16 = 1.0f16
+    val b: bf16 = 2.0bf16
+    val c = a + b
     return 0
 }
 "#;
-    let result = test.compile_and_run("half_no_arith.nr", source);
+    let result = test.compile_and_run("half_mixed_arith.nr", source);
     assert!(
         result.is_err(),
-        "half-precision arithmetic must be a compile error, got {result:?}"
+        "mixing f16 and bf16 must be a compile error, got {result:?}"
     );
 }
@@ -6,14 +6,15 @@
 mod common;
 
 use common::CompileTest;
-use hir_lowering::lower_program;
+use hir_lowering::lower_program_with_consts;
 use neuro_hir::{HirExpr, HirExprKind, HirItem, HirProgram, HirStmt, HirType};
 
 /// Parse, type-check, then lower `src`, asserting each stage succeeds.
 fn lower(src: &str) -> HirProgram {
     let ast = syntax_parsing::parse(src).expect("source should parse");
     semantic_analysis::type_check(&ast).expect("source should type-check");
-    lower_program(&ast).expect("type-checked source should lower")
+    let consts = semantic_analysis::const_array_lengths(&ast);
+    lower_program_with_consts(&ast, &consts).expect("type-checked source should lower")
 }
 
 fn function_body<'a>(program: &'a HirProgram, name: &str) -> &'a [HirStmt] {
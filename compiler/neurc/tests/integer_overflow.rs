@@ -2,7 +2,8 @@
 //
 // Debug builds (`-O0`) trap on `+`/`-`/`*` overflow; release builds (`-O1..-O3`)
 // wrap (two's complement). These tests compile the same overflowing program at
-// both optimization levels and assert the runtime behavior differs accordingly.
+// both optimization levels and assert the runtime behavior differs accordingly;
+// `-C overflow-checks=on|off` overrides the level's default in either direction.
 use std::path::PathBuf;
 use std::process::{Command, ExitStatus};
 
@@ -18,6 +19,11 @@ fn neurc_path() -> PathBuf {
 
 /// Compile `source` at optimization level `opt`, returning the executable path.
 fn compile_source(source: &str, tag: &str, opt: &str) -> PathBuf {
+    compile_source_with(source, tag, opt, &[])
+}
+
+/// Compile `source` at optimization level `opt` with extra `neurc compile` args.
+fn compile_source_with(source: &str, tag: &str, opt: &str, extra: &[&str]) -> PathBuf {
     let dir = std::env::temp_dir();
     let src = dir.join(format!("neuro_overflow_{tag}.nr"));
     let exe = dir.join(format!("neuro_overflow_{tag}"));
@@ -28,6 +34,7 @@ fn compile_source(source: &str, tag: &str, opt: &str) -> PathBuf {
         .arg(&src)
         .arg("-O")
         .arg(opt)
+        .args(extra)
         .arg("-o")
         .arg(&exe)
         .output()
@@ -124,3 +131,52 @@ fn signed_overflow_wraps_in_release() {
     // 2147483647 * 2 wraps to -2; the low byte of the exit code is 254.
     assert_eq!(exit_low_byte(status), Some(254));
 }
+
+#[test]
+fn overflow_checks_off_wraps_in_debug() {
+    let exe = compile_source_with(
+        UNSIGNED_OVERFLOW,
+        "u_dbg_off",
+        "0",
+        &["-C", "overflow-checks=off"],
+    );
+    let status = run(&exe);
+    assert_eq!(exit_low_byte(status), Some(44));
+}
+
+#[test]
+fn overflow_checks_on_traps_in_release() {
+    let exe = compile_source_with(
+        SIGNED_OVERFLOW,
+        "s_rel_on",
+        "2",
+        &["-C", "overflow-checks=on"],
+    );
+    let status = run(&exe);
+    assert!(
+        trapped(status),
+        "expected -C overflow-checks=on to trap, but it exited with {:?}",
+        status.code()
+    );
+}
+
+#[test]
+fn overflow_checks_rejects_unknown_value() {
+    let src = std::env::temp_dir().join("neuro_overflow_bad_flag.nr");
+    std::fs::write(&src, SIGNED_OVERFLOW).expect("write source");
+
+    let output = Command::new(neurc_path())
+        .arg("compile")
+        .arg(&src)
+        .arg("-C")
+        .arg("overflow-checks=maybe")
+        .output()
+        .expect("run neurc");
+
+    assert!(!output.status.success(), "bad -C value must be rejected");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("expected on or off"),
+        "expected a value hint in stderr, got: {stderr}"
+    );
+}
@@ -19,11 +19,17 @@ fn neurc_path() -> std::path::PathBuf {
 }
 
 fn run_check(source: &str) -> (i32, String, String) {
+    run_check_with(&[], source)
+}
+
+/// `neurc check` on `source`, with `flags` before the subcommand.
+fn run_check_with(flags: &[&str], source: &str) -> (i32, String, String) {
     let dir = TempDir::new().expect("temp dir");
     let path = dir.path().join("lint_test.nr");
     fs::write(&path, source).expect("write source");
 
     let output = Command::new(neurc_path())
+        .args(flags)
         .arg("check")
         .arg(&path)
         .output()
@@ -77,3 +83,54 @@ func main() -> i32 {
         stderr
     );
 }
+
+#[test]
+fn float_equality_warning_is_opt_in() {
+    let source = r#"
+func main() -> i32 {
+    val a: f64 = 0.1 + 0.2
+    if a == 0.3 { return 1 }
+    return 0
+}
+"#;
+    let (code, _stdout, stderr) = run_check(source);
+    assert_eq!(code, 0, "check should succeed; stderr: {}", stderr);
+    assert!(!stderr.contains("float-equality"), "got: {}", stderr);
+
+    let (code, _stdout, stderr) = run_check_with(&["-W", "float-equality"], source);
+    assert_eq!(code, 0, "check should succeed; stderr: {}", stderr);
+    assert!(
+        stderr.contains("float-equality"),
+        "expected warning in stderr, got: {}",
+        stderr
+    );
+}
+
+#[test]
+fn redundant_parens_hint_is_opt_in() {
+    let source = r#"
+func main() -> i32 {
+    val x: i32 = 2
+    return (x) * (x + 1)
+}
+"#;
+    let (code, _stdout, stderr) = run_check(source);
+    assert_eq!(code, 0, "check should succeed; stderr: {}", stderr);
+    assert!(!stderr.contains("redundant-parens"), "got: {}", stderr);
+
+    let (code, _stdout, stderr) = run_check_with(
+        &["-W", "redundant-parens", "--error-format", "short"],
+        source,
+    );
+    assert_eq!(code, 0, "check should succeed; stderr: {}", stderr);
+    let hints: Vec<&str> = stderr.lines().collect();
+    assert_eq!(hints.len(), 1, "got: {}", stderr);
+    assert!(
+        hints[0].ends_with(
+            ":4:12: hint: unnecessary parentheses around `x`; remove them, or silence with \
+             `@allow(redundant_parens)` on the enclosing function [redundant-parens]"
+        ),
+        "got: {}",
+        stderr
+    );
+}
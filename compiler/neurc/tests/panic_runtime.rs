@@ -102,6 +102,23 @@ func main() -> i32 {
 }
 "#;
 
+/// A non-void function whose `else` branch panics needs no `return` after the `if`:
+/// the branch diverges, so the other branch's `return` covers every path.
+const PANIC_ELSE_PROG: &str = r#"
+func checked_div(a: i32, b: i32) -> i32 {
+    if b != 0 {
+        return a / b
+    } else {
+        panic("division by zero")
+    }
+}
+
+func main() -> i32 {
+    val half = checked_div(10, 2)
+    return checked_div(half, 0)
+}
+"#;
+
 #[test]
 fn panic_aborts_and_prints_message() {
     let exe = compile_source(PANIC_PROG, "msg");
@@ -170,3 +187,15 @@ fn panic_in_tail_position_compiles_and_aborts() {
         stderr(&output)
     );
 }
+
+#[test]
+fn panic_in_else_branch_aborts_with_message() {
+    let exe = compile_source(PANIC_ELSE_PROG, "else");
+    let output = run(&exe);
+    assert!(aborted(&output), "expected the else-branch panic to abort");
+    assert!(
+        stderr(&output).contains("panic: division by zero"),
+        "stderr: {}",
+        stderr(&output)
+    );
+}
@@ -0,0 +1,371 @@
+// Compile-time evaluation of constant expressions: the shared engine behind
+// `const` folding, array lengths, and compile-time assertions.
+//
+// The evaluator walks the AST directly, so it can run before type checking. It
+// accepts literals, arithmetic, comparisons, logical and bitwise operators, casts
+// between primitive types, and references to other constants. Anything that needs
+// a running program (calls, variables, field access) is refused.
+
+use std::collections::HashMap;
+
+use ast_types::{BinaryOp, ConstDef, Expr, Item, Spanned, Type as TypeAnnotation, UnaryOp};
+use shared_types::{FloatSuffix, IntSuffix, Literal, Span};
+use thiserror::Error;
+
+/// Every integer suffix, for mapping a cast target's name back to its width.
+const INT_SUFFIXES: [IntSuffix; 8] = [
+    IntSuffix::I8,
+    IntSuffix::I16,
+    IntSuffix::I32,
+    IntSuffix::I64,
+    IntSuffix::U8,
+    IntSuffix::U16,
+    IntSuffix::U32,
+    IntSuffix::U64,
+];
+
+/// Every float suffix, for mapping a cast target's name back to its width.
+const FLOAT_SUFFIXES: [FloatSuffix; 4] = [
+    FloatSuffix::F16,
+    FloatSuffix::BF16,
+    FloatSuffix::F32,
+    FloatSuffix::F64,
+];
+
+/// Values of the constants visible to [`const_eval`], by name.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ConstEnv {
+    values: HashMap<String, Literal>,
+}
+
+impl ConstEnv {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Bind `name` to an already evaluated value, replacing any earlier binding.
+    pub fn define(&mut self, name: impl Into<String>, value: Literal) {
+        self.values.insert(name.into(), value);
+    }
+
+    /// The value bound to `name`, if any.
+    pub fn get(&self, name: &str) -> Option<&Literal> {
+        self.values.get(name)
+    }
+
+    /// The value bound to `name` as an array length: a non-negative integer.
+    pub fn array_length(&self, name: &str) -> Option<u64> {
+        match self.values.get(name)? {
+            Literal::Integer(value, ..) => u64::try_from(*value).ok(),
+            _ => None,
+        }
+    }
+}
+
+/// Why a constant expression could not be evaluated.
+#[derive(Debug, Error, Clone, PartialEq)]
+pub enum ConstEvalError {
+    #[error("expression at {span:?} is not a constant expression; calls, variables, and field accesses cannot be evaluated at compile time")]
+    NotConstant { span: Span },
+
+    #[error("unknown constant '{name}' at {span:?}")]
+    UnknownConst { name: String, span: Span },
+
+    #[error("operator '{op}' at {span:?} cannot be applied to these constant operands")]
+    InvalidOperands { op: String, span: Span },
+
+    #[error("division by zero in constant expression at {span:?}")]
+    DivisionByZero { span: Span },
+
+    #[error("integer overflow in constant expression at {span:?}")]
+    Overflow { span: Span },
+}
+
+impl ConstEvalError {
+    pub fn span(&self) -> Span {
+        match self {
+            Self::NotConstant { span }
+            | Self::UnknownConst { span, .. }
+            | Self::InvalidOperands { span, .. }
+            | Self::DivisionByZero { span }
+            | Self::Overflow { span } => *span,
+        }
+    }
+}
+
+/// Evaluate a constant expression to a literal value.
+///
+/// Integers are computed in 64 bits. A result whose operands carry a suffix must
+/// fit that type, so `200u8 + 100u8` is an overflow; an unsuffixed result only has
+/// to fit in `i64`. A computed value is always reported in decimal.
+///
+/// # Examples
+///
+/// ```
+/// use semantic_analysis::{const_eval, ConstEnv};
+/// use shared_types::Literal;
+/// use syntax_parsing::{parse, Item};
+///
+/// let ast = parse("const SIZE: i32 = BASE * 4 + 1").unwrap();
+/// let Item::Const(def) = &ast[0] else { unreachable!() };
+///
+/// let mut env = ConstEnv::new();
+/// env.define("BASE", Literal::Integer(8, None));
+/// assert_eq!(
+///     const_eval(&def.value, &env),
+///     Ok(Literal::Integer(33, None))
+/// );
+/// ```
+pub fn const_eval(expr: &Expr, env: &ConstEnv) -> Result<Literal, ConstEvalError> {
+    match expr {
+        Expr::Literal(literal, _) => Ok(literal.clone()),
+        Expr::Paren(inner, _) => const_eval(inner, env),
+        Expr::Identifier(name) => {
+            env.get(&name.name)
+                .cloned()
+                .ok_or_else(|| ConstEvalError::UnknownConst {
+                    name: name.name.clone(),
+                    span: name.span,
+                })
+        }
+        Expr::Unary { op, operand, span } => unary(*op, const_eval(operand, env)?, *span),
+        Expr::Binary {
+            left,
+            op,
+            right,
+            span,
+        } => binary(*op, const_eval(left, env)?, const_eval(right, env)?, *span),
+        Expr::Cast {
+            expr: inner,
+            target_type,
+            span,
+        } => cast(const_eval(inner, env)?, target_type, *span),
+        other => Err(ConstEvalError::NotConstant { span: other.span() }),
+    }
+}
+
+/// Evaluate module-level constants in dependency order, so each may refer to any
+/// other regardless of source order. Returns the values of those that evaluated
+/// and, for each that did not, the error from its last attempt. A constant stuck
+/// on [`ConstEvalError::UnknownConst`] naming another failed constant is either
+/// downstream of that failure or part of a reference cycle. When a name is
+/// declared twice, the first declaration wins.
+pub(crate) fn fold_consts<'a>(
+    defs: impl IntoIterator<Item = &'a ConstDef>,
+) -> (ConstEnv, HashMap<String, ConstEvalError>) {
+    let mut pending: Vec<&ConstDef> = Vec::new();
+    for def in defs {
+        if !pending.iter().any(|seen| seen.name.name == def.name.name) {
+            pending.push(def);
+        }
+    }
+
+    let mut env = ConstEnv::new();
+    loop {
+        let before = pending.len();
+        pending.retain(|def| match const_eval(&def.value, &env) {
+            Ok(value) => {
+                env.define(def.name.name.clone(), value);
+                false
+            }
+            Err(_) => true,
+        });
+        if pending.len() == before {
+            break;
+        }
+    }
+
+    let failures = pending
+        .into_iter()
+        .filter_map(|def| {
+            let error = const_eval(&def.value, &env).err()?;
+            Some((def.name.name.clone(), error))
+        })
+        .collect();
+    (env, failures)
+}
+
+/// The value of every module-level constant that evaluates to a non-negative
+/// integer, by name. After checking, this is what an array length naming a
+/// constant (`[f32; SIZE]`) resolves to.
+pub fn const_array_lengths(items: &[Item]) -> HashMap<String, u64> {
+    let (env, _) = fold_consts(items.iter().filter_map(|item| match item {
+        Item::Const(def) => Some(def),
+        _ => None,
+    }));
+    env.values
+        .keys()
+        .filter_map(|name| Some((name.clone(), env.array_length(name)?)))
+        .collect()
+}
+
+fn unary(op: UnaryOp, value: Literal, span: Span) -> Result<Literal, ConstEvalError> {
+    let invalid = || ConstEvalError::InvalidOperands {
+        op: match op {
+            UnaryOp::Negate => "-",
+            UnaryOp::Not => "!",
+            UnaryOp::BitNot => "~",
+        }
+        .to_string(),
+        span,
+    };
+    match (op, value) {
+        (UnaryOp::Negate, Literal::Integer(v, suffix)) => integer(v.checked_neg(), suffix, span),
+        (UnaryOp::Negate, Literal::Float(v, suffix)) => Ok(Literal::Float(-v, suffix)),
+        (UnaryOp::Not, Literal::Boolean(b)) => Ok(Literal::Boolean(!b)),
+        (UnaryOp::BitNot, Literal::Integer(v, suffix)) => {
+            let flipped = match suffix {
+                Some(suffix) => wrap_to(!v, suffix),
+                None => !v,
+            };
+            Ok(Literal::Integer(flipped, suffix))
+        }
+        _ => Err(invalid()),
+    }
+}
+
+fn binary(
+    op: BinaryOp,
+    left: Literal,
+    right: Literal,
+    span: Span,
+) -> Result<Literal, ConstEvalError> {
+    let invalid = || ConstEvalError::InvalidOperands {
+        op: op.to_string(),
+        span,
+    };
+    match (left, right) {
+        (Literal::Integer(a, a_suffix), Literal::Integer(b, b_suffix)) => {
+            let suffix = match (a_suffix, b_suffix) {
+                (Some(a), Some(b)) if a != b => return Err(invalid()),
+                (a, b) => a.or(b),
+            };
+            let value = match op {
+                BinaryOp::Add => a.checked_add(b),
+                BinaryOp::Subtract => a.checked_sub(b),
+                BinaryOp::Multiply => a.checked_mul(b),
+                BinaryOp::Divide | BinaryOp::Modulo if b == 0 => {
+                    return Err(ConstEvalError::DivisionByZero { span })
+                }
+                BinaryOp::Divide => a.checked_div(b),
+                BinaryOp::Modulo => a.checked_rem(b),
+                BinaryOp::BitAnd => Some(a & b),
+                BinaryOp::BitOr => Some(a | b),
+                BinaryOp::BitXor => Some(a ^ b),
+                BinaryOp::Shl => u32::try_from(b).ok().and_then(|b| a.checked_shl(b)),
+                _ => {
+                    return compare(op, &a, &b)
+                        .map(Literal::Boolean)
+                        .ok_or_else(invalid)
+                }
+            };
+            integer(value, suffix, span)
+        }
+        (Literal::Float(a, a_suffix), Literal::Float(b, b_suffix)) => {
+            let suffix = match (a_suffix, b_suffix) {
+                (Some(a), Some(b)) if a != b => return Err(invalid()),
+                (a, b) => a.or(b),
+            };
+            let value = match op {
+                BinaryOp::Add => a + b,
+                BinaryOp::Subtract => a - b,
+                BinaryOp::Multiply => a * b,
+                BinaryOp::Divide => a / b,
+                BinaryOp::Modulo => a % b,
+                _ => {
+                    return compare(op, &a, &b)
+                        .map(Literal::Boolean)
+                        .ok_or_else(invalid)
+                }
+            };
+            Ok(Literal::Float(value, suffix))
+        }
+        (Literal::Boolean(a), Literal::Boolean(b)) => match op {
+            BinaryOp::And => Ok(Literal::Boolean(a && b)),
+            BinaryOp::Or => Ok(Literal::Boolean(a || b)),
+            BinaryOp::Equal => Ok(Literal::Boolean(a == b)),
+            BinaryOp::NotEqual => Ok(Literal::Boolean(a != b)),
+            _ => Err(invalid()),
+        },
+        (Literal::Char(a), Literal::Char(b)) => compare(op, &a, &b)
+            .map(Literal::Boolean)
+            .ok_or_else(invalid),
+        (Literal::String(a), Literal::String(b)) => match op {
+            BinaryOp::Equal => Ok(Literal::Boolean(a == b)),
+            BinaryOp::NotEqual => Ok(Literal::Boolean(a != b)),
+            _ => Err(invalid()),
+        },
+        _ => Err(invalid()),
+    }
+}
+
+/// The result of a comparison operator, or `None` when `op` is not one.
+fn compare<T: PartialOrd>(op: BinaryOp, a: &T, b: &T) -> Option<bool> {
+    match op {
+        BinaryOp::Equal => Some(a == b),
+        BinaryOp::NotEqual => Some(a != b),
+        BinaryOp::Less => Some(a < b),
+        BinaryOp::Greater => Some(a > b),
+        BinaryOp::LessEqual => Some(a <= b),
+        BinaryOp::GreaterEqual => Some(a >= b),
+        _ => None,
+    }
+}
+
+/// An integer result, checked against the range of its suffix type.
+fn integer(
+    value: Option<i64>,
+    suffix: Option<IntSuffix>,
+    span: Span,
+) -> Result<Literal, ConstEvalError> {
+    value
+        .filter(|v| suffix.is_none_or(|suffix| wrap_to(*v, suffix) == *v))
+        .map(|v| Literal::Integer(v, suffix))
+        .ok_or(ConstEvalError::Overflow { span })
+}
+
+/// `value` truncated to the width of `suffix`, as an `as` cast would. `u64`
+/// values are kept as their `i64` bit pattern.
+fn wrap_to(value: i64, suffix: IntSuffix) -> i64 {
+    match suffix {
+        IntSuffix::I8 => value as i8 as i64,
+        IntSuffix::I16 => value as i16 as i64,
+        IntSuffix::I32 => value as i32 as i64,
+        IntSuffix::U8 => value as u8 as i64,
+        IntSuffix::U16 => value as u16 as i64,
+        IntSuffix::U32 => value as u32 as i64,
+        IntSuffix::I64 | IntSuffix::U64 => value,
+    }
+}
+
+/// An `as` cast to a primitive numeric type.
+fn cast(value: Literal, target: &TypeAnnotation, span: Span) -> Result<Literal, ConstEvalError> {
+    let not_constant = || ConstEvalError::NotConstant { span };
+    let TypeAnnotation::Named(name) = target else {
+        return Err(not_constant());
+    };
+    let name = name.name.as_str();
+
+    if let Some(suffix) = INT_SUFFIXES.into_iter().find(|s| s.as_str() == name) {
+        let bits = match value {
+            Literal::Integer(v, ..) => v,
+            // Saturating, like Rust's float-to-int `as`.
+            Literal::Float(v, _) => v as i64,
+            Literal::Boolean(b) => i64::from(b),
+            Literal::Char(c) => i64::from(u32::from(c)),
+            Literal::String(_) => return Err(not_constant()),
+        };
+        return Ok(Literal::Integer(wrap_to(bits, suffix), Some(suffix)));
+    }
+
+    if let Some(suffix) = FLOAT_SUFFIXES.into_iter().find(|s| s.as_str() == name) {
+        let v = match value {
+            Literal::Integer(v, ..) => v as f64,
+            Literal::Float(v, _) => v,
+            _ => return Err(not_constant()),
+        };
+        return Ok(Literal::Float(v, Some(suffix)));
+    }
+
+    Err(not_constant())
+}
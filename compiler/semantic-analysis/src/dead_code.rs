@@ -0,0 +1,94 @@
+// Item-level dead code: top-level functions no path of calls from `main` reaches.
+//
+// The call graph comes from the reference index: a use of a top-level function's
+// name inside another function's span is an edge from that function. Naming a
+// function as a value (`val f = helper`) counts as a call, since the value may be
+// called later. Uses outside any top-level function — in a method, a trait default
+// body, or a constant — make their target a root, because method calls are not
+// resolved here and so methods are treated as live.
+
+use std::collections::HashMap;
+
+use ast_types::{FunctionDef, Item};
+
+use crate::references::build_reference_index;
+use crate::warnings::{Warning, WarningCode};
+
+/// The attribute marking a benchmark, which `neurc bench` calls directly.
+const BENCH_ATTRIBUTE: &str = "bench";
+
+/// The name prefix marking a test, which `neurc compile --test` calls directly.
+const TEST_PREFIX: &str = "test_";
+
+/// A `dead-code` warning, at the function's span, for each top-level function
+/// that `main`, `@bench` and `test_*` functions, and methods never reach. A
+/// program without a `main` is a library whose functions are all entry points,
+/// and gets none.
+pub(crate) fn unreachable_functions(items: &[Item]) -> Vec<Warning> {
+    let functions: Vec<&FunctionDef> = items
+        .iter()
+        .filter_map(|item| match item {
+            Item::Function(func) => Some(func),
+            _ => None,
+        })
+        .collect();
+    if !functions.iter().any(|func| func.name.name == "main") {
+        return Vec::new();
+    }
+
+    let by_definition: HashMap<usize, usize> = functions
+        .iter()
+        .enumerate()
+        .map(|(index, func)| (func.name.span.start, index))
+        .collect();
+    let mut callees: Vec<Vec<usize>> = vec![Vec::new(); functions.len()];
+    let mut live: Vec<bool> = functions
+        .iter()
+        .map(|func| {
+            func.name.name == "main"
+                || func.name.name.starts_with(TEST_PREFIX)
+                || func
+                    .attributes
+                    .iter()
+                    .any(|attr| attr.name.name == BENCH_ATTRIBUTE)
+        })
+        .collect();
+
+    for (use_span, definition) in build_reference_index(items).references() {
+        let Some(&callee) = by_definition.get(&definition.start) else {
+            continue;
+        };
+        let caller = functions
+            .iter()
+            .position(|func| func.span.start <= use_span.start && use_span.end <= func.span.end);
+        match caller {
+            Some(caller) => callees[caller].push(callee),
+            None => live[callee] = true,
+        }
+    }
+
+    let mut pending: Vec<usize> = (0..functions.len()).filter(|&i| live[i]).collect();
+    while let Some(caller) = pending.pop() {
+        for &callee in &callees[caller] {
+            if !live[callee] {
+                live[callee] = true;
+                pending.push(callee);
+            }
+        }
+    }
+
+    functions
+        .iter()
+        .zip(live)
+        .filter(|(_, live)| !live)
+        .map(|(func, _)| Warning {
+            code: WarningCode::DeadCode,
+            message: format!(
+                "function '{}' is never called from `main`; remove it or silence with \
+                 `@allow(dead_code)` on the function",
+                func.name.name
+            ),
+            span: func.span,
+        })
+        .collect()
+}
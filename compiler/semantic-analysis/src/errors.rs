@@ -1,45 +1,53 @@
 // Type checking error definitions
 
+use diagnostics::{Diagnostic, DiagnosticCode, Suggestion};
 use shared_types::Span;
 use thiserror::Error;
 
 use crate::types::Type;
 
-/// Type checking errors with source location information
+/// Type checking errors. Each carries the span it points at ([`TypeError::span`]);
+/// the message leaves the location to whoever renders it.
 #[derive(Debug, Error, Clone, PartialEq)]
 pub enum TypeError {
-    #[error("type mismatch at {span:?}: expected {expected}, found {found}")]
+    #[error("type mismatch: expected {expected}, found {found}")]
     Mismatch {
         expected: Type,
         found: Type,
         span: Span,
     },
 
-    #[error("undefined variable '{name}' at {span:?}")]
-    UndefinedVariable { name: String, span: Span },
+    /// `suggestion` is the closest variable or constant in scope when `name`
+    /// looks like a typo of it; [`TypeError::to_diagnostic`] offers it as a fix.
+    #[error("undefined variable '{name}'")]
+    UndefinedVariable {
+        name: String,
+        span: Span,
+        suggestion: Option<String>,
+    },
 
-    #[error("undefined function '{name}' at {span:?}")]
+    #[error("undefined function '{name}'")]
     UndefinedFunction { name: String, span: Span },
 
-    #[error("generic type parameter '{name}' at {span:?} shadows a built-in type name")]
+    #[error("generic type parameter '{name}' shadows a built-in type name")]
     GenericParamShadowsBuiltin { name: String, span: Span },
 
-    #[error("generic parameter '{name}' at {span:?} cannot be inferred from the call arguments; supply it explicitly with a turbofish, e.g. `f::<...>(...)`")]
+    #[error("generic parameter '{name}' cannot be inferred from the call arguments; supply it explicitly with a turbofish, e.g. `f::<...>(...)`")]
     GenericParamNotInferable { name: String, span: Span },
 
-    #[error("array length '{name}' at {span:?} is not a known constant; use an integer literal or an in-scope `const` generic parameter")]
+    #[error("array length '{name}' is not a known constant; use an integer literal, a module-level integer `const`, or an in-scope `const` generic parameter")]
     UnknownArrayLength { name: String, span: Span },
 
-    #[error("undeclared lifetime `'{name}` at {span:?}; declare it in the generic parameter list, e.g. `func f<'{name}>(...)`")]
+    #[error("undeclared lifetime `'{name}`; declare it in the generic parameter list, e.g. `func f<'{name}>(...)`")]
     UndeclaredLifetime { name: String, span: Span },
 
-    #[error("const generic parameter '{name}' at {span:?} has non-integer type '{ty}'; const parameters must be an integer type")]
+    #[error("const generic parameter '{name}' has non-integer type '{ty}'; const parameters must be an integer type")]
     ConstParamNotInteger { name: String, ty: Type, span: Span },
 
-    #[error("`where` predicate at {span:?} is not satisfied for this instantiation")]
+    #[error("`where` predicate is not satisfied for this instantiation")]
     ConstPredicateViolated { span: Span },
 
-    #[error("turbofish at {span:?} supplies {found} generic argument(s), but '{name}' declares {expected}")]
+    #[error("turbofish supplies {found} generic argument(s), but '{name}' declares {expected}")]
     TurbofishCountMismatch {
         name: String,
         expected: usize,
@@ -47,22 +55,20 @@ pub enum TypeError {
         span: Span,
     },
 
-    #[error("turbofish argument for parameter '{param}' at {span:?} has the wrong kind: a {expected} argument was expected")]
+    #[error("turbofish argument for parameter '{param}' has the wrong kind: a {expected} argument was expected")]
     TurbofishKindMismatch {
         param: String,
         expected: String,
         span: Span,
     },
 
-    #[error("type argument '{ty}' for generic parameter '{param}' at {span:?} is not Copy; generic type arguments are restricted to Copy types in this phase")]
+    #[error("type argument '{ty}' for generic parameter '{param}' is not Copy; generic type arguments are restricted to Copy types in this phase")]
     GenericArgumentNotCopy { param: String, ty: Type, span: Span },
 
-    #[error("generic struct '{name}' at {span:?} requires type arguments, e.g. `{name}<...>`")]
+    #[error("generic struct '{name}' requires type arguments, e.g. `{name}<...>`")]
     GenericStructNeedsArgs { name: String, span: Span },
 
-    #[error(
-        "generic struct '{name}' at {span:?} expects {expected} type argument(s), found {found}"
-    )]
+    #[error("generic struct '{name}' expects {expected} type argument(s), found {found}")]
     GenericArgCountMismatch {
         name: String,
         expected: usize,
@@ -70,29 +76,29 @@ pub enum TypeError {
         span: Span,
     },
 
-    #[error("type argument list applied to non-generic type '{name}' at {span:?}")]
+    #[error("type argument list applied to non-generic type '{name}'")]
     NotAGenericType { name: String, span: Span },
 
-    #[error("nested generic type argument at {span:?} is not yet supported: a generic type may not be instantiated with an enclosing type parameter in this phase")]
+    #[error("nested generic type argument is not yet supported: a generic type may not be instantiated with an enclosing type parameter in this phase")]
     NestedGenericTypeArg { span: Span },
 
-    #[error("variable '{name}' already defined in this scope at {span:?}")]
+    #[error("variable '{name}' already defined in this scope")]
     VariableAlreadyDefined { name: String, span: Span },
 
-    #[error("function '{name}' already defined at {span:?}")]
+    #[error("function '{name}' already defined")]
     FunctionAlreadyDefined { name: String, span: Span },
 
-    #[error("incorrect number of arguments at {span:?}: expected {expected}, found {found}")]
+    #[error("incorrect number of arguments: expected {expected}, found {found}")]
     ArgumentCountMismatch {
         expected: usize,
         found: usize,
         span: Span,
     },
 
-    #[error("cannot apply operator {op} to type {ty} at {span:?}")]
+    #[error("cannot apply operator {op} to type {ty}")]
     InvalidOperator { op: String, ty: Type, span: Span },
 
-    #[error("struct '{struct_name}' cannot derive Copy at {span:?}: field '{field_name}' has type {field_type}, which is not Copy")]
+    #[error("struct '{struct_name}' cannot derive Copy: field '{field_name}' has type {field_type}, which is not Copy")]
     CopyDeriveNonCopyField {
         struct_name: String,
         field_name: String,
@@ -100,48 +106,49 @@ pub enum TypeError {
         span: Span,
     },
 
-    #[error("type '{type_name}' implements Drop at {span:?} and so cannot be Copy: a type with a destructor must be moved, not duplicated")]
+    #[error("type '{type_name}' implements Drop and so cannot be Copy: a type with a destructor must be moved, not duplicated")]
     DropTypeCannotBeCopy { type_name: String, span: Span },
 
-    #[error("invalid `impl Drop for {type_name}` at {span:?}: {reason}")]
+    #[error("invalid `impl Drop for {type_name}`: {reason}")]
     InvalidDropImpl {
         type_name: String,
         reason: String,
         span: Span,
     },
 
-    #[error("unknown trait '{trait_name}' at {span:?}: no `trait {trait_name}` is declared")]
+    #[error("invalid `@optimize` attribute: {reason}")]
+    InvalidOptimizeAttribute { reason: String, span: Span },
+
+    #[error("unknown trait '{trait_name}': no `trait {trait_name}` is declared")]
     UnknownTrait { trait_name: String, span: Span },
 
-    #[error("trait '{trait_name}' is already defined at {span:?}")]
+    #[error("trait '{trait_name}' is already defined")]
     TraitAlreadyDefined { trait_name: String, span: Span },
 
-    #[error("`dyn {trait_name}` at {span:?} is unsized and must appear behind a reference — write `&dyn {trait_name}` or `&mut dyn {trait_name}`")]
+    #[error("`dyn {trait_name}` is unsized and must appear behind a reference — write `&dyn {trait_name}` or `&mut dyn {trait_name}`")]
     DynTraitNotBehindReference { trait_name: String, span: Span },
 
-    #[error("trait '{trait_name}' is not object-safe and cannot be used as `dyn {trait_name}` at {span:?}: {reason}")]
+    #[error("trait '{trait_name}' is not object-safe and cannot be used as `dyn {trait_name}`: {reason}")]
     TraitNotObjectSafe {
         trait_name: String,
         reason: String,
         span: Span,
     },
 
-    #[error("`impl Trait` at {span:?} is only allowed in a function parameter or return type")]
+    #[error("`impl Trait` is only allowed in a function parameter or return type")]
     ImplTraitNotAllowedHere { span: Span },
 
-    #[error("cannot infer the concrete type of the `impl {trait_name}` return at {span:?}: return a direct constructor (a struct literal or enum value); other forms await closures/iterators")]
+    #[error("cannot infer the concrete type of the `impl {trait_name}` return: return a direct constructor (a struct literal or enum value); other forms await closures/iterators")]
     ImplReturnNotInferable { trait_name: String, span: Span },
 
-    #[error("the `impl {trait_name}` return type at {span:?} resolves to `{ty}`, which does not implement '{trait_name}'")]
+    #[error("the `impl {trait_name}` return type resolves to `{ty}`, which does not implement '{trait_name}'")]
     ImplReturnDoesNotImplement {
         trait_name: String,
         ty: Type,
         span: Span,
     },
 
-    #[error(
-        "`impl {trait_name} for {type_name}` at {span:?} is missing required method '{method}'"
-    )]
+    #[error("`impl {trait_name} for {type_name}` is missing required method '{method}'")]
     MissingTraitMethod {
         trait_name: String,
         type_name: String,
@@ -149,14 +156,14 @@ pub enum TypeError {
         span: Span,
     },
 
-    #[error("method '{method}' at {span:?} is not a member of trait '{trait_name}'")]
+    #[error("method '{method}' is not a member of trait '{trait_name}'")]
     NotATraitMethod {
         trait_name: String,
         method: String,
         span: Span,
     },
 
-    #[error("method '{method}' in `impl {trait_name} for {type_name}` at {span:?} does not match the trait signature: {detail}")]
+    #[error("method '{method}' in `impl {trait_name} for {type_name}` does not match the trait signature: {detail}")]
     TraitMethodSignatureMismatch {
         trait_name: String,
         type_name: String,
@@ -165,7 +172,7 @@ pub enum TypeError {
         span: Span,
     },
 
-    #[error("type argument `{ty}` for '{param}' does not implement required trait '{trait_name}' at {span:?}")]
+    #[error("type argument `{ty}` for '{param}' does not implement required trait '{trait_name}'")]
     TraitBoundNotSatisfied {
         param: String,
         ty: Type,
@@ -173,7 +180,7 @@ pub enum TypeError {
         span: Span,
     },
 
-    #[error("cannot apply binary operator {op} to types {left} and {right} at {span:?}")]
+    #[error("cannot apply binary operator {op} to types {left} and {right}")]
     InvalidBinaryOperator {
         op: String,
         left: Type,
@@ -181,14 +188,14 @@ pub enum TypeError {
         span: Span,
     },
 
-    #[error("operator trait '{trait_name}' can only be implemented for a `Copy` type; '{type_name}' at {span:?} is not `Copy`")]
+    #[error("operator trait '{trait_name}' can only be implemented for a `Copy` type; '{type_name}' is not `Copy`")]
     OperatorTraitRequiresCopy {
         trait_name: String,
         type_name: String,
         span: Span,
     },
 
-    #[error("in `impl {trait_name}`, `type Output = {expected}` does not match method return type {found} at {span:?}")]
+    #[error("in `impl {trait_name}`, `type Output = {expected}` does not match method return type {found}")]
     AssociatedTypeMismatch {
         trait_name: String,
         expected: Type,
@@ -196,7 +203,7 @@ pub enum TypeError {
         span: Span,
     },
 
-    #[error("`impl {trait_name} for {type_name}` at {span:?} requires `impl {supertrait} for {type_name}`")]
+    #[error("`impl {trait_name} for {type_name}` requires `impl {supertrait} for {type_name}`")]
     MissingSupertraitImpl {
         trait_name: String,
         supertrait: String,
@@ -204,235 +211,266 @@ pub enum TypeError {
         span: Span,
     },
 
-    #[error("arithmetic operator {op} is not defined on half-precision type {ty} at {span:?}: compute in f32, e.g. `(a as f32 {op} b as f32)`")]
-    HalfFloatArithmetic { op: String, ty: Type, span: Span },
-
-    #[error("return type mismatch at {span:?}: expected {expected}, found {found}")]
+    #[error("return type mismatch: expected {expected}, found {found}")]
     ReturnTypeMismatch {
         expected: Type,
         found: Type,
         span: Span,
     },
 
-    #[error("missing return statement in function returning {expected} at {span:?}")]
+    #[error("missing return statement in function returning {expected}")]
     MissingReturn { expected: Type, span: Span },
 
-    #[error("unknown type name '{name}' at {span:?}")]
+    #[error("unknown type name '{name}'")]
     UnknownTypeName { name: String, span: Span },
 
-    #[error("cannot call non-function type {ty} at {span:?}")]
+    #[error("cannot call non-function type {ty}")]
     NotCallable { ty: Type, span: Span },
 
-    #[error("variable '{name}' used without initialization at {span:?}")]
+    #[error("variable '{name}' used without initialization")]
     UninitializedVariable { name: String, span: Span },
 
-    #[error("cannot assign to immutable variable '{name}' at {span:?}")]
+    #[error("nested function cannot use '{name}' from the enclosing function: nested functions capture nothing; pass it as a parameter or use a closure")]
+    NestedFunctionCapture { name: String, span: Span },
+
+    #[error("nested function '{name}' cannot be generic: declare it at the top level")]
+    GenericNestedFunction { name: String, span: Span },
+
+    #[error(
+        "`@{attribute}` is not supported on nested function '{name}': declare it at the top level"
+    )]
+    UnsupportedNestedAttribute {
+        name: String,
+        attribute: String,
+        span: Span,
+    },
+
+    #[error("variable '{name}' is used in its own initializer: it is not in scope until the declaration completes")]
+    UseBeforeInit { name: String, span: Span },
+
+    #[error("cannot assign to immutable variable '{name}'")]
     AssignToImmutable { name: String, span: Span },
 
-    #[error("integer literal {value} out of range for type {ty} at {span:?}")]
+    #[error("integer literal {value} out of range for type {ty}")]
     IntegerLiteralOutOfRange { value: i64, ty: Type, span: Span },
 
-    #[error("'break' used outside of a loop at {span:?}")]
+    #[error("'break' used outside of a loop")]
     BreakOutsideLoop { span: Span },
 
-    #[error("'continue' used outside of a loop at {span:?}")]
+    #[error("'continue' used outside of a loop")]
     ContinueOutsideLoop { span: Span },
 
-    #[error("use of undefined loop label '{name}' at {span:?}")]
+    #[error("use of undefined loop label '{name}'")]
     UndefinedLabel { name: String, span: Span },
 
-    #[error("'break' with a value is only allowed in a 'loop'; 'while' and 'for' always yield unit, at {span:?}")]
+    #[error(
+        "'break' with a value is only allowed in a 'loop'; 'while' and 'for' always yield unit,"
+    )]
     BreakValueInUnitLoop { span: Span },
 
-    #[error("for-range bound must be an integer type, found {found} at {span:?}")]
+    #[error("for-range bound must be an integer type, found {found}")]
     InvalidForRangeType { found: Type, span: Span },
 
-    #[error("name '{name}' at {span:?} contains '__', which is reserved for compiler-generated symbols; use a single underscore")]
+    #[error("name '{name}' contains '__', which is reserved for compiler-generated symbols; use a single underscore")]
     ReservedNameSeparator { name: String, span: Span },
 
-    #[error("struct '{name}' already defined at {span:?}")]
+    #[error("struct '{name}' already defined")]
     StructAlreadyDefined { name: String, span: Span },
 
-    #[error("unknown struct '{name}' at {span:?}")]
+    #[error("field '{name}' is declared more than once in this struct")]
+    DuplicateField { name: String, span: Span },
+
+    #[error("unknown struct '{name}'")]
     UnknownStruct { name: String, span: Span },
 
-    #[error("struct '{struct_name}' has no field '{field_name}' at {span:?}")]
+    #[error("struct '{struct_name}' has no field '{field_name}'")]
     UnknownField {
         struct_name: String,
         field_name: String,
         span: Span,
     },
 
-    #[error("missing field '{field_name}' in struct literal for '{struct_name}' at {span:?}")]
+    #[error("missing field '{field_name}' in struct literal for '{struct_name}'")]
     MissingStructField {
         struct_name: String,
         field_name: String,
         span: Span,
     },
 
-    #[error("field '{field_name}' provided more than once in struct literal at {span:?}")]
+    #[error("field '{field_name}' provided more than once in struct literal")]
     DuplicateStructField { field_name: String, span: Span },
 
-    #[error("cannot assign to field '{field_name}' of immutable binding '{var_name}' at {span:?}")]
+    #[error("cannot assign to field '{field_name}' of immutable binding '{var_name}'")]
     AssignToImmutableField {
         var_name: String,
         field_name: String,
         span: Span,
     },
 
-    #[error("struct '{struct_name}' has no method '{method_name}' at {span:?}")]
+    #[error("struct '{struct_name}' has no method '{method_name}'")]
     MethodNotFound {
         struct_name: String,
         method_name: String,
         span: Span,
     },
 
-    #[error("impl block for '{type_name}' at {span:?}: '{self_param}' methods are not yet supported (ownership semantics pending)")]
+    #[error("impl block for '{type_name}': '{self_param}' methods are not yet supported (ownership semantics pending)")]
     UnsupportedSelfParam {
         type_name: String,
         self_param: String,
         span: Span,
     },
 
-    #[error("unknown type '{type_name}' in path expression '{type_name}::{member}' at {span:?}")]
+    #[error("unknown type '{type_name}' in path expression '{type_name}::{member}'")]
     UnknownPathType {
         type_name: String,
         member: String,
         span: Span,
     },
 
-    #[error("'{type_name}' has no associated function '{member}' at {span:?}")]
+    #[error("'{type_name}' has no associated function '{member}'")]
     UnknownAssociatedFunction {
         type_name: String,
         member: String,
         span: Span,
     },
 
-    #[error("constant '{name}' already defined at {span:?}")]
+    #[error("constant '{name}' already defined")]
     ConstAlreadyDefined { name: String, span: Span },
 
-    #[error("constant expression required at {span:?}: only literals, arithmetic on literals, and references to other constants are allowed")]
+    #[error("constant expression required: only literals, operators, casts, and references to other constants are allowed")]
     InvalidConstExpr { span: Span },
 
-    #[error("const '{name}' references undefined constant '{referenced}' at {span:?}")]
+    #[error("division by zero in constant expression")]
+    ConstDivisionByZero { span: Span },
+
+    #[error("integer overflow in constant expression")]
+    ConstOverflow { span: Span },
+
+    #[error(
+        "constant '{name}' is cyclic: a constant may not refer to itself directly or transitively"
+    )]
+    CyclicConst { name: String, span: Span },
+
+    #[error("const '{name}' references undefined constant '{referenced}'")]
     UndefinedConst {
         name: String,
         referenced: String,
         span: Span,
     },
 
-    #[error("operator '{op}' is not yet supported at {span:?}: {hint}")]
+    #[error("operator '{op}' is not yet supported: {hint}")]
     OperatorNotYetSupported {
         op: String,
         hint: String,
         span: Span,
     },
 
-    #[error("comparison operators cannot be chained at {span:?}: use `&&` to combine separate comparisons")]
+    #[error("comparison operators cannot be chained: use `&&` to combine separate comparisons")]
     ComparisonChain { span: Span },
 
-    #[error("use of moved value '{name}' at {span:?}: it was moved at {moved_at:?}; bind a `.clone()` if you need an independent copy")]
+    #[error("use of moved value '{name}': it was moved at {moved_at:?}; bind a `.clone()` if you need an independent copy")]
     UseOfMovedValue {
         name: String,
         span: Span,
         moved_at: Span,
     },
 
-    #[error("cannot borrow this expression at {span:?}: `&` requires a place (a variable); bind it to a `val` first")]
+    #[error("cannot borrow this expression: `&` requires a place (a variable); bind it to a `val` first")]
     CannotBorrowValue { span: Span },
 
-    #[error("cannot mutably borrow '{name}' at {span:?}: `&mut` requires a `mut` binding; declare it with `mut`")]
+    #[error(
+        "cannot mutably borrow '{name}': `&mut` requires a `mut` binding; declare it with `mut`"
+    )]
     CannotBorrowMutably { name: String, span: Span },
 
-    #[error("cannot dereference a non-reference value of type `{found}` at {span:?}: `*` applies only to `&T` / `&mut T`")]
+    #[error("cannot dereference a non-reference value of type `{found}`: `*` applies only to `&T` / `&mut T`")]
     CannotDereference { found: Type, span: Span },
 
-    #[error("cannot assign through an immutable reference `&{inner}` at {span:?}: writing through `*` requires a `&mut {inner}`")]
+    #[error("cannot assign through an immutable reference `&{inner}`: writing through `*` requires a `&mut {inner}`")]
     CannotAssignThroughRef { inner: Type, span: Span },
 
-    #[error("cannot borrow '{name}' as mutable at {span:?}: it is already borrowed; a `&mut` borrow is exclusive — no other borrow of '{name}' may be live at the same time")]
+    #[error("cannot borrow '{name}' as mutable: it is already borrowed; a `&mut` borrow is exclusive — no other borrow of '{name}' may be live at the same time")]
     CannotMutablyBorrowWhileBorrowed { name: String, span: Span },
 
-    #[error("cannot borrow '{name}' as immutable at {span:?}: it is already mutably borrowed; an active `&mut` borrow excludes all other borrows of '{name}'")]
+    #[error("cannot borrow '{name}' as immutable: it is already mutably borrowed; an active `&mut` borrow excludes all other borrows of '{name}'")]
     CannotBorrowWhileMutablyBorrowed { name: String, span: Span },
 
-    #[error("cannot return a reference to '{name}' at {span:?}: it is local to this function and does not outlive the call; return a reference derived from a parameter instead")]
+    #[error("cannot return a reference to '{name}': it is local to this function and does not outlive the call; return a reference derived from a parameter instead")]
     ReturnsReferenceToLocal { name: String, span: Span },
 
-    #[error("a range expression `a..b` is only valid as the argument to `.slice()` at {span:?}")]
+    #[error("a range expression `a..b` is only valid as the argument to `.slice()`")]
     RangeNotAllowed { span: Span },
 
-    #[error("`.slice()` expects a range argument `a..b` or `a..=b` at {span:?}")]
+    #[error("`.slice()` expects a range argument `a..b` or `a..=b`")]
     SliceExpectsRange { span: Span },
 
-    #[error("array element type {ty} is not Copy at {span:?}: arrays of non-Copy element types (strings, non-Copy structs) are not yet supported")]
+    #[error("array element type {ty} is not Copy: arrays of non-Copy element types (strings, non-Copy structs) are not yet supported")]
     NonCopyArrayElement { ty: Type, span: Span },
 
-    #[error("cannot index a value of type {found} at {span:?}: indexing applies only to arrays `[T; N]`")]
+    #[error("cannot index a value of type {found}: indexing applies only to arrays `[T; N]`")]
     NotIndexable { found: Type, span: Span },
 
-    #[error("array index must be an integer, found {found} at {span:?}")]
+    #[error("array index must be an integer, found {found}")]
     IndexNotInteger { found: Type, span: Span },
 
-    #[error(
-        "array literal has {found} elements but type annotation expects {expected} at {span:?}"
-    )]
+    #[error("array literal has {found} elements but type annotation expects {expected}")]
     ArrayLengthMismatch {
         expected: usize,
         found: usize,
         span: Span,
     },
 
-    #[error("tuple element type {ty} is not Copy at {span:?}: tuples of non-Copy element types (strings, non-Copy structs) are not yet supported")]
+    #[error("tuple element type {ty} is not Copy: tuples of non-Copy element types (strings, non-Copy structs) are not yet supported")]
     NonCopyTupleElement { ty: Type, span: Span },
 
-    #[error("cannot index a value of type {found} at {span:?}: `.N` tuple indexing applies only to tuples `(T1, T2, ...)`")]
+    #[error("cannot index a value of type {found}: `.N` tuple indexing applies only to tuples `(T1, T2, ...)`")]
     NotATuple { found: Type, span: Span },
 
-    #[error("tuple index {index} is out of range at {span:?}: the tuple has {arity} elements")]
+    #[error("tuple index {index} is out of range: the tuple has {arity} elements")]
     TupleIndexOutOfBounds {
         index: usize,
         arity: usize,
         span: Span,
     },
 
-    #[error("cannot infer the element type of an empty array literal at {span:?}: add a type annotation like `[i32; 0]`")]
+    #[error("cannot infer the element type of an empty array literal: add a type annotation like `[i32; 0]`")]
     CannotInferEmptyArray { span: Span },
 
-    #[error("array destructuring pattern binds {expected} element(s) but the array has {found} at {span:?}: list every element or add a `..rest` pattern")]
+    #[error("array destructuring pattern binds {expected} element(s) but the array has {found}: list every element or add a `..rest` pattern")]
     ArrayPatternLengthMismatch {
         expected: usize,
         found: usize,
         span: Span,
     },
 
-    #[error("enum '{name}' is already defined at {span:?}")]
+    #[error("enum '{name}' is already defined")]
     EnumAlreadyDefined { name: String, span: Span },
 
-    #[error("type name '{name}' is already defined at {span:?}: a newtype may not reuse the name of an existing type")]
+    #[error("type name '{name}' is already defined: a newtype may not reuse the name of an existing type")]
     NewtypeAlreadyDefined { name: String, span: Span },
 
-    #[error("newtype '{name}' wraps non-Copy inner type {inner} at {span:?}: newtype inner types are restricted to Copy types in this phase")]
+    #[error("newtype '{name}' wraps non-Copy inner type {inner}: newtype inner types are restricted to Copy types in this phase")]
     NewtypeInnerNotCopy {
         name: String,
         inner: Type,
         span: Span,
     },
 
-    #[error("newtype '{name}' is cyclic at {span:?}: a newtype may not wrap itself directly or transitively")]
+    #[error("newtype '{name}' is cyclic: a newtype may not wrap itself directly or transitively")]
     CyclicNewtype { name: String, span: Span },
 
-    #[error("enum variant payload type {ty} is not supported at {span:?}: enum variants may only carry scalar Copy primitives (integers, floats, bool, char) in this phase")]
+    #[error("enum variant payload type {ty} is not supported: enum variants may only carry scalar Copy primitives (integers, floats, bool, char) in this phase")]
     UnsupportedEnumPayload { ty: Type, span: Span },
 
-    #[error("enum '{enum_name}' has no variant '{variant}' at {span:?}")]
+    #[error("enum '{enum_name}' has no variant '{variant}'")]
     UnknownEnumVariant {
         enum_name: String,
         variant: String,
         span: Span,
     },
 
-    #[error("enum variant '{enum_name}::{variant}' is a {expected} variant at {span:?}: {hint}")]
+    #[error("enum variant '{enum_name}::{variant}' is a {expected} variant: {hint}")]
     EnumVariantFormMismatch {
         enum_name: String,
         variant: String,
@@ -441,7 +479,9 @@ pub enum TypeError {
         span: Span,
     },
 
-    #[error("enum variant '{enum_name}::{variant}' takes {expected} field(s) but {found} were provided at {span:?}")]
+    #[error(
+        "enum variant '{enum_name}::{variant}' takes {expected} field(s) but {found} were provided"
+    )]
     EnumVariantArityMismatch {
         enum_name: String,
         variant: String,
@@ -450,7 +490,7 @@ pub enum TypeError {
         span: Span,
     },
 
-    #[error("enum variant '{enum_name}::{variant}' has no field '{field}' at {span:?}")]
+    #[error("enum variant '{enum_name}::{variant}' has no field '{field}'")]
     UnknownEnumField {
         enum_name: String,
         variant: String,
@@ -458,7 +498,7 @@ pub enum TypeError {
         span: Span,
     },
 
-    #[error("missing field '{field}' for enum variant '{enum_name}::{variant}' at {span:?}")]
+    #[error("missing field '{field}' for enum variant '{enum_name}::{variant}'")]
     MissingEnumField {
         enum_name: String,
         variant: String,
@@ -466,7 +506,7 @@ pub enum TypeError {
         span: Span,
     },
 
-    #[error("field '{field}' is set more than once for enum variant '{enum_name}::{variant}' at {span:?}")]
+    #[error("field '{field}' is set more than once for enum variant '{enum_name}::{variant}'")]
     DuplicateEnumField {
         enum_name: String,
         variant: String,
@@ -474,30 +514,39 @@ pub enum TypeError {
         span: Span,
     },
 
-    #[error("non-exhaustive match at {span:?}: {reason} — add the missing pattern(s) or a `_` wildcard arm")]
+    #[error("non-exhaustive match: {reason} — add the missing pattern(s) or a `_` wildcard arm")]
     NonExhaustiveMatch { reason: String, span: Span },
 
-    #[error("cannot match on a value of type {ty} at {span:?}: `match` supports enums, integers, `char`, and `bool` in this phase")]
+    #[error("cannot match on a value of type {ty}: `match` supports enums, integers, `char`, and `bool` in this phase")]
     UnsupportedMatchScrutinee { ty: Type, span: Span },
 
-    #[error("this pattern matches {pattern_ty} but the value being matched has type {scrutinee_ty} at {span:?}")]
+    #[error(
+        "this pattern matches {pattern_ty} but the value being matched has type {scrutinee_ty}"
+    )]
     PatternTypeMismatch {
         pattern_ty: String,
         scrutinee_ty: Type,
         span: Span,
     },
 
-    #[error("match arms have incompatible types at {span:?}: expected {expected}, found {found}")]
+    #[error("match arms have incompatible types: expected {expected}, found {found}")]
     MatchArmTypeMismatch {
         expected: Type,
         found: Type,
         span: Span,
     },
 
-    #[error("a range pattern requires an ordered scalar (integer or `char`) at {span:?}")]
+    #[error("`if` branches have incompatible types: expected {expected}, found {found}; every branch of an `if` used as a value must have the same type")]
+    IfArmTypeMismatch {
+        expected: Type,
+        found: Type,
+        span: Span,
+    },
+
+    #[error("a range pattern requires an ordered scalar (integer or `char`)")]
     InvalidRangePattern { span: Span },
 
-    #[error("enum variant '{enum_name}::{variant}' is a {expected} variant; its pattern must match that form at {span:?}")]
+    #[error("enum variant '{enum_name}::{variant}' is a {expected} variant; its pattern must match that form")]
     VariantPatternFormMismatch {
         enum_name: String,
         variant: String,
@@ -505,21 +554,241 @@ pub enum TypeError {
         span: Span,
     },
 
-    #[error("an alternative (`|`) pattern may not bind a variable at {span:?}: move the binding to a separate arm")]
+    #[error(
+        "an alternative (`|`) pattern may not bind a variable: move the binding to a separate arm"
+    )]
     OrPatternBinding { span: Span },
 
-    #[error("a payload sub-pattern must be a binding or `_` at {span:?}: match a payload value with a guard instead (e.g. `Some(n) if n == 0`)")]
+    #[error("a payload sub-pattern must be a binding or `_`: match a payload value with a guard instead (e.g. `Some(n) if n == 0`)")]
     RefutablePayloadPattern { span: Span },
 
-    #[error("closure parameter '{name}' needs a type annotation at {span:?}: write `|{name}: T| ...` — closure parameter-type inference is not yet supported")]
+    #[error("closure parameter '{name}' needs a type annotation: write `|{name}: T| ...` — closure parameter-type inference is not yet supported")]
     ClosureParamNeedsType { name: String, span: Span },
 
-    #[error("closure captures '{name}' of non-Copy type {ty} at {span:?}: only Copy values may be captured in this phase (capture by reference / move of owned values is not yet supported)")]
+    #[error("closure captures '{name}' of non-Copy type {ty}: only Copy values may be captured in this phase (capture by reference / move of owned values is not yet supported)")]
     ClosureCapturesNonCopy { name: String, ty: Type, span: Span },
 
-    #[error("closure assigns to captured variable '{name}' at {span:?}: a captured variable is read-only in this phase (mutable capture / FnMut is not yet supported)")]
+    #[error("closure assigns to captured variable '{name}': a captured variable is read-only in this phase (mutable capture / FnMut is not yet supported)")]
     ClosureAssignsCapture { name: String, span: Span },
 
-    #[error("a block-bodied closure needs an explicit return type at {span:?}: write `|params| -> R {{ ... }}` (only single-expression closures `|x| expr` infer their return type)")]
+    #[error("a block-bodied closure needs an explicit return type: write `|params| -> R {{ ... }}` (only single-expression closures `|x| expr` infer their return type)")]
     ClosureBlockNeedsReturnType { span: Span },
+
+    #[error("a void value is used: the expression produces no value to bind or operate on")]
+    VoidValueUsed { span: Span },
+
+    #[error("'{name}' takes the default literal type {ty}: pedantic mode requires an annotation (`val {name}: {ty} = ...`) or a suffixed literal")]
+    PedanticDefaultLiteralType { name: String, ty: Type, span: Span },
+
+    #[error("the {ty} result of this call is discarded: pedantic mode requires a non-void result to be used")]
+    PedanticUnusedResult { ty: Type, span: Span },
+}
+
+impl TypeError {
+    /// The source span the error points at.
+    pub fn span(&self) -> Span {
+        match self {
+            Self::Mismatch { span, .. }
+            | Self::UndefinedVariable { span, .. }
+            | Self::UndefinedFunction { span, .. }
+            | Self::GenericParamShadowsBuiltin { span, .. }
+            | Self::GenericParamNotInferable { span, .. }
+            | Self::UnknownArrayLength { span, .. }
+            | Self::UndeclaredLifetime { span, .. }
+            | Self::ConstParamNotInteger { span, .. }
+            | Self::ConstPredicateViolated { span, .. }
+            | Self::TurbofishCountMismatch { span, .. }
+            | Self::TurbofishKindMismatch { span, .. }
+            | Self::GenericArgumentNotCopy { span, .. }
+            | Self::GenericStructNeedsArgs { span, .. }
+            | Self::GenericArgCountMismatch { span, .. }
+            | Self::NotAGenericType { span, .. }
+            | Self::NestedGenericTypeArg { span, .. }
+            | Self::VariableAlreadyDefined { span, .. }
+            | Self::FunctionAlreadyDefined { span, .. }
+            | Self::ArgumentCountMismatch { span, .. }
+            | Self::InvalidOperator { span, .. }
+            | Self::CopyDeriveNonCopyField { span, .. }
+            | Self::DropTypeCannotBeCopy { span, .. }
+            | Self::InvalidDropImpl { span, .. }
+            | Self::InvalidOptimizeAttribute { span, .. }
+            | Self::UnknownTrait { span, .. }
+            | Self::TraitAlreadyDefined { span, .. }
+            | Self::DynTraitNotBehindReference { span, .. }
+            | Self::TraitNotObjectSafe { span, .. }
+            | Self::ImplTraitNotAllowedHere { span, .. }
+            | Self::ImplReturnNotInferable { span, .. }
+            | Self::ImplReturnDoesNotImplement { span, .. }
+            | Self::MissingTraitMethod { span, .. }
+            | Self::NotATraitMethod { span, .. }
+            | Self::TraitMethodSignatureMismatch { span, .. }
+            | Self::TraitBoundNotSatisfied { span, .. }
+            | Self::InvalidBinaryOperator { span, .. }
+            | Self::OperatorTraitRequiresCopy { span, .. }
+            | Self::AssociatedTypeMismatch { span, .. }
+            | Self::MissingSupertraitImpl { span, .. }
+            | Self::ReturnTypeMismatch { span, .. }
+            | Self::MissingReturn { span, .. }
+            | Self::UnknownTypeName { span, .. }
+            | Self::NotCallable { span, .. }
+            | Self::UninitializedVariable { span, .. }
+            | Self::UseBeforeInit { span, .. }
+            | Self::NestedFunctionCapture { span, .. }
+            | Self::GenericNestedFunction { span, .. }
+            | Self::UnsupportedNestedAttribute { span, .. }
+            | Self::AssignToImmutable { span, .. }
+            | Self::IntegerLiteralOutOfRange { span, .. }
+            | Self::BreakOutsideLoop { span, .. }
+            | Self::ContinueOutsideLoop { span, .. }
+            | Self::UndefinedLabel { span, .. }
+            | Self::BreakValueInUnitLoop { span, .. }
+            | Self::InvalidForRangeType { span, .. }
+            | Self::ReservedNameSeparator { span, .. }
+            | Self::StructAlreadyDefined { span, .. }
+            | Self::DuplicateField { span, .. }
+            | Self::UnknownStruct { span, .. }
+            | Self::UnknownField { span, .. }
+            | Self::MissingStructField { span, .. }
+            | Self::DuplicateStructField { span, .. }
+            | Self::AssignToImmutableField { span, .. }
+            | Self::MethodNotFound { span, .. }
+            | Self::UnsupportedSelfParam { span, .. }
+            | Self::UnknownPathType { span, .. }
+            | Self::UnknownAssociatedFunction { span, .. }
+            | Self::ConstAlreadyDefined { span, .. }
+            | Self::InvalidConstExpr { span, .. }
+            | Self::ConstDivisionByZero { span, .. }
+            | Self::ConstOverflow { span, .. }
+            | Self::CyclicConst { span, .. }
+            | Self::UndefinedConst { span, .. }
+            | Self::OperatorNotYetSupported { span, .. }
+            | Self::ComparisonChain { span, .. }
+            | Self::UseOfMovedValue { span, .. }
+            | Self::CannotBorrowValue { span, .. }
+            | Self::CannotBorrowMutably { span, .. }
+            | Self::CannotDereference { span, .. }
+            | Self::CannotAssignThroughRef { span, .. }
+            | Self::CannotMutablyBorrowWhileBorrowed { span, .. }
+            | Self::CannotBorrowWhileMutablyBorrowed { span, .. }
+            | Self::ReturnsReferenceToLocal { span, .. }
+            | Self::RangeNotAllowed { span, .. }
+            | Self::SliceExpectsRange { span, .. }
+            | Self::NonCopyArrayElement { span, .. }
+            | Self::NotIndexable { span, .. }
+            | Self::IndexNotInteger { span, .. }
+            | Self::ArrayLengthMismatch { span, .. }
+            | Self::NonCopyTupleElement { span, .. }
+            | Self::NotATuple { span, .. }
+            | Self::TupleIndexOutOfBounds { span, .. }
+            | Self::CannotInferEmptyArray { span, .. }
+            | Self::ArrayPatternLengthMismatch { span, .. }
+            | Self::EnumAlreadyDefined { span, .. }
+            | Self::NewtypeAlreadyDefined { span, .. }
+            | Self::NewtypeInnerNotCopy { span, .. }
+            | Self::CyclicNewtype { span, .. }
+            | Self::UnsupportedEnumPayload { span, .. }
+            | Self::UnknownEnumVariant { span, .. }
+            | Self::EnumVariantFormMismatch { span, .. }
+            | Self::EnumVariantArityMismatch { span, .. }
+            | Self::UnknownEnumField { span, .. }
+            | Self::MissingEnumField { span, .. }
+            | Self::DuplicateEnumField { span, .. }
+            | Self::NonExhaustiveMatch { span, .. }
+            | Self::UnsupportedMatchScrutinee { span, .. }
+            | Self::PatternTypeMismatch { span, .. }
+            | Self::MatchArmTypeMismatch { span, .. }
+            | Self::IfArmTypeMismatch { span, .. }
+            | Self::InvalidRangePattern { span, .. }
+            | Self::VariantPatternFormMismatch { span, .. }
+            | Self::OrPatternBinding { span, .. }
+            | Self::RefutablePayloadPattern { span, .. }
+            | Self::ClosureParamNeedsType { span, .. }
+            | Self::ClosureCapturesNonCopy { span, .. }
+            | Self::ClosureAssignsCapture { span, .. }
+            | Self::ClosureBlockNeedsReturnType { span, .. }
+            | Self::VoidValueUsed { span, .. }
+            | Self::PedanticDefaultLiteralType { span, .. }
+            | Self::PedanticUnusedResult { span, .. } => *span,
+        }
+    }
+
+    /// The error as a [`Diagnostic`] at its span, with a machine-applicable fix
+    /// where one is known. `source` is the checked program's text, used to
+    /// recognise a mismatched numeric literal, whose fix gives it the expected
+    /// type's suffix.
+    pub fn to_diagnostic(&self, source: &str) -> Diagnostic {
+        let span = self.span();
+        let code = match self {
+            Self::UndefinedVariable { .. } | Self::UndefinedFunction { .. } => {
+                DiagnosticCode::NameError
+            }
+            _ => DiagnosticCode::TypeError,
+        };
+        let diagnostic = Diagnostic::error(code, self.to_string()).with_span(span);
+
+        match self {
+            Self::UndefinedVariable {
+                suggestion: Some(name),
+                ..
+            } => diagnostic.with_suggestion(Suggestion {
+                span,
+                replacement: name.clone(),
+                label: format!("a variable with a similar name exists: `{}`", name),
+            }),
+            Self::Mismatch {
+                expected, found, ..
+            }
+            | Self::ReturnTypeMismatch {
+                expected, found, ..
+            } if expected.is_numeric() && found.is_numeric() => {
+                match source
+                    .get(span.start..span.end)
+                    .and_then(|text| with_suffix(text, expected))
+                {
+                    Some(replacement) => diagnostic.with_suggestion(Suggestion {
+                        span,
+                        label: format!("make the literal {}: `{}`", expected, replacement),
+                        replacement,
+                    }),
+                    None => diagnostic,
+                }
+            }
+            _ => diagnostic,
+        }
+    }
+}
+
+/// Integer type suffixes, as written after a literal.
+const INT_SUFFIXES: [&str; 8] = ["i8", "i16", "i32", "i64", "u8", "u16", "u32", "u64"];
+
+/// Float type suffixes, as written after a literal.
+const FLOAT_SUFFIXES: [&str; 4] = ["bf16", "f16", "f32", "f64"];
+
+/// `text`, a numeric literal, rewritten with the suffix of `ty`: `5` and `5i32`
+/// become `5i64`, `2` becomes `2.0f64`. `None` when `text` is not a single
+/// numeric literal, or when no suffix can express the change: a float literal
+/// for an integer type, or a hex, octal, or binary literal for a float type.
+fn with_suffix(text: &str, ty: &Type) -> Option<String> {
+    let is_literal = text.starts_with(|c: char| c.is_ascii_digit())
+        && text
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.');
+    if !is_literal {
+        return None;
+    }
+
+    // A prefixed literal's digits may end in `f16` or `bf16`, so only an
+    // integer suffix is stripped from one.
+    let prefixed = matches!(text.get(..2), Some("0x" | "0X" | "0o" | "0O" | "0b" | "0B"));
+    let strip = |suffixes: &[&str]| suffixes.iter().find_map(|suffix| text.strip_suffix(suffix));
+    let digits = strip(&INT_SUFFIXES)
+        .or_else(|| strip(&FLOAT_SUFFIXES).filter(|_| !prefixed))
+        .unwrap_or(text);
+    let is_float = !prefixed && digits.contains(['.', 'e', 'E']);
+
+    match (ty.is_float(), is_float) {
+        (false, false) | (true, true) => Some(format!("{digits}{ty}")),
+        (true, false) if !prefixed => Some(format!("{digits}.0{ty}")),
+        _ => None,
+    }
 }
@@ -1,13 +1,24 @@
 // Feature slice for type checking and semantic validation.
-// Public API: the `type_check()` entry point.
+// Public API: the `type_check()` entry point (`type_check_with()` to pass
+// `TypeCheckOptions`), `const_eval()` for compile-time
+// constant evaluation, `const_array_lengths()` for the constants an array length
+// may name, and `build_reference_index()` for editor tooling.
 
+mod const_eval;
+mod dead_code;
 mod errors;
+mod options;
+mod references;
+mod suggestions;
 mod symbol_table;
 pub(crate) mod type_checkers;
 mod types;
 mod warnings;
 
+pub use const_eval::{const_array_lengths, const_eval, ConstEnv, ConstEvalError};
 pub use errors::TypeError;
+pub use options::TypeCheckOptions;
+pub use references::{build_reference_index, ReferenceIndex};
 pub use types::Type;
 pub use warnings::{Warning, WarningCode};
 
@@ -55,7 +66,31 @@ use type_checkers::TypeChecker;
 /// to provide comprehensive feedback to the user. All errors include source
 /// location information (spans) for precise error reporting.
 pub fn type_check(items: &[Item]) -> Result<Vec<Warning>, Vec<TypeError>> {
-    let mut checker = TypeChecker::new();
+    type_check_with(items, &TypeCheckOptions::default())
+}
+
+/// Type check a Neuro program like [`type_check`], under `options`.
+///
+/// # Examples
+///
+/// ```
+/// use semantic_analysis::{type_check_with, TypeCheckOptions, TypeError};
+/// use syntax_parsing::parse;
+///
+/// let ast = parse("func main() -> i32 {\n    val x = 1\n    return x\n}").unwrap();
+/// let pedantic = TypeCheckOptions {
+///     pedantic: true,
+///     ..TypeCheckOptions::default()
+/// };
+///
+/// let errors = type_check_with(&ast, &pedantic).unwrap_err();
+/// assert!(matches!(errors[0], TypeError::PedanticDefaultLiteralType { .. }));
+/// ```
+pub fn type_check_with(
+    items: &[Item],
+    options: &TypeCheckOptions,
+) -> Result<Vec<Warning>, Vec<TypeError>> {
+    let mut checker = TypeChecker::with_options(options);
     let outcome = checker.check_program(items);
     if outcome.is_err() {
         Err(checker.into_errors())
@@ -63,3 +98,32 @@ pub fn type_check(items: &[Item]) -> Result<Vec<Warning>, Vec<TypeError>> {
         Ok(checker.into_warnings())
     }
 }
+
+/// Type check a Neuro program under `options`, returning its type errors and its
+/// lint warnings together.
+///
+/// The lints run whether or not the program has errors; [`type_check_with`] drops
+/// their warnings when it returns the errors, this keeps both, so a driver can show
+/// the author every diagnostic of a failed check.
+///
+/// # Examples
+///
+/// ```
+/// use semantic_analysis::{type_check_diagnostics, TypeCheckOptions};
+/// use syntax_parsing::parse;
+///
+/// let source = "func main() -> i32 {\n    val x: i32 = true\n    while true {\n        return 0\n    }\n    return 1\n}";
+/// let ast = parse(source).unwrap();
+///
+/// let (errors, warnings) = type_check_diagnostics(&ast, &TypeCheckOptions::default());
+/// assert_eq!(errors.len(), 1);
+/// assert!(!warnings.is_empty());
+/// ```
+pub fn type_check_diagnostics(
+    items: &[Item],
+    options: &TypeCheckOptions,
+) -> (Vec<TypeError>, Vec<Warning>) {
+    let mut checker = TypeChecker::with_options(options);
+    let _ = checker.check_program(items);
+    checker.into_diagnostics()
+}
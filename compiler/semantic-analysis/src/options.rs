@@ -0,0 +1,19 @@
+// Settings that change what the type checker accepts.
+
+/// Options for [`crate::type_check_with`]. The default is the lenient checker
+/// that [`crate::type_check`] runs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TypeCheckOptions {
+    /// Turn reliance on inference defaults into errors (`neurc --pedantic`):
+    /// an unannotated binding whose type is an unsuffixed literal's default
+    /// (`val x = 1` is `i32` only by fiat), and a call statement that discards
+    /// a non-void result. Numeric types never widen implicitly, so there is no
+    /// widening to flag.
+    pub pedantic: bool,
+    /// Warn when `==` or `!=` compares two `f32`/`f64` values (`neurc -W
+    /// float-equality`), whose rounding makes exact equality unreliable.
+    pub float_equality: bool,
+    /// Hint at parentheses that change nothing (`neurc -W redundant-parens`),
+    /// such as `return (x)` or `(a) + b`.
+    pub redundant_parens: bool,
+}
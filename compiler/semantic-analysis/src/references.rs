@@ -0,0 +1,416 @@
+// Reference index for editor tooling: maps each identifier use to the span of the
+// definition it resolves to (a variable, parameter, or function).
+//
+// This is a name-resolution walk only. It scopes bindings the same way the type
+// checker's symbol table does, but ignores types, so it also indexes programs
+// that fail to type-check.
+
+use std::collections::HashMap;
+
+use ast_types::{EnumPatternPayload, Expr, Item, MatchArm, Parameter, Pattern, Stmt};
+use shared_types::{Identifier, ScopedMap, Span};
+
+/// Every resolved identifier use in a program, paired with the span of the name
+/// at its definition site.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ReferenceIndex {
+    /// `(use, definition)` pairs, sorted by the use's start offset. Uses are
+    /// single identifiers, so their spans never overlap.
+    references: Vec<(Span, Span)>,
+}
+
+impl ReferenceIndex {
+    /// The definition of the identifier use covering byte `offset`, or `None`
+    /// when no resolved use covers it.
+    pub fn definition_at(&self, offset: usize) -> Option<Span> {
+        let after = self
+            .references
+            .partition_point(|(use_span, _)| use_span.start <= offset);
+        let (use_span, definition) = self.references.get(after.checked_sub(1)?)?;
+        (offset < use_span.end).then_some(*definition)
+    }
+
+    /// Number of resolved identifier uses.
+    pub fn len(&self) -> usize {
+        self.references.len()
+    }
+
+    /// Whether no identifier use resolved.
+    pub fn is_empty(&self) -> bool {
+        self.references.is_empty()
+    }
+
+    /// Every `(use, definition)` pair, in the order of the uses.
+    pub(crate) fn references(&self) -> &[(Span, Span)] {
+        &self.references
+    }
+}
+
+/// Build the reference index for a parsed program.
+///
+/// Uses of local variables, parameters, loop and pattern bindings, top-level
+/// functions, nested functions, and constants are indexed. A definition span
+/// covers the defining name only, e.g. the `x` of `val x = 1`. Names that do not
+/// resolve, and type or member names (`Point::new`, `p.x`), are not indexed.
+///
+/// # Examples
+///
+/// ```
+/// use semantic_analysis::build_reference_index;
+/// use syntax_parsing::parse;
+///
+/// let source = "func main() -> i32 {\n    val x = 1\n    return x\n}\n";
+/// let ast = parse(source).unwrap();
+/// let index = build_reference_index(&ast);
+///
+/// let definition = index.definition_at(source.rfind('x').unwrap()).unwrap();
+/// assert_eq!(&source[definition.start..definition.end], "x");
+/// assert_eq!(definition.start, source.find("x =").unwrap());
+/// ```
+pub fn build_reference_index(items: &[Item]) -> ReferenceIndex {
+    let mut resolver = Resolver::new(items);
+    for item in items {
+        resolver.item(item);
+    }
+    let mut references = resolver.references;
+    references.sort_by_key(|(use_span, _)| use_span.start);
+    ReferenceIndex { references }
+}
+
+struct Resolver {
+    /// Top-level functions and constants, visible everywhere.
+    globals: HashMap<String, Span>,
+    /// Local bindings of the body being walked.
+    locals: ScopedMap<Span>,
+    references: Vec<(Span, Span)>,
+}
+
+impl Resolver {
+    fn new(items: &[Item]) -> Self {
+        let mut globals = HashMap::new();
+        for item in items {
+            let name = match item {
+                Item::Function(func) => &func.name,
+                Item::Const(def) => &def.name,
+                _ => continue,
+            };
+            globals.entry(name.name.clone()).or_insert(name.span);
+        }
+        Self {
+            globals,
+            locals: ScopedMap::new(),
+            references: Vec::new(),
+        }
+    }
+
+    fn item(&mut self, item: &Item) {
+        match item {
+            Item::Function(func) => self.body(&func.params, &func.body),
+            Item::Impl(def) => {
+                for method in &def.methods {
+                    self.body(&method.params, &method.body);
+                }
+            }
+            Item::Trait(def) => {
+                for method in &def.methods {
+                    if let Some(body) = &method.default_body {
+                        self.body(&method.params, body);
+                    }
+                }
+            }
+            Item::Const(def) => self.expr(&def.value),
+            Item::Struct(_) | Item::Enum(_) | Item::Newtype(_) => {}
+        }
+    }
+
+    /// Walk a function or method body with only its parameters in local scope.
+    fn body(&mut self, params: &[Parameter], body: &[Stmt]) {
+        let outer = std::mem::replace(&mut self.locals, ScopedMap::new());
+        for param in params {
+            self.define(&param.name);
+        }
+        self.block(body);
+        self.locals = outer;
+    }
+
+    fn define(&mut self, name: &Identifier) {
+        // A repeated name in one scope is a type error; keep the first binding.
+        let _ = self.locals.define(name.name.clone(), name.span);
+    }
+
+    fn resolve(&mut self, name: &Identifier) {
+        let definition = self
+            .locals
+            .lookup(&name.name)
+            .or_else(|| self.globals.get(&name.name))
+            .copied();
+        if let Some(definition) = definition {
+            self.references.push((name.span, definition));
+        }
+    }
+
+    /// Walk a block in its own scope.
+    fn block(&mut self, stmts: &[Stmt]) {
+        self.locals.push_scope();
+        self.stmts(stmts);
+        self.locals.pop_scope();
+    }
+
+    fn stmts(&mut self, stmts: &[Stmt]) {
+        for stmt in stmts {
+            self.stmt(stmt);
+        }
+    }
+
+    fn stmt(&mut self, stmt: &Stmt) {
+        match stmt {
+            Stmt::VarDecl { name, init, .. } => {
+                if let Some(init) = init {
+                    self.expr(init);
+                }
+                self.define(name);
+            }
+            Stmt::Const { name, value, .. } => {
+                self.expr(value);
+                self.define(name);
+            }
+            Stmt::Assignment { target, value, .. } => {
+                self.expr(value);
+                self.resolve(target);
+            }
+            Stmt::FieldAssignment { object, value, .. } => {
+                self.expr(value);
+                self.resolve(object);
+            }
+            Stmt::IndexAssignment {
+                target,
+                index,
+                value,
+                ..
+            } => {
+                self.resolve(target);
+                self.expr(index);
+                self.expr(value);
+            }
+            Stmt::DerefAssignment { pointer, value, .. } => {
+                self.expr(pointer);
+                self.expr(value);
+            }
+            Stmt::Return { value, .. } | Stmt::Break { value, .. } => {
+                if let Some(value) = value {
+                    self.expr(value);
+                }
+            }
+            Stmt::If {
+                condition,
+                then_block,
+                else_if_blocks,
+                else_block,
+                ..
+            } => self.if_chain(condition, then_block, else_if_blocks, else_block),
+            Stmt::IfLet {
+                pattern,
+                scrutinee,
+                then_block,
+                else_block,
+                ..
+            } => {
+                self.expr(scrutinee);
+                self.locals.push_scope();
+                self.pattern(pattern);
+                self.stmts(then_block);
+                self.locals.pop_scope();
+                if let Some(else_block) = else_block {
+                    self.block(else_block);
+                }
+            }
+            Stmt::While {
+                condition, body, ..
+            } => {
+                self.expr(condition);
+                self.block(body);
+            }
+            Stmt::WhileLet {
+                pattern,
+                scrutinee,
+                body,
+                ..
+            } => {
+                self.expr(scrutinee);
+                self.locals.push_scope();
+                self.pattern(pattern);
+                self.stmts(body);
+                self.locals.pop_scope();
+            }
+            Stmt::ForRange {
+                iterator,
+                start,
+                end,
+                body,
+                ..
+            } => {
+                self.expr(start);
+                self.expr(end);
+                self.loop_body(iterator, body);
+            }
+            Stmt::ForEach {
+                iterator,
+                iterable,
+                body,
+                ..
+            } => {
+                self.expr(iterable);
+                self.loop_body(iterator, body);
+            }
+            Stmt::Loop { body, .. } | Stmt::Block(body, _) => self.block(body),
+            Stmt::Continue { .. } => {}
+            Stmt::Func(func) => {
+                // In scope for the rest of the block; its body sees only its own
+                // name, its parameters, and top-level items.
+                self.define(&func.name);
+                let outer = std::mem::replace(&mut self.locals, ScopedMap::new());
+                self.define(&func.name);
+                self.locals.push_scope();
+                for param in &func.params {
+                    self.define(&param.name);
+                }
+                self.block(&func.body);
+                self.locals = outer;
+            }
+            Stmt::Expr(expr) => self.expr(expr),
+        }
+    }
+
+    fn loop_body(&mut self, iterator: &Identifier, body: &[Stmt]) {
+        self.locals.push_scope();
+        self.define(iterator);
+        self.stmts(body);
+        self.locals.pop_scope();
+    }
+
+    fn if_chain(
+        &mut self,
+        condition: &Expr,
+        then_block: &[Stmt],
+        else_if_blocks: &[(Expr, Vec<Stmt>)],
+        else_block: &Option<Vec<Stmt>>,
+    ) {
+        self.expr(condition);
+        self.block(then_block);
+        for (condition, block) in else_if_blocks {
+            self.expr(condition);
+            self.block(block);
+        }
+        if let Some(else_block) = else_block {
+            self.block(else_block);
+        }
+    }
+
+    fn pattern(&mut self, pattern: &Pattern) {
+        match pattern {
+            Pattern::Binding(name) => self.define(name),
+            Pattern::Enum { payload, .. } => match payload {
+                EnumPatternPayload::Unit => {}
+                EnumPatternPayload::Tuple(patterns) => {
+                    for pattern in patterns {
+                        self.pattern(pattern);
+                    }
+                }
+                EnumPatternPayload::Struct(fields) => {
+                    for field in fields {
+                        self.pattern(&field.pattern);
+                    }
+                }
+            },
+            Pattern::Wildcard(_) | Pattern::Literal(..) | Pattern::Range { .. } => {}
+        }
+    }
+
+    fn match_arm(&mut self, arm: &MatchArm) {
+        self.locals.push_scope();
+        for pattern in &arm.patterns {
+            self.pattern(pattern);
+        }
+        if let Some(guard) = &arm.guard {
+            self.expr(guard);
+        }
+        self.expr(&arm.body);
+        self.locals.pop_scope();
+    }
+
+    fn expr(&mut self, expr: &Expr) {
+        match expr {
+            Expr::Identifier(name) => self.resolve(name),
+            Expr::Literal(..) | Expr::Path { .. } => {}
+            Expr::Binary { left, right, .. } => {
+                self.expr(left);
+                self.expr(right);
+            }
+            Expr::Call { func, args, .. } => {
+                self.expr(func);
+                for arg in args {
+                    self.expr(arg);
+                }
+            }
+            Expr::Unary { operand, .. }
+            | Expr::Reference { operand, .. }
+            | Expr::Deref { operand, .. } => self.expr(operand),
+            Expr::Paren(inner, _) | Expr::Cast { expr: inner, .. } => self.expr(inner),
+            Expr::StructLiteral { fields, base, .. } => {
+                for field in fields {
+                    self.expr(&field.value);
+                }
+                if let Some(base) = base {
+                    self.expr(base);
+                }
+            }
+            Expr::EnumStructLiteral { fields, .. } => {
+                for field in fields {
+                    self.expr(&field.value);
+                }
+            }
+            Expr::FieldAccess { object, .. }
+            | Expr::TupleIndex { object, .. }
+            | Expr::ArrayRest { array: object, .. } => self.expr(object),
+            Expr::If {
+                condition,
+                then_block,
+                else_if_blocks,
+                else_block,
+                ..
+            } => self.if_chain(condition, then_block, else_if_blocks, else_block),
+            Expr::Block { stmts, .. }
+            | Expr::Unsafe { stmts, .. }
+            | Expr::Loop { body: stmts, .. } => self.block(stmts),
+            Expr::Range { start, end, .. } => {
+                self.expr(start);
+                self.expr(end);
+            }
+            Expr::ArrayLiteral { elements, .. } | Expr::TupleLiteral { elements, .. } => {
+                for element in elements {
+                    self.expr(element);
+                }
+            }
+            Expr::Index { object, index, .. } => {
+                self.expr(object);
+                self.expr(index);
+            }
+            Expr::Match {
+                scrutinee, arms, ..
+            } => {
+                self.expr(scrutinee);
+                for arm in arms {
+                    self.match_arm(arm);
+                }
+            }
+            Expr::Closure { params, body, .. } => {
+                self.locals.push_scope();
+                for param in params {
+                    self.define(&param.name);
+                }
+                self.expr(body);
+                self.locals.pop_scope();
+            }
+        }
+    }
+}
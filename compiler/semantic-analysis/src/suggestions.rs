@@ -0,0 +1,61 @@
+// "Did you mean" support: the closest known name to a misspelled one.
+
+/// The Levenshtein distance between `a` and `b`: the fewest single-character
+/// insertions, deletions, and substitutions turning one into the other.
+pub(crate) fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let above = row[j + 1];
+            row[j + 1] = if ca == *cb {
+                diagonal
+            } else {
+                1 + diagonal.min(above).min(row[j])
+            };
+            diagonal = above;
+        }
+    }
+    row[b.len()]
+}
+
+/// The candidate closest to `name`, if any is close enough to be a plausible
+/// typo: at most one edit per three characters of `name` (and at least one).
+/// Ties go to the alphabetically first candidate, so the answer does not depend
+/// on the order the candidates come in.
+pub(crate) fn closest_name<'a>(
+    name: &str,
+    candidates: impl IntoIterator<Item = &'a str>,
+) -> Option<&'a str> {
+    let limit = name.chars().count().max(3) / 3;
+    candidates
+        .into_iter()
+        .filter(|candidate| *candidate != name)
+        .map(|candidate| (edit_distance(name, candidate), candidate))
+        .filter(|(distance, _)| *distance <= limit)
+        .min()
+        .map(|(_, candidate)| candidate)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn edit_distance_counts_single_character_edits() {
+        assert_eq!(edit_distance("count", "count"), 0);
+        assert_eq!(edit_distance("coutn", "count"), 2);
+        assert_eq!(edit_distance("cont", "count"), 1);
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+    }
+
+    #[test]
+    fn closest_name_rejects_distant_candidates() {
+        assert_eq!(closest_name("totl", ["total", "tot"]), Some("tot"));
+        assert_eq!(closest_name("x", ["y", "z"]), Some("y"));
+        assert_eq!(closest_name("value", ["other", "result"]), None);
+    }
+}
@@ -1,8 +1,6 @@
 // Symbol table with lexical scoping support
 
-use std::collections::HashMap;
-
-use shared_types::Span;
+use shared_types::{ScopedMap, Span};
 
 use crate::types::Type;
 
@@ -20,6 +18,9 @@ struct BorrowProvenance {
 pub(crate) struct SymbolInfo {
     pub(crate) ty: Type,
     pub(crate) mutable: bool,
+    /// Set when this binding is a nested `func`. A nested function captures
+    /// nothing, so another nested function may call it.
+    pub(crate) nested_function: bool,
     /// The span at which this binding's value was moved out, or `None` while the
     /// binding still owns its value. Drives use-after-move detection.
     pub(crate) moved_at: Option<Span>,
@@ -42,6 +43,7 @@ impl SymbolInfo {
         Self {
             ty,
             mutable,
+            nested_function: false,
             moved_at: None,
             shared_persistent: 0,
             exclusive_persistent: 0,
@@ -55,20 +57,21 @@ impl SymbolInfo {
 /// Symbol table with lexical scoping support
 #[derive(Debug)]
 pub(crate) struct SymbolTable {
-    /// Stack of scopes (innermost scope is last)
-    scopes: Vec<HashMap<String, SymbolInfo>>,
+    /// Scoped bindings; the generic scope stack lives in `shared-types` and this
+    /// table layers the ownership and borrow bookkeeping on top of it.
+    scopes: ScopedMap<SymbolInfo>,
 }
 
 impl SymbolTable {
     pub(crate) fn new() -> Self {
         Self {
-            scopes: vec![HashMap::new()],
+            scopes: ScopedMap::new(),
         }
     }
 
     /// Enter a new scope (e.g., function body, block)
     pub(crate) fn push_scope(&mut self) {
-        self.scopes.push(HashMap::new());
+        self.scopes.push_scope();
     }
 
     /// Exit the current scope, releasing every borrow held by a binding that
@@ -78,10 +81,7 @@ impl SymbolTable {
     /// place that lived in the same dying scope need no release — the place is
     /// gone too — so a target absent from the surviving scopes is simply skipped.
     pub(crate) fn pop_scope(&mut self) {
-        if self.scopes.len() <= 1 {
-            return;
-        }
-        let Some(dying) = self.scopes.pop() else {
+        let Some(dying) = self.scopes.pop_scope() else {
             return;
         };
         for info in dying.values() {
@@ -93,34 +93,29 @@ impl SymbolTable {
 
     /// Define a variable in the current scope
     pub(crate) fn define(&mut self, name: String, ty: Type, mutable: bool) -> Result<(), String> {
-        if let Some(current_scope) = self.scopes.last_mut() {
-            if current_scope.contains_key(&name) {
-                return Err(name);
-            }
-            current_scope.insert(name, SymbolInfo::new(ty, mutable));
-            Ok(())
-        } else {
-            Err(name)
-        }
+        self.scopes.define(name, SymbolInfo::new(ty, mutable))
+    }
+
+    /// Define a nested `func` in the current scope: an immutable binding of its
+    /// function type that a sibling nested function may call.
+    pub(crate) fn define_nested_function(&mut self, name: String, ty: Type) -> Result<(), String> {
+        let mut info = SymbolInfo::new(ty, false);
+        info.nested_function = true;
+        self.scopes.define(name, info)
     }
 
     /// Look up a variable in all scopes (innermost to outermost)
     pub(crate) fn lookup(&self, name: &str) -> Option<&SymbolInfo> {
-        for scope in self.scopes.iter().rev() {
-            if let Some(info) = scope.get(name) {
-                return Some(info);
-            }
-        }
-        None
+        self.scopes.lookup(name)
+    }
+
+    /// Every name visible from the current scope, for "did you mean" suggestions.
+    pub(crate) fn names(&self) -> impl Iterator<Item = &str> {
+        self.scopes.names()
     }
 
     fn lookup_mut(&mut self, name: &str) -> Option<&mut SymbolInfo> {
-        for scope in self.scopes.iter_mut().rev() {
-            if let Some(info) = scope.get_mut(name) {
-                return Some(info);
-            }
-        }
-        None
+        self.scopes.lookup_mut(name)
     }
 
     /// Total borrows currently active against `place` — persistent plus
@@ -207,11 +202,9 @@ impl SymbolTable {
     /// it must not block a later borrow of the same place. Persistent
     /// borrows (held by live reference bindings) are untouched.
     pub(crate) fn clear_transient_borrows(&mut self) {
-        for scope in &mut self.scopes {
-            for info in scope.values_mut() {
-                info.shared_transient = 0;
-                info.exclusive_transient = 0;
-            }
+        for info in self.scopes.values_mut() {
+            info.shared_transient = 0;
+            info.exclusive_transient = 0;
         }
     }
 
@@ -219,22 +212,16 @@ impl SymbolTable {
     /// No-op when the name is not bound (e.g. a constant, which is a value, not
     /// a moveable owner).
     pub(crate) fn mark_moved(&mut self, name: &str, span: Span) {
-        for scope in self.scopes.iter_mut().rev() {
-            if let Some(info) = scope.get_mut(name) {
-                info.moved_at = Some(span);
-                return;
-            }
+        if let Some(info) = self.lookup_mut(name) {
+            info.moved_at = Some(span);
         }
     }
 
     /// Clear the moved state of `name` — the binding owns a fresh value again
     /// (e.g. after reassigning a `mut`).
     pub(crate) fn clear_moved(&mut self, name: &str) {
-        for scope in self.scopes.iter_mut().rev() {
-            if let Some(info) = scope.get_mut(name) {
-                info.moved_at = None;
-                return;
-            }
+        if let Some(info) = self.lookup_mut(name) {
+            info.moved_at = None;
         }
     }
 
@@ -246,13 +233,7 @@ impl SymbolTable {
     ///
     /// [`restore_moves`]: SymbolTable::restore_moves
     pub(crate) fn snapshot_moves(&self) -> Vec<Option<Span>> {
-        let mut snapshot = Vec::new();
-        for scope in &self.scopes {
-            for info in scope.values() {
-                snapshot.push(info.moved_at);
-            }
-        }
-        snapshot
+        self.scopes.values().map(|info| info.moved_at).collect()
     }
 
     /// Restore moved-state captured by [`snapshot_moves`]. Entries beyond the
@@ -260,14 +241,8 @@ impl SymbolTable {
     ///
     /// [`snapshot_moves`]: SymbolTable::snapshot_moves
     pub(crate) fn restore_moves(&mut self, snapshot: &[Option<Span>]) {
-        let mut idx = 0;
-        for scope in &mut self.scopes {
-            for info in scope.values_mut() {
-                if let Some(state) = snapshot.get(idx) {
-                    info.moved_at = *state;
-                }
-                idx += 1;
-            }
+        for (info, state) in self.scopes.values_mut().zip(snapshot) {
+            info.moved_at = *state;
         }
     }
 }
@@ -7,7 +7,7 @@
 
 use std::collections::HashSet;
 
-use ast_types::{ClosureParam, EnumPatternPayload, Expr, Pattern, Stmt};
+use ast_types::{ClosureParam, EnumPatternPayload, Expr, Pattern, Spanned, Stmt};
 use shared_types::Span;
 
 use super::TypeChecker;
@@ -220,12 +220,36 @@ fn collect_stmt(stmt: &Stmt, fv: &mut FreeVars) {
                 collect_block(block, fv);
             }
         }
+        Stmt::IfLet {
+            pattern,
+            scrutinee,
+            then_block,
+            else_block,
+            ..
+        } => {
+            collect_expr(scrutinee, fv);
+            collect_pattern_bindings(pattern, fv);
+            collect_block(then_block, fv);
+            if let Some(block) = else_block {
+                collect_block(block, fv);
+            }
+        }
         Stmt::While {
             condition, body, ..
         } => {
             collect_expr(condition, fv);
             collect_block(body, fv);
         }
+        Stmt::WhileLet {
+            pattern,
+            scrutinee,
+            body,
+            ..
+        } => {
+            collect_expr(scrutinee, fv);
+            collect_pattern_bindings(pattern, fv);
+            collect_block(body, fv);
+        }
         Stmt::ForRange {
             iterator,
             start,
@@ -248,7 +272,7 @@ fn collect_stmt(stmt: &Stmt, fv: &mut FreeVars) {
             fv.bound.insert(iterator.name.clone());
             collect_block(body, fv);
         }
-        Stmt::Loop { body, .. } => collect_block(body, fv),
+        Stmt::Loop { body, .. } | Stmt::Block(body, _) => collect_block(body, fv),
         Stmt::Break { value, .. } => {
             if let Some(value) = value {
                 collect_expr(value, fv);
@@ -282,6 +306,10 @@ fn collect_stmt(stmt: &Stmt, fv: &mut FreeVars) {
             collect_expr(value, fv);
             fv.bound.insert(name.name.clone());
         }
+        // A nested function captures nothing itself; it only binds its name.
+        Stmt::Func(func) => {
+            fv.bound.insert(func.name.name.clone());
+        }
         Stmt::Expr(expr) => collect_expr(expr, fv),
     }
 }
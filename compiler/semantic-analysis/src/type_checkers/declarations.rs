@@ -1,10 +1,12 @@
 use super::operator_traits::{is_operator_trait, operator_trait_spec};
 use super::{EnumVariantInfo, TraitInfo, TraitMethodSig, TypeChecker, VariantForm};
+use crate::const_eval::ConstEvalError;
 use crate::errors::TypeError;
+use crate::symbol_table::SymbolTable;
 use crate::types::{ArrayLen, Type};
 use ast_types::{
-    ConstDef, EnumDef, Expr, FunctionDef, ImplDef, Item, NewtypeDef, SelfParam, Stmt, StructDef,
-    TraitDef, VariantPayload,
+    ConstDef, EnumDef, Expr, FunctionDef, ImplDef, Item, NewtypeDef, SelfParam, Spanned, Stmt,
+    StructDef, TraitDef, VariantPayload,
 };
 use shared_types::{Identifier, Span};
 use std::collections::{HashMap, HashSet};
@@ -20,12 +22,29 @@ fn is_builtin_type_name(name: &str) -> bool {
     BUILTIN_TYPE_NAMES.contains(&name)
 }
 
+/// The span of a body's trailing statement when it is the returned value: the body
+/// belongs to a non-void function and ends in a bare expression or an `if`.
+fn returned_tail(body: &[Stmt], return_type: &Type) -> Option<Span> {
+    if matches!(return_type, Type::Void) {
+        return None;
+    }
+    match body.last() {
+        Some(Stmt::Expr(expr)) => Some(expr.span()),
+        Some(Stmt::If { span, .. }) => Some(*span),
+        _ => None,
+    }
+}
+
 /// The attribute name carrying trait derivations (`@derive(...)`).
 const DERIVE_ATTRIBUTE: &str = "derive";
 /// Derive argument requesting the `Copy` trait.
 const COPY_TRAIT: &str = "Copy";
 /// Derive argument requesting the `Clone` trait.
 const CLONE_TRAIT: &str = "Clone";
+/// The attribute name overriding a function's optimization level (`@optimize(N)`).
+const OPTIMIZE_ATTRIBUTE: &str = "optimize";
+/// The attribute name gating an item on the build configuration (`@cfg(...)`).
+const CFG_ATTRIBUTE: &str = "cfg";
 /// The compiler-known `Drop` lang-item trait name.
 const DROP_TRAIT: &str = "Drop";
 /// The destructor method name required inside an `impl Drop` block.
@@ -100,6 +119,8 @@ impl TypeChecker {
     /// [`Type::Generic`] placeholders, and its signature is recorded in `generic_funcs`
     /// rather than `functions`. Concrete instantiation happens per call site.
     pub(crate) fn check_function(&mut self, func: &FunctionDef) -> Option<()> {
+        self.validate_optimize_attribute(func);
+
         // Put the generic type + const parameters in scope for signature + body
         // resolution. A parameter may not shadow a built-in type name.
         self.enter_generic_scope(&func.generics, &func.lifetimes);
@@ -151,6 +172,7 @@ impl TypeChecker {
         }
 
         if func.generics.is_empty() {
+            self.free_functions.insert(func.name.name.clone());
             self.functions.insert(
                 func.name.name.clone(),
                 Type::Function {
@@ -201,6 +223,21 @@ impl TypeChecker {
             );
         }
 
+        self.check_function_body(func, &param_types, &return_type);
+        self.exit_generic_scope();
+
+        Some(())
+    }
+
+    /// Check a function's body against its resolved signature: bind the parameters in
+    /// a fresh scope, check every statement, and validate the trailing expression
+    /// against the return type.
+    fn check_function_body(
+        &mut self,
+        func: &FunctionDef,
+        param_types: &[Type],
+        return_type: &Type,
+    ) {
         // Enter function scope
         self.symbols.push_scope();
         self.current_function_return_type = Some(return_type.clone());
@@ -236,16 +273,21 @@ impl TypeChecker {
         }
 
         // Check function body
+        let outer_tail = std::mem::replace(
+            &mut self.returned_tail,
+            returned_tail(&func.body, return_type),
+        );
         for stmt in &func.body {
             let _ = self.check_stmt(stmt);
         }
+        self.returned_tail = outer_tail;
 
         // A trailing expression acts as an expression-based return, so it must
         // match the declared return type.
         if !matches!(return_type, Type::Void) && !func.body.is_empty() {
             if let Some(Stmt::Expr(expr)) = func.body.last() {
-                if let Some(expr_type) = self.check_expr(expr, Some(&return_type)) {
-                    if !self.assignable(&expr_type, &return_type) {
+                if let Some(expr_type) = self.check_expr(expr, Some(return_type)) {
+                    if !self.assignable(&expr_type, return_type) {
                         self.record_error(TypeError::ReturnTypeMismatch {
                             expected: return_type.clone(),
                             found: expr_type,
@@ -262,16 +304,103 @@ impl TypeChecker {
             }
             // Note: Other statement types at the end are allowed - LLVM will catch missing returns
         }
+        self.check_empty_body(&func.body, return_type, func.span);
 
         // Exit function scope
         self.symbols.pop_scope();
         self.current_function_return_type = None;
         self.current_fn_outliving.clear();
-        self.exit_generic_scope();
+    }
+
+    /// Check a function declared inside a function body.
+    ///
+    /// Its name is bound in the enclosing scope as a local of function type, so the
+    /// rest of the block calls it like a closure. The body is checked against an empty
+    /// symbol table holding only the function itself: a nested function captures
+    /// nothing, and using an enclosing local is a `NestedFunctionCapture` error. An
+    /// enclosing nested function captures nothing either, so calling one is allowed.
+    /// `@cfg` and `@optimize` act on top-level items only and are rejected here;
+    /// `@allow` is applied by the lint pass.
+    pub(crate) fn check_nested_function(&mut self, func: &FunctionDef) -> Option<()> {
+        for attr in &func.attributes {
+            if [CFG_ATTRIBUTE, OPTIMIZE_ATTRIBUTE].contains(&attr.name.name.as_str()) {
+                self.record_error(TypeError::UnsupportedNestedAttribute {
+                    name: func.name.name.clone(),
+                    attribute: attr.name.name.clone(),
+                    span: attr.span,
+                });
+            }
+        }
+        if !func.generics.is_empty() || !func.lifetimes.is_empty() {
+            self.record_error(TypeError::GenericNestedFunction {
+                name: func.name.name.clone(),
+                span: func.name.span,
+            });
+            return None;
+        }
 
+        let param_types: Vec<Type> = func
+            .params
+            .iter()
+            .map(|param| self.resolve_type(&param.ty).unwrap_or(Type::Unknown))
+            .collect();
+        let return_type = match &func.return_type {
+            Some(ret_ty) => self.resolve_type(ret_ty).unwrap_or(Type::Void),
+            None => Type::Void,
+        };
+        let fn_ty = Type::Function {
+            params: param_types.clone(),
+            ret: Box::new(return_type.clone()),
+        };
+
+        // Bound before the body is checked so later statements see it even when the
+        // body has errors.
+        if let Err(duplicate_name) = self
+            .symbols
+            .define_nested_function(func.name.name.clone(), fn_ty.clone())
+        {
+            self.record_error(TypeError::VariableAlreadyDefined {
+                name: duplicate_name,
+                span: func.name.span,
+            });
+        }
+
+        let enclosing = std::mem::replace(&mut self.symbols, SymbolTable::new());
+        self.enclosing_symbols.push(enclosing);
+        let saved_return = self.current_function_return_type.take();
+        let saved_outliving = std::mem::take(&mut self.current_fn_outliving);
+        let saved_loops = std::mem::take(&mut self.loop_stack);
+        let saved_initializing = std::mem::take(&mut self.initializing);
+
+        self.symbols.push_scope();
+        let _ = self
+            .symbols
+            .define_nested_function(func.name.name.clone(), fn_ty);
+        self.check_function_body(func, &param_types, &return_type);
+        self.symbols.pop_scope();
+
+        if let Some(enclosing) = self.enclosing_symbols.pop() {
+            self.symbols = enclosing;
+        }
+        self.current_function_return_type = saved_return;
+        self.current_fn_outliving = saved_outliving;
+        self.loop_stack = saved_loops;
+        self.initializing = saved_initializing;
         Some(())
     }
 
+    /// An empty body produces no value, so a function or method declaring a return
+    /// type must have at least a trailing expression or a `return`. An `Unknown`
+    /// return type has already been reported.
+    fn check_empty_body(&mut self, body: &[Stmt], return_type: &Type, span: Span) {
+        if body.is_empty() && !matches!(return_type, Type::Void | Type::Unknown) {
+            self.record_error(TypeError::MissingReturn {
+                expected: return_type.clone(),
+                span,
+            });
+        }
+    }
+
     /// Resolve a return-position `impl Trait` to the single concrete type the
     /// body produces, and verify that type implements the named trait.
     ///
@@ -600,16 +729,65 @@ impl TypeChecker {
             return None;
         }
 
+        let fields = self.resolve_struct_fields(def);
+        self.struct_defs.insert(def.name.name.clone(), fields);
+        self.record_derive_intent(def);
+        Some(())
+    }
+
+    /// Resolve a struct's field types in declaration order. A field whose name was
+    /// already declared is reported as [`TypeError::DuplicateField`] at the repeat and
+    /// left out, so field lookups keep seeing the first declaration; a field whose type
+    /// does not resolve is left out after `resolve_type` reports it.
+    fn resolve_struct_fields(&mut self, def: &StructDef) -> Vec<(String, Type)> {
+        let mut seen = HashSet::new();
         let mut fields: Vec<(String, Type)> = Vec::new();
         for field in &def.fields {
+            if !seen.insert(field.name.name.as_str()) {
+                self.record_error(TypeError::DuplicateField {
+                    name: field.name.name.clone(),
+                    span: field.name.span,
+                });
+                continue;
+            }
             if let Some(ty) = self.resolve_type(&field.ty) {
                 fields.push((field.name.name.clone(), ty));
             }
         }
+        fields
+    }
 
-        self.struct_defs.insert(def.name.name.clone(), fields);
-        self.record_derive_intent(def);
-        Some(())
+    /// Validate `@optimize(N)` on a function: at most one per function, carrying a
+    /// single level from 0 to 3. The level itself is acted on by the backend.
+    fn validate_optimize_attribute(&mut self, func: &FunctionDef) {
+        let mut seen = false;
+        for attr in &func.attributes {
+            if attr.name.name != OPTIMIZE_ATTRIBUTE {
+                continue;
+            }
+            let reason = if seen {
+                Some("a function may carry only one `@optimize`".to_string())
+            } else {
+                match attr.args.as_slice() {
+                    [level] if matches!(level.name.as_str(), "0" | "1" | "2" | "3") => None,
+                    [level] => Some(format!(
+                        "level '{}' is not an optimization level (expected 0 to 3)",
+                        level.name
+                    )),
+                    args => Some(format!(
+                        "expected one optimization level, found {} arguments",
+                        args.len()
+                    )),
+                }
+            };
+            seen = true;
+            if let Some(reason) = reason {
+                self.record_error(TypeError::InvalidOptimizeAttribute {
+                    reason,
+                    span: attr.span,
+                });
+            }
+        }
     }
 
     /// Record the `@derive(Copy, Clone)` intent declared on a struct.
@@ -694,12 +872,7 @@ impl TypeChecker {
         }
 
         self.enter_generic_scope(&def.generics, &def.lifetimes);
-        let mut fields: Vec<(String, Type)> = Vec::new();
-        for field in &def.fields {
-            if let Some(ty) = self.resolve_type(&field.ty) {
-                fields.push((field.name.name.clone(), ty));
-            }
-        }
+        let fields = self.resolve_struct_fields(def);
         self.exit_generic_scope();
 
         self.struct_defs.insert(def.name.name.clone(), fields);
@@ -913,7 +1086,7 @@ impl TypeChecker {
             // an operator-trait method `func add(self, ...)` run on the scalar path
             // `&mut self` is supported and recorded below.
             if matches!(method.self_param, Some(SelfParam::Owned)) && !struct_is_copy {
-                self.errors.push(TypeError::UnsupportedSelfParam {
+                self.record_error(TypeError::UnsupportedSelfParam {
                     type_name: struct_name.clone(),
                     self_param: "self".to_string(),
                     span: method.span,
@@ -1321,15 +1494,16 @@ impl TypeChecker {
         Some(())
     }
 
-    /// Validate a module-level constant declaration.
+    /// Validate a module-level constant declaration: its value must have folded
+    /// to a constant in pass 0b and must match the declared type.
     pub(crate) fn check_const_item(&mut self, def: &ConstDef) -> Option<()> {
         let declared_ty = self.resolve_type(&def.ty)?;
 
-        if !self.is_const_expr(&def.value) {
-            self.record_error(TypeError::InvalidConstExpr {
-                span: def.value.span(),
-            });
-            return None;
+        if let Some(error) = self.const_failures.get(&def.name.name).cloned() {
+            if let Some(error) = self.const_eval_error(&def.name, error) {
+                self.record_error(error);
+                return None;
+            }
         }
 
         if let Some(expr_ty) = self.check_expr(&def.value, Some(&declared_ty)) {
@@ -1345,25 +1519,63 @@ impl TypeChecker {
         Some(())
     }
 
-    /// Returns true if `expr` is a valid constant expression.
-    ///
-    /// Valid constant expressions are: literals, arithmetic/unary on literal
-    /// sub-expressions, parenthesized const expressions, and identifiers that
-    /// refer to a previously declared `const`.
-    pub(crate) fn is_const_expr(&self, expr: &Expr) -> bool {
-        match expr {
-            Expr::Literal(_, _) => true,
-            Expr::Paren(inner, _) => self.is_const_expr(inner),
-            Expr::Unary { operand, .. } => self.is_const_expr(operand),
-            Expr::Binary { left, right, .. } => {
-                self.is_const_expr(left) && self.is_const_expr(right)
+    /// The diagnostic for constant `name`, whose value failed to evaluate with
+    /// `error`. `None` when the type check reports the problem better (operands of
+    /// the wrong type), or when it was already reported on a constant `name`
+    /// depends on.
+    pub(crate) fn const_eval_error(
+        &self,
+        name: &Identifier,
+        error: ConstEvalError,
+    ) -> Option<TypeError> {
+        match error {
+            ConstEvalError::NotConstant { span } => Some(TypeError::InvalidConstExpr { span }),
+            ConstEvalError::UnknownConst {
+                name: referenced,
+                span,
+            } => {
+                if !self.constants.contains_key(&referenced) {
+                    Some(TypeError::UndefinedConst {
+                        name: name.name.clone(),
+                        referenced,
+                        span,
+                    })
+                } else if self.in_const_cycle(&name.name) {
+                    Some(TypeError::CyclicConst {
+                        name: name.name.clone(),
+                        span: name.span,
+                    })
+                } else {
+                    None
+                }
             }
-            Expr::Cast { expr: inner, .. } => self.is_const_expr(inner),
-            Expr::Identifier(ident) => self.constants.contains_key(&ident.name),
-            _ => false,
+            ConstEvalError::DivisionByZero { span } => {
+                Some(TypeError::ConstDivisionByZero { span })
+            }
+            ConstEvalError::Overflow { span } => Some(TypeError::ConstOverflow { span }),
+            ConstEvalError::InvalidOperands { .. } => None,
         }
     }
 
+    /// Whether following the unevaluated constants that module constant `name`
+    /// refers to leads back to `name` itself.
+    fn in_const_cycle(&self, name: &str) -> bool {
+        let mut seen = HashSet::new();
+        let mut current = name;
+        while let Some(ConstEvalError::UnknownConst { name: next, .. }) =
+            self.const_failures.get(current)
+        {
+            if next == name {
+                return true;
+            }
+            if !seen.insert(next.as_str()) {
+                return false;
+            }
+            current = next;
+        }
+        false
+    }
+
     /// Type-check the body of each method in an `impl` block.
     pub(crate) fn check_impl(&mut self, def: &ImplDef) {
         let struct_name = def.type_name.name.clone();
@@ -1434,9 +1646,14 @@ impl TypeChecker {
                 }
             }
 
+            let outer_tail = std::mem::replace(
+                &mut self.returned_tail,
+                returned_tail(&method.body, &return_type),
+            );
             for stmt in &method.body {
                 let _ = self.check_stmt(stmt);
             }
+            self.returned_tail = outer_tail;
 
             // Validate trailing expression return (same rule as free functions).
             if !matches!(return_type, Type::Void) && !method.body.is_empty() {
@@ -1455,6 +1672,7 @@ impl TypeChecker {
                     }
                 }
             }
+            self.check_empty_body(&method.body, &return_type, method.span);
 
             self.symbols.pop_scope();
             self.current_function_return_type = None;
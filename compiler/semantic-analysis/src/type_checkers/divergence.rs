@@ -0,0 +1,357 @@
+// Divergence analysis: which blocks never complete normally, and which
+// functions never return.
+//
+// A block diverges when control cannot reach its end: it returns, breaks,
+// continues, calls a function that never returns, or runs a `loop` with no
+// `break` out of it. A diverging `if`/`match` arm produces no value, so the
+// checker types it `Type::Never` and lets the other arms decide the type.
+//
+// The analysis is syntactic and conservative: anything it cannot prove diverges
+// is treated as completing, which is always safe.
+
+use std::collections::HashSet;
+
+use ast_types::{BinaryOp, Expr, Item, Stmt};
+use shared_types::Identifier;
+
+/// The diverging panic-family builtins. `assert` returns when its condition holds.
+const DIVERGING_BUILTINS: &[&str] = &["panic", "unreachable"];
+
+/// Names of the top-level functions that never return, plus the diverging builtins
+/// a user function does not shadow.
+///
+/// A function never returns when its body diverges and contains no `return`. A call
+/// to such a function diverges in turn, so the set is grown to a fixed point.
+pub(crate) fn diverging_functions(items: &[Item]) -> HashSet<String> {
+    let functions: Vec<_> = items
+        .iter()
+        .filter_map(|item| match item {
+            Item::Function(func) => Some(func),
+            _ => None,
+        })
+        .collect();
+
+    let mut diverging: HashSet<String> = DIVERGING_BUILTINS
+        .iter()
+        .filter(|builtin| !functions.iter().any(|func| func.name.name == **builtin))
+        .map(|builtin| builtin.to_string())
+        .collect();
+
+    loop {
+        let found: Vec<String> = functions
+            .iter()
+            .filter(|func| !diverging.contains(&func.name.name))
+            .filter(|func| {
+                Divergence::new(&diverging).block(&func.body) && !contains_return(&func.body)
+            })
+            .map(|func| func.name.name.clone())
+            .collect();
+        if found.is_empty() {
+            return diverging;
+        }
+        diverging.extend(found);
+    }
+}
+
+/// Divergence queries against a known set of diverging functions.
+pub(crate) struct Divergence<'a> {
+    diverging: &'a HashSet<String>,
+}
+
+impl<'a> Divergence<'a> {
+    pub(crate) fn new(diverging: &'a HashSet<String>) -> Self {
+        Self { diverging }
+    }
+
+    /// Whether control never reaches the end of `stmts`.
+    pub(crate) fn block(&self, stmts: &[Stmt]) -> bool {
+        stmts.iter().any(|stmt| self.stmt(stmt))
+    }
+
+    fn stmt(&self, stmt: &Stmt) -> bool {
+        match stmt {
+            Stmt::Return { .. } | Stmt::Break { .. } | Stmt::Continue { .. } => true,
+            Stmt::VarDecl { init, .. } => init.as_ref().is_some_and(|init| self.expr(init)),
+            Stmt::Const { value, .. }
+            | Stmt::Assignment { value, .. }
+            | Stmt::FieldAssignment { value, .. } => self.expr(value),
+            Stmt::IndexAssignment { index, value, .. } => self.expr(index) || self.expr(value),
+            Stmt::DerefAssignment { pointer, value, .. } => self.expr(pointer) || self.expr(value),
+            Stmt::If {
+                condition,
+                then_block,
+                else_if_blocks,
+                else_block,
+                ..
+            } => self.if_chain(condition, then_block, else_if_blocks, else_block),
+            Stmt::IfLet {
+                scrutinee,
+                then_block,
+                else_block,
+                ..
+            } => {
+                self.expr(scrutinee)
+                    || else_block
+                        .as_ref()
+                        .is_some_and(|else_block| self.block(then_block) && self.block(else_block))
+            }
+            // A `while` or `for` may run zero times, so only its header can diverge.
+            Stmt::While { condition, .. } => self.expr(condition),
+            Stmt::WhileLet { scrutinee, .. } => self.expr(scrutinee),
+            Stmt::ForRange { start, end, .. } => self.expr(start) || self.expr(end),
+            Stmt::ForEach { iterable, .. } => self.expr(iterable),
+            Stmt::Loop { label, body, .. } => !loop_exits(label.as_ref(), body),
+            Stmt::Func(_) => false,
+            Stmt::Block(stmts, _) => self.block(stmts),
+            Stmt::Expr(expr) => self.expr(expr),
+        }
+    }
+
+    fn if_chain(
+        &self,
+        condition: &Expr,
+        then_block: &[Stmt],
+        else_if_blocks: &[(Expr, Vec<Stmt>)],
+        else_block: &Option<Vec<Stmt>>,
+    ) -> bool {
+        // Without an `else`, the condition can be false and skip every arm.
+        self.expr(condition)
+            || else_block.as_ref().is_some_and(|else_block| {
+                self.block(then_block)
+                    && else_if_blocks.iter().all(|(_, block)| self.block(block))
+                    && self.block(else_block)
+            })
+    }
+
+    /// Whether evaluating `expr` never produces a value.
+    pub(crate) fn expr(&self, expr: &Expr) -> bool {
+        match expr {
+            Expr::Call { func, args, .. } => {
+                let diverging_callee = matches!(&**func, Expr::Identifier(name) if self.diverging.contains(&name.name));
+                diverging_callee || self.expr(func) || args.iter().any(|arg| self.expr(arg))
+            }
+            // The right operand of a short-circuiting operator may never run.
+            Expr::Binary {
+                left, op, right, ..
+            } => match op {
+                BinaryOp::And | BinaryOp::Or | BinaryOp::NullCoalesce => self.expr(left),
+                _ => self.expr(left) || self.expr(right),
+            },
+            Expr::Unary { operand, .. }
+            | Expr::Reference { operand, .. }
+            | Expr::Deref { operand, .. } => self.expr(operand),
+            Expr::Paren(inner, _) | Expr::Cast { expr: inner, .. } => self.expr(inner),
+            Expr::FieldAccess { object, .. }
+            | Expr::TupleIndex { object, .. }
+            | Expr::ArrayRest { array: object, .. } => self.expr(object),
+            Expr::Index { object, index, .. } => self.expr(object) || self.expr(index),
+            Expr::Range { start, end, .. } => self.expr(start) || self.expr(end),
+            Expr::ArrayLiteral { elements, .. } | Expr::TupleLiteral { elements, .. } => {
+                elements.iter().any(|element| self.expr(element))
+            }
+            Expr::StructLiteral { fields, base, .. } => {
+                fields.iter().any(|field| self.expr(&field.value))
+                    || base.as_ref().is_some_and(|base| self.expr(base))
+            }
+            Expr::EnumStructLiteral { fields, .. } => {
+                fields.iter().any(|field| self.expr(&field.value))
+            }
+            Expr::If {
+                condition,
+                then_block,
+                else_if_blocks,
+                else_block,
+                ..
+            } => self.if_chain(condition, then_block, else_if_blocks, else_block),
+            Expr::Block { stmts, .. } | Expr::Unsafe { stmts, .. } => self.block(stmts),
+            Expr::Loop { label, body, .. } => !loop_exits(label.as_ref(), body),
+            Expr::Match {
+                scrutinee, arms, ..
+            } => {
+                self.expr(scrutinee)
+                    || (!arms.is_empty() && arms.iter().all(|arm| self.expr(&arm.body)))
+            }
+            Expr::Identifier(_) | Expr::Literal(..) | Expr::Path { .. } | Expr::Closure { .. } => {
+                false
+            }
+        }
+    }
+}
+
+/// Whether a `break` in `body` leaves the loop labelled `label`: an unlabelled
+/// `break` outside any nested loop, or a `break` naming the label.
+pub(crate) fn loop_exits(label: Option<&Identifier>, body: &[Stmt]) -> bool {
+    any_stmt(body, 0, &mut |stmt, depth| match stmt {
+        Stmt::Break {
+            label: Some(target),
+            ..
+        } => label.is_some_and(|label| label.name == target.name),
+        Stmt::Break { label: None, .. } => depth == 0,
+        _ => false,
+    })
+}
+
+/// Whether a `return` appears anywhere in `body`, outside nested functions and
+/// closures.
+fn contains_return(body: &[Stmt]) -> bool {
+    any_stmt(body, 0, &mut |stmt, _| matches!(stmt, Stmt::Return { .. }))
+}
+
+/// Whether `found` holds for any statement in `stmts` or in the blocks nested in
+/// them, given the number of loops entered to reach it. Closure bodies and nested
+/// functions are skipped: control flow inside them never leaves the enclosing body.
+fn any_stmt(stmts: &[Stmt], depth: usize, found: &mut impl FnMut(&Stmt, usize) -> bool) -> bool {
+    stmts.iter().any(|stmt| {
+        found(stmt, depth)
+            || match stmt {
+                Stmt::VarDecl { init, .. } => init
+                    .as_ref()
+                    .is_some_and(|init| any_in_expr(init, depth, found)),
+                Stmt::Const { value, .. }
+                | Stmt::Assignment { value, .. }
+                | Stmt::FieldAssignment { value, .. } => any_in_expr(value, depth, found),
+                Stmt::Return { value, .. } | Stmt::Break { value, .. } => value
+                    .as_ref()
+                    .is_some_and(|value| any_in_expr(value, depth, found)),
+                Stmt::IndexAssignment { index, value, .. } => {
+                    any_in_expr(index, depth, found) || any_in_expr(value, depth, found)
+                }
+                Stmt::DerefAssignment { pointer, value, .. } => {
+                    any_in_expr(pointer, depth, found) || any_in_expr(value, depth, found)
+                }
+                Stmt::If {
+                    condition,
+                    then_block,
+                    else_if_blocks,
+                    else_block,
+                    ..
+                } => any_in_if(
+                    condition,
+                    then_block,
+                    else_if_blocks,
+                    else_block,
+                    depth,
+                    found,
+                ),
+                Stmt::IfLet {
+                    scrutinee,
+                    then_block,
+                    else_block,
+                    ..
+                } => {
+                    any_in_expr(scrutinee, depth, found)
+                        || any_stmt(then_block, depth, found)
+                        || else_block
+                            .as_ref()
+                            .is_some_and(|else_block| any_stmt(else_block, depth, found))
+                }
+                Stmt::While {
+                    condition, body, ..
+                } => any_in_expr(condition, depth, found) || any_stmt(body, depth + 1, found),
+                Stmt::WhileLet {
+                    scrutinee, body, ..
+                } => any_in_expr(scrutinee, depth, found) || any_stmt(body, depth + 1, found),
+                Stmt::ForRange {
+                    start, end, body, ..
+                } => {
+                    any_in_expr(start, depth, found)
+                        || any_in_expr(end, depth, found)
+                        || any_stmt(body, depth + 1, found)
+                }
+                Stmt::ForEach { iterable, body, .. } => {
+                    any_in_expr(iterable, depth, found) || any_stmt(body, depth + 1, found)
+                }
+                Stmt::Loop { body, .. } => any_stmt(body, depth + 1, found),
+                Stmt::Block(stmts, _) => any_stmt(stmts, depth, found),
+                Stmt::Continue { .. } | Stmt::Func(_) => false,
+                Stmt::Expr(expr) => any_in_expr(expr, depth, found),
+            }
+    })
+}
+
+fn any_in_if(
+    condition: &Expr,
+    then_block: &[Stmt],
+    else_if_blocks: &[(Expr, Vec<Stmt>)],
+    else_block: &Option<Vec<Stmt>>,
+    depth: usize,
+    found: &mut impl FnMut(&Stmt, usize) -> bool,
+) -> bool {
+    any_in_expr(condition, depth, found)
+        || any_stmt(then_block, depth, found)
+        || else_if_blocks.iter().any(|(condition, block)| {
+            any_in_expr(condition, depth, found) || any_stmt(block, depth, found)
+        })
+        || else_block
+            .as_ref()
+            .is_some_and(|else_block| any_stmt(else_block, depth, found))
+}
+
+/// [`any_stmt`] over the blocks nested in an expression.
+fn any_in_expr(expr: &Expr, depth: usize, found: &mut impl FnMut(&Stmt, usize) -> bool) -> bool {
+    match expr {
+        Expr::Call { func, args, .. } => {
+            any_in_expr(func, depth, found) || args.iter().any(|arg| any_in_expr(arg, depth, found))
+        }
+        Expr::Binary { left, right, .. }
+        | Expr::Index {
+            object: left,
+            index: right,
+            ..
+        }
+        | Expr::Range {
+            start: left,
+            end: right,
+            ..
+        } => any_in_expr(left, depth, found) || any_in_expr(right, depth, found),
+        Expr::Unary { operand, .. }
+        | Expr::Reference { operand, .. }
+        | Expr::Deref { operand, .. } => any_in_expr(operand, depth, found),
+        Expr::Paren(inner, _) | Expr::Cast { expr: inner, .. } => any_in_expr(inner, depth, found),
+        Expr::FieldAccess { object, .. }
+        | Expr::TupleIndex { object, .. }
+        | Expr::ArrayRest { array: object, .. } => any_in_expr(object, depth, found),
+        Expr::ArrayLiteral { elements, .. } | Expr::TupleLiteral { elements, .. } => elements
+            .iter()
+            .any(|element| any_in_expr(element, depth, found)),
+        Expr::StructLiteral { fields, base, .. } => {
+            fields
+                .iter()
+                .any(|field| any_in_expr(&field.value, depth, found))
+                || base
+                    .as_ref()
+                    .is_some_and(|base| any_in_expr(base, depth, found))
+        }
+        Expr::EnumStructLiteral { fields, .. } => fields
+            .iter()
+            .any(|field| any_in_expr(&field.value, depth, found)),
+        Expr::If {
+            condition,
+            then_block,
+            else_if_blocks,
+            else_block,
+            ..
+        } => any_in_if(
+            condition,
+            then_block,
+            else_if_blocks,
+            else_block,
+            depth,
+            found,
+        ),
+        Expr::Block { stmts, .. } | Expr::Unsafe { stmts, .. } => any_stmt(stmts, depth, found),
+        Expr::Loop { body, .. } => any_stmt(body, depth + 1, found),
+        Expr::Match {
+            scrutinee, arms, ..
+        } => {
+            any_in_expr(scrutinee, depth, found)
+                || arms.iter().any(|arm| {
+                    arm.guard
+                        .as_ref()
+                        .is_some_and(|guard| any_in_expr(guard, depth, found))
+                        || any_in_expr(&arm.body, depth, found)
+                })
+        }
+        Expr::Identifier(_) | Expr::Literal(..) | Expr::Path { .. } | Expr::Closure { .. } => false,
+    }
+}
@@ -1,8 +1,12 @@
+use super::divergence::{self, Divergence};
+use super::parens::ungrouped_callee;
 use super::{TypeChecker, VariantForm};
+use crate::const_eval::const_eval;
 use crate::errors::TypeError;
 use crate::types::{ArrayLen, Type};
+use crate::warnings::{Warning, WarningCode};
 use ast_types::FieldInit;
-use ast_types::{BinaryOp, Expr, UnaryOp};
+use ast_types::{BinaryOp, Expr, Spanned, UnaryOp};
 use shared_types::{Identifier, Literal, Span};
 use std::collections::HashMap;
 
@@ -149,11 +153,11 @@ impl TypeChecker {
     ///
     /// Returns `Some(ty)` when `func_name` names a builtin — recording an arity or
     /// argument-type diagnostic on violation — and `None` otherwise, so the caller falls
-    /// through to ordinary function resolution. The result type is `Type::Unknown`: these
-    /// builtins **diverge** (they abort and never return), so the call must satisfy any
+    /// through to ordinary function resolution. `panic` and `unreachable` **diverge** (they
+    /// abort and never return), so their result type is [`Type::Never`], which satisfies any
     /// context — a unit statement, a non-`void` tail return (`func f() -> i32 { panic(..) }`),
-    /// or a value binding. `Type::Unknown` is the type system's "compatible with everything"
-    /// type, which is exactly the divergent (`never`) contract until a dedicated `!` type lands.
+    /// or a value binding. `assert` returns when its condition holds; its result stays
+    /// `Type::Unknown` so it too fits any context.
     fn resolve_panic_builtin(
         &mut self,
         func_name: &str,
@@ -190,7 +194,27 @@ impl TypeChecker {
             }
         }
 
-        Some(Type::Unknown)
+        Some(if func_name == "assert" {
+            Type::Unknown
+        } else {
+            Type::Never
+        })
+    }
+
+    /// Type-check a call to the `black_box(value)` builtin, which returns its
+    /// argument unchanged but hides it from the optimizer. Its result type is the
+    /// argument's type; a call with any other arity is an
+    /// [`TypeError::ArgumentCountMismatch`].
+    fn check_black_box(&mut self, args: &[Expr], span: Span) -> Type {
+        let [value] = args else {
+            self.record_error(TypeError::ArgumentCountMismatch {
+                expected: 1,
+                found: args.len(),
+                span,
+            });
+            return Type::Unknown;
+        };
+        self.check_expr(value, None).unwrap_or(Type::Unknown)
     }
 
     /// Enforce the rules for the receiver of a `&mut self` method call, which
@@ -494,15 +518,37 @@ impl TypeChecker {
         recovery
     }
 
+    /// Whether `name` is a local of a function enclosing the nested function being
+    /// checked. Such a local is out of scope in the nested body.
+    fn is_enclosing_local(&self, name: &str) -> bool {
+        self.enclosing_symbols
+            .iter()
+            .any(|table| table.lookup(name).is_some())
+    }
+
+    /// The type of `name` when, seen from the nested function being checked, it
+    /// names a nested function of an enclosing body. That function captures
+    /// nothing, so using it is no capture. `None` when the innermost enclosing
+    /// binding of `name` is anything else.
+    fn enclosing_nested_function(&self, name: &str) -> Option<Type> {
+        self.enclosing_symbols
+            .iter()
+            .rev()
+            .find_map(|table| table.lookup(name))
+            .filter(|info| info.nested_function)
+            .map(|info| info.ty.clone())
+    }
+
     /// Type-check a plain identifier call (free function or previously registered
     /// method with a mangled name). Extracted so the `Call` arm can delegate here.
     pub(crate) fn check_plain_call(
         &mut self,
-        func_name: &str,
+        callee: &Identifier,
         type_args: &[ast_types::GenericArg],
         args: &[ast_types::Expr],
         span: shared_types::Span,
     ) -> Option<Type> {
+        let func_name = callee.name.as_str();
         // A call to a generic function: unify its parameters against the call
         // arguments (and any explicit turbofish), then yield the substituted return type.
         if self.generic_funcs.contains_key(func_name) {
@@ -530,20 +576,45 @@ impl TypeChecker {
             if let Some(ret) = self.resolve_panic_builtin(func_name, args, span) {
                 return Some(ret);
             }
+            if func_name == "black_box" {
+                return Some(self.check_black_box(args, span));
+            }
         }
 
         // A local binding of function type — a closure or a function-typed
         // parameter — is callable directly: `f(args)`. It shadows a same-named
         // top-level function, matching the usual locals-over-globals precedence.
-        if let Some(Type::Function { params, ret }) =
-            self.symbols.lookup(func_name).map(|info| info.ty.clone())
-        {
-            self.check_call_args(args, &params, span);
-            return Some(*ret);
+        // Any other local binding is a value, not a callee: `x()` on `x: i32` is
+        // rejected at the callee even when a top-level function shares the name,
+        // since the local shadows it.
+        match self.symbols.lookup(func_name).map(|info| info.ty.clone()) {
+            Some(Type::Function { params, ret }) => {
+                self.check_call_args(args, &params, span);
+                return Some(*ret);
+            }
+            Some(Type::Unknown) => return Some(Type::Unknown),
+            Some(ty) => {
+                self.record_error(TypeError::NotCallable {
+                    ty,
+                    span: callee.span,
+                });
+                return Some(Type::Unknown);
+            }
+            None => {}
         }
 
         let func_ty = if let Some(ty) = self.functions.get(func_name) {
             ty.clone()
+        } else if let Some(ty) = self.enclosing_nested_function(func_name) {
+            ty
+        } else if self.is_enclosing_local(func_name) {
+            // A call to any other local of an enclosing function from a nested body
+            // is a capture, like reading one.
+            self.record_error(TypeError::NestedFunctionCapture {
+                name: func_name.to_string(),
+                span: callee.span,
+            });
+            return Some(Type::Unknown);
         } else {
             self.record_error(TypeError::UndefinedFunction {
                 name: func_name.to_string(),
@@ -582,6 +653,9 @@ impl TypeChecker {
             self.record_move(arg);
         }
 
+        if self.diverging_functions.contains(func_name) {
+            return Some(Type::Never);
+        }
         Some(return_type)
     }
 
@@ -806,17 +880,25 @@ impl TypeChecker {
         }
     }
 
-    /// Evaluate every value predicate from a `where` clause against the concrete
-    /// const values in `subst`. A predicate that resolves to `false` is an error; one that
-    /// cannot be fully evaluated (still symbolic) is skipped — it is re-checked at the
-    /// concrete instantiation.
+    /// Evaluate every value predicate from a `where` clause with [`const_eval`],
+    /// against the module's constants and the concrete const values in `subst`. A
+    /// predicate that evaluates to `false` is an error; one that cannot be evaluated
+    /// (still symbolic) is skipped — it is re-checked at the concrete instantiation.
     pub(crate) fn check_where_predicates(
         &mut self,
         predicates: &[ast_types::Expr],
         subst: &std::collections::HashMap<String, Type>,
     ) {
+        let mut env = self.const_values.clone();
+        for (name, ty) in subst {
+            if let Type::ConstValue(value) = ty {
+                if let Ok(value) = i64::try_from(*value) {
+                    env.define(name.clone(), Literal::Integer(value, None));
+                }
+            }
+        }
         for pred in predicates {
-            if let Some(false) = eval_const_predicate(pred, subst) {
+            if let Ok(Literal::Boolean(false)) = const_eval(pred, &env) {
                 self.record_error(TypeError::ConstPredicateViolated { span: pred.span() });
             }
         }
@@ -1017,12 +1099,32 @@ impl TypeChecker {
                     // A const generic parameter used as a value in a generic body
                     // has its declared integer type.
                     Some(const_param_ty)
-                } else {
-                    self.record_error(TypeError::UndefinedVariable {
+                } else if let Some(func_ty) = self
+                    .functions
+                    .get(&ident.name)
+                    .filter(|_| self.free_functions.contains(&ident.name))
+                    .cloned()
+                {
+                    // A top-level function named as a value (`val f = add`) has its
+                    // function type, so it can be stored, passed, and called later.
+                    Some(func_ty)
+                } else if let Some(func_ty) = self.enclosing_nested_function(&ident.name) {
+                    Some(func_ty)
+                } else if self.is_enclosing_local(&ident.name) {
+                    self.record_error(TypeError::NestedFunctionCapture {
+                        name: ident.name.clone(),
+                        span: ident.span,
+                    });
+                    None
+                } else if self.initializing.contains(&ident.name) {
+                    self.record_error(TypeError::UseBeforeInit {
                         name: ident.name.clone(),
                         span: ident.span,
                     });
                     None
+                } else {
+                    self.record_error(self.undefined_variable(&ident.name, ident.span));
+                    None
                 }
             }
 
@@ -1053,6 +1155,31 @@ impl TypeChecker {
                 if matches!(left_ty, Type::Unknown) || matches!(right_ty, Type::Unknown) {
                     return Some(Type::Unknown);
                 }
+                // A diverging operand means the operation never produces a value.
+                if matches!(left_ty, Type::Never) || matches!(right_ty, Type::Never) {
+                    return Some(Type::Never);
+                }
+                let left_void = self.reject_void_value(&left_ty, left);
+                let right_void = self.reject_void_value(&right_ty, right);
+                if left_void || right_void {
+                    return Some(Type::Unknown);
+                }
+                // A float may be NaN, which compares unequal to itself: `x != x` is the
+                // standard NaN test, not a tautology. Float `==` is the business of the
+                // `float-equality` lint.
+                let float_operands =
+                    left_ty.referent().is_float() || left_ty.referent().is_half_float();
+                if let Some(operand) = self_compared(*op, left, right).filter(|_| !float_operands) {
+                    self.record_warning(Warning {
+                        code: WarningCode::SelfComparison,
+                        message: format!(
+                            "both sides of `{op}` are `{operand}`, so the comparison always \
+                             gives the same result; silence with `@allow(self_comparison)` on \
+                             the enclosing function"
+                        ),
+                        span: *span,
+                    });
+                }
 
                 // Operator-trait dispatch on a user type: when the left operand is
                 // a struct that implements the operator's trait, the operator lowers to
@@ -1107,21 +1234,7 @@ impl TypeChecker {
                             return Some(Type::Unknown);
                         }
 
-                        // Half-precision scalars have no arithmetic: point the
-                        // programmer at the `f32` workaround rather than a generic error.
-                        if let Some(half) = [&left_ty, &right_ty]
-                            .into_iter()
-                            .find(|t| t.is_half_float())
-                        {
-                            self.record_error(TypeError::HalfFloatArithmetic {
-                                op: op.to_string(),
-                                ty: half.clone(),
-                                span: *span,
-                            });
-                            return Some(Type::Unknown);
-                        }
-
-                        if !left_ty.is_numeric() {
+                        if !(left_ty.is_numeric() || left_ty.is_half_float()) {
                             self.record_error(TypeError::InvalidBinaryOperator {
                                 op: op.to_string(),
                                 left: left_ty.clone(),
@@ -1157,29 +1270,45 @@ impl TypeChecker {
                             });
                             return Some(Type::Unknown);
                         }
+                        if self.float_equality && left_cmp.is_float() {
+                            self.record_warning(Warning {
+                                code: WarningCode::FloatEquality,
+                                message: format!(
+                                    "`{op}` on {left_cmp} values depends on exact rounding; \
+                                     compare the difference against a small epsilon instead, \
+                                     or silence with `@allow(float_equality)` on the \
+                                     enclosing function"
+                                ),
+                                span: *span,
+                            });
+                        }
                         Some(Type::Bool)
                     }
 
                     // Ordering operators: require numeric or `char` operands (this gives
-                    // `char` a built-in total order), return bool.
+                    // `char` a built-in total order), return bool. An operand with no
+                    // order at all (`i32 < true`) is an invalid operator; two orderable
+                    // types that differ (`i32 < i64`) are a `Mismatch`, as for arithmetic,
+                    // since numbers never widen implicitly.
                     BinaryOp::Less
                     | BinaryOp::Greater
                     | BinaryOp::LessEqual
                     | BinaryOp::GreaterEqual => {
-                        if !left_ty.is_compatible_with(&right_ty) {
-                            self.record_error(TypeError::Mismatch {
-                                expected: left_ty,
-                                found: right_ty,
+                        let orderable = |ty: &Type| ty.is_numeric() || ty.is_char();
+                        if !orderable(&left_ty) || !orderable(&right_ty) {
+                            self.record_error(TypeError::InvalidBinaryOperator {
+                                op: op.to_string(),
+                                left: left_ty.clone(),
+                                right: right_ty.clone(),
                                 span: *span,
                             });
                             return Some(Type::Unknown);
                         }
 
-                        if !left_ty.is_numeric() && !left_ty.is_char() {
-                            self.record_error(TypeError::InvalidBinaryOperator {
-                                op: op.to_string(),
-                                left: left_ty.clone(),
-                                right: right_ty.clone(),
+                        if !left_ty.is_compatible_with(&right_ty) {
+                            self.record_error(TypeError::Mismatch {
+                                expected: left_ty,
+                                found: right_ty,
                                 span: *span,
                             });
                             return Some(Type::Unknown);
@@ -1269,7 +1398,9 @@ impl TypeChecker {
                     .check_expr(operand, expected_operand)
                     .unwrap_or(Type::Unknown);
 
-                if matches!(operand_ty, Type::Unknown) {
+                if matches!(operand_ty, Type::Unknown)
+                    || self.reject_void_value(&operand_ty, operand)
+                {
                     return Some(Type::Unknown);
                 }
 
@@ -1285,7 +1416,7 @@ impl TypeChecker {
 
                 match op {
                     UnaryOp::Negate => {
-                        if !operand_ty.is_numeric() {
+                        if !(operand_ty.is_numeric() || operand_ty.is_half_float()) {
                             self.record_error(TypeError::InvalidOperator {
                                 op: op.to_string(),
                                 ty: operand_ty,
@@ -1354,9 +1485,7 @@ impl TypeChecker {
                 span,
             } => {
                 match &**func {
-                    Expr::Identifier(ident) => {
-                        self.check_plain_call(&ident.name, type_args, args, *span)
-                    }
+                    Expr::Identifier(ident) => self.check_plain_call(ident, type_args, args, *span),
 
                     // Method call: `instance.method(args)`
                     // The object type determines which struct's methods to search.
@@ -1584,14 +1713,28 @@ impl TypeChecker {
                         Some(return_type)
                     }
 
-                    _ => {
-                        let expr_ty = self.check_expr(func, None).unwrap_or(Type::Unknown);
-                        self.record_error(TypeError::NotCallable {
-                            ty: expr_ty,
-                            span: *span,
-                        });
-                        Some(Type::Unknown)
-                    }
+                    // Any other callee is callable when it evaluates to a function
+                    // value, e.g. the result of another call: `make_adder()(1)`.
+                    // Parentheses around the callee are checked through, not linted:
+                    // `(s.f)(1)` calls the function stored in field `f`, where
+                    // `s.f(1)` would look for a method.
+                    _ => match self
+                        .check_expr(ungrouped_callee(func), None)
+                        .unwrap_or(Type::Unknown)
+                    {
+                        Type::Function { params, ret } => {
+                            self.check_call_args(args, &params, *span);
+                            Some(*ret)
+                        }
+                        Type::Unknown => Some(Type::Unknown),
+                        expr_ty => {
+                            self.record_error(TypeError::NotCallable {
+                                ty: expr_ty,
+                                span: *span,
+                            });
+                            Some(Type::Unknown)
+                        }
+                    },
                 }
             }
 
@@ -1628,7 +1771,8 @@ impl TypeChecker {
                 }
             }
 
-            Expr::Paren(inner, _) => {
+            Expr::Paren(inner, span) => {
+                self.lint_paren(inner, *span);
                 // Propagate expected type through parentheses
                 self.check_expr(inner, expected)
             }
@@ -1831,19 +1975,17 @@ impl TypeChecker {
                     return Some(Type::Void);
                 }
 
-                // All arms must agree on type
-                let result_ty = arm_types[0].clone();
-                for arm_ty in &arm_types[1..] {
-                    if !arm_ty.is_compatible_with(&result_ty) {
-                        self.record_error(TypeError::Mismatch {
-                            expected: result_ty.clone(),
-                            found: arm_ty.clone(),
+                match unify_arm_types(&arm_types) {
+                    Ok(result_ty) => Some(result_ty),
+                    Err((expected, found)) => {
+                        self.record_error(TypeError::IfArmTypeMismatch {
+                            expected,
+                            found,
                             span: *span,
                         });
-                        return Some(Type::Unknown);
+                        Some(Type::Unknown)
                     }
                 }
-                Some(result_ty)
             }
 
             Expr::Block { stmts, .. } => {
@@ -1858,6 +2000,9 @@ impl TypeChecker {
             // value-break it yields unit. `while`/`for` have no expression form.
             Expr::Loop { label, body, .. } => {
                 let value_ty = self.check_loop_body(label.as_ref(), true, body);
+                if !divergence::loop_exits(label.as_ref(), body) {
+                    return Some(Type::Never);
+                }
                 Some(value_ty.unwrap_or(Type::Void))
             }
 
@@ -2232,7 +2377,20 @@ impl TypeChecker {
         }
     }
 
+    /// Report `expr` as `VoidValueUsed` when its type `ty` is void: a call to a
+    /// function without a return type produces nothing to bind or operate on.
+    /// Returns whether it was reported.
+    pub(crate) fn reject_void_value(&mut self, ty: &Type, expr: &Expr) -> bool {
+        if !matches!(ty, Type::Void) {
+            return false;
+        }
+        self.record_error(TypeError::VoidValueUsed { span: expr.span() });
+        true
+    }
+
     /// Check all stmts in a block and return the type of the trailing expression, or Void.
+    /// A block that never completes (it returns, breaks, or calls something that never
+    /// returns) is `Type::Never`.
     fn check_block_expr_type(&mut self, stmts: &[ast_types::Stmt]) -> Type {
         self.symbols.push_scope();
         let mut result = Type::Void;
@@ -2240,82 +2398,43 @@ impl TypeChecker {
             if i == stmts.len() - 1 {
                 if let ast_types::Stmt::Expr(expr) = stmt {
                     result = self.check_expr(expr, None).unwrap_or(Type::Unknown);
-                    self.symbols.pop_scope();
-                    return result;
+                    break;
                 }
             }
             let _ = self.check_stmt(stmt);
         }
         self.symbols.pop_scope();
+        if Divergence::new(&self.diverging_functions).block(stmts) {
+            return Type::Never;
+        }
         result
     }
 }
 
-/// Evaluate a `where`-clause value predicate to a boolean, given the const
-/// parameter values in `subst`. Returns `None` when the predicate is not a fully
-/// resolved boolean over const values (it is then deferred to the concrete instance).
-pub(crate) fn eval_const_predicate(expr: &Expr, subst: &HashMap<String, Type>) -> Option<bool> {
-    match expr {
-        Expr::Literal(Literal::Boolean(b), _) => Some(*b),
-        Expr::Paren(inner, _) => eval_const_predicate(inner, subst),
-        Expr::Binary {
-            left, op, right, ..
-        } => match op {
-            BinaryOp::And => {
-                Some(eval_const_predicate(left, subst)? && eval_const_predicate(right, subst)?)
-            }
-            BinaryOp::Or => {
-                Some(eval_const_predicate(left, subst)? || eval_const_predicate(right, subst)?)
-            }
-            BinaryOp::Less
-            | BinaryOp::Greater
-            | BinaryOp::LessEqual
-            | BinaryOp::GreaterEqual
-            | BinaryOp::Equal
-            | BinaryOp::NotEqual => {
-                let l = eval_const_int(left, subst)?;
-                let r = eval_const_int(right, subst)?;
-                Some(match op {
-                    BinaryOp::Less => l < r,
-                    BinaryOp::Greater => l > r,
-                    BinaryOp::LessEqual => l <= r,
-                    BinaryOp::GreaterEqual => l >= r,
-                    BinaryOp::Equal => l == r,
-                    BinaryOp::NotEqual => l != r,
-                    _ => unreachable!(),
-                })
-            }
-            _ => None,
-        },
-        _ => None,
+/// The common type of the arms of an `if` or `match`, or the `(expected, found)` pair
+/// of the first arm that disagrees. A diverging arm produces no value, so it defers to
+/// the others; when every arm diverges, so does the whole expression.
+pub(crate) fn unify_arm_types(arm_types: &[Type]) -> Result<Type, (Type, Type)> {
+    let mut values = arm_types.iter().filter(|ty| !matches!(ty, Type::Never));
+    let Some(result_ty) = values.next() else {
+        return Ok(Type::Never);
+    };
+    match values.find(|arm_ty| !arm_ty.is_compatible_with(result_ty)) {
+        Some(arm_ty) => Err((result_ty.clone(), arm_ty.clone())),
+        None => Ok(result_ty.clone()),
     }
 }
 
-/// Evaluate a const-integer expression: an integer literal, a const parameter
-/// looked up in `subst`, or an arithmetic combination of these. `None` when it is not a
-/// fully resolved const integer.
-fn eval_const_int(expr: &Expr, subst: &HashMap<String, Type>) -> Option<i128> {
-    match expr {
-        Expr::Literal(Literal::Integer(v, _), _) => Some(*v as i128),
-        Expr::Paren(inner, _) => eval_const_int(inner, subst),
-        Expr::Identifier(id) => match subst.get(&id.name) {
-            Some(Type::ConstValue(v)) => Some(*v as i128),
-            _ => None,
-        },
-        Expr::Binary {
-            left, op, right, ..
-        } => {
-            let l = eval_const_int(left, subst)?;
-            let r = eval_const_int(right, subst)?;
-            match op {
-                BinaryOp::Add => Some(l + r),
-                BinaryOp::Subtract => Some(l - r),
-                BinaryOp::Multiply => Some(l * r),
-                BinaryOp::Divide if r != 0 => Some(l / r),
-                BinaryOp::Modulo if r != 0 => Some(l % r),
-                _ => None,
-            }
-        }
+/// The shared operand of a comparison whose two sides are the same identifier or
+/// the same literal, rendered for the warning. Only those two forms are matched:
+/// anything with structure (a call, a field access) is left alone.
+fn self_compared(op: BinaryOp, left: &Expr, right: &Expr) -> Option<String> {
+    if !op.is_comparison() {
+        return None;
+    }
+    match (left, right) {
+        (Expr::Identifier(l), Expr::Identifier(r)) if l.name == r.name => Some(l.name.clone()),
+        (Expr::Literal(l, _), Expr::Literal(r, _)) if l == r => Some(l.to_string()),
         _ => None,
     }
 }
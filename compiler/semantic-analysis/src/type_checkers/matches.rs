@@ -1,12 +1,14 @@
 // Type checking for `match` expressions: pattern/scrutinee typing, arm-body
 // unification, binding introduction, and exhaustiveness.
 
-use ast_types::{EnumPatternPayload, Expr, MatchArm, Pattern};
+use ast_types::{EnumPatternPayload, Expr, MatchArm, Pattern, Spanned};
 use shared_types::{IntSuffix, Literal, Span};
 
+use super::expressions::unify_arm_types;
 use super::{TypeChecker, VariantForm};
 use crate::errors::TypeError;
 use crate::types::Type;
+use crate::warnings::{Warning, WarningCode};
 
 /// What a single guardless pattern proves about coverage of the scrutinee.
 enum Coverage {
@@ -16,7 +18,10 @@ enum Coverage {
     Variant(String),
     /// Covers one boolean value.
     Bool(bool),
-    /// Contributes nothing decidable (a literal or range over an unbounded scalar).
+    /// Covers the inclusive range `lo..=hi` of an integer or `char` scrutinee
+    /// (a `char` as its scalar value). A literal covers a one-value range.
+    Values(i128, i128),
+    /// Contributes nothing decidable (a float or string literal, or an empty range).
     Nothing,
 }
 
@@ -31,19 +36,9 @@ impl TypeChecker {
     ) -> Type {
         let scrut_ty = self.check_expr(scrutinee, None).unwrap_or(Type::Unknown);
 
-        // Only Copy scalars and enums are matchable in this phase; reject anything
-        // else up front (but keep checking arms so their bodies still get diagnosed).
-        let matchable = matches!(&scrut_ty, Type::Enum(_))
-            || scrut_ty.is_integer()
-            || scrut_ty.is_char()
-            || scrut_ty.is_bool()
-            || matches!(scrut_ty, Type::Unknown);
-        if !matchable {
-            self.record_error(TypeError::UnsupportedMatchScrutinee {
-                ty: scrut_ty.clone(),
-                span,
-            });
-        }
+        // Reject unmatchable scrutinees up front, but keep checking arms so their
+        // bodies still get diagnosed.
+        let matchable = self.check_matchable(&scrut_ty, span);
 
         // Each arm runs on its own path, so snapshot the move state after the
         // scrutinee and restore it between arms — mirroring `if`.
@@ -56,7 +51,7 @@ impl TypeChecker {
         for arm in arms {
             self.symbols.restore_moves(&move_snapshot);
             let arm_ty = self.check_arm(arm, &scrut_ty, hint.as_ref());
-            if hint.is_none() && !matches!(arm_ty, Type::Unknown) {
+            if hint.is_none() && !matches!(arm_ty, Type::Unknown | Type::Never) {
                 hint = Some(arm_ty.clone());
             }
             arm_types.push(arm_ty);
@@ -68,21 +63,53 @@ impl TypeChecker {
         }
 
         // Unify arm body types, mirroring the `if`-expression rule.
-        let Some((first, rest)) = arm_types.split_first() else {
+        if arm_types.is_empty() {
             return Type::Void;
-        };
-        let result_ty = first.clone();
-        for arm_ty in rest {
-            if !arm_ty.is_compatible_with(&result_ty) {
+        }
+        match unify_arm_types(&arm_types) {
+            Ok(result_ty) => result_ty,
+            Err((expected, found)) => {
                 self.record_error(TypeError::MatchArmTypeMismatch {
-                    expected: result_ty.clone(),
-                    found: arm_ty.clone(),
+                    expected,
+                    found,
                     span,
                 });
-                return Type::Unknown;
+                Type::Unknown
             }
         }
-        result_ty
+    }
+
+    /// Check the `let pattern = scrutinee` head of an `if let` / `while let`
+    /// and return the bindings the pattern introduces. The caller defines them
+    /// in the scope of the guarded block.
+    pub(crate) fn check_let_condition(
+        &mut self,
+        pattern: &Pattern,
+        scrutinee: &Expr,
+    ) -> Vec<(String, Type, Span)> {
+        let scrut_ty = self.check_expr(scrutinee, None).unwrap_or(Type::Unknown);
+        let mut bindings = Vec::new();
+        if self.check_matchable(&scrut_ty, scrutinee.span()) {
+            self.check_pattern(pattern, &scrut_ty, &mut bindings);
+        }
+        bindings
+    }
+
+    /// Only Copy scalars and enums are matchable in this phase. Records an error
+    /// and returns false for anything else.
+    fn check_matchable(&mut self, scrut_ty: &Type, span: Span) -> bool {
+        let matchable = matches!(scrut_ty, Type::Enum(_))
+            || scrut_ty.is_integer()
+            || scrut_ty.is_char()
+            || scrut_ty.is_bool()
+            || matches!(scrut_ty, Type::Unknown);
+        if !matchable {
+            self.record_error(TypeError::UnsupportedMatchScrutinee {
+                ty: scrut_ty.clone(),
+                span,
+            });
+        }
+        matchable
     }
 
     /// Check one arm: its patterns, guard, and body. Introduces the pattern bindings
@@ -295,13 +322,16 @@ impl TypeChecker {
         }
     }
 
-    /// Verify the arms cover every possible scrutinee value.
+    /// Verify the arms cover every possible scrutinee value, and warn about arms
+    /// that follow the point where coverage became complete and about integer or
+    /// `char` patterns that overlap values an earlier pattern already matches.
     fn check_exhaustive(&mut self, arms: &[MatchArm], scrut_ty: &Type, span: Span) {
         let mut has_catch_all = false;
         let mut covered_variants: Vec<String> = Vec::new();
         let mut bools_covered = [false; 2];
+        let mut covered_values = ValueSet::default();
 
-        for arm in arms {
+        for (index, arm) in arms.iter().enumerate() {
             // A guarded arm may not fire, so it never contributes to exhaustiveness.
             if arm.guard.is_some() {
                 continue;
@@ -315,13 +345,40 @@ impl TypeChecker {
                         }
                     }
                     Coverage::Bool(b) => bools_covered[b as usize] = true,
+                    Coverage::Values(lo, hi) => {
+                        if let Some(warning) =
+                            overlap_warning(&covered_values, lo, hi, scrut_ty, pat.span())
+                        {
+                            self.record_warning(warning);
+                        }
+                        covered_values.insert(lo, hi);
+                    }
                     Coverage::Nothing => {}
                 }
             }
-        }
 
-        if has_catch_all {
-            return;
+            let exhausted = has_catch_all
+                || match scrut_ty {
+                    Type::Enum(name) => self.enum_defs.get(name).is_some_and(|vs| {
+                        !vs.is_empty() && vs.iter().all(|v| covered_variants.contains(&v.name))
+                    }),
+                    Type::Bool => bools_covered[0] && bools_covered[1],
+                    _ => value_domain(scrut_ty)
+                        .is_some_and(|domain| covered_values.first_gap(&domain).is_none()),
+                };
+            if exhausted {
+                for later in &arms[index + 1..] {
+                    self.record_warning(Warning {
+                        code: WarningCode::UnreachableMatchArm,
+                        message: "unreachable match arm: the arms above already cover every \
+                             value; silence with `@allow(unreachable_match_arm)` on the \
+                             enclosing function"
+                            .to_string(),
+                        span: later.span,
+                    });
+                }
+                return;
+            }
         }
 
         match scrut_ty {
@@ -350,13 +407,21 @@ impl TypeChecker {
                     });
                 }
             }
-            // Integers and `char` have too many values to enumerate; they require a
-            // wildcard arm.
+            // Integers and `char` are covered by literals and ranges only when they
+            // leave no gap in the type's domain; otherwise they need a wildcard arm.
+            // The first gap is named once any values are covered.
             _ => {
-                self.record_error(TypeError::NonExhaustiveMatch {
-                    reason: format!("a `{}` match requires a `_` wildcard arm", scrut_ty),
-                    span,
-                });
+                let gap =
+                    value_domain(scrut_ty).and_then(|domain| covered_values.first_gap(&domain));
+                let reason = match gap {
+                    Some((lo, hi)) if !covered_values.is_empty() => format!(
+                        "unhandled `{}` value(s) {}",
+                        scrut_ty,
+                        value_range_text(lo, hi, scrut_ty)
+                    ),
+                    _ => format!("a `{}` match requires a `_` wildcard arm", scrut_ty),
+                };
+                self.record_error(TypeError::NonExhaustiveMatch { reason, span });
             }
         }
     }
@@ -368,7 +433,164 @@ fn pattern_coverage(pat: &Pattern) -> Coverage {
         Pattern::Wildcard(_) | Pattern::Binding(_) => Coverage::CatchAll,
         Pattern::Enum { variant, .. } => Coverage::Variant(variant.name.clone()),
         Pattern::Literal(Literal::Boolean(b), _) => Coverage::Bool(*b),
-        Pattern::Literal(_, _) | Pattern::Range { .. } => Coverage::Nothing,
+        Pattern::Literal(lit, _) => match literal_value(lit) {
+            Some(value) => Coverage::Values(value, value),
+            None => Coverage::Nothing,
+        },
+        Pattern::Range {
+            start,
+            end,
+            inclusive,
+            ..
+        } => match (literal_value(start), literal_value(end)) {
+            (Some(lo), Some(end)) => {
+                let hi = if *inclusive { end } else { end - 1 };
+                if lo <= hi {
+                    Coverage::Values(lo, hi)
+                } else {
+                    Coverage::Nothing
+                }
+            }
+            _ => Coverage::Nothing,
+        },
+    }
+}
+
+/// The ordinal an integer or `char` literal pattern matches; `None` for the
+/// other literal kinds.
+fn literal_value(lit: &Literal) -> Option<i128> {
+    match lit {
+        Literal::Integer(value, _) => Some(i128::from(*value)),
+        Literal::Char(c) => Some(i128::from(u32::from(*c))),
+        Literal::Float(_, _) | Literal::String(_) | Literal::Boolean(_) => None,
+    }
+}
+
+/// Every value of an integer or `char` type, as disjoint ascending inclusive
+/// ranges (`char` skips the surrogate block). `None` for any other type.
+fn value_domain(ty: &Type) -> Option<Vec<(i128, i128)>> {
+    let range = match ty {
+        Type::I8 => (i128::from(i8::MIN), i128::from(i8::MAX)),
+        Type::I16 => (i128::from(i16::MIN), i128::from(i16::MAX)),
+        Type::I32 => (i128::from(i32::MIN), i128::from(i32::MAX)),
+        Type::I64 => (i128::from(i64::MIN), i128::from(i64::MAX)),
+        Type::U8 => (0, i128::from(u8::MAX)),
+        Type::U16 => (0, i128::from(u16::MAX)),
+        Type::U32 => (0, i128::from(u32::MAX)),
+        Type::U64 => (0, i128::from(u64::MAX)),
+        Type::Char => return Some(vec![(0, 0xD7FF), (0xE000, 0x10FFFF)]),
+        _ => return None,
+    };
+    Some(vec![range])
+}
+
+/// The integer or `char` values matched so far, as disjoint, non-adjacent
+/// inclusive ranges in ascending order.
+#[derive(Default)]
+struct ValueSet {
+    ranges: Vec<(i128, i128)>,
+}
+
+impl ValueSet {
+    fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Add `lo..=hi`, merging it with every range it overlaps or touches.
+    fn insert(&mut self, lo: i128, hi: i128) {
+        let (mut lo, mut hi) = (lo, hi);
+        let mut merged = Vec::with_capacity(self.ranges.len() + 1);
+        for &(a, b) in &self.ranges {
+            if b + 1 < lo || hi + 1 < a {
+                merged.push((a, b));
+            } else {
+                lo = lo.min(a);
+                hi = hi.max(b);
+            }
+        }
+        merged.push((lo, hi));
+        merged.sort_unstable();
+        self.ranges = merged;
+    }
+
+    /// The first part of `lo..=hi` already in the set, if any.
+    fn first_overlap(&self, lo: i128, hi: i128) -> Option<(i128, i128)> {
+        self.ranges
+            .iter()
+            .find(|&&(a, b)| a <= hi && lo <= b)
+            .map(|&(a, b)| (a.max(lo), b.min(hi)))
+    }
+
+    /// Whether every value of `lo..=hi` is already in the set.
+    fn contains_all(&self, lo: i128, hi: i128) -> bool {
+        self.ranges.iter().any(|&(a, b)| a <= lo && hi <= b)
+    }
+
+    /// The first run of `domain` values missing from the set, if any.
+    fn first_gap(&self, domain: &[(i128, i128)]) -> Option<(i128, i128)> {
+        for &(lo, hi) in domain {
+            let mut next = lo;
+            for &(a, b) in &self.ranges {
+                if b < next || a > hi {
+                    continue;
+                }
+                if a > next {
+                    return Some((next, a - 1));
+                }
+                next = b + 1;
+            }
+            if next <= hi {
+                return Some((next, hi));
+            }
+        }
+        None
+    }
+}
+
+/// The warning for a pattern covering `lo..=hi` when earlier patterns of the
+/// match already cover some or all of those values.
+fn overlap_warning(
+    covered: &ValueSet,
+    lo: i128,
+    hi: i128,
+    scrut_ty: &Type,
+    span: Span,
+) -> Option<Warning> {
+    let (overlap_lo, overlap_hi) = covered.first_overlap(lo, hi)?;
+    let message = if covered.contains_all(lo, hi) {
+        "unreachable pattern: earlier patterns already match every value it covers; silence \
+         with `@allow(overlapping_pattern)` on the enclosing function"
+            .to_string()
+    } else {
+        let values = value_range_text(overlap_lo, overlap_hi, scrut_ty);
+        let overlap = if overlap_lo == overlap_hi {
+            format!("value {values} is already matched by an earlier pattern and never reaches")
+        } else {
+            format!("values {values} are already matched by an earlier pattern and never reach")
+        };
+        format!(
+            "overlapping pattern: {overlap} this one; silence with \
+             `@allow(overlapping_pattern)` on the enclosing function"
+        )
+    };
+    Some(Warning {
+        code: WarningCode::OverlappingPattern,
+        message,
+        span,
+    })
+}
+
+/// `lo..=hi` as source text for the scrutinee type: `'a'..='f'` for a `char`,
+/// and a single value when the range has one.
+fn value_range_text(lo: i128, hi: i128, scrut_ty: &Type) -> String {
+    let value = |v: i128| match u32::try_from(v).ok().and_then(char::from_u32) {
+        Some(c) if scrut_ty.is_char() => format!("{:?}", c),
+        _ => v.to_string(),
+    };
+    if lo == hi {
+        value(lo)
+    } else {
+        format!("{}..={}", value(lo), value(hi))
     }
 }
 
@@ -2,9 +2,14 @@
 
 use std::collections::{HashMap, HashSet};
 
-use ast_types::{Attribute, Item, MethodDef, Stmt};
+use ast_types::{Attribute, Item, MethodDef, Spanned, Stmt};
+use shared_types::Span;
 
+use crate::const_eval::{fold_consts, ConstEnv, ConstEvalError};
+use crate::dead_code::unreachable_functions;
 use crate::errors::TypeError;
+use crate::options::TypeCheckOptions;
+use crate::suggestions::closest_name;
 use crate::symbol_table::SymbolTable;
 use crate::types::Type;
 use crate::warnings::{Warning, WarningCode};
@@ -15,6 +20,9 @@ pub(crate) struct TypeChecker {
     symbols: SymbolTable,
     /// Function signatures (global scope) — includes mangled method names
     functions: HashMap<String, Type>,
+    /// Names of the non-generic top-level functions in `functions`. Only these may
+    /// be named as values; the mangled method and instance keys are internal symbols.
+    free_functions: HashSet<String>,
     /// Struct definitions: name → ordered list of (field_name, field_type)
     struct_defs: HashMap<String, Vec<(String, Type)>>,
     /// Enum definitions: name → ordered list of variants. The order is the
@@ -38,6 +46,9 @@ pub(crate) struct TypeChecker {
     /// borrow of the receiver, so the receiver must be a mutable place and must not
     /// already be borrowed — checked at the call site like a `&mut place` borrow.
     mut_self_methods: HashSet<String>,
+    /// Top-level functions that never return, plus the unshadowed `panic` and
+    /// `unreachable` builtins. A call to one has type [`Type::Never`].
+    diverging_functions: HashSet<String>,
     /// Generic free-function templates, keyed by name. A generic function is
     /// NOT placed in `functions` — calls to it route through generic inference, which
     /// substitutes concrete type arguments per call site (monomorphization).
@@ -87,6 +98,15 @@ pub(crate) struct TypeChecker {
     pub(crate) lifetime_scope: HashSet<String>,
     /// Compile-time constant names and their declared types (module and function scope).
     pub(crate) constants: HashMap<String, Type>,
+    /// Values of the module-level constants, folded before any type is resolved so
+    /// an array length may name one.
+    pub(crate) module_const_values: ConstEnv,
+    /// Why each module-level constant that could not be folded failed, by name.
+    pub(crate) const_failures: HashMap<String, ConstEvalError>,
+    /// Values of every constant evaluated so far: the module's, then each local
+    /// `const` as its statement is checked. Later constants and `where` predicates
+    /// are evaluated against these.
+    pub(crate) const_values: ConstEnv,
     /// Collected type errors
     errors: Vec<TypeError>,
     /// Collected non-fatal lint warnings
@@ -102,6 +122,28 @@ pub(crate) struct TypeChecker {
     /// loop-nesting count used to reject `break` / `continue` outside any loop;
     /// each entry carries its label and value-break typing state.
     loop_stack: Vec<LoopContext>,
+    /// Names of `val`/`mut` bindings whose initializer is being checked, innermost
+    /// last. The binding is not in scope yet, so an otherwise-unresolved use of one
+    /// of these names is a self-reference, reported as `UseBeforeInit`.
+    initializing: Vec<String>,
+    /// Symbol tables of the functions enclosing the nested function being checked,
+    /// outermost first. Their locals are out of scope in the nested body; a use of
+    /// one is reported as `NestedFunctionCapture` instead of an undefined name.
+    enclosing_symbols: Vec<SymbolTable>,
+    /// Span of the current function body's trailing expression or `if` when it is
+    /// the returned value. A trailing expression is checked as a statement too, but
+    /// its result is not discarded, so pedantic mode leaves it alone; a trailing `if`
+    /// checks each arm's tail against the return type.
+    returned_tail: Option<Span>,
+    /// Reject programs that rely on inference defaults (`--pedantic`): see
+    /// [`crate::TypeCheckOptions::pedantic`].
+    pedantic: bool,
+    /// Warn on `==`/`!=` between floats (`-W float-equality`): see
+    /// [`crate::TypeCheckOptions::float_equality`].
+    float_equality: bool,
+    /// Hint at parentheses that change nothing (`-W redundant-parens`): see
+    /// [`crate::TypeCheckOptions::redundant_parens`].
+    redundant_parens: bool,
 }
 
 /// The construction form of an enum variant, determining how it is built:
@@ -185,11 +227,13 @@ struct LoopContext {
 
 mod closures;
 mod declarations;
+mod divergence;
 mod expressions;
 mod literals;
 mod matches;
 mod moves;
 pub(crate) mod operator_traits;
+mod parens;
 mod resolution;
 mod statements;
 
@@ -201,6 +245,7 @@ impl TypeChecker {
         Self {
             symbols: SymbolTable::new(),
             functions: HashMap::new(),
+            free_functions: HashSet::new(),
             struct_defs: HashMap::new(),
             enum_defs: HashMap::new(),
             newtype_defs: HashMap::new(),
@@ -208,6 +253,7 @@ impl TypeChecker {
             clone_structs: HashSet::new(),
             impl_methods: HashMap::new(),
             mut_self_methods: HashSet::new(),
+            diverging_functions: HashSet::new(),
             generic_funcs: HashMap::new(),
             generic_structs: HashMap::new(),
             generic_impls: HashMap::new(),
@@ -221,17 +267,66 @@ impl TypeChecker {
             const_scope: HashMap::new(),
             lifetime_scope: HashSet::new(),
             constants: HashMap::new(),
+            module_const_values: ConstEnv::new(),
+            const_failures: HashMap::new(),
+            const_values: ConstEnv::new(),
             errors: Vec::new(),
             warnings: Vec::new(),
             current_function_return_type: None,
             current_fn_outliving: HashSet::new(),
             loop_stack: Vec::new(),
+            initializing: Vec::new(),
+            enclosing_symbols: Vec::new(),
+            returned_tail: None,
+            pedantic: false,
+            float_equality: false,
+            redundant_parens: false,
         }
     }
 
-    /// Record an error and continue type checking
+    /// A checker configured by `options`.
+    pub(crate) fn with_options(options: &TypeCheckOptions) -> Self {
+        Self {
+            pedantic: options.pedantic,
+            float_equality: options.float_equality,
+            redundant_parens: options.redundant_parens,
+            ..Self::new()
+        }
+    }
+
+    /// Record an error and continue type checking.
+    ///
+    /// An error equal to one already recorded (same variant, fields, and span) is
+    /// dropped: some nodes, such as a block's tail expression, are checked by more
+    /// than one pass and would otherwise report the same problem twice.
     pub(crate) fn record_error(&mut self, error: TypeError) {
-        self.errors.push(error);
+        if !self.errors.contains(&error) {
+            self.errors.push(error);
+        }
+    }
+
+    /// The error for a name that resolves to no variable or constant, suggesting
+    /// the closest one in scope when it looks like a typo of it.
+    pub(crate) fn undefined_variable(&self, name: &str, span: Span) -> TypeError {
+        let candidates = self
+            .symbols
+            .names()
+            .chain(self.constants.keys().map(String::as_str))
+            .chain(self.const_scope.keys().map(String::as_str));
+        TypeError::UndefinedVariable {
+            name: name.to_string(),
+            span,
+            suggestion: closest_name(name, candidates).map(str::to_string),
+        }
+    }
+
+    /// Record a lint warning raised while checking, dropping exact duplicates for
+    /// the same reason as `record_error`. `run_lints` later removes any that an
+    /// `@allow(...)` on the enclosing function suppresses.
+    pub(crate) fn record_warning(&mut self, warning: Warning) {
+        if !self.warnings.contains(&warning) {
+            self.warnings.push(warning);
+        }
     }
 
     /// Get all collected errors
@@ -244,6 +339,11 @@ impl TypeChecker {
         self.warnings
     }
 
+    /// Get the collected errors and lint warnings together.
+    pub(crate) fn into_diagnostics(self) -> (Vec<TypeError>, Vec<Warning>) {
+        (self.errors, self.warnings)
+    }
+
     /// Check if there are any errors
     pub(crate) fn has_errors(&self) -> bool {
         !self.errors.is_empty()
@@ -334,6 +434,16 @@ impl TypeChecker {
             }
         }
 
+        // Pass 0b: evaluate module-level constants. Evaluation needs no types, and
+        // doing it first lets an array length anywhere name a constant.
+        let (values, failures) = fold_consts(items.iter().filter_map(|item| match item {
+            Item::Const(def) => Some(def),
+            _ => None,
+        }));
+        self.module_const_values = values.clone();
+        self.const_values = values;
+        self.const_failures = failures;
+
         // Pass 0: register enum definitions before structs, so an enum used as a
         // struct field type (or vice versa) resolves regardless of source order.
         for item in items {
@@ -400,6 +510,10 @@ impl TypeChecker {
             }
         }
 
+        // Pass 3b: find the functions that never return, so a call to one can stand
+        // in for a value of any type regardless of source order.
+        self.diverging_functions = divergence::diverging_functions(items);
+
         // Pass 4: check function, method, and const bodies.
         for item in items {
             match item {
@@ -440,19 +554,27 @@ impl TypeChecker {
     /// `while true { ... }` statement is replaced by `loop { ... }` for
     /// stylistic reasons; the warning is suppressed when the enclosing
     /// function carries `@allow(prefer_loop_over_while_true)`.
+    ///
+    /// Warnings raised during checking (`unreachable-match-arm`,
+    /// `self-comparison`, `overlapping-pattern`, `float-equality`,
+    /// `redundant-parens`) and the whole-program `dead-code` warnings are
+    /// filtered here too, so `@allow` works the same way for them.
     fn run_lints(&mut self, items: &[Item]) {
+        self.warnings.extend(unreachable_functions(items));
         for item in items {
             match item {
                 Item::Function(func) => {
                     let suppress_while_true =
                         attr_allows(&func.attributes, WarningCode::PreferLoopOverWhileTrue);
                     self.lint_block(&func.body, suppress_while_true);
+                    self.drop_allowed_warnings(&func.attributes, func.span);
                 }
                 Item::Impl(def) => {
                     for method in &def.methods {
                         let suppress_while_true =
                             attr_allows(&method.attributes, WarningCode::PreferLoopOverWhileTrue);
                         self.lint_method(method, suppress_while_true);
+                        self.drop_allowed_warnings(&method.attributes, method.span);
                     }
                 }
                 Item::Struct(_)
@@ -464,6 +586,24 @@ impl TypeChecker {
         }
     }
 
+    /// Remove checker-raised warnings inside `span` whose code `attributes` allow.
+    fn drop_allowed_warnings(&mut self, attributes: &[Attribute], span: Span) {
+        for code in [
+            WarningCode::UnreachableMatchArm,
+            WarningCode::SelfComparison,
+            WarningCode::OverlappingPattern,
+            WarningCode::DeadCode,
+            WarningCode::FloatEquality,
+            WarningCode::RedundantParens,
+        ] {
+            if attr_allows(attributes, code) {
+                self.warnings.retain(|w| {
+                    w.code != code || w.span.start < span.start || w.span.end > span.end
+                });
+            }
+        }
+    }
+
     fn lint_method(&mut self, method: &MethodDef, suppress_while_true: bool) {
         self.lint_block(&method.body, suppress_while_true);
     }
@@ -505,12 +645,33 @@ impl TypeChecker {
                     self.lint_block(block, suppress_while_true);
                 }
             }
-            Stmt::ForRange { body, .. } => {
+            Stmt::IfLet {
+                then_block,
+                else_block,
+                ..
+            } => {
+                self.lint_block(then_block, suppress_while_true);
+                if let Some(block) = else_block {
+                    self.lint_block(block, suppress_while_true);
+                }
+            }
+            Stmt::WhileLet { body, .. } => {
+                self.lint_block(body, suppress_while_true);
+            }
+            Stmt::ForRange { body, .. } | Stmt::ForEach { body, .. } => {
                 self.lint_block(body, suppress_while_true);
             }
-            Stmt::Loop { body, .. } => {
+            Stmt::Loop { body, .. } | Stmt::Block(body, _) => {
                 self.lint_block(body, suppress_while_true);
             }
+            // A nested function's own `@allow` covers its body, on top of whatever
+            // the enclosing function allows.
+            Stmt::Func(func) => {
+                let suppress_while_true = suppress_while_true
+                    || attr_allows(&func.attributes, WarningCode::PreferLoopOverWhileTrue);
+                self.lint_block(&func.body, suppress_while_true);
+                self.drop_allowed_warnings(&func.attributes, func.span);
+            }
             _ => {}
         }
     }
@@ -0,0 +1,88 @@
+//! Opt-in `redundant-parens` hints: parentheses that change nothing.
+//!
+//! Two kinds are flagged. Parentheses around an expression that already binds
+//! tighter than any operator (a name, literal, path, call, field or index
+//! access, or another parenthesized expression) never matter, wherever they sit:
+//! `(a) + b`. Parentheses around a whole returned, initialized, or assigned value
+//! never matter either, since no operator outside them competes for the operands:
+//! `return (a + b)`. Everything else is left alone; in `(a + b) * c` the
+//! parentheses are what make the sum happen first. A parenthesized callee is
+//! never flagged either: `(s.f)(1)` calls the function in field `f`, while
+//! `s.f(1)` is a method call. The check is syntactic, so it never flags a needed
+//! pair, though it does not find every removable one. Each hint carries a fix
+//! that removes the pair ([`crate::Warning::to_diagnostic`]).
+
+use ast_types::{Expr, Stmt};
+use shared_types::Span;
+
+use super::TypeChecker;
+use crate::warnings::{Warning, WarningCode};
+
+impl TypeChecker {
+    /// Hint at the parentheses of `Expr::Paren(inner, span)` when `inner` binds
+    /// tighter than any operator. Called from the `Expr::Paren` arm of `check_expr`.
+    pub(crate) fn lint_paren(&mut self, inner: &Expr, span: Span) {
+        if self.redundant_parens && binds_tightly(inner) {
+            self.redundant_parens_hint(inner, span);
+        }
+    }
+
+    /// Hint at parentheses enclosing the whole value `stmt` returns, binds, or
+    /// assigns.
+    pub(crate) fn lint_statement_value_parens(&mut self, stmt: &Stmt) {
+        if !self.redundant_parens {
+            return;
+        }
+        let value = match stmt {
+            Stmt::VarDecl { init, .. } => init.as_ref(),
+            Stmt::Return { value, .. } => value.as_ref(),
+            Stmt::Assignment { value, .. }
+            | Stmt::FieldAssignment { value, .. }
+            | Stmt::IndexAssignment { value, .. }
+            | Stmt::DerefAssignment { value, .. } => Some(value),
+            _ => None,
+        };
+        if let Some(Expr::Paren(inner, span)) = value {
+            self.redundant_parens_hint(inner, *span);
+        }
+    }
+
+    fn redundant_parens_hint(&mut self, inner: &Expr, span: Span) {
+        self.record_warning(Warning {
+            code: WarningCode::RedundantParens,
+            message: format!(
+                "unnecessary parentheses around `{inner}`; remove them, or silence with \
+                 `@allow(redundant_parens)` on the enclosing function"
+            ),
+            span,
+        });
+    }
+}
+
+/// Whether `expr` is a single operand that no operator can split: removing
+/// parentheses around it never changes how the program parses.
+fn binds_tightly(expr: &Expr) -> bool {
+    matches!(
+        expr,
+        Expr::Identifier(_)
+            | Expr::Literal(..)
+            | Expr::Path { .. }
+            | Expr::Call { .. }
+            | Expr::FieldAccess { .. }
+            | Expr::TupleIndex { .. }
+            | Expr::Index { .. }
+            | Expr::Paren(..)
+            | Expr::ArrayLiteral { .. }
+            | Expr::TupleLiteral { .. }
+    )
+}
+
+/// `callee` with one outer pair of parentheses removed. The call arm of
+/// `check_expr` checks a parenthesized callee through this, so the pair, which
+/// keeps `(s.f)(1)` from parsing as a method call, is never linted.
+pub(super) fn ungrouped_callee(callee: &Expr) -> &Expr {
+    match callee {
+        Expr::Paren(inner, _) => inner,
+        other => other,
+    }
+}
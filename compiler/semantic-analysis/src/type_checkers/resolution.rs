@@ -220,8 +220,9 @@ impl TypeChecker {
     }
 
     /// Resolve an array length annotation to a semantic [`ArrayLen`]. A
-    /// literal becomes `Fixed`; a name is accepted only when it is an in-scope const
-    /// generic parameter (becoming `Param`), else it is an unknown length.
+    /// literal becomes `Fixed`; a name is an in-scope const generic parameter
+    /// (becoming `Param`) or a module-level constant that folded to a non-negative
+    /// integer (becoming `Fixed`), else it is an unknown length.
     fn resolve_array_size(
         &mut self,
         size: &ArraySize,
@@ -232,6 +233,8 @@ impl TypeChecker {
             ArraySize::Const(ident) => {
                 if self.const_scope.contains_key(&ident.name) {
                     Some(ArrayLen::Param(ident.name.clone()))
+                } else if let Some(len) = self.module_const_values.array_length(&ident.name) {
+                    Some(ArrayLen::Fixed(len as usize))
                 } else {
                     self.record_error(TypeError::UnknownArrayLength {
                         name: ident.name.clone(),
@@ -1,8 +1,11 @@
+use super::divergence::Divergence;
+use super::expressions::unify_arm_types;
 use super::{LoopContext, TypeChecker};
+use crate::const_eval::const_eval;
 use crate::errors::TypeError;
 use crate::types::Type;
-use ast_types::{Expr, Stmt};
-use shared_types::Identifier;
+use ast_types::{BinaryOp, Expr, Spanned, Stmt};
+use shared_types::{Identifier, Literal, Span};
 
 /// If `expr` is a direct borrow of a named place (`&x` / `&mut x`, possibly
 /// parenthesised), return that place's name and whether the borrow is exclusive.
@@ -41,6 +44,27 @@ fn tail_expr(stmts: &[Stmt]) -> Option<&Expr> {
     }
 }
 
+/// The span of the unsuffixed numeric literal whose default type an initializer
+/// takes, looking through parentheses and unary operators. A shift takes the type
+/// of its left operand alone; any other operator is defaulted only when both
+/// operands are, since a typed operand (`1 + y`) decides the type instead.
+fn defaulted_literal(expr: &Expr) -> Option<Span> {
+    match expr {
+        Expr::Literal(Literal::Integer(_, None) | Literal::Float(_, None), span) => Some(*span),
+        Expr::Paren(inner, _) | Expr::Unary { operand: inner, .. } => defaulted_literal(inner),
+        Expr::Binary {
+            left,
+            op: BinaryOp::Shl,
+            ..
+        } => defaulted_literal(left),
+        Expr::Binary { left, right, .. } => {
+            let span = defaulted_literal(left)?;
+            defaulted_literal(right).map(|_| span)
+        }
+        _ => None,
+    }
+}
+
 /// The base place identifier a borrow points into, peeling parentheses, field
 /// access, and dereference (`&self.field` roots at `self`, `&(x)` at `x`). A
 /// non-place operand (literal, call) has no root and yields `None`.
@@ -241,11 +265,82 @@ impl TypeChecker {
     /// Persistent borrows held by reference bindings are untouched; they are
     /// released when their binding leaves scope.
     pub(crate) fn check_stmt(&mut self, stmt: &Stmt) -> Option<()> {
+        self.lint_statement_value_parens(stmt);
         let result = self.check_stmt_inner(stmt);
         self.symbols.clear_transient_borrows();
         result
     }
 
+    /// Check the statements of one `if` arm in their own scope.
+    fn check_if_arm(&mut self, stmts: &[Stmt]) {
+        self.symbols.push_scope();
+        for stmt in stmts {
+            let _ = self.check_stmt(stmt);
+        }
+        self.symbols.pop_scope();
+    }
+
+    /// Check one arm of a trailing `if` that is the function's return value and
+    /// return the type of its tail. The tail expression is checked against the return
+    /// type; a nested trailing `if` checks its own arms the same way and reports any
+    /// disagreement itself, so it yields `Unknown` here. An arm that neither ends in a
+    /// value nor diverges is missing its return value: that is reported at the arm's
+    /// last statement (or at `if_span` for an empty arm), and the arm yields `Unknown`
+    /// so the arms are not also reported as disagreeing.
+    fn check_returned_arm(&mut self, stmts: &[Stmt], ret: &Type, if_span: Span) -> Type {
+        self.symbols.push_scope();
+        let mut result = None;
+        for (i, stmt) in stmts.iter().enumerate() {
+            let is_tail = i + 1 == stmts.len();
+            match stmt {
+                Stmt::Expr(expr) if is_tail => {
+                    result = Some(self.check_expr(expr, Some(ret)).unwrap_or(Type::Unknown));
+                }
+                Stmt::If {
+                    span,
+                    else_block: Some(_),
+                    ..
+                } if is_tail => {
+                    let outer_tail = self.returned_tail.replace(*span);
+                    let _ = self.check_stmt(stmt);
+                    self.returned_tail = outer_tail;
+                    result = Some(Type::Unknown);
+                }
+                _ => {
+                    let _ = self.check_stmt(stmt);
+                }
+            }
+        }
+        self.symbols.pop_scope();
+        if Divergence::new(&self.diverging_functions).block(stmts) {
+            return Type::Never;
+        }
+        result.unwrap_or_else(|| {
+            self.record_error(TypeError::MissingReturn {
+                expected: ret.clone(),
+                span: stmts.last().map_or(if_span, Spanned::span),
+            });
+            Type::Unknown
+        })
+    }
+
+    /// Pedantic mode: a call statement must not drop a value. Function, method
+    /// (`c.get()`), and associated (`T::make()`) calls all count, parenthesized or
+    /// not. A void result has nothing to drop, and a diverging or failed call has no
+    /// result at all.
+    fn check_discarded_result(&mut self, expr: &Expr, ty: Option<Type>) {
+        let mut call = expr;
+        while let Expr::Paren(inner, _) = call {
+            call = inner;
+        }
+        let Expr::Call { span, .. } = call else {
+            return;
+        };
+        if let Some(ty) = ty.filter(|ty| !matches!(ty, Type::Void | Type::Never | Type::Unknown)) {
+            self.record_error(TypeError::PedanticUnusedResult { ty, span: *span });
+        }
+    }
+
     /// Check a statement.
     /// Returns None if there was a fatal error, Some(()) otherwise.
     /// Non-fatal errors are recorded and checking continues.
@@ -265,13 +360,24 @@ impl TypeChecker {
                     None
                 };
 
-                // Pass any declared type as the expected hint for inference.
+                // Pass any declared type as the expected hint for inference. The
+                // binding enters scope only after its initializer, so a use of `name`
+                // inside it resolves to an outer binding if there is one.
                 let init_ty = if let Some(init_expr) = init {
-                    self.check_expr(init_expr, declared_ty.as_ref())
+                    self.initializing.push(name.name.clone());
+                    let init_ty = self.check_expr(init_expr, declared_ty.as_ref());
+                    self.initializing.pop();
+                    match init_ty {
+                        Some(ty) if self.reject_void_value(&ty, init_expr) => Some(Type::Unknown),
+                        other => other,
+                    }
                 } else {
                     None
                 };
 
+                // An unsuffixed literal that decides the binding's type takes the
+                // default `i32`/`f64`; pedantic mode wants that spelled out.
+                let defaulted = init.as_ref().and_then(defaulted_literal);
                 let final_ty = match (declared_ty, init_ty) {
                     (Some(decl), Some(init)) => {
                         // Both declared and initialized: types must match
@@ -291,6 +397,15 @@ impl TypeChecker {
                     }
                     (None, Some(init)) => {
                         // Only initialized: infer from initializer (Phase 1: simple inference)
+                        if self.pedantic && init.is_numeric() {
+                            if let Some(literal_span) = defaulted {
+                                self.record_error(TypeError::PedanticDefaultLiteralType {
+                                    name: name.name.clone(),
+                                    ty: init.clone(),
+                                    span: literal_span,
+                                });
+                            }
+                        }
                         init
                     }
                     (None, None) => {
@@ -385,10 +500,7 @@ impl TypeChecker {
 
                     Some(())
                 } else {
-                    self.record_error(TypeError::UndefinedVariable {
-                        name: target.name.clone(),
-                        span: target.span,
-                    });
+                    self.record_error(self.undefined_variable(&target.name, target.span));
                     None
                 }
             }
@@ -435,8 +547,16 @@ impl TypeChecker {
                 then_block,
                 else_if_blocks,
                 else_block,
-                span: _,
+                span,
             } => {
+                // A trailing `if`/`else` of a non-void function is its implicit return
+                // value, so every arm's tail must agree and match the return type.
+                let returned = match (&self.current_function_return_type, else_block) {
+                    (Some(ret), Some(_)) if Some(*span) == self.returned_tail => Some(ret.clone()),
+                    _ => None,
+                };
+                let mut arm_types = Vec::new();
+
                 // Check condition is boolean - no type inference needed (must be bool)
                 if let Some(cond_ty) = self.check_expr(condition, Some(&Type::Bool)) {
                     if !cond_ty.is_bool() {
@@ -453,11 +573,10 @@ impl TypeChecker {
                 // so only unconditional (straight-line) moves persist.
                 let move_snapshot = self.symbols.snapshot_moves();
 
-                self.symbols.push_scope();
-                for stmt in then_block {
-                    let _ = self.check_stmt(stmt);
+                match &returned {
+                    Some(ret) => arm_types.push(self.check_returned_arm(then_block, ret, *span)),
+                    None => self.check_if_arm(then_block),
                 }
-                self.symbols.pop_scope();
                 self.symbols.restore_moves(&move_snapshot);
 
                 for (else_if_cond, else_if_stmts) in else_if_blocks {
@@ -471,14 +590,74 @@ impl TypeChecker {
                         }
                     }
 
-                    self.symbols.push_scope();
-                    for stmt in else_if_stmts {
-                        let _ = self.check_stmt(stmt);
+                    match &returned {
+                        Some(ret) => {
+                            arm_types.push(self.check_returned_arm(else_if_stmts, ret, *span))
+                        }
+                        None => self.check_if_arm(else_if_stmts),
                     }
-                    self.symbols.pop_scope();
                     self.symbols.restore_moves(&move_snapshot);
                 }
 
+                if let Some(else_stmts) = else_block {
+                    match &returned {
+                        Some(ret) => {
+                            arm_types.push(self.check_returned_arm(else_stmts, ret, *span))
+                        }
+                        None => self.check_if_arm(else_stmts),
+                    }
+                    self.symbols.restore_moves(&move_snapshot);
+                }
+
+                if let Some(ret) = returned {
+                    match unify_arm_types(&arm_types) {
+                        Ok(found) => {
+                            if !matches!(found, Type::Unknown | Type::Never)
+                                && !self.assignable(&found, &ret)
+                            {
+                                self.record_error(TypeError::ReturnTypeMismatch {
+                                    expected: ret,
+                                    found,
+                                    span: *span,
+                                });
+                            }
+                        }
+                        Err((expected, found)) => {
+                            self.record_error(TypeError::IfArmTypeMismatch {
+                                expected,
+                                found,
+                                span: *span,
+                            });
+                        }
+                    }
+                }
+
+                Some(())
+            }
+
+            // Sugar over a two-arm match: the pattern's bindings are visible in
+            // the then-block only, and each branch restores the move state like
+            // `if`.
+            Stmt::IfLet {
+                pattern,
+                scrutinee,
+                then_block,
+                else_block,
+                span: _,
+            } => {
+                let bindings = self.check_let_condition(pattern, scrutinee);
+                let move_snapshot = self.symbols.snapshot_moves();
+
+                self.symbols.push_scope();
+                for (name, ty, _span) in bindings {
+                    let _ = self.symbols.define(name, ty, false);
+                }
+                for stmt in then_block {
+                    let _ = self.check_stmt(stmt);
+                }
+                self.symbols.pop_scope();
+                self.symbols.restore_moves(&move_snapshot);
+
                 if let Some(else_stmts) = else_block {
                     self.symbols.push_scope();
                     for stmt in else_stmts {
@@ -513,6 +692,27 @@ impl TypeChecker {
                 Some(())
             }
 
+            // The scrutinee is re-evaluated each iteration; the pattern's
+            // bindings live in a scope wrapping the loop body.
+            Stmt::WhileLet {
+                label,
+                pattern,
+                scrutinee,
+                body,
+                span: _,
+            } => {
+                let bindings = self.check_let_condition(pattern, scrutinee);
+
+                self.symbols.push_scope();
+                for (name, ty, _span) in bindings {
+                    let _ = self.symbols.define(name, ty, false);
+                }
+                let _ = self.check_loop_body(label.as_ref(), false, body);
+                self.symbols.pop_scope();
+
+                Some(())
+            }
+
             Stmt::Loop {
                 label,
                 body,
@@ -662,10 +862,7 @@ impl TypeChecker {
                 let symbol = if let Some(s) = self.symbols.lookup(&target.name) {
                     s.clone()
                 } else {
-                    self.record_error(TypeError::UndefinedVariable {
-                        name: target.name.clone(),
-                        span: target.span,
-                    });
+                    self.record_error(self.undefined_variable(&target.name, target.span));
                     return None;
                 };
 
@@ -736,10 +933,7 @@ impl TypeChecker {
                 let symbol = if let Some(s) = self.symbols.lookup(&object.name) {
                     s.clone()
                 } else {
-                    self.record_error(TypeError::UndefinedVariable {
-                        name: object.name.clone(),
-                        span: object.span,
-                    });
+                    self.record_error(self.undefined_variable(&object.name, object.span));
                     return None;
                 };
 
@@ -850,6 +1044,18 @@ impl TypeChecker {
                 Some(())
             }
 
+            Stmt::Func(func) => self.check_nested_function(func),
+
+            // A bare block scopes its bindings; its statements check as usual.
+            Stmt::Block(stmts, _) => {
+                self.symbols.push_scope();
+                for stmt in stmts {
+                    let _ = self.check_stmt(stmt);
+                }
+                self.symbols.pop_scope();
+                Some(())
+            }
+
             Stmt::Const {
                 name,
                 ty,
@@ -866,9 +1072,12 @@ impl TypeChecker {
 
                 let declared_ty = self.resolve_type(ty)?;
 
-                if !self.is_const_expr(value) {
-                    self.record_error(TypeError::InvalidConstExpr { span: value.span() });
-                    return None;
+                let folded = const_eval(value, &self.const_values);
+                if let Err(error) = &folded {
+                    if let Some(error) = self.const_eval_error(name, error.clone()) {
+                        self.record_error(error);
+                        return None;
+                    }
                 }
 
                 if let Some(expr_ty) = self.check_expr(value, Some(&declared_ty)) {
@@ -883,12 +1092,18 @@ impl TypeChecker {
                 }
 
                 self.constants.insert(name.name.clone(), declared_ty);
+                if let Ok(folded) = folded {
+                    self.const_values.define(name.name.clone(), folded);
+                }
                 Some(())
             }
 
             Stmt::Expr(expr) => {
                 // Expression statements have no expected type context
-                let _ = self.check_expr(expr, None);
+                let ty = self.check_expr(expr, None);
+                if self.pedantic && Some(expr.span()) != self.returned_tail {
+                    self.check_discarded_result(expr, ty);
+                }
                 Some(())
             }
         }
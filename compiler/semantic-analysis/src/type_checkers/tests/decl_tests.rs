@@ -103,3 +103,66 @@ fn a_user_method_cannot_forge_a_generic_instance_symbol() {
 
     assert_eq!(reserved_names(&checker.into_errors()), vec!["g_i32__push"]);
 }
+
+#[test]
+fn repeated_struct_field_is_reported_at_the_second_declaration() {
+    let mut checker = TypeChecker::new();
+    let mut item = struct_item("P", &[("x", "i32"), ("x", "f64")]);
+    if let Item::Struct(def) = &mut item {
+        def.fields[1].name.span = Span::new(20, 21);
+    }
+
+    let _ = checker.check_program(&[item]);
+
+    let errors = checker.into_errors();
+    assert!(
+        errors.iter().any(|e| matches!(
+            e,
+            TypeError::DuplicateField { name, span } if name == "x" && *span == Span::new(20, 21)
+        )),
+        "expected DuplicateField for 'x', got {errors:?}"
+    );
+}
+
+#[test]
+fn struct_field_of_unknown_type_is_rejected() {
+    let mut checker = TypeChecker::new();
+    let items = vec![struct_item("P", &[("x", "Missing")])];
+
+    let _ = checker.check_program(&items);
+
+    let errors = checker.into_errors();
+    assert!(
+        errors
+            .iter()
+            .any(|e| matches!(e, TypeError::UnknownTypeName { name, .. } if name == "Missing")),
+        "expected UnknownTypeName for 'Missing', got {errors:?}"
+    );
+}
+
+#[test]
+fn unsupported_tensor_field_type_reports_its_annotation_span() {
+    // Tensor types are not supported yet; the rejection must still point at the
+    // annotation rather than at the start of the file.
+    let mut checker = TypeChecker::new();
+    let mut item = struct_item("Layer", &[("weights", "i32")]);
+    if let Item::Struct(def) = &mut item {
+        def.fields[0].ty = ast_types::Type::Tensor {
+            element_type: Box::new(make_type("f32")),
+            shape: vec![2, 3],
+            span: Span::new(24, 43),
+        };
+    }
+
+    let _ = checker.check_program(&[item]);
+
+    let errors = checker.into_errors();
+    assert!(
+        errors.iter().any(|e| matches!(
+            e,
+            TypeError::UnknownTypeName { name, span }
+                if name == "Tensor" && *span == Span::new(24, 43)
+        )),
+        "expected the tensor error at its annotation, got {errors:?}"
+    );
+}
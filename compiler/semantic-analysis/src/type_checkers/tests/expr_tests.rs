@@ -566,7 +566,8 @@ fn unsafe_block_yields_trailing_expr_type_without_errors() {
     );
 }
 
-/// `panic("boom")` is a builtin returning unit; a correct call type-checks cleanly.
+/// `panic("boom")` is a diverging builtin of type `never`; a correct call type-checks
+/// cleanly.
 #[test]
 fn panic_builtin_accepts_string_argument() {
     let mut checker = TypeChecker::new();
@@ -582,7 +583,7 @@ fn panic_builtin_accepts_string_argument() {
     };
 
     let ty = checker.check_expr(&call, None);
-    assert_eq!(ty, Some(Type::Unknown));
+    assert_eq!(ty, Some(Type::Never));
     assert!(!checker.has_errors(), "got: {:?}", checker.into_errors());
 }
 
@@ -923,6 +924,45 @@ func main() -> i32 { f(0) }
     );
 }
 
+#[test]
+fn ranges_covering_every_u8_value_are_exhaustive() {
+    let errors = semantic_errors(
+        r#"
+func f(n: u8) -> i32 {
+    match n {
+        0..=127 => 1,
+        128..255 => 2,
+        255 => 3
+    }
+}
+func main() -> i32 { f(0u8) }
+"#,
+    );
+    assert!(errors.is_empty(), "ranges cover all of u8; got {errors:?}");
+}
+
+#[test]
+fn integer_ranges_with_a_gap_name_the_first_unhandled_values() {
+    let errors = semantic_errors(
+        r#"
+func f(n: u8) -> i32 {
+    match n {
+        0..=9 => 1,
+        20..=255 => 2
+    }
+}
+func main() -> i32 { f(0u8) }
+"#,
+    );
+    assert!(
+        errors.iter().any(|e| matches!(
+            e,
+            TypeError::NonExhaustiveMatch { reason, .. } if reason.contains("10..=19")
+        )),
+        "the gap 10..=19 must be reported; got {errors:?}"
+    );
+}
+
 #[test]
 fn match_arm_type_mismatch_is_rejected() {
     let errors = semantic_errors(
@@ -984,3 +1024,92 @@ func main() -> i32 { f("x") }
         "matching on a string must be rejected in phase 1E; got {errors:?}"
     );
 }
+
+#[test]
+fn if_let_binds_payload_in_then_block() {
+    // `r` is bound (as the variant's `i32` payload) only inside the then-block.
+    let errors = semantic_errors(
+        r#"
+enum Shape { Circle(i32), Unit }
+func radius(s: Shape) -> i32 {
+    mut out: i32 = 0
+    if let Shape::Circle(r) = s {
+        out = r * 2
+    } else {
+        out = -1
+    }
+    return out
+}
+func main() -> i32 { radius(Shape::Unit) }
+"#,
+    );
+    assert!(errors.is_empty(), "valid if-let program; got {errors:?}");
+}
+
+#[test]
+fn if_let_binding_does_not_leak_past_then_block() {
+    let errors = semantic_errors(
+        r#"
+enum Shape { Circle(i32), Unit }
+func radius(s: Shape) -> i32 {
+    if let Shape::Circle(r) = s {
+        return r
+    }
+    return r
+}
+func main() -> i32 { radius(Shape::Unit) }
+"#,
+    );
+    assert!(
+        errors
+            .iter()
+            .any(|e| matches!(e, TypeError::UndefinedVariable { .. })),
+        "`r` must be out of scope after the then-block; got {errors:?}"
+    );
+}
+
+#[test]
+fn if_let_pattern_of_other_enum_is_rejected() {
+    let errors = semantic_errors(
+        r#"
+enum Shape { Circle(i32), Unit }
+enum Color { Red, Green }
+func f(c: Color) -> i32 {
+    if let Shape::Circle(r) = c {
+        return r
+    }
+    return 0
+}
+func main() -> i32 { f(Color::Red) }
+"#,
+    );
+    assert!(
+        errors
+            .iter()
+            .any(|e| matches!(e, TypeError::PatternTypeMismatch { .. })),
+        "a `Shape` pattern against a `Color` scrutinee must be rejected; got {errors:?}"
+    );
+}
+
+#[test]
+fn while_let_binds_payload_in_body() {
+    let errors = semantic_errors(
+        r#"
+enum Step { Next(i32), Done }
+func next(n: i32) -> Step {
+    if n < 3 {
+        return Step::Next(n + 1)
+    }
+    return Step::Done
+}
+func main() -> i32 {
+    mut last: i32 = 0
+    while let Step::Next(n) = next(last) {
+        last = n
+    }
+    return last
+}
+"#,
+    );
+    assert!(errors.is_empty(), "valid while-let program; got {errors:?}");
+}
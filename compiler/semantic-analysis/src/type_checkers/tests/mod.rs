@@ -843,6 +843,47 @@ func main() -> i32 { 0 }
     );
 }
 
+#[test]
+fn optimize_attribute_accepts_levels_zero_to_three() {
+    let errors = semantic_errors(
+        r#"
+@optimize(3)
+func hot() -> i32 { 1 }
+
+@optimize(0)
+func main() -> i32 { hot() }
+"#,
+    );
+    assert!(errors.is_empty(), "got {errors:?}");
+}
+
+#[test]
+fn malformed_optimize_attribute_is_rejected() {
+    let errors = semantic_errors(
+        r#"
+@optimize(4)
+func too_high() -> i32 { 1 }
+
+@optimize(fast)
+func not_a_level() -> i32 { 1 }
+
+@optimize
+func no_level() -> i32 { 1 }
+
+@optimize(1)
+@optimize(2)
+func twice() -> i32 { 1 }
+
+func main() -> i32 { 0 }
+"#,
+    );
+    let invalid = errors
+        .iter()
+        .filter(|e| matches!(e, TypeError::InvalidOptimizeAttribute { .. }))
+        .count();
+    assert_eq!(invalid, 4, "got {errors:?}");
+}
+
 #[test]
 fn drop_with_ref_self_is_rejected() {
     // `Drop::drop` must take `&mut self` so the destructor can release resources.
@@ -931,6 +972,102 @@ func main() -> i32 {
     );
 }
 
+#[test]
+fn assigning_an_array_of_another_length_or_element_is_rejected() {
+    // Array-to-array compatibility needs both the element type and the length to
+    // match; either difference is a plain type mismatch at the assigned value.
+    let errors = semantic_errors(
+        r#"
+func main() -> i32 {
+    mut a: [i32; 3] = [1, 2, 3]
+    val shorter: [i32; 2] = [4, 5]
+    val wider: [i64; 3] = [4, 5, 6]
+    a = shorter
+    a = wider
+    return a[0]
+}
+"#,
+    );
+    let mismatches: Vec<_> = errors
+        .iter()
+        .filter(|e| matches!(e, TypeError::Mismatch { .. }))
+        .map(|e| e.to_string())
+        .collect();
+    assert_eq!(mismatches.len(), 2, "got {errors:?}");
+    assert!(mismatches[0].contains("expected [i32; 3], found [i32; 2]"));
+    assert!(mismatches[1].contains("expected [i32; 3], found [i64; 3]"));
+}
+
+#[test]
+fn half_precision_arithmetic_and_casts_type_check() {
+    let errors = semantic_errors(
+        r#"
+func main() -> i32 {
+    val a: f16 = 1.5f16
+    val b: f16 = -a * 2.0f16 + a / a - a % a
+    val wide: f32 = b as f32
+    val g: bf16 = 4.0bf16
+    val h: bf16 = (g - 1.0bf16) as f64 as bf16
+    return (wide + h as f32) as i32
+}
+"#,
+    );
+    assert!(errors.is_empty(), "got {errors:?}");
+}
+
+#[test]
+fn half_precision_arithmetic_needs_matching_operands() {
+    // Halves never widen implicitly: mixing `f16` with `bf16` or `f32` is a mismatch.
+    let errors = semantic_errors(
+        r#"
+func main() -> i32 {
+    val a: f16 = 1.5f16
+    val b: bf16 = 1.5bf16
+    val x = a + b
+    val y = a * 2.0f32
+    return 0
+}
+"#,
+    );
+    let mismatches: Vec<_> = errors
+        .iter()
+        .filter(|e| matches!(e, TypeError::Mismatch { .. }))
+        .map(|e| e.to_string())
+        .collect();
+    assert_eq!(mismatches.len(), 2, "got {errors:?}");
+    assert!(mismatches[0].contains("expected f16, found bf16"));
+    assert!(mismatches[1].contains("expected f16, found f32"));
+}
+
+#[test]
+fn compound_assignment_checks_as_plain_assignment() {
+    // `x OP= e` reaches the checker as `x = x OP e`: the target must be `mut`
+    // and the operands must agree, exactly as for the spelled-out form.
+    let errors = semantic_errors(
+        r#"
+func main() -> i32 {
+    mut total: i32 = 0
+    total += 2
+    total *= 3
+    val fixed: i32 = 1
+    fixed -= 1
+    mut ratio: f64 = 1.0
+    ratio /= true
+    return total
+}
+"#,
+    );
+    assert_eq!(errors.len(), 2, "got {errors:?}");
+    assert!(
+        matches!(&errors[0], TypeError::AssignToImmutable { name, .. } if name == "fixed"),
+        "got {errors:?}"
+    );
+    assert!(
+        matches!(errors[1], TypeError::Mismatch { .. }),
+        "got {errors:?}"
+    );
+}
+
 #[test]
 fn non_integer_array_index_is_rejected() {
     let errors = semantic_errors(
@@ -1643,3 +1780,471 @@ func main() -> i32 {
         "using `+` on a struct without an Add impl must be rejected; got {errors:?}"
     );
 }
+
+#[test]
+fn self_referential_initializer_is_use_before_init() {
+    let errors = semantic_errors(
+        r#"
+func main() -> i32 {
+    val x = x + 1
+    0
+}
+"#,
+    );
+    assert!(
+        errors
+            .iter()
+            .any(|e| matches!(e, TypeError::UseBeforeInit { name, .. } if name == "x")),
+        "a binding used in its own initializer must be reported; got {errors:?}"
+    );
+    assert!(
+        !errors
+            .iter()
+            .any(|e| matches!(e, TypeError::UndefinedVariable { .. })),
+        "the self-reference must not also be reported as undefined; got {errors:?}"
+    );
+}
+
+#[test]
+fn initializer_may_read_the_outer_binding_it_shadows() {
+    let errors = semantic_errors(
+        r#"
+func main() -> i32 {
+    val x = 1
+    {
+        val x = x + 1
+        x
+    }
+}
+"#,
+    );
+    assert!(errors.is_empty(), "got {errors:?}");
+}
+
+#[test]
+fn by_value_self_trait_impl_type_checks() {
+    let errors = semantic_errors(
+        r#"
+trait Printable { func show(self) -> string }
+@derive(Copy, Clone)
+struct Point { x: i32 }
+impl Printable for Point {
+    func show(self) -> string { "point" }
+}
+func main() -> i32 {
+    val p = Point { x: 1 }
+    val s = p.show()
+    0
+}
+"#,
+    );
+    assert!(
+        errors.is_empty(),
+        "an impl providing every required method must type-check; got {errors:?}"
+    );
+}
+
+#[test]
+fn by_value_self_trait_impl_missing_method_is_rejected() {
+    let errors = semantic_errors(
+        r#"
+trait Printable {
+    func show(self) -> string
+    func width(self) -> i32
+}
+@derive(Copy, Clone)
+struct Point { x: i32 }
+impl Printable for Point {
+    func show(self) -> string { "point" }
+}
+func main() -> i32 { 0 }
+"#,
+    );
+    assert!(
+        errors.iter().any(
+            |e| matches!(e, TypeError::MissingTraitMethod { method, .. } if method == "width")
+        ),
+        "the impl omits `width`; got {errors:?}"
+    );
+}
+
+#[test]
+fn identical_errors_are_reported_once() {
+    // A block's tail expression is reached by more than one checking pass; its
+    // error must still appear once.
+    let errors = semantic_errors(
+        r#"
+func main() -> i32 {
+    missing
+}
+"#,
+    );
+    let undefined = errors
+        .iter()
+        .filter(|e| matches!(e, TypeError::UndefinedVariable { .. }))
+        .count();
+    assert_eq!(undefined, 1, "got {errors:?}");
+}
+
+#[test]
+fn same_error_at_different_spans_is_kept() {
+    let errors = semantic_errors(
+        r#"
+func main() -> i32 {
+    val a = missing
+    missing
+}
+"#,
+    );
+    let undefined = errors
+        .iter()
+        .filter(|e| matches!(e, TypeError::UndefinedVariable { .. }))
+        .count();
+    assert_eq!(undefined, 2, "got {errors:?}");
+}
+
+#[test]
+fn empty_body_with_return_type_is_missing_return() {
+    let errors = semantic_errors(
+        r#"
+struct S { x: i32 }
+impl S { func get(&self) -> i32 { } }
+func f() -> i32 {}
+func main() -> i32 { 0 }
+"#,
+    );
+    let missing = errors
+        .iter()
+        .filter(|e| {
+            matches!(
+                e,
+                TypeError::MissingReturn {
+                    expected: Type::I32,
+                    ..
+                }
+            )
+        })
+        .count();
+    assert_eq!(
+        missing, 2,
+        "the function and the method must be flagged; got {errors:?}"
+    );
+}
+
+#[test]
+fn empty_body_without_return_type_is_accepted() {
+    let errors = semantic_errors(
+        r#"
+struct S { x: i32 }
+impl S { func touch(&self) { } }
+func f() {}
+func main() -> i32 { 0 }
+"#,
+    );
+    assert!(errors.is_empty(), "got {errors:?}");
+}
+
+#[test]
+fn closure_capturing_outer_val_type_checks() {
+    let errors = semantic_errors(
+        r#"
+func main() -> i32 {
+    val base = 41
+    val add = |x: i32| x + base
+    add(1)
+}
+"#,
+    );
+    assert!(errors.is_empty(), "got {errors:?}");
+}
+
+#[test]
+fn closure_without_captures_type_checks() {
+    let errors = semantic_errors(
+        r#"
+func main() -> i32 {
+    val inc = |x: i32| x + 1
+    inc(41)
+}
+"#,
+    );
+    assert!(errors.is_empty(), "got {errors:?}");
+}
+
+#[test]
+fn nested_function_is_callable_in_its_block() {
+    let errors = semantic_errors(
+        r#"
+func main() -> i32 {
+    func fact(n: i32) -> i32 {
+        if n <= 1 {
+            return 1
+        }
+        n * fact(n - 1)
+    }
+    fact(5) - 78
+}
+"#,
+    );
+    assert!(errors.is_empty(), "got {errors:?}");
+}
+
+#[test]
+fn nested_function_cannot_capture_enclosing_local() {
+    let errors = semantic_errors(
+        r#"
+func main() -> i32 {
+    val base = 41
+    func add(x: i32) -> i32 { x + base }
+    add(1)
+}
+"#,
+    );
+    assert!(
+        errors
+            .iter()
+            .any(|e| matches!(e, TypeError::NestedFunctionCapture { name, .. } if name == "base")),
+        "got {errors:?}"
+    );
+}
+
+#[test]
+fn nested_function_may_call_a_sibling() {
+    // A nested function captures nothing, so calling or naming one from another
+    // nested function captures nothing either.
+    let errors = semantic_errors(
+        r#"
+func main() -> i32 {
+    func g() -> i32 { 1 }
+    func h() -> i32 {
+        val f = g
+        g() + f()
+    }
+    h()
+}
+"#,
+    );
+    assert!(errors.is_empty(), "got {errors:?}");
+}
+
+#[test]
+fn nested_function_calling_an_enclosing_closure_is_a_capture() {
+    let errors = semantic_errors(
+        r#"
+func main() -> i32 {
+    val g = |x: i32| x + 1
+    func h() -> i32 { g(1) }
+    h()
+}
+"#,
+    );
+    assert!(
+        errors
+            .iter()
+            .any(|e| matches!(e, TypeError::NestedFunctionCapture { name, .. } if name == "g")),
+        "got {errors:?}"
+    );
+    assert!(
+        !errors
+            .iter()
+            .any(|e| matches!(e, TypeError::UndefinedFunction { .. })),
+        "got {errors:?}"
+    );
+}
+
+#[test]
+fn nested_function_rejects_cfg_and_optimize() {
+    let errors = semantic_errors(
+        r#"
+func main() -> i32 {
+    @cfg(debug)
+    func g() -> i32 { 1 }
+    @optimize(3)
+    func h() -> i32 { 2 }
+    g() + h()
+}
+"#,
+    );
+    let rejected: Vec<&str> = errors
+        .iter()
+        .filter_map(|e| match e {
+            TypeError::UnsupportedNestedAttribute { attribute, .. } => Some(attribute.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(rejected, ["cfg", "optimize"], "got {errors:?}");
+}
+
+#[test]
+fn nested_function_is_scoped_to_its_block() {
+    let errors = semantic_errors(
+        r#"
+func main() -> i32 {
+    {
+        func one() -> i32 { 1 }
+    }
+    one()
+}
+"#,
+    );
+    assert!(
+        !errors.is_empty(),
+        "a nested function must not escape its block"
+    );
+}
+
+#[test]
+fn function_stored_in_a_variable_is_callable() {
+    let errors = semantic_errors(
+        r#"
+func add(a: i32, b: i32) -> i32 { a + b }
+
+func main() -> i32 {
+    val f = add
+    f(1, 2)
+}
+"#,
+    );
+    assert!(errors.is_empty(), "got {errors:?}");
+}
+
+#[test]
+fn function_value_call_checks_arguments_against_its_type() {
+    let errors = semantic_errors(
+        r#"
+func add(a: i32, b: i32) -> i32 { a + b }
+
+func main() -> i32 {
+    val f = add
+    f(1)
+}
+"#,
+    );
+    assert!(
+        errors.iter().any(|e| matches!(
+            e,
+            TypeError::ArgumentCountMismatch {
+                expected: 2,
+                found: 1,
+                ..
+            }
+        )),
+        "got {errors:?}"
+    );
+}
+
+#[test]
+fn mangled_method_name_is_not_a_function_value() {
+    // Methods live in the function table under `Type__method`; that internal key
+    // must not resolve as a first-class value.
+    let errors = semantic_errors(
+        r#"
+struct P { x: i32 }
+
+impl P {
+    func get(&self) -> i32 { self.x }
+}
+
+func main() -> i32 {
+    val f = P__get
+    0
+}
+"#,
+    );
+    assert!(
+        errors.iter().any(|e| matches!(
+            e,
+            TypeError::UndefinedVariable { name, .. } if name == "P__get"
+        )),
+        "got {errors:?}"
+    );
+}
+
+#[test]
+fn result_of_a_call_is_callable() {
+    let errors = semantic_errors(
+        r#"
+func inc(x: i32) -> i32 { x + 1 }
+
+func pick() -> (i32) -> i32 { inc }
+
+func main() -> i32 {
+    pick()(41)
+}
+"#,
+    );
+    assert!(errors.is_empty(), "got {errors:?}");
+}
+
+#[test]
+fn calling_a_non_function_result_is_not_callable() {
+    let errors = semantic_errors(
+        r#"
+func one() -> i32 { 1 }
+
+func main() -> i32 {
+    one()(2)
+}
+"#,
+    );
+    assert!(
+        errors
+            .iter()
+            .any(|e| matches!(e, TypeError::NotCallable { ty: Type::I32, .. })),
+        "got {errors:?}"
+    );
+}
+
+#[test]
+fn nested_array_literal_infers_its_shape() {
+    let errors = semantic_errors(
+        r#"
+func main() -> i32 {
+    val m = [[1.0, 2.0], [3.0, 4.0]]
+    val shaped: [[f64; 2]; 2] = m
+    0
+}
+"#,
+    );
+    assert!(errors.is_empty(), "got {errors:?}");
+
+    let errors = semantic_errors(
+        r#"
+func main() -> i32 {
+    val m = [[1.0, 2.0], [3.0, 4.0]]
+    val shaped: [[f64; 2]; 3] = m
+    0
+}
+"#,
+    );
+    assert!(
+        errors
+            .iter()
+            .any(|e| matches!(e, TypeError::Mismatch { .. })),
+        "a 2x2 literal is not a 3x2 array, got {errors:?}"
+    );
+}
+
+#[test]
+fn ragged_nested_array_literal_is_rejected() {
+    let errors = semantic_errors(
+        r#"
+func main() -> i32 {
+    val m = [[1, 2], [3]]
+    0
+}
+"#,
+    );
+    // The second row's length is checked against the first row's.
+    assert!(
+        errors.iter().any(|e| matches!(
+            e,
+            TypeError::ArrayLengthMismatch {
+                expected: 2,
+                found: 1,
+                ..
+            }
+        )),
+        "got {errors:?}"
+    );
+}
@@ -103,6 +103,11 @@ pub enum Type {
     /// template: monomorphization substitutes each `Generic` with a concrete type, so a
     /// `Generic` never reaches the HIR.
     Generic(std::string::String),
+    /// The type of an expression that never produces a value: a `panic`, a call to a
+    /// function that never returns, or a block that always returns, breaks, or loops
+    /// forever. It is compatible with every type, so a diverging `if` or `match` arm
+    /// defers to the other arms. There is no syntax for it; it is only inferred.
+    Never,
     Unknown,
 }
 
@@ -203,6 +208,9 @@ impl Type {
                 a.len() == b.len() && a.iter().zip(b.iter()).all(|(x, y)| x.is_compatible_with(y))
             }
 
+            // A diverging expression stands in for a value of any type.
+            (Type::Never, _) | (_, Type::Never) => true,
+
             // Unknown type for error recovery
             (Type::Unknown, _) | (_, Type::Unknown) => true,
 
@@ -305,10 +313,9 @@ impl Type {
 
     /// Check if this is a full-precision floating-point type (`f32`/`f64`).
     ///
-    /// Deliberately excludes `f16`/`bf16`: half-precision has a narrow scalar
-    /// contract (no arithmetic), so it must not flow through the arithmetic and
-    /// contextual-inference paths gated on this predicate. Use
-    /// `Type::is_half_float` for the half-precision-only checks.
+    /// Excludes `f16`/`bf16`, which are not `is_numeric` either: half-precision
+    /// literals must carry their suffix, so contextual inference skips them, and
+    /// the arithmetic and negation checks ask `Type::is_half_float` explicitly.
     pub fn is_float(&self) -> bool {
         matches!(self, Type::F32 | Type::F64)
     }
@@ -354,6 +361,7 @@ impl fmt::Display for Type {
             Type::Char => write!(f, "char"),
             Type::String => write!(f, "string"),
             Type::Void => write!(f, "void"),
+            Type::Never => write!(f, "never"),
             Type::Unknown => write!(f, "<error>"),
             Type::Struct(name) => write!(f, "{}", name),
             Type::Enum(name) => write!(f, "{}", name),
@@ -655,7 +663,7 @@ mod tests {
         assert_eq!(Type::F16.to_string(), "f16");
         assert_eq!(Type::BF16.to_string(), "bf16");
 
-        // Half-precision is neither numeric (no arithmetic) nor full-precision float.
+        // Half-precision is neither `is_numeric` nor `is_float`; its checks are explicit.
         assert!(Type::F16.is_half_float());
         assert!(Type::BF16.is_half_float());
         assert!(!Type::F16.is_numeric());
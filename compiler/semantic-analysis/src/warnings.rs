@@ -1,5 +1,6 @@
 // Lint warnings emitted alongside successful type checking.
 
+use diagnostics::{Diagnostic, DiagnosticCode, Severity, Suggestion};
 use shared_types::Span;
 use std::fmt;
 
@@ -10,6 +11,23 @@ use std::fmt;
 pub enum WarningCode {
     /// `while true { ... }` should be written as `loop { ... }`.
     PreferLoopOverWhileTrue,
+    /// A `match` arm after earlier arms already cover every value.
+    UnreachableMatchArm,
+    /// A comparison whose two operands are the same identifier or literal.
+    SelfComparison,
+    /// An integer or `char` pattern matching values an earlier pattern of the
+    /// same `match` already matches.
+    OverlappingPattern,
+    /// A top-level function that no chain of calls from `main` reaches.
+    DeadCode,
+    /// `==` or `!=` on `f32`/`f64` operands. Opt-in: raised only under
+    /// [`crate::TypeCheckOptions::float_equality`].
+    FloatEquality,
+    /// Parentheses that change nothing: around a name, literal, call, or other
+    /// expression that binds tighter than any operator, or around a whole
+    /// returned or assigned value. Opt-in: raised only under
+    /// [`crate::TypeCheckOptions::redundant_parens`].
+    RedundantParens,
 }
 
 impl WarningCode {
@@ -17,6 +35,12 @@ impl WarningCode {
     pub fn name(self) -> &'static str {
         match self {
             WarningCode::PreferLoopOverWhileTrue => "prefer-loop-over-while-true",
+            WarningCode::UnreachableMatchArm => "unreachable-match-arm",
+            WarningCode::SelfComparison => "self-comparison",
+            WarningCode::OverlappingPattern => "overlapping-pattern",
+            WarningCode::DeadCode => "dead-code",
+            WarningCode::FloatEquality => "float-equality",
+            WarningCode::RedundantParens => "redundant-parens",
         }
     }
 
@@ -26,6 +50,21 @@ impl WarningCode {
     pub fn allow_identifier(self) -> &'static str {
         match self {
             WarningCode::PreferLoopOverWhileTrue => "prefer_loop_over_while_true",
+            WarningCode::UnreachableMatchArm => "unreachable_match_arm",
+            WarningCode::SelfComparison => "self_comparison",
+            WarningCode::OverlappingPattern => "overlapping_pattern",
+            WarningCode::DeadCode => "dead_code",
+            WarningCode::FloatEquality => "float_equality",
+            WarningCode::RedundantParens => "redundant_parens",
+        }
+    }
+
+    /// How the warning is labelled when printed. Style suggestions are hints;
+    /// everything else is a warning.
+    pub fn severity(self) -> Severity {
+        match self {
+            WarningCode::RedundantParens => Severity::Hint,
+            _ => Severity::Warning,
         }
     }
 }
@@ -39,11 +78,54 @@ pub struct Warning {
     pub span: Span,
 }
 
+impl Warning {
+    /// The warning as a [`Diagnostic`] at its span and severity, with a
+    /// machine-applicable fix where one is known. `source` is the checked
+    /// program's text; a `redundant-parens` hint's fix replaces the parenthesized
+    /// expression with its contents.
+    pub fn to_diagnostic(&self, source: &str) -> Diagnostic {
+        let mut diagnostic =
+            Diagnostic::warning(DiagnosticCode::Unknown, self.message.clone()).with_span(self.span);
+        diagnostic.severity = self.code.severity();
+        match self.code {
+            WarningCode::RedundantParens => match without_parens(source, self.span) {
+                Some(replacement) => diagnostic.with_suggestion(Suggestion {
+                    span: self.span,
+                    label: format!("remove the parentheses: `{}`", replacement.trim()),
+                    replacement,
+                }),
+                None => diagnostic,
+            },
+            _ => diagnostic,
+        }
+    }
+}
+
+/// The text of the parenthesized expression at `span` with its outer `(` and `)`
+/// removed. A space stands in for a parenthesis that separated two word
+/// characters, so `return(x)` becomes `return x` rather than `returnx`. `None`
+/// when `span` does not cover a parenthesized expression in `source`.
+fn without_parens(source: &str, span: Span) -> Option<String> {
+    let text = source.get(span.start..span.end)?;
+    let inner = text.strip_prefix('(')?.strip_suffix(')')?.trim();
+    let is_word = |c: Option<char>| c.is_some_and(|c| c.is_alphanumeric() || c == '_');
+    let mut replacement = String::new();
+    if is_word(source[..span.start].chars().next_back()) && is_word(inner.chars().next()) {
+        replacement.push(' ');
+    }
+    replacement.push_str(inner);
+    if is_word(source[span.end..].chars().next()) && is_word(inner.chars().next_back()) {
+        replacement.push(' ');
+    }
+    Some(replacement)
+}
+
 impl fmt::Display for Warning {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "warning[{}] at {}..{}: {}",
+            "{}[{}] at {}..{}: {}",
+            self.code.severity(),
             self.code.name(),
             self.span.start,
             self.span.end,
@@ -0,0 +1,50 @@
+// Integration tests: `@cfg`-gated items and type checking
+
+use semantic_analysis::type_check;
+use syntax_parsing::{parse_with_cfg, CfgSet};
+
+// `kernel` has a type error, so type checking reveals whether it was kept.
+const GATED: &str = r#"
+@cfg(target = "cuda")
+func kernel() -> i32 {
+    return true
+}
+
+func main() -> i32 {
+    0
+}
+"#;
+
+#[test]
+fn item_gated_on_unset_cfg_is_not_type_checked() {
+    let items = syntax_parsing::parse(GATED).unwrap();
+    let result = type_check(&items);
+    assert!(result.is_ok(), "got {:?}", result);
+}
+
+#[test]
+fn item_gated_on_set_cfg_is_type_checked() {
+    let mut cfg = CfgSet::new();
+    cfg.set_value("target", "cuda");
+    let items = parse_with_cfg(GATED, &cfg).unwrap();
+    assert!(type_check(&items).is_err());
+}
+
+#[test]
+fn call_to_excluded_function_is_undefined() {
+    let source = r#"
+@cfg(debug)
+func trace() -> i32 { 1 }
+
+func main() -> i32 {
+    trace()
+}
+"#;
+    let items = syntax_parsing::parse(source).unwrap();
+    assert!(type_check(&items).is_err());
+
+    let mut cfg = CfgSet::new();
+    cfg.set_flag("debug");
+    let items = parse_with_cfg(source, &cfg).unwrap();
+    assert!(type_check(&items).is_ok());
+}
@@ -0,0 +1,243 @@
+// Integration tests: compile-time evaluation of constant expressions
+
+use semantic_analysis::{
+    const_array_lengths, const_eval, type_check, ConstEnv, ConstEvalError, TypeError,
+};
+use shared_types::{IntSuffix, Literal};
+use syntax_parsing::{parse, Item};
+
+/// Evaluate the initializer of `const X: i64 = <expr>` with no other consts.
+fn eval(expr: &str) -> Result<Literal, ConstEvalError> {
+    let items = parse(&format!("const X: i64 = {expr}")).expect("parse should succeed");
+    let Some(Item::Const(def)) = items.first() else {
+        panic!("expected a const item, got {items:?}");
+    };
+    const_eval(&def.value, &ConstEnv::new())
+}
+
+/// Type check `source`, returning its errors.
+fn check(source: &str) -> Result<(), Vec<TypeError>> {
+    let items = parse(source).expect("parse should succeed");
+    type_check(&items).map(|_warnings| ())
+}
+
+fn int(value: i64) -> Literal {
+    Literal::Integer(value, None)
+}
+
+#[test]
+fn integer_arithmetic_follows_precedence() {
+    assert_eq!(eval("2 + 3 * 4"), Ok(int(14)));
+    assert_eq!(eval("(2 + 3) * 4"), Ok(int(20)));
+    assert_eq!(eval("17 / 5 - 17 % 5"), Ok(int(1)));
+    assert_eq!(eval("-(1 << 4) | 0x3"), Ok(int(-13)));
+}
+
+#[test]
+fn float_arithmetic() {
+    assert_eq!(eval("1.5 * 4.0 - 0.5"), Ok(Literal::Float(5.5, None)));
+}
+
+#[test]
+fn comparisons_and_logic_produce_booleans() {
+    assert_eq!(eval("3 * 3 > 8"), Ok(Literal::Boolean(true)));
+    assert_eq!(eval("2.0 <= 1.0"), Ok(Literal::Boolean(false)));
+    assert_eq!(eval("1 == 1 && !(2 != 2)"), Ok(Literal::Boolean(true)));
+    assert_eq!(eval("'a' < 'b'"), Ok(Literal::Boolean(true)));
+}
+
+#[test]
+fn suffixed_operands_keep_their_type() {
+    assert_eq!(
+        eval("200u8 + 55"),
+        Ok(Literal::Integer(255, Some(IntSuffix::U8)))
+    );
+    assert_eq!(
+        eval("300 as u8"),
+        Ok(Literal::Integer(44, Some(IntSuffix::U8)))
+    );
+    assert!(matches!(
+        eval("200u8 + 100u8"),
+        Err(ConstEvalError::Overflow { .. })
+    ));
+}
+
+#[test]
+fn const_references_another_const() {
+    let items =
+        parse("const BASE: i32 = 8\nconst SIZE: i32 = BASE * 4 + 1").expect("parse should succeed");
+    let mut env = ConstEnv::new();
+    for item in &items {
+        let Item::Const(def) = item else {
+            panic!("expected a const item, got {item:?}");
+        };
+        let value = const_eval(&def.value, &env).expect("const should evaluate");
+        env.define(def.name.name.clone(), value);
+    }
+    assert_eq!(env.get("SIZE"), Some(&int(33)));
+}
+
+#[test]
+fn unknown_const_is_reported_by_name() {
+    let err = eval("LIMIT + 1").expect_err("LIMIT is not defined");
+    assert!(
+        matches!(&err, ConstEvalError::UnknownConst { name, .. } if name == "LIMIT"),
+        "got {err:?}"
+    );
+}
+
+#[test]
+fn call_is_not_constant() {
+    let source = "const X: i64 = 1 + compute(2)";
+    let items = parse(source).expect("parse should succeed");
+    let Some(Item::Const(def)) = items.first() else {
+        panic!("expected a const item, got {items:?}");
+    };
+    let err = const_eval(&def.value, &ConstEnv::new()).expect_err("calls are not constant");
+    assert!(
+        matches!(err, ConstEvalError::NotConstant { .. }),
+        "got {err:?}"
+    );
+    assert_eq!(&source[err.span().start..err.span().end], "compute(2)");
+}
+
+#[test]
+fn division_by_zero_and_mixed_operands_error() {
+    assert!(matches!(
+        eval("1 / (2 - 2)"),
+        Err(ConstEvalError::DivisionByZero { .. })
+    ));
+    assert!(matches!(
+        eval("1 + 2.0"),
+        Err(ConstEvalError::InvalidOperands { .. })
+    ));
+    assert!(matches!(
+        eval("true + 1"),
+        Err(ConstEvalError::InvalidOperands { .. })
+    ));
+}
+
+#[test]
+fn module_consts_fold_regardless_of_source_order() {
+    let source = "const SIZE: i32 = BASE * 4\nconst BASE: i32 = 2\nfunc main() -> i32 { SIZE }";
+    assert!(check(source).is_ok());
+
+    let items = parse(source).expect("parse should succeed");
+    assert_eq!(const_array_lengths(&items).get("SIZE"), Some(&8));
+}
+
+#[test]
+fn const_evaluation_errors_are_type_errors() {
+    let errors = check("const X: i32 = 1 / (2 - 2)").expect_err("division by zero");
+    assert!(
+        matches!(errors.as_slice(), [TypeError::ConstDivisionByZero { .. }]),
+        "got {errors:?}"
+    );
+
+    let errors = check("const X: u8 = 200u8 + 100u8").expect_err("overflow");
+    assert!(
+        matches!(errors.as_slice(), [TypeError::ConstOverflow { .. }]),
+        "got {errors:?}"
+    );
+
+    let errors = check("func compute() -> i32 { 1 }\nconst X: i32 = compute()")
+        .expect_err("calls are not constant");
+    assert!(
+        matches!(errors.as_slice(), [TypeError::InvalidConstExpr { .. }]),
+        "got {errors:?}"
+    );
+}
+
+#[test]
+fn const_depending_on_a_failed_const_is_not_reported_again() {
+    let errors = check("const A: i32 = 1 / 0\nconst B: i32 = A + 1").expect_err("A fails");
+    assert!(
+        matches!(errors.as_slice(), [TypeError::ConstDivisionByZero { .. }]),
+        "got {errors:?}"
+    );
+}
+
+#[test]
+fn cyclic_consts_are_errors() {
+    let errors = check("const A: i32 = B + 1\nconst B: i32 = A").expect_err("A and B are cyclic");
+    let names: Vec<&str> = errors
+        .iter()
+        .map(|e| match e {
+            TypeError::CyclicConst { name, .. } => name.as_str(),
+            other => panic!("expected a cyclic-const error, got {other:?}"),
+        })
+        .collect();
+    assert_eq!(names, ["A", "B"]);
+}
+
+#[test]
+fn local_consts_fold_against_earlier_consts() {
+    let source = r#"
+const BASE: i32 = 4
+func main() -> i32 {
+    const DOUBLE: i32 = BASE * 2
+    const LIMIT: i32 = DOUBLE + 1
+    LIMIT
+}"#;
+    assert!(check(source).is_ok());
+
+    let source = "func main() -> i32 {\n    const BAD: i32 = 1 % 0\n    0\n}";
+    let errors = check(source).expect_err("modulo by zero");
+    assert!(
+        matches!(errors.as_slice(), [TypeError::ConstDivisionByZero { .. }]),
+        "got {errors:?}"
+    );
+}
+
+#[test]
+fn array_length_may_name_a_module_const() {
+    let source = r#"
+const SIZE: i32 = 1 + 2
+func sum(a: [i32; SIZE]) -> i32 { a[0] + a[1] + a[2] }
+func main() -> i32 {
+    val xs: [i32; SIZE] = [1, 2, 3]
+    sum(xs)
+}"#;
+    assert!(check(source).is_ok());
+
+    let source =
+        "const SIZE: i32 = 3\nfunc main() -> i32 {\n    val xs: [i32; SIZE] = [1, 2]\n    0\n}";
+    let errors = check(source).expect_err("two elements do not fill [i32; 3]");
+    assert!(
+        errors.iter().any(|e| matches!(
+            e,
+            TypeError::ArrayLengthMismatch {
+                expected: 3,
+                found: 2,
+                ..
+            }
+        )),
+        "got {errors:?}"
+    );
+
+    let errors = check("const SIZE: i32 = -1\nfunc f(a: [i32; SIZE]) -> i32 { 0 }")
+        .expect_err("a negative constant is not a length");
+    assert!(
+        matches!(errors.as_slice(), [TypeError::UnknownArrayLength { .. }]),
+        "got {errors:?}"
+    );
+}
+
+#[test]
+fn where_predicate_may_name_a_module_const() {
+    let source = r#"
+const LIMIT: u32 = 4
+func head<const N: u32>(a: [i32; N]) -> i32 where N <= LIMIT { a[0] }
+func main() -> i32 {
+    val xs: [i32; 5] = [1, 2, 3, 4, 5]
+    head(xs)
+}"#;
+    let errors = check(source).expect_err("N = 5 violates N <= LIMIT");
+    assert!(
+        matches!(
+            errors.as_slice(),
+            [TypeError::ConstPredicateViolated { .. }]
+        ),
+        "got {errors:?}"
+    );
+}
@@ -152,6 +152,47 @@ fn error_continue_outside_loop() {
         .any(|e| matches!(e, TypeError::ContinueOutsideLoop { .. })));
 }
 
+#[test]
+fn type_check_break_and_continue_inside_for_loop() {
+    let source = r#"func test(n: i32) -> i32 {
+        mut sum: i32 = 0
+        for i in 0..n {
+            if i == 2 {
+                continue
+            }
+            if i > 5 {
+                break
+            }
+            sum = sum + i
+        }
+        return sum
+    }"#;
+    let items = syntax_parsing::parse(source).unwrap();
+    let result = type_check(&items);
+    assert!(
+        result.is_ok(),
+        "Expected break and continue in for to type check"
+    );
+}
+
+#[test]
+fn error_break_and_continue_after_loop_ends() {
+    // Leaving a loop's body pops it, so control statements after it have no loop.
+    let source = r#"func test() -> i32 {
+        while true {
+            break
+        }
+        break
+        continue
+        return 0
+    }"#;
+    let items = syntax_parsing::parse(source).unwrap();
+    let errors = type_check(&items).unwrap_err();
+    assert_eq!(errors.len(), 2, "got {errors:?}");
+    assert!(matches!(errors[0], TypeError::BreakOutsideLoop { .. }));
+    assert!(matches!(errors[1], TypeError::ContinueOutsideLoop { .. }));
+}
+
 #[test]
 fn error_if_condition_not_bool() {
     let source = r#"func test() -> i32 {
@@ -186,3 +227,51 @@ fn error_while_condition_not_bool() {
         .iter()
         .any(|e| matches!(e, TypeError::Mismatch { .. })));
 }
+
+#[test]
+fn type_check_bare_block_statements() {
+    let source = r#"func test() -> i32 {
+        mut total: i32 = 0
+        {
+            val step: i32 = 2
+            total = total + step
+        }
+        {
+            val step: i32 = 3
+            total = total + step
+        }
+        return total
+    }"#;
+    let items = syntax_parsing::parse(source).unwrap();
+    assert!(type_check(&items).is_ok());
+}
+
+#[test]
+fn error_bare_block_binding_out_of_scope() {
+    let source = r#"func test() -> i32 {
+        {
+            val inner: i32 = 1
+        }
+        return inner
+    }"#;
+    let items = syntax_parsing::parse(source).unwrap();
+    let errors = type_check(&items).unwrap_err();
+    assert!(errors
+        .iter()
+        .any(|e| matches!(e, TypeError::UndefinedVariable { name, .. } if name == "inner")));
+}
+
+#[test]
+fn error_bare_block_statement_is_checked() {
+    let source = r#"func test() -> i32 {
+        {
+            val flag: bool = 1
+        }
+        return 0
+    }"#;
+    let items = syntax_parsing::parse(source).unwrap();
+    let errors = type_check(&items).unwrap_err();
+    assert!(errors
+        .iter()
+        .any(|e| matches!(e, TypeError::Mismatch { .. })));
+}
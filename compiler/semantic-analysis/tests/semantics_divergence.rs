@@ -0,0 +1,159 @@
+// Integration tests: divergence, the `never` type of code that does not complete
+
+use semantic_analysis::{type_check, Type, TypeError};
+
+fn check(source: &str) -> Result<(), Vec<TypeError>> {
+    let items = syntax_parsing::parse(source).expect("parse should succeed");
+    type_check(&items).map(|_| ())
+}
+
+/// The single mismatch reported for `source`, as `(expected, found)`.
+fn mismatch(source: &str) -> (Type, Type) {
+    let errors = check(source).expect_err("expected a type mismatch");
+    match errors.as_slice() {
+        [TypeError::Mismatch {
+            expected, found, ..
+        }] => (expected.clone(), found.clone()),
+        other => panic!("expected one mismatch, got {other:?}"),
+    }
+}
+
+/// Asserts the only error is binding a void call's result, so the call completes
+/// (a `never` call would fit `i32` without complaint).
+fn assert_void_value_used(source: &str) {
+    let errors = check(source).expect_err("expected the void result to be rejected");
+    assert!(
+        matches!(errors.as_slice(), [TypeError::VoidValueUsed { .. }]),
+        "got {errors:?}"
+    );
+}
+
+#[test]
+fn returning_branch_lets_the_other_branch_type_dominate() {
+    let source = r#"
+func takes_u8(x: u8) -> u8 { x }
+
+func pick(flag: bool) -> u8 {
+    val x = if flag { return 0u8 } else { 7u8 }
+    takes_u8(x)
+}
+"#;
+    assert_eq!(check(source), Ok(()));
+}
+
+#[test]
+fn dominating_branch_type_is_enforced() {
+    let source = r#"
+func takes_string(s: string) -> i32 { 0 }
+
+func pick(flag: bool) -> i32 {
+    val x = if flag { return 0 } else { 7u8 }
+    takes_string(x)
+}
+"#;
+    assert_eq!(mismatch(source), (Type::String, Type::U8));
+}
+
+#[test]
+fn panicking_and_breaking_arms_diverge() {
+    let source = r#"
+func first_even(values: [i32; 3]) -> i32 {
+    mut found: i32 = 0
+    for v in values {
+        val half = if v % 2 == 0 { v / 2 } else { continue }
+        found = half
+    }
+    val checked = if found >= 0 { found } else { panic("negative") }
+    checked
+}
+"#;
+    assert_eq!(check(source), Ok(()));
+}
+
+#[test]
+fn match_arm_that_returns_defers_to_the_others() {
+    let source = r#"
+func classify(n: i32) -> bool {
+    val label = match n {
+        0 => { return false },
+        _ => true,
+    }
+    label
+}
+"#;
+    assert_eq!(check(source), Ok(()));
+}
+
+#[test]
+fn function_that_never_returns_fits_any_type() {
+    let source = r#"
+func fail(msg: string) {
+    panic(msg)
+}
+
+func spin() {
+    loop {}
+}
+
+func pick(flag: bool) -> i32 {
+    val value: i32 = if flag { fail("no") } else { 1 }
+    if value > 0 { spin() } else { value }
+}
+"#;
+    assert_eq!(check(source), Ok(()));
+}
+
+#[test]
+fn else_branch_that_panics_needs_no_return() {
+    let source = r#"
+func checked_div(a: i32, b: i32) -> i32 {
+    if b != 0 {
+        return a / b
+    } else {
+        panic("division by zero")
+    }
+}
+
+func checked_rem(a: i32, b: i32) -> i32 {
+    if b != 0 { a % b } else { panic("division by zero") }
+}
+"#;
+    assert_eq!(check(source), Ok(()));
+}
+
+#[test]
+fn function_with_a_return_path_keeps_its_type() {
+    let source = r#"
+func maybe(flag: bool) {
+    if flag {
+        return
+    }
+    panic("unset")
+}
+
+func caller() -> i32 {
+    val value: i32 = maybe(true)
+    value
+}
+"#;
+    assert_void_value_used(source);
+}
+
+#[test]
+fn loop_with_a_break_completes() {
+    let source = r#"
+func settle() {
+    outer: loop {
+        loop {
+            break outer
+        }
+    }
+}
+
+func caller() -> i32 {
+    val value: i32 = settle()
+    value
+}
+"#;
+    assert_void_value_used(source);
+}
@@ -15,6 +15,88 @@ fn error_undefined_variable() {
     assert!(matches!(errors[0], TypeError::UndefinedVariable { .. }));
 }
 
+#[test]
+fn error_undefined_variable_suggests_nearest_name() {
+    let source = r#"func test() -> i32 {
+        val total = 1
+        val other = 2
+        return totl + other
+    }"#;
+    let items = syntax_parsing::parse(source).unwrap();
+    let errors = type_check(&items).unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(
+        &errors[0],
+        TypeError::UndefinedVariable { suggestion: Some(name), .. } if name == "total"
+    ));
+
+    let diagnostic = errors[0].to_diagnostic(source);
+    assert_eq!(diagnostic.suggestions.len(), 1);
+    let fix = &diagnostic.suggestions[0];
+    assert_eq!(&source[fix.span.start..fix.span.end], "totl");
+    assert_eq!(fix.replacement, "total");
+    assert!(diagnostic
+        .to_string()
+        .contains("help: a variable with a similar name exists: `total`"));
+}
+
+#[test]
+fn error_undefined_variable_without_close_name_has_no_suggestion() {
+    let source = r#"func test() -> i32 {
+        val total = 1
+        return missing
+    }"#;
+    let items = syntax_parsing::parse(source).unwrap();
+    let errors = type_check(&items).unwrap_err();
+    assert!(matches!(
+        errors[0],
+        TypeError::UndefinedVariable {
+            suggestion: None,
+            ..
+        }
+    ));
+    assert!(errors[0].to_diagnostic(source).suggestions.is_empty());
+}
+
+/// The suggested fixes for the first error in `source`, as replacement text.
+fn suggested_fixes(source: &str) -> Vec<String> {
+    let items = syntax_parsing::parse(source).unwrap();
+    let errors = type_check(&items).unwrap_err();
+    errors[0]
+        .to_diagnostic(source)
+        .suggestions
+        .into_iter()
+        .map(|fix| fix.replacement)
+        .collect()
+}
+
+#[test]
+fn error_mismatched_numeric_literal_suggests_suffix() {
+    let source = "func scale(x: f64) -> f64 { x }\nfunc test() -> f64 { scale(2) }";
+    let items = syntax_parsing::parse(source).unwrap();
+    let errors = type_check(&items).unwrap_err();
+    assert!(matches!(errors[0], TypeError::Mismatch { .. }));
+
+    let diagnostic = errors[0].to_diagnostic(source);
+    assert_eq!(diagnostic.message, "type mismatch: expected f64, found i32");
+    assert_eq!(diagnostic.suggestions.len(), 1);
+    assert_eq!(diagnostic.suggestions[0].replacement, "2.0f64");
+
+    assert_eq!(
+        suggested_fixes("func widen(x: i64) -> i64 { x }\nfunc test() -> i64 { widen(5i32) }"),
+        ["5i64"]
+    );
+    assert_eq!(
+        suggested_fixes("func half(x: f32) -> f32 { x }\nfunc test() -> f32 { half(0.5f64) }"),
+        ["0.5f32"]
+    );
+    // No suffix turns a float literal into an integer.
+    assert!(
+        suggested_fixes("func count(x: i32) -> i32 { x }\nfunc test() -> i32 { count(2.5) }")
+            .is_empty()
+    );
+}
+
 #[test]
 fn error_array_destructure_length_mismatch() {
     // A rest-less array pattern must bind every element. Binding two from a
@@ -134,6 +216,57 @@ fn error_argument_type_mismatch() {
         .any(|e| matches!(e, TypeError::Mismatch { .. })));
 }
 
+#[test]
+fn error_each_wrong_argument_reported_at_its_own_span() {
+    let source = r#"
+        struct Gauge { level: i32 }
+
+        impl Gauge {
+            func set(&self, value: i32, enabled: bool, scale: i32) -> i32 {
+                return value
+            }
+        }
+
+        func set(value: i32, enabled: bool, scale: i32) -> i32 {
+            return value
+        }
+
+        func main() -> i32 {
+            val gauge = Gauge { level: 0 }
+            val a: i32 = set(true, 1, 2)
+            return gauge.set(false, 3, 4)
+        }
+    "#;
+    let items = syntax_parsing::parse(source).unwrap();
+    let errors = type_check(&items).unwrap_err();
+    // One mismatch per wrong argument, pointing at that argument rather than the
+    // call, and nothing cascading from the first.
+    let mismatches: Vec<(&str, String, String)> = errors
+        .iter()
+        .map(|e| match e {
+            TypeError::Mismatch {
+                expected,
+                found,
+                span,
+            } => (
+                &source[span.start..span.end],
+                expected.to_string(),
+                found.to_string(),
+            ),
+            other => panic!("expected only argument mismatches, got {other:?}"),
+        })
+        .collect();
+    assert_eq!(
+        mismatches,
+        [
+            ("true", "i32".to_string(), "bool".to_string()),
+            ("1", "bool".to_string(), "i32".to_string()),
+            ("false", "i32".to_string(), "bool".to_string()),
+            ("3", "bool".to_string(), "i32".to_string()),
+        ]
+    );
+}
+
 #[test]
 fn error_undefined_function() {
     let source = r#"func main() -> i32 {
@@ -197,3 +330,218 @@ fn error_unknown_type_name() {
         .iter()
         .any(|e| matches!(e, TypeError::UnknownTypeName { .. })));
 }
+
+#[test]
+fn error_calling_integer_variable_is_not_callable() {
+    let source = r#"func test() -> i32 {
+        val count: i32 = 3
+        return count(1)
+    }"#;
+    let items = syntax_parsing::parse(source).unwrap();
+    let errors = type_check(&items).unwrap_err();
+    assert_eq!(errors.len(), 1, "unexpected errors: {errors:?}");
+    match &errors[0] {
+        TypeError::NotCallable { span, .. } => {
+            // The diagnostic points at the callee name, not the whole call.
+            assert_eq!(&source[span.start..span.end], "count");
+        }
+        other => panic!("expected NotCallable, got {other:?}"),
+    }
+}
+
+#[test]
+fn error_local_variable_shadows_function_of_same_name() {
+    // The local binding shadows the top-level function, so the call targets an
+    // `i32` and is rejected rather than resolving to `helper`.
+    let source = r#"func helper() -> i32 {
+        return 1
+    }
+
+    func test() -> i32 {
+        val helper: i32 = 2
+        return helper()
+    }"#;
+    let items = syntax_parsing::parse(source).unwrap();
+    let errors = type_check(&items).unwrap_err();
+    assert!(
+        errors
+            .iter()
+            .any(|e| matches!(e, TypeError::NotCallable { .. })),
+        "expected NotCallable, got {errors:?}"
+    );
+    assert!(!errors
+        .iter()
+        .any(|e| matches!(e, TypeError::UndefinedFunction { .. })));
+}
+
+#[test]
+fn error_void_call_bound_to_val() {
+    let source = r#"
+func log_value(x: i32) { }
+
+func test() -> i32 {
+    val x = log_value(1)
+    return 0
+}
+"#;
+    let items = syntax_parsing::parse(source).unwrap();
+    let errors = type_check(&items).unwrap_err();
+    assert_eq!(errors.len(), 1, "got {errors:?}");
+    match &errors[0] {
+        TypeError::VoidValueUsed { span } => {
+            assert_eq!(&source[span.start..span.end], "log_value(1)");
+        }
+        other => panic!("expected VoidValueUsed, got {other:?}"),
+    }
+}
+
+#[test]
+fn error_void_call_in_arithmetic() {
+    let source = r#"
+func log_value(x: i32) { }
+
+func test() -> i32 {
+    return log_value(1) + 2
+}
+"#;
+    let items = syntax_parsing::parse(source).unwrap();
+    let errors = type_check(&items).unwrap_err();
+    assert_eq!(errors.len(), 1, "got {errors:?}");
+    assert!(matches!(errors[0], TypeError::VoidValueUsed { .. }));
+}
+
+#[test]
+fn void_call_as_statement_is_allowed() {
+    let source = r#"
+func log_value(x: i32) { }
+
+func test() -> i32 {
+    log_value(1)
+    return 0
+}
+"#;
+    let items = syntax_parsing::parse(source).unwrap();
+    assert!(type_check(&items).is_ok());
+}
+
+#[test]
+fn error_if_expression_arms_disagree() {
+    // Both arms are checked and the disagreement is the one error, not a second
+    // mismatch against the binding's annotation.
+    let source = r#"func test(flag: bool) -> i32 {
+        val x: i32 = if flag { 1 } else { true }
+        return x
+    }"#;
+    let items = syntax_parsing::parse(source).unwrap();
+    let errors = type_check(&items).unwrap_err();
+    assert_eq!(errors.len(), 1, "got {errors:?}");
+    match &errors[0] {
+        TypeError::IfArmTypeMismatch {
+            expected, found, ..
+        } => {
+            assert_eq!(expected.to_string(), "i32");
+            assert_eq!(found.to_string(), "bool");
+        }
+        other => panic!("expected IfArmTypeMismatch, got {other:?}"),
+    }
+}
+
+#[test]
+fn error_else_if_arm_disagrees() {
+    let source = r#"func test(a: bool, b: bool) -> i32 {
+        return if a { 1 } else if b { "two" } else { 3 }
+    }"#;
+    let items = syntax_parsing::parse(source).unwrap();
+    let errors = type_check(&items).unwrap_err();
+    assert_eq!(errors.len(), 1, "got {errors:?}");
+    assert!(matches!(errors[0], TypeError::IfArmTypeMismatch { .. }));
+}
+
+#[test]
+fn error_trailing_if_arms_disagree_with_return_type() {
+    // A trailing `if` statement is the function's implicit return value, so its
+    // arms are held to the same agreement as an `if` expression.
+    let source = r#"func f(c: bool) -> i32 { if c { 1 } else { true } }"#;
+    let items = syntax_parsing::parse(source).unwrap();
+    let errors = type_check(&items).unwrap_err();
+    assert_eq!(errors.len(), 1, "got {errors:?}");
+    match &errors[0] {
+        TypeError::IfArmTypeMismatch {
+            expected, found, ..
+        } => {
+            assert_eq!(expected.to_string(), "i32");
+            assert_eq!(found.to_string(), "bool");
+        }
+        other => panic!("expected IfArmTypeMismatch, got {other:?}"),
+    }
+}
+
+#[test]
+fn error_trailing_if_arm_without_value_is_missing_return() {
+    // An arm ending in a declaration produces no value. That is the arm's missing
+    // return, not a disagreement with the other arm or with the return type.
+    for source in [
+        r#"func f(c: bool) -> i32 { if c { return 1 } else { val y = 2 } }"#,
+        r#"func f(c: bool) -> i32 { if c { 1 } else { val y = 2 } }"#,
+    ] {
+        let items = syntax_parsing::parse(source).unwrap();
+        let errors = type_check(&items).unwrap_err();
+        assert_eq!(errors.len(), 1, "{source}: got {errors:?}");
+        match &errors[0] {
+            TypeError::MissingReturn { expected, span } => {
+                assert_eq!(expected.to_string(), "i32");
+                assert_eq!(&source[span.start..span.end], "val y = 2");
+            }
+            other => panic!("{source}: expected MissingReturn, got {other:?}"),
+        }
+    }
+}
+
+#[test]
+fn type_check_reassign_mut_variable() {
+    let source = r#"func test() -> i32 {
+        mut counter: i32 = 0
+        counter = counter + 1
+        return counter
+    }"#;
+    let items = syntax_parsing::parse(source).unwrap();
+    let result = type_check(&items);
+    assert!(result.is_ok(), "got {:?}", result.err());
+}
+
+#[test]
+fn error_reassign_val_variable() {
+    let source = r#"func test() -> i32 {
+        val counter: i32 = 0
+        counter = 1
+        return counter
+    }"#;
+    let items = syntax_parsing::parse(source).unwrap();
+    let errors = type_check(&items).unwrap_err();
+    assert_eq!(errors.len(), 1, "got {errors:?}");
+    assert!(
+        matches!(&errors[0], TypeError::AssignToImmutable { name, .. } if name == "counter"),
+        "got {errors:?}"
+    );
+}
+
+#[test]
+fn error_assign_wrong_type() {
+    let source = r#"func test() -> i32 {
+        mut counter: i32 = 0
+        counter = true
+        return counter
+    }"#;
+    let items = syntax_parsing::parse(source).unwrap();
+    let errors = type_check(&items).unwrap_err();
+    assert_eq!(errors.len(), 1, "got {errors:?}");
+    match &errors[0] {
+        TypeError::Mismatch {
+            expected, found, ..
+        } => {
+            assert_eq!(expected.to_string(), "i32");
+            assert_eq!(found.to_string(), "bool");
+        }
+        other => panic!("expected Mismatch, got {other:?}"),
+    }
+}
@@ -1,6 +1,6 @@
 // Integration tests: Functions, variables, scopes
 
-use semantic_analysis::type_check;
+use semantic_analysis::{type_check, TypeError};
 
 #[test]
 fn type_check_simple_function() {
@@ -94,3 +94,62 @@ fn type_check_milestone_program() {
         result
     );
 }
+
+#[test]
+fn type_check_black_box_returns_its_argument_type() {
+    let source = r#"
+        func work() -> f64 {
+            return 2.5
+        }
+
+        func tick() {
+        }
+
+        func main() -> i32 {
+            val x: f64 = black_box(work())
+            black_box(tick())
+            return black_box(7)
+        }
+    "#;
+    let items = syntax_parsing::parse(source).unwrap();
+    let result = type_check(&items);
+    assert!(
+        result.is_ok(),
+        "black_box should type check, got: {:?}",
+        result
+    );
+}
+
+#[test]
+fn type_check_black_box_takes_exactly_one_argument() {
+    let source = r#"
+        func main() -> i32 {
+            return black_box(1, 2)
+        }
+    "#;
+    let items = syntax_parsing::parse(source).unwrap();
+    let errors = type_check(&items).unwrap_err();
+    assert!(errors.iter().any(|e| matches!(
+        e,
+        TypeError::ArgumentCountMismatch {
+            expected: 1,
+            found: 2,
+            ..
+        }
+    )));
+}
+
+#[test]
+fn type_check_user_black_box_shadows_the_builtin() {
+    let source = r#"
+        func black_box(a: i32, b: i32) -> i32 {
+            return a + b
+        }
+
+        func main() -> i32 {
+            return black_box(1, 2)
+        }
+    "#;
+    let items = syntax_parsing::parse(source).unwrap();
+    assert!(type_check(&items).is_ok());
+}
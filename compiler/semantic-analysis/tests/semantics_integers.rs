@@ -124,3 +124,37 @@ fn error_unsigned_with_float() {
     let result = type_check(&items);
     assert!(result.is_err(), "u32 + f32 should fail type check");
 }
+
+#[test]
+fn error_comparison_different_width_mismatch() {
+    // Comparisons follow arithmetic: numbers never widen implicitly.
+    let source = r#"func test(a: i32, b: i64) -> bool {
+        return a < b
+    }"#;
+    let items = syntax_parsing::parse(source).unwrap();
+    let errors = type_check(&items).expect_err("i32 < i64 should fail type check");
+    assert!(errors
+        .iter()
+        .any(|e| matches!(e, TypeError::Mismatch { .. })));
+
+    let cast = r#"func test(a: i32, b: i64) -> bool {
+        return (a as i64) < b
+    }"#;
+    let items = syntax_parsing::parse(cast).unwrap();
+    assert!(type_check(&items).is_ok());
+}
+
+#[test]
+fn error_comparison_with_bool_is_invalid_operator() {
+    let source = r#"func test(a: i32) -> bool {
+        return a < true
+    }"#;
+    let items = syntax_parsing::parse(source).unwrap();
+    let errors = type_check(&items).expect_err("i32 < bool should fail type check");
+    assert_eq!(errors.len(), 1);
+    assert!(
+        matches!(&errors[0], TypeError::InvalidBinaryOperator { op, left, right, .. }
+            if op == "<" && left.to_string() == "i32" && right.to_string() == "bool"),
+        "{errors:?}"
+    );
+}
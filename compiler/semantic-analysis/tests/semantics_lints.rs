@@ -53,6 +53,40 @@ fn lint_allow_attribute_suppresses_while_true() {
     );
 }
 
+#[test]
+fn lint_reaches_nested_function_bodies() {
+    use semantic_analysis::WarningCode;
+
+    let source = r#"func test() -> i32 {
+        func spin() -> i32 {
+            while true {
+                return 1
+            }
+            0
+        }
+        spin()
+    }"#;
+    let items = syntax_parsing::parse(source).unwrap();
+    let warnings = type_check(&items).expect("expected successful type check");
+    assert_eq!(warnings.len(), 1, "got {:?}", warnings);
+    assert_eq!(warnings[0].code, WarningCode::PreferLoopOverWhileTrue);
+
+    // The nested function's own `@allow` covers its body.
+    let allowed = r#"func test() -> i32 {
+        @allow(prefer_loop_over_while_true)
+        func spin() -> i32 {
+            while true {
+                return 1
+            }
+            0
+        }
+        spin()
+    }"#;
+    let items = syntax_parsing::parse(allowed).unwrap();
+    let warnings = type_check(&items).expect("expected successful type check");
+    assert!(warnings.is_empty(), "got {:?}", warnings);
+}
+
 #[test]
 fn lint_parenthesised_while_true_not_flagged() {
     let source = r#"func test() -> i32 {
@@ -133,3 +167,478 @@ fn lint_allow_on_method_suppresses_while_true() {
     let warnings = type_check(&items).expect("expected successful type check");
     assert!(warnings.is_empty());
 }
+
+#[test]
+fn lint_arm_after_exhaustive_bool_match_is_unreachable() {
+    use semantic_analysis::WarningCode;
+
+    let source = r#"func test(b: bool) -> i32 {
+        match b {
+            true => 1,
+            false => 2,
+            _ => 3,
+        }
+    }"#;
+
+    let items = syntax_parsing::parse(source).unwrap();
+    let warnings = type_check(&items).expect("expected successful type check");
+    assert_eq!(
+        warnings.len(),
+        1,
+        "expected one lint warning, got {:?}",
+        warnings
+    );
+    assert_eq!(warnings[0].code, WarningCode::UnreachableMatchArm);
+    let third_arm = source.find("_ => 3").expect("source has a third arm");
+    assert_eq!(warnings[0].span.start, third_arm);
+}
+
+#[test]
+fn lint_every_arm_after_covered_enum_is_unreachable() {
+    let source = r#"
+        enum Light { Red, Green }
+
+        func test(l: Light) -> i32 {
+            match l {
+                Light::Red => 1,
+                Light::Green => 2,
+                Light::Red => 3,
+                _ => 4,
+            }
+        }
+    "#;
+
+    let items = syntax_parsing::parse(source).unwrap();
+    let warnings = type_check(&items).expect("expected successful type check");
+    assert_eq!(warnings.len(), 2, "got {:?}", warnings);
+}
+
+#[test]
+fn lint_wildcard_completing_coverage_is_not_flagged() {
+    let source = r#"func test(b: bool, n: i32) -> i32 {
+        val x = match b {
+            true if n > 0 => 1,
+            true => 2,
+            _ => 3,
+        }
+        x
+    }"#;
+
+    let items = syntax_parsing::parse(source).unwrap();
+    let warnings = type_check(&items).expect("expected successful type check");
+    assert!(warnings.is_empty(), "got {:?}", warnings);
+}
+
+#[test]
+fn lint_allow_attribute_suppresses_unreachable_arm() {
+    let source = r#"
+        @allow(unreachable_match_arm)
+        func test(b: bool) -> i32 {
+            match b {
+                true => 1,
+                false => 2,
+                _ => 3,
+            }
+        }
+    "#;
+
+    let items = syntax_parsing::parse(source).unwrap();
+    let warnings = type_check(&items).expect("expected successful type check");
+    assert!(
+        warnings.is_empty(),
+        "@allow should suppress the lint, got {:?}",
+        warnings
+    );
+}
+
+#[test]
+fn lint_identifier_compared_with_itself_is_flagged() {
+    use semantic_analysis::WarningCode;
+
+    let source = r#"func test(x: i32) -> bool {
+        x == x
+    }"#;
+
+    let items = syntax_parsing::parse(source).unwrap();
+    let warnings = type_check(&items).expect("expected successful type check");
+    assert_eq!(warnings.len(), 1, "got {:?}", warnings);
+    assert_eq!(warnings[0].code, WarningCode::SelfComparison);
+    assert!(
+        warnings[0].message.contains("`x`"),
+        "{}",
+        warnings[0].message
+    );
+}
+
+#[test]
+fn lint_literal_compared_with_itself_is_flagged() {
+    use semantic_analysis::WarningCode;
+
+    let source = r#"func test() -> bool {
+        val a = 3 < 3
+        a
+    }"#;
+
+    let items = syntax_parsing::parse(source).unwrap();
+    let warnings = type_check(&items).expect("expected successful type check");
+    assert_eq!(warnings.len(), 1, "got {:?}", warnings);
+    assert_eq!(warnings[0].code, WarningCode::SelfComparison);
+}
+
+#[test]
+fn lint_distinct_operands_not_flagged() {
+    let source = r#"func test(x: i32, y: i32) -> bool {
+        val a = x == y
+        val b = x + x == 2
+        a && b
+    }"#;
+
+    let items = syntax_parsing::parse(source).unwrap();
+    let warnings = type_check(&items).expect("expected successful type check");
+    assert!(warnings.is_empty(), "got {:?}", warnings);
+}
+
+#[test]
+fn lint_float_nan_test_not_flagged() {
+    // `x != x` is how a NaN is detected, so it is not a self-comparison.
+    let source = r#"func is_nan(x: f64) -> bool {
+        x != x
+    }"#;
+
+    let items = syntax_parsing::parse(source).unwrap();
+    let warnings = type_check(&items).expect("expected successful type check");
+    assert!(warnings.is_empty(), "got {:?}", warnings);
+}
+
+#[test]
+fn lint_allow_attribute_suppresses_self_comparison() {
+    let source = r#"
+        @allow(self_comparison)
+        func test(x: i32) -> bool {
+            x != x
+        }
+    "#;
+
+    let items = syntax_parsing::parse(source).unwrap();
+    let warnings = type_check(&items).expect("expected successful type check");
+    assert!(
+        warnings.is_empty(),
+        "@allow should suppress the lint, got {:?}",
+        warnings
+    );
+}
+
+#[test]
+fn lint_ranges_with_wildcard_are_exhaustive_without_warnings() {
+    let source = r#"func test(n: i32) -> i32 {
+        match n {
+            0..=9 | 10..=19 => 1,
+            _ => 2,
+        }
+    }"#;
+
+    let items = syntax_parsing::parse(source).unwrap();
+    let warnings = type_check(&items).expect("expected successful type check");
+    assert!(warnings.is_empty(), "got {:?}", warnings);
+}
+
+#[test]
+fn lint_overlapping_integer_range_is_flagged() {
+    use semantic_analysis::WarningCode;
+
+    let source = r#"func test(n: i32) -> i32 {
+        match n {
+            0..=10 | 5..=15 => 1,
+            _ => 2,
+        }
+    }"#;
+
+    let items = syntax_parsing::parse(source).unwrap();
+    let warnings = type_check(&items).expect("expected successful type check");
+    assert_eq!(warnings.len(), 1, "got {:?}", warnings);
+    assert_eq!(warnings[0].code, WarningCode::OverlappingPattern);
+    assert_eq!(warnings[0].span.start, source.find("5..=15").unwrap());
+    assert!(
+        warnings[0].message.contains(
+            "values 5..=10 are already matched by an earlier pattern and never reach this one"
+        ),
+        "got {}",
+        warnings[0].message
+    );
+}
+
+#[test]
+fn lint_pattern_covered_by_earlier_arms_is_unreachable() {
+    let source = r#"func test(c: char) -> i32 {
+        match c {
+            'a'..='z' => 1,
+            'q' => 2,
+            _ => 3,
+        }
+    }"#;
+
+    let items = syntax_parsing::parse(source).unwrap();
+    let warnings = type_check(&items).expect("expected successful type check");
+    assert_eq!(warnings.len(), 1, "got {:?}", warnings);
+    assert!(warnings[0].message.starts_with("unreachable pattern"));
+}
+
+#[test]
+fn lint_allow_attribute_suppresses_overlapping_pattern() {
+    let source = r#"
+        @allow(overlapping_pattern)
+        func test(n: i32) -> i32 {
+            match n {
+                0..=10 => 1,
+                5..=15 => 2,
+                _ => 3,
+            }
+        }
+    "#;
+
+    let items = syntax_parsing::parse(source).unwrap();
+    let warnings = type_check(&items).expect("expected successful type check");
+    assert!(warnings.is_empty(), "got {:?}", warnings);
+}
+
+#[test]
+fn lint_function_never_called_from_main_is_dead_code() {
+    use semantic_analysis::WarningCode;
+
+    let source = r#"func unused(x: i32) -> i32 {
+    x + 1
+}
+
+func main() -> i32 {
+    0
+}"#;
+
+    let items = syntax_parsing::parse(source).unwrap();
+    let warnings = type_check(&items).expect("expected successful type check");
+    assert_eq!(warnings.len(), 1, "got {:?}", warnings);
+    assert_eq!(warnings[0].code, WarningCode::DeadCode);
+    assert_eq!(warnings[0].span.start, 0);
+    assert!(warnings[0].message.contains("'unused'"));
+}
+
+#[test]
+fn lint_transitively_reachable_function_is_not_dead_code() {
+    let source = r#"func leaf(x: i32) -> i32 {
+    x * 2
+}
+
+func middle(x: i32) -> i32 {
+    leaf(x) + 1
+}
+
+func recursive(n: i32) -> i32 {
+    if n <= 0 { 0 } else { recursive(n - 1) }
+}
+
+func main() -> i32 {
+    val f = recursive
+    middle(1) + f(3)
+}"#;
+
+    let items = syntax_parsing::parse(source).unwrap();
+    let warnings = type_check(&items).expect("expected successful type check");
+    assert!(warnings.is_empty(), "got {:?}", warnings);
+}
+
+#[test]
+fn lint_dead_code_skips_allowed_functions_tests_and_programs_without_main() {
+    let allowed = r#"
+        @allow(dead_code)
+        func spare() -> i32 { 1 }
+
+        func main() -> i32 { 0 }
+    "#;
+    let items = syntax_parsing::parse(allowed).unwrap();
+    let warnings = type_check(&items).expect("expected successful type check");
+    assert!(warnings.is_empty(), "got {:?}", warnings);
+
+    let tests = r#"
+        func expected() -> i32 { 2 }
+
+        func test_expected() { assert(expected() == 2) }
+
+        func main() -> i32 { 0 }
+    "#;
+    let items = syntax_parsing::parse(tests).unwrap();
+    let warnings = type_check(&items).expect("expected successful type check");
+    assert!(warnings.is_empty(), "got {:?}", warnings);
+
+    let library = "func a() -> i32 { 1 }\nfunc b() -> i32 { 2 }";
+    let items = syntax_parsing::parse(library).unwrap();
+    let warnings = type_check(&items).expect("expected successful type check");
+    assert!(warnings.is_empty(), "got {:?}", warnings);
+}
+
+fn check_float_equality(source: &str) -> Vec<semantic_analysis::Warning> {
+    use semantic_analysis::{type_check_with, TypeCheckOptions};
+
+    let options = TypeCheckOptions {
+        float_equality: true,
+        ..TypeCheckOptions::default()
+    };
+    let items = syntax_parsing::parse(source).unwrap();
+    type_check_with(&items, &options).expect("expected successful type check")
+}
+
+#[test]
+fn lint_float_equality_is_flagged_when_enabled() {
+    use semantic_analysis::WarningCode;
+
+    let source = r#"func test(a: f64, b: f64) -> bool {
+        a == b
+    }"#;
+
+    let warnings = check_float_equality(source);
+    assert_eq!(warnings.len(), 1, "got {:?}", warnings);
+    assert_eq!(warnings[0].code, WarningCode::FloatEquality);
+    assert!(
+        warnings[0].message.contains("epsilon"),
+        "{}",
+        warnings[0].message
+    );
+
+    let items = syntax_parsing::parse(source).unwrap();
+    let warnings = type_check(&items).expect("expected successful type check");
+    assert!(
+        warnings.is_empty(),
+        "the lint is opt-in, got {:?}",
+        warnings
+    );
+}
+
+#[test]
+fn lint_float_equality_ignores_integers_and_allowed_functions() {
+    let integers = r#"func test(a: i32, b: i32) -> bool {
+        a == b || a != b
+    }"#;
+    assert!(check_float_equality(integers).is_empty());
+
+    let allowed = r#"
+        @allow(float_equality)
+        func test(a: f32, b: f32) -> bool {
+            a != b
+        }
+    "#;
+    assert!(check_float_equality(allowed).is_empty());
+}
+
+fn check_redundant_parens(source: &str) -> Vec<semantic_analysis::Warning> {
+    use semantic_analysis::{type_check_with, TypeCheckOptions};
+
+    let options = TypeCheckOptions {
+        redundant_parens: true,
+        ..TypeCheckOptions::default()
+    };
+    let items = syntax_parsing::parse(source).unwrap();
+    type_check_with(&items, &options).expect("expected successful type check")
+}
+
+#[test]
+fn lint_redundant_parens_flags_returned_and_single_operand_parens() {
+    use diagnostics::Severity;
+    use semantic_analysis::WarningCode;
+
+    let source = r#"func test(x: i32, a: i32, b: i32) -> i32 {
+    val y = (a) + b
+    return (x)
+}"#;
+
+    let warnings = check_redundant_parens(source);
+    assert_eq!(warnings.len(), 2, "got {:?}", warnings);
+    assert!(warnings
+        .iter()
+        .all(|w| w.code == WarningCode::RedundantParens));
+    assert_eq!(WarningCode::RedundantParens.severity(), Severity::Hint);
+    let flagged: Vec<&str> = warnings
+        .iter()
+        .map(|w| &source[w.span.start..w.span.end])
+        .collect();
+    assert_eq!(flagged, ["(a)", "(x)"]);
+    assert!(warnings[1].message.contains("around `x`"));
+
+    let items = syntax_parsing::parse(source).unwrap();
+    let warnings = type_check(&items).expect("expected successful type check");
+    assert!(
+        warnings.is_empty(),
+        "the lint is opt-in, got {:?}",
+        warnings
+    );
+}
+
+#[test]
+fn lint_redundant_parens_keeps_parens_that_group() {
+    let source = r#"func test(a: i32, b: i32, c: i32) -> i32 {
+    val product = (a + b) * c
+    val negated = -(a - b)
+    product + negated
+}"#;
+    assert!(check_redundant_parens(source).is_empty());
+
+    let allowed = r#"
+        @allow(redundant_parens)
+        func test(x: i32) -> i32 {
+            return (x)
+        }
+    "#;
+    assert!(check_redundant_parens(allowed).is_empty());
+}
+
+#[test]
+fn lint_redundant_parens_honors_allow_on_a_nested_function() {
+    let source = r#"func test(x: i32) -> i32 {
+    @allow(redundant_parens)
+    func quiet(y: i32) -> i32 {
+        return (y)
+    }
+    func loud(y: i32) -> i32 {
+        return (y)
+    }
+    quiet(x) + loud(x)
+}"#;
+    let warnings = check_redundant_parens(source);
+    assert_eq!(warnings.len(), 1, "got {:?}", warnings);
+    assert!(source[warnings[0].span.end..].starts_with("\n    }\n    quiet"));
+}
+
+#[test]
+fn lint_redundant_parens_keeps_parens_around_a_callee() {
+    // `s.f(1)` would look for a method `f`; the parentheses make it a call of
+    // the function stored in the field.
+    let source = r#"struct S { f: (i32) -> i32 }
+func double(x: i32) -> i32 { x * 2 }
+func test() -> i32 {
+    val s = S { f: double }
+    val r = (s.f)(1)
+    r
+}"#;
+    assert!(check_redundant_parens(source).is_empty());
+}
+
+#[test]
+fn lint_redundant_parens_suggests_removing_the_pair() {
+    let source = r#"func test(x: i32, a: i32, b: i32) -> i32 {
+    val y = (a) + b
+    return(x + y)
+}"#;
+    let warnings = check_redundant_parens(source);
+    assert_eq!(warnings.len(), 2, "got {:?}", warnings);
+    let fixes: Vec<(String, String)> = warnings
+        .iter()
+        .map(|w| {
+            let diagnostic = w.to_diagnostic(source);
+            assert_eq!(diagnostic.suggestions.len(), 1);
+            let fix = &diagnostic.suggestions[0];
+            assert_eq!(fix.span, w.span);
+            (fix.replacement.clone(), fix.label.clone())
+        })
+        .collect();
+    assert_eq!(fixes[0].0, "a");
+    assert_eq!(fixes[0].1, "remove the parentheses: `a`");
+    // The keyword and the operand must stay apart once the pair is gone.
+    assert_eq!(fixes[1].0, " x + y");
+}
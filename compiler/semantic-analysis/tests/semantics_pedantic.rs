@@ -0,0 +1,177 @@
+// Integration tests: pedantic mode, which rejects reliance on inference defaults
+
+use semantic_analysis::{type_check, type_check_with, Type, TypeCheckOptions, TypeError};
+
+fn check_pedantic(source: &str) -> Result<(), Vec<TypeError>> {
+    let items = syntax_parsing::parse(source).expect("parse should succeed");
+    let options = TypeCheckOptions {
+        pedantic: true,
+        ..TypeCheckOptions::default()
+    };
+    type_check_with(&items, &options).map(|_| ())
+}
+
+fn check_lenient(source: &str) -> Result<(), Vec<TypeError>> {
+    let items = syntax_parsing::parse(source).expect("parse should succeed");
+    type_check(&items).map(|_| ())
+}
+
+#[test]
+fn unannotated_literal_binding_is_rejected() {
+    let source = r#"
+func main() -> i32 {
+    val x = 1
+    return x
+}
+"#;
+    let errors = check_pedantic(source).expect_err("pedantic mode should reject `val x = 1`");
+    match errors.as_slice() {
+        [TypeError::PedanticDefaultLiteralType { name, ty, .. }] => {
+            assert_eq!(name, "x");
+            assert_eq!(*ty, Type::I32);
+        }
+        other => panic!("expected one default-literal error, got {other:?}"),
+    }
+    assert_eq!(check_lenient(source), Ok(()));
+}
+
+#[test]
+fn annotated_or_suffixed_binding_passes() {
+    let source = r#"
+func main() -> i32 {
+    val x: i32 = 1
+    val y = 2i32
+    val z: f64 = 0.5 * 2.0
+    return x + y
+}
+"#;
+    assert_eq!(check_pedantic(source), Ok(()));
+}
+
+#[test]
+fn default_type_through_arithmetic_is_rejected() {
+    let source = r#"
+func main() -> i32 {
+    val scale = -(2.5) * 4.0
+    return 0
+}
+"#;
+    let errors = check_pedantic(source).expect_err("the binding still takes `f64` by default");
+    assert!(
+        matches!(
+            errors.as_slice(),
+            [TypeError::PedanticDefaultLiteralType { ty: Type::F64, .. }]
+        ),
+        "got {errors:?}"
+    );
+}
+
+#[test]
+fn binding_typed_by_a_non_literal_passes() {
+    let source = r#"
+func width() -> u16 { 640u16 }
+
+func main() -> i32 {
+    val w = width()
+    val doubled = w * 2
+    val ok = 1 < 2
+    return 0
+}
+"#;
+    assert_eq!(check_pedantic(source), Ok(()));
+}
+
+#[test]
+fn discarded_call_result_is_rejected() {
+    let source = r#"
+func compute() -> i32 { 42i32 }
+
+func main() -> i32 {
+    compute()
+    return 0
+}
+"#;
+    let errors = check_pedantic(source).expect_err("the i32 result is dropped");
+    assert!(
+        matches!(
+            errors.as_slice(),
+            [TypeError::PedanticUnusedResult { ty: Type::I32, .. }]
+        ),
+        "got {errors:?}"
+    );
+    assert_eq!(check_lenient(source), Ok(()));
+}
+
+#[test]
+fn void_calls_and_returned_tails_pass() {
+    let source = r#"
+func log_it() { }
+
+func compute() -> i32 { 42i32 }
+
+func main() -> i32 {
+    log_it()
+    compute()
+}
+"#;
+    assert_eq!(check_pedantic(source), Ok(()));
+}
+
+#[test]
+fn literal_next_to_a_typed_operand_passes() {
+    // A typed operand decides the type, so the literal's default is not relied on.
+    let source = r#"
+func main() -> i32 {
+    val y: i32 = 5
+    val a = 1 + y
+    val w: i64 = 7
+    val b = w + 1
+    val c = -(w * 2) - 3
+    return 0
+}
+"#;
+    assert_eq!(check_pedantic(source), Ok(()));
+
+    let both_literals = r#"
+func main() -> i32 {
+    val d = 1 + 2
+    return 0
+}
+"#;
+    let errors = check_pedantic(both_literals).expect_err("`1 + 2` takes `i32` by default");
+    assert!(
+        matches!(
+            errors.as_slice(),
+            [TypeError::PedanticDefaultLiteralType { ty: Type::I32, .. }]
+        ),
+        "got {errors:?}"
+    );
+}
+
+#[test]
+fn discarded_method_call_results_are_rejected() {
+    let source = r#"
+struct Counter { n: i32 }
+impl Counter {
+    func get(&self) -> i32 { self.n }
+    func make() -> Counter { Counter { n: 1i32 } }
+}
+
+func main() -> i32 {
+    (Counter::make())
+    val c = Counter { n: 1i32 }
+    c.get()
+    "abc".len()
+    return 0
+}
+"#;
+    let errors = check_pedantic(source).expect_err("three results are dropped");
+    let dropped: Vec<String> = errors
+        .iter()
+        .map(|e| match e {
+            TypeError::PedanticUnusedResult { ty, .. } => ty.to_string(),
+            other => panic!("expected only unused results, got {other:?}"),
+        })
+        .collect();
+    assert_eq!(dropped, ["Counter", "i32", "u64"]);
+}
@@ -0,0 +1,129 @@
+// Integration tests: the reference index behind go-to-definition
+
+use semantic_analysis::{build_reference_index, ReferenceIndex};
+use shared_types::Span;
+
+fn index(source: &str) -> ReferenceIndex {
+    let items = syntax_parsing::parse(source).expect("parse should succeed");
+    build_reference_index(&items)
+}
+
+/// Byte offset of the `nth` (0-based) occurrence of `needle` in `source`.
+fn offset_of(source: &str, needle: &str, nth: usize) -> usize {
+    source
+        .match_indices(needle)
+        .nth(nth)
+        .map(|(offset, _)| offset)
+        .unwrap_or_else(|| panic!("`{needle}` occurs fewer than {} times", nth + 1))
+}
+
+/// Span of the identifier `name` at the `nth` (0-based) occurrence of `context`,
+/// which must start with `name`.
+fn span_of(source: &str, name: &str, context: &str, nth: usize) -> Span {
+    let start = offset_of(source, context, nth);
+    Span::new(start, start + name.len())
+}
+
+#[test]
+fn variable_use_resolves_to_its_val_declaration() {
+    let source = r#"
+func main() -> i32 {
+    val total = 40
+    return total + 2
+}
+"#;
+    let index = index(source);
+    let use_site = offset_of(source, "total", 1);
+    assert_eq!(
+        index.definition_at(use_site),
+        Some(span_of(source, "total", "total", 0))
+    );
+    // Any byte inside the identifier resolves, the byte after it does not.
+    assert_eq!(
+        index.definition_at(use_site + 4),
+        Some(span_of(source, "total", "total", 0))
+    );
+    assert_eq!(index.definition_at(use_site + 5), None);
+}
+
+#[test]
+fn call_resolves_to_its_function_definition() {
+    let source = r#"
+func square(n: i32) -> i32 {
+    return n * n
+}
+
+func main() -> i32 {
+    return square(7)
+}
+"#;
+    let index = index(source);
+    assert_eq!(
+        index.definition_at(offset_of(source, "square", 1)),
+        Some(span_of(source, "square", "square", 0))
+    );
+    // Parameter uses resolve to the parameter.
+    assert_eq!(
+        index.definition_at(offset_of(source, "n * n", 0)),
+        Some(span_of(source, "n", "n:", 0))
+    );
+}
+
+#[test]
+fn inner_binding_shadows_outer_one() {
+    let source = r#"
+func main() -> i32 {
+    val x = 1
+    if true {
+        val x = 2
+        return x
+    }
+    return x
+}
+"#;
+    let index = index(source);
+    assert_eq!(
+        index.definition_at(offset_of(source, "x", 2)),
+        Some(span_of(source, "x", "x = 2", 0))
+    );
+    assert_eq!(
+        index.definition_at(offset_of(source, "x", 3)),
+        Some(span_of(source, "x", "x = 1", 0))
+    );
+}
+
+#[test]
+fn pattern_and_loop_bindings_are_indexed() {
+    let source = r#"
+func main() -> i32 {
+    mut sum: i32 = 0
+    for i in 0..3 {
+        sum = sum + i
+    }
+    return match sum {
+        n => n
+    }
+}
+"#;
+    let index = index(source);
+    assert_eq!(
+        index.definition_at(offset_of(source, "i\n", 0)),
+        Some(span_of(source, "i", "i in", 0))
+    );
+    assert_eq!(
+        index.definition_at(offset_of(source, "=> n", 0) + 3),
+        Some(span_of(source, "n", "n =>", 0))
+    );
+}
+
+#[test]
+fn unresolved_names_are_not_indexed() {
+    let source = r#"
+func main() -> i32 {
+    return missing
+}
+"#;
+    let index = index(source);
+    assert!(index.is_empty());
+    assert_eq!(index.definition_at(offset_of(source, "missing", 0)), None);
+}
@@ -0,0 +1,15 @@
+// Fuzz target: `tokenize` and `parse` must return `Err`, never panic, on any input.
+//
+// Run with `cargo +nightly fuzz run parse` from `compiler/syntax-parsing`.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    // The front end takes `&str`; invalid UTF-8 is rejected before it is reached.
+    if let Ok(source) = std::str::from_utf8(data) {
+        let _ = lexical_analysis::tokenize(source);
+        let _ = syntax_parsing::parse(source);
+    }
+});
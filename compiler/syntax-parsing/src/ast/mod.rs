@@ -4,5 +4,5 @@ pub use ast_types::{
     ArraySize, Attribute, BinaryOp, ClosureParam, ConstDef, EnumDef, EnumPatternPayload,
     EnumVariant, Expr, FieldDef, FieldInit, FieldPattern, FunctionDef, GenericArg, GenericParam,
     GenericParamKind, ImplDef, Item, MatchArm, MethodDef, NewtypeDef, Parameter, Pattern,
-    SelfParam, Stmt, StructDef, TraitDef, TraitMethod, Type, UnaryOp, VariantPayload,
+    SelfParam, Spanned, Stmt, StructDef, TraitDef, TraitMethod, Type, UnaryOp, VariantPayload,
 };
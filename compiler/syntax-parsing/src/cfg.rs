@@ -0,0 +1,89 @@
+// Conditional compilation: drop items whose `@cfg(...)` condition the active
+// configuration does not satisfy, before any later pass sees them.
+
+use std::collections::HashSet;
+
+use crate::ast::{Attribute, Item};
+use crate::errors::{ParseError, ParseResult};
+
+/// The attribute name that gates an item on the compile-time configuration.
+pub(crate) const CFG_ATTRIBUTE: &str = "cfg";
+
+/// The compile-time configuration `@cfg(...)` conditions are evaluated against:
+/// bare flags (`neurc --cfg debug`) and `key=value` pairs (`neurc --cfg target=cuda`).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CfgSet {
+    flags: HashSet<String>,
+    values: HashSet<(String, String)>,
+}
+
+impl CfgSet {
+    /// An empty configuration: every `@cfg`-gated item is excluded.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set a bare flag, satisfying `@cfg(name)`.
+    pub fn set_flag(&mut self, name: impl Into<String>) {
+        self.flags.insert(name.into());
+    }
+
+    /// Set a key to a value, satisfying `@cfg(key = "value")`. A key may hold
+    /// several values at once.
+    pub fn set_value(&mut self, key: impl Into<String>, value: impl Into<String>) {
+        self.values.insert((key.into(), value.into()));
+    }
+
+    /// Whether every condition of one `@cfg` attribute holds.
+    fn satisfies(&self, attr: &Attribute) -> bool {
+        attr.args.iter().all(|flag| self.flags.contains(&flag.name))
+            && attr
+                .named_args
+                .iter()
+                .all(|(key, value)| self.values.contains(&(key.name.clone(), value.clone())))
+    }
+
+    /// Whether an item carrying `attributes` is compiled in: every `@cfg` on it
+    /// must hold. An item with no `@cfg` is always kept.
+    fn includes(&self, attributes: &[Attribute]) -> ParseResult<bool> {
+        for attr in attributes.iter().filter(|a| a.name.name == CFG_ATTRIBUTE) {
+            if attr.args.is_empty() && attr.named_args.is_empty() {
+                return Err(ParseError::EmptyCfg { span: attr.span });
+            }
+            if !self.satisfies(attr) {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+}
+
+/// Remove every function, struct, and method whose `@cfg` conditions `cfg` does
+/// not satisfy. Other items carry no attributes and are always kept.
+pub(crate) fn strip_cfg(items: Vec<Item>, cfg: &CfgSet) -> ParseResult<Vec<Item>> {
+    let mut kept = Vec::with_capacity(items.len());
+    for item in items {
+        let include = match &item {
+            Item::Function(func) => cfg.includes(&func.attributes)?,
+            Item::Struct(def) => cfg.includes(&def.attributes)?,
+            _ => true,
+        };
+        if !include {
+            continue;
+        }
+        match item {
+            Item::Impl(mut def) => {
+                let mut methods = Vec::with_capacity(def.methods.len());
+                for method in def.methods {
+                    if cfg.includes(&method.attributes)? {
+                        methods.push(method);
+                    }
+                }
+                def.methods = methods;
+                kept.push(Item::Impl(def));
+            }
+            other => kept.push(other),
+        }
+    }
+    Ok(kept)
+}
@@ -0,0 +1,833 @@
+// Item-level AST diff for incremental recompilation
+
+use std::collections::HashMap;
+
+use ast_types::{
+    ArraySize, Attribute, ClosureParam, ConstDef, EnumDef, EnumPatternPayload, EnumVariant, Expr,
+    FieldDef, FieldInit, FieldPattern, FunctionDef, GenericArg, GenericParam, GenericParamKind,
+    ImplDef, Item, MatchArm, MethodDef, NewtypeDef, Parameter, Pattern, Stmt, StructDef, TraitDef,
+    TraitMethod, Type, VariantPayload,
+};
+use shared_types::{Identifier, Span};
+
+/// How a top-level item differs between two parses of a file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ItemChange {
+    /// Present only in the new file.
+    Added,
+    /// Present only in the old file.
+    Removed,
+    /// Present in both, with a different structure.
+    Modified,
+}
+
+/// A top-level item that must be recompiled.
+///
+/// `name` is the item's declared name; an `impl` block is named `impl T` or
+/// `impl Trait for T`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChangedItem {
+    pub name: String,
+    pub change: ItemChange,
+}
+
+/// The top-level items of `new` that differ from `old`, matched by name.
+///
+/// Items are compared by structure, not position: spans are ignored, so moving a
+/// function or editing whitespace and comments around it reports nothing, while any
+/// change to its signature, attributes, or body reports it as `Modified`. Blocks that
+/// share a name, such as two `impl T` blocks, are matched in source order.
+///
+/// The result lists added and modified items in the order of `new`, then removed
+/// items in the order of `old`. A caller keying a cache per function still decides
+/// what a changed struct or trait invalidates; this only says which items changed.
+///
+/// # Examples
+///
+/// ```
+/// use syntax_parsing::{ast_diff, parse, ChangedItem, ItemChange};
+///
+/// let old = parse("func a() -> i32 { return 1 }\nfunc b() -> i32 { return 2 }").unwrap();
+/// let new = parse("func b() -> i32 { return 3 }\n\nfunc a() -> i32 { return 1 }").unwrap();
+///
+/// assert_eq!(
+///     ast_diff(&old, &new),
+///     vec![ChangedItem { name: "b".to_string(), change: ItemChange::Modified }]
+/// );
+/// ```
+pub fn ast_diff(old: &[Item], new: &[Item]) -> Vec<ChangedItem> {
+    let mut old_items: HashMap<String, Vec<Item>> = HashMap::new();
+    let mut old_order = Vec::new();
+    for item in old {
+        let name = item_name(item);
+        let versions = old_items.entry(name.clone()).or_default();
+        if versions.is_empty() {
+            old_order.push(name);
+        }
+        versions.push(without_spans(item));
+    }
+
+    let mut changed = Vec::new();
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    for item in new {
+        let name = item_name(item);
+        let occurrence = seen.entry(name.clone()).or_default();
+        let change = match old_items.get(&name).and_then(|v| v.get(*occurrence)) {
+            None => Some(ItemChange::Added),
+            Some(before) if *before != without_spans(item) => Some(ItemChange::Modified),
+            Some(_) => None,
+        };
+        *occurrence += 1;
+        if let Some(change) = change {
+            changed.push(ChangedItem { name, change });
+        }
+    }
+
+    for name in old_order {
+        let kept = seen.get(&name).copied().unwrap_or(0);
+        let removed = old_items
+            .get(&name)
+            .map_or(0, Vec::len)
+            .saturating_sub(kept);
+        for _ in 0..removed {
+            changed.push(ChangedItem {
+                name: name.clone(),
+                change: ItemChange::Removed,
+            });
+        }
+    }
+    changed
+}
+
+/// The name an item is matched by across the two files.
+fn item_name(item: &Item) -> String {
+    match item {
+        Item::Function(def) => def.name.name.clone(),
+        Item::Struct(def) => def.name.name.clone(),
+        Item::Enum(def) => def.name.name.clone(),
+        Item::Trait(def) => def.name.name.clone(),
+        Item::Const(def) => def.name.name.clone(),
+        Item::Newtype(def) => def.name.name.clone(),
+        Item::Impl(def) => match &def.trait_name {
+            Some(trait_name) => format!("impl {} for {}", trait_name.name, def.type_name.name),
+            None => format!("impl {}", def.type_name.name),
+        },
+    }
+}
+
+/// The span every node is reset to before two items are compared.
+const NO_SPAN: Span = Span { start: 0, end: 0 };
+
+/// A copy of `item` with every span reset, so the derived `PartialEq` compares two
+/// items apart from where they sit in the file.
+///
+/// The walk below destructures every node without `..`, so a field added to the
+/// AST fails to compile here until the walk visits it.
+fn without_spans(item: &Item) -> Item {
+    let mut item = item.clone();
+    clear_item(&mut item);
+    item
+}
+
+fn clear_item(item: &mut Item) {
+    match item {
+        Item::Function(def) => clear_function(def),
+        Item::Struct(StructDef {
+            name,
+            generics,
+            lifetimes,
+            where_predicates,
+            fields,
+            attributes,
+            span,
+        }) => {
+            clear_ident(name);
+            generics.iter_mut().for_each(clear_generic_param);
+            lifetimes.iter_mut().for_each(clear_ident);
+            where_predicates.iter_mut().for_each(clear_expr);
+            fields.iter_mut().for_each(clear_field_def);
+            attributes.iter_mut().for_each(clear_attribute);
+            *span = NO_SPAN;
+        }
+        Item::Enum(EnumDef {
+            name,
+            variants,
+            span,
+        }) => {
+            clear_ident(name);
+            variants.iter_mut().for_each(clear_variant);
+            *span = NO_SPAN;
+        }
+        Item::Trait(TraitDef {
+            name,
+            methods,
+            span,
+        }) => {
+            clear_ident(name);
+            methods.iter_mut().for_each(clear_trait_method);
+            *span = NO_SPAN;
+        }
+        Item::Impl(ImplDef {
+            trait_name,
+            type_name,
+            generics,
+            lifetimes,
+            type_args,
+            where_predicates,
+            assoc_types,
+            methods,
+            span,
+        }) => {
+            trait_name.iter_mut().for_each(clear_ident);
+            clear_ident(type_name);
+            generics.iter_mut().for_each(clear_generic_param);
+            lifetimes.iter_mut().for_each(clear_ident);
+            type_args.iter_mut().for_each(clear_type);
+            where_predicates.iter_mut().for_each(clear_expr);
+            for (name, ty) in assoc_types {
+                clear_ident(name);
+                clear_type(ty);
+            }
+            methods.iter_mut().for_each(clear_method);
+            *span = NO_SPAN;
+        }
+        Item::Const(ConstDef {
+            name,
+            ty,
+            value,
+            span,
+        }) => {
+            clear_ident(name);
+            clear_type(ty);
+            clear_expr(value);
+            *span = NO_SPAN;
+        }
+        Item::Newtype(NewtypeDef { name, inner, span }) => {
+            clear_ident(name);
+            clear_type(inner);
+            *span = NO_SPAN;
+        }
+    }
+}
+
+fn clear_function(def: &mut FunctionDef) {
+    let FunctionDef {
+        name,
+        generics,
+        lifetimes,
+        where_predicates,
+        params,
+        return_type,
+        body,
+        attributes,
+        span,
+    } = def;
+    clear_ident(name);
+    generics.iter_mut().for_each(clear_generic_param);
+    lifetimes.iter_mut().for_each(clear_ident);
+    where_predicates.iter_mut().for_each(clear_expr);
+    params.iter_mut().for_each(clear_parameter);
+    return_type.iter_mut().for_each(clear_type);
+    clear_block(body);
+    attributes.iter_mut().for_each(clear_attribute);
+    *span = NO_SPAN;
+}
+
+fn clear_method(def: &mut MethodDef) {
+    let MethodDef {
+        name,
+        self_param: _,
+        params,
+        return_type,
+        body,
+        attributes,
+        span,
+    } = def;
+    clear_ident(name);
+    params.iter_mut().for_each(clear_parameter);
+    return_type.iter_mut().for_each(clear_type);
+    clear_block(body);
+    attributes.iter_mut().for_each(clear_attribute);
+    *span = NO_SPAN;
+}
+
+fn clear_trait_method(method: &mut TraitMethod) {
+    let TraitMethod {
+        name,
+        self_param: _,
+        params,
+        return_type,
+        default_body,
+        span,
+    } = method;
+    clear_ident(name);
+    params.iter_mut().for_each(clear_parameter);
+    return_type.iter_mut().for_each(clear_type);
+    if let Some(body) = default_body {
+        clear_block(body);
+    }
+    *span = NO_SPAN;
+}
+
+fn clear_generic_param(param: &mut GenericParam) {
+    let GenericParam {
+        name,
+        kind,
+        bounds,
+        span,
+    } = param;
+    clear_ident(name);
+    match kind {
+        GenericParamKind::Type => {}
+        GenericParamKind::Const(ty) => clear_type(ty),
+    }
+    bounds.iter_mut().for_each(clear_ident);
+    *span = NO_SPAN;
+}
+
+fn clear_attribute(attribute: &mut Attribute) {
+    let Attribute {
+        name,
+        args,
+        named_args,
+        span,
+    } = attribute;
+    clear_ident(name);
+    args.iter_mut().for_each(clear_ident);
+    for (name, _) in named_args {
+        clear_ident(name);
+    }
+    *span = NO_SPAN;
+}
+
+fn clear_parameter(param: &mut Parameter) {
+    let Parameter { name, ty, span } = param;
+    clear_ident(name);
+    clear_type(ty);
+    *span = NO_SPAN;
+}
+
+fn clear_field_def(field: &mut FieldDef) {
+    let FieldDef { name, ty, span } = field;
+    clear_ident(name);
+    clear_type(ty);
+    *span = NO_SPAN;
+}
+
+fn clear_variant(variant: &mut EnumVariant) {
+    let EnumVariant {
+        name,
+        payload,
+        span,
+    } = variant;
+    clear_ident(name);
+    match payload {
+        VariantPayload::Unit => {}
+        VariantPayload::Tuple(types) => types.iter_mut().for_each(clear_type),
+        VariantPayload::Struct(fields) => fields.iter_mut().for_each(clear_field_def),
+    }
+    *span = NO_SPAN;
+}
+
+fn clear_type(ty: &mut Type) {
+    match ty {
+        Type::Named(name) => clear_ident(name),
+        Type::Reference {
+            inner,
+            mutable: _,
+            lifetime,
+            span,
+        } => {
+            clear_type(inner);
+            lifetime.iter_mut().for_each(clear_ident);
+            *span = NO_SPAN;
+        }
+        Type::Array {
+            element,
+            size,
+            span,
+        } => {
+            clear_type(element);
+            match size {
+                ArraySize::Literal(_) => {}
+                ArraySize::Const(name) => clear_ident(name),
+            }
+            *span = NO_SPAN;
+        }
+        Type::Tuple { elements, span } => {
+            elements.iter_mut().for_each(clear_type);
+            *span = NO_SPAN;
+        }
+        Type::Generic { name, args, span } => {
+            clear_ident(name);
+            args.iter_mut().for_each(clear_generic_arg);
+            *span = NO_SPAN;
+        }
+        Type::ImplTrait { trait_name, span } | Type::DynTrait { trait_name, span } => {
+            clear_ident(trait_name);
+            *span = NO_SPAN;
+        }
+        Type::Function { params, ret, span } => {
+            params.iter_mut().for_each(clear_type);
+            clear_type(ret);
+            *span = NO_SPAN;
+        }
+        Type::Tensor {
+            element_type,
+            shape: _,
+            span,
+        } => {
+            clear_type(element_type);
+            *span = NO_SPAN;
+        }
+    }
+}
+
+fn clear_generic_arg(arg: &mut GenericArg) {
+    match arg {
+        GenericArg::Type(ty) => clear_type(ty),
+        GenericArg::Const { value: _, span } => *span = NO_SPAN,
+    }
+}
+
+fn clear_block(stmts: &mut [Stmt]) {
+    stmts.iter_mut().for_each(clear_stmt);
+}
+
+fn clear_stmt(stmt: &mut Stmt) {
+    match stmt {
+        Stmt::VarDecl {
+            name,
+            ty,
+            init,
+            mutable: _,
+            span,
+        } => {
+            clear_ident(name);
+            ty.iter_mut().for_each(clear_type);
+            init.iter_mut().for_each(clear_expr);
+            *span = NO_SPAN;
+        }
+        Stmt::Assignment {
+            target,
+            value,
+            span,
+        } => {
+            clear_ident(target);
+            clear_expr(value);
+            *span = NO_SPAN;
+        }
+        Stmt::Return { value, span } => {
+            value.iter_mut().for_each(clear_expr);
+            *span = NO_SPAN;
+        }
+        Stmt::If {
+            condition,
+            then_block,
+            else_if_blocks,
+            else_block,
+            span,
+        } => {
+            clear_if(condition, then_block, else_if_blocks, else_block);
+            *span = NO_SPAN;
+        }
+        Stmt::IfLet {
+            pattern,
+            scrutinee,
+            then_block,
+            else_block,
+            span,
+        } => {
+            clear_pattern(pattern);
+            clear_expr(scrutinee);
+            clear_block(then_block);
+            if let Some(block) = else_block {
+                clear_block(block);
+            }
+            *span = NO_SPAN;
+        }
+        Stmt::While {
+            label,
+            condition,
+            body,
+            span,
+        } => {
+            label.iter_mut().for_each(clear_ident);
+            clear_expr(condition);
+            clear_block(body);
+            *span = NO_SPAN;
+        }
+        Stmt::WhileLet {
+            label,
+            pattern,
+            scrutinee,
+            body,
+            span,
+        } => {
+            label.iter_mut().for_each(clear_ident);
+            clear_pattern(pattern);
+            clear_expr(scrutinee);
+            clear_block(body);
+            *span = NO_SPAN;
+        }
+        Stmt::ForRange {
+            label,
+            iterator,
+            start,
+            end,
+            inclusive: _,
+            body,
+            span,
+        } => {
+            label.iter_mut().for_each(clear_ident);
+            clear_ident(iterator);
+            clear_expr(start);
+            clear_expr(end);
+            clear_block(body);
+            *span = NO_SPAN;
+        }
+        Stmt::ForEach {
+            label,
+            iterator,
+            iterable,
+            body,
+            span,
+        } => {
+            label.iter_mut().for_each(clear_ident);
+            clear_ident(iterator);
+            clear_expr(iterable);
+            clear_block(body);
+            *span = NO_SPAN;
+        }
+        Stmt::Loop { label, body, span } => {
+            label.iter_mut().for_each(clear_ident);
+            clear_block(body);
+            *span = NO_SPAN;
+        }
+        Stmt::Break { label, value, span } => {
+            label.iter_mut().for_each(clear_ident);
+            value.iter_mut().for_each(clear_expr);
+            *span = NO_SPAN;
+        }
+        Stmt::Continue { label, span } => {
+            label.iter_mut().for_each(clear_ident);
+            *span = NO_SPAN;
+        }
+        Stmt::FieldAssignment {
+            object,
+            field,
+            value,
+            span,
+        } => {
+            clear_ident(object);
+            clear_ident(field);
+            clear_expr(value);
+            *span = NO_SPAN;
+        }
+        Stmt::DerefAssignment {
+            pointer,
+            value,
+            span,
+        } => {
+            clear_expr(pointer);
+            clear_expr(value);
+            *span = NO_SPAN;
+        }
+        Stmt::IndexAssignment {
+            target,
+            index,
+            value,
+            span,
+        } => {
+            clear_ident(target);
+            clear_expr(index);
+            clear_expr(value);
+            *span = NO_SPAN;
+        }
+        Stmt::Const {
+            name,
+            ty,
+            value,
+            span,
+        } => {
+            clear_ident(name);
+            clear_type(ty);
+            clear_expr(value);
+            *span = NO_SPAN;
+        }
+        Stmt::Func(def) => clear_function(def),
+        Stmt::Block(stmts, span) => {
+            clear_block(stmts);
+            *span = NO_SPAN;
+        }
+        Stmt::Expr(expr) => clear_expr(expr),
+    }
+}
+
+fn clear_if(
+    condition: &mut Expr,
+    then_block: &mut [Stmt],
+    else_if_blocks: &mut [(Expr, Vec<Stmt>)],
+    else_block: &mut Option<Vec<Stmt>>,
+) {
+    clear_expr(condition);
+    clear_block(then_block);
+    for (condition, block) in else_if_blocks {
+        clear_expr(condition);
+        clear_block(block);
+    }
+    if let Some(block) = else_block {
+        clear_block(block);
+    }
+}
+
+fn clear_expr(expr: &mut Expr) {
+    match expr {
+        Expr::Literal(_, span) => *span = NO_SPAN,
+        Expr::Identifier(name) => clear_ident(name),
+        Expr::Binary {
+            left,
+            op: _,
+            right,
+            span,
+        } => {
+            clear_expr(left);
+            clear_expr(right);
+            *span = NO_SPAN;
+        }
+        Expr::Call {
+            func,
+            type_args,
+            args,
+            span,
+        } => {
+            clear_expr(func);
+            type_args.iter_mut().for_each(clear_generic_arg);
+            args.iter_mut().for_each(clear_expr);
+            *span = NO_SPAN;
+        }
+        Expr::Unary {
+            op: _,
+            operand,
+            span,
+        }
+        | Expr::Deref { operand, span }
+        | Expr::Reference {
+            operand,
+            mutable: _,
+            span,
+        } => {
+            clear_expr(operand);
+            *span = NO_SPAN;
+        }
+        Expr::Paren(inner, span) => {
+            clear_expr(inner);
+            *span = NO_SPAN;
+        }
+        Expr::StructLiteral {
+            name,
+            fields,
+            base,
+            span,
+        } => {
+            clear_ident(name);
+            fields.iter_mut().for_each(clear_field_init);
+            if let Some(base) = base {
+                clear_expr(base);
+            }
+            *span = NO_SPAN;
+        }
+        Expr::FieldAccess {
+            object,
+            field,
+            span,
+        } => {
+            clear_expr(object);
+            clear_ident(field);
+            *span = NO_SPAN;
+        }
+        Expr::EnumStructLiteral {
+            enum_name,
+            variant,
+            fields,
+            span,
+        } => {
+            clear_ident(enum_name);
+            clear_ident(variant);
+            fields.iter_mut().for_each(clear_field_init);
+            *span = NO_SPAN;
+        }
+        Expr::Path {
+            type_name,
+            member,
+            span,
+        } => {
+            clear_ident(type_name);
+            clear_ident(member);
+            *span = NO_SPAN;
+        }
+        Expr::Cast {
+            expr,
+            target_type,
+            span,
+        } => {
+            clear_expr(expr);
+            clear_type(target_type);
+            *span = NO_SPAN;
+        }
+        Expr::If {
+            condition,
+            then_block,
+            else_if_blocks,
+            else_block,
+            span,
+        } => {
+            clear_if(condition, then_block, else_if_blocks, else_block);
+            *span = NO_SPAN;
+        }
+        Expr::Block { stmts, span } | Expr::Unsafe { stmts, span } => {
+            clear_block(stmts);
+            *span = NO_SPAN;
+        }
+        Expr::Loop { label, body, span } => {
+            label.iter_mut().for_each(clear_ident);
+            clear_block(body);
+            *span = NO_SPAN;
+        }
+        Expr::Range {
+            start,
+            end,
+            inclusive: _,
+            span,
+        } => {
+            clear_expr(start);
+            clear_expr(end);
+            *span = NO_SPAN;
+        }
+        Expr::ArrayLiteral { elements, span } | Expr::TupleLiteral { elements, span } => {
+            elements.iter_mut().for_each(clear_expr);
+            *span = NO_SPAN;
+        }
+        Expr::Index {
+            object,
+            index,
+            span,
+        } => {
+            clear_expr(object);
+            clear_expr(index);
+            *span = NO_SPAN;
+        }
+        Expr::TupleIndex {
+            object,
+            index: _,
+            span,
+        } => {
+            clear_expr(object);
+            *span = NO_SPAN;
+        }
+        Expr::ArrayRest {
+            array,
+            start: _,
+            exact: _,
+            span,
+        } => {
+            clear_expr(array);
+            *span = NO_SPAN;
+        }
+        Expr::Match {
+            scrutinee,
+            arms,
+            span,
+        } => {
+            clear_expr(scrutinee);
+            arms.iter_mut().for_each(clear_match_arm);
+            *span = NO_SPAN;
+        }
+        Expr::Closure {
+            params,
+            ret,
+            body,
+            is_move: _,
+            span,
+        } => {
+            params.iter_mut().for_each(clear_closure_param);
+            ret.iter_mut().for_each(clear_type);
+            clear_expr(body);
+            *span = NO_SPAN;
+        }
+    }
+}
+
+fn clear_field_init(field: &mut FieldInit) {
+    let FieldInit { name, value, span } = field;
+    clear_ident(name);
+    clear_expr(value);
+    *span = NO_SPAN;
+}
+
+fn clear_match_arm(arm: &mut MatchArm) {
+    let MatchArm {
+        patterns,
+        guard,
+        body,
+        span,
+    } = arm;
+    patterns.iter_mut().for_each(clear_pattern);
+    if let Some(guard) = guard {
+        clear_expr(guard);
+    }
+    clear_expr(body);
+    *span = NO_SPAN;
+}
+
+fn clear_closure_param(param: &mut ClosureParam) {
+    let ClosureParam { name, ty, span } = param;
+    clear_ident(name);
+    ty.iter_mut().for_each(clear_type);
+    *span = NO_SPAN;
+}
+
+fn clear_pattern(pattern: &mut Pattern) {
+    match pattern {
+        Pattern::Wildcard(span) | Pattern::Literal(_, span) => *span = NO_SPAN,
+        Pattern::Binding(name) => clear_ident(name),
+        Pattern::Range {
+            start: _,
+            end: _,
+            inclusive: _,
+            span,
+        } => *span = NO_SPAN,
+        Pattern::Enum {
+            enum_name,
+            variant,
+            payload,
+            span,
+        } => {
+            clear_ident(enum_name);
+            clear_ident(variant);
+            match payload {
+                EnumPatternPayload::Unit => {}
+                EnumPatternPayload::Tuple(patterns) => patterns.iter_mut().for_each(clear_pattern),
+                EnumPatternPayload::Struct(fields) => {
+                    fields.iter_mut().for_each(clear_field_pattern)
+                }
+            }
+            *span = NO_SPAN;
+        }
+    }
+}
+
+fn clear_field_pattern(field: &mut FieldPattern) {
+    let FieldPattern {
+        field,
+        pattern,
+        span,
+    } = field;
+    clear_ident(field);
+    clear_pattern(pattern);
+    *span = NO_SPAN;
+}
+
+fn clear_ident(ident: &mut Identifier) {
+    ident.span = NO_SPAN;
+}
@@ -32,9 +32,35 @@ pub enum ParseError {
     #[error("type alias '{name}' is defined in terms of itself (cyclic alias)")]
     CyclicTypeAlias { name: String, span: Span },
 
+    #[error(
+        "`@cfg` needs at least one condition, such as `@cfg(debug)` or `@cfg(target = \"cuda\")`"
+    )]
+    EmptyCfg { span: Span },
+
+    #[error("`key = value` arguments are only accepted by `@cfg`, not `@{attribute}`")]
+    NamedAttributeArgument { attribute: String, span: Span },
+
     #[error("lexical error: {0}")]
     LexError(#[from] LexError),
 }
 
+impl ParseError {
+    /// The source span the error points at, or `None` when it has no single
+    /// location (end of input, nesting-depth overflow).
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            Self::UnexpectedToken { span, .. }
+            | Self::DuplicateParameter { span, .. }
+            | Self::DuplicateTypeAlias { span, .. }
+            | Self::TypeAliasShadowsBuiltin { span, .. }
+            | Self::CyclicTypeAlias { span, .. }
+            | Self::EmptyCfg { span }
+            | Self::NamedAttributeArgument { span, .. } => Some(*span),
+            Self::LexError(error) => Some(error.span()),
+            Self::UnexpectedEof { .. } | Self::MaxDepthExceeded(_) => None,
+        }
+    }
+}
+
 /// Result type for parsing operations
 pub type ParseResult<T> = Result<T, ParseError>;
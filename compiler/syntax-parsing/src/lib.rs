@@ -1,19 +1,25 @@
 // Feature slice for AST generation and syntax analysis.
-// Public API: the `parse()` and `parse_expr()` entry points.
+// Public API: the `parse()`, `parse_with_cfg()`, `parse_tokens()`, and `parse_expr()`
+// entry points, plus `ast_diff()` comparing two parses item by item.
 
 mod ast;
+mod cfg;
+mod diff;
 mod errors;
 mod parser;
 mod precedence;
 
 pub use ast::{
     ArraySize, Attribute, BinaryOp, EnumDef, EnumPatternPayload, EnumVariant, Expr, FieldDef,
-    FieldInit, FieldPattern, FunctionDef, GenericArg, GenericParamKind, ImplDef, Item, MatchArm,
-    MethodDef, Parameter, Pattern, SelfParam, Stmt, StructDef, Type, UnaryOp, VariantPayload,
+    FieldInit, FieldPattern, FunctionDef, GenericArg, GenericParam, GenericParamKind, ImplDef,
+    Item, MatchArm, MethodDef, Parameter, Pattern, SelfParam, Spanned, Stmt, StructDef, Type,
+    UnaryOp, VariantPayload,
 };
+pub use cfg::CfgSet;
+pub use diff::{ast_diff, ChangedItem, ItemChange};
 pub use errors::{ParseError, ParseResult};
 
-use lexical_analysis::tokenize;
+use lexical_analysis::{tokenize, Token};
 use parser::Parser;
 use precedence::Precedence;
 
@@ -41,9 +47,52 @@ use precedence::Precedence;
 /// }
 /// ```
 pub fn parse(source: &str) -> ParseResult<Vec<Item>> {
-    let tokens = tokenize(source)?;
+    parse_with_cfg(source, &CfgSet::new())
+}
+
+/// Parse Neuro source like [`parse`], keeping `@cfg(...)`-gated functions, structs,
+/// and methods only when `cfg` satisfies their conditions.
+///
+/// [`parse`] uses an empty configuration, so it drops every gated item.
+///
+/// # Examples
+///
+/// ```
+/// use syntax_parsing::{parse_with_cfg, CfgSet};
+///
+/// let source = r#"
+///     @cfg(target = "cuda")
+///     func kernel() -> i32 { 1 }
+/// "#;
+///
+/// let mut cfg = CfgSet::new();
+/// cfg.set_value("target", "cuda");
+/// assert_eq!(parse_with_cfg(source, &cfg).unwrap().len(), 1);
+/// assert_eq!(parse_with_cfg(source, &CfgSet::new()).unwrap().len(), 0);
+/// ```
+pub fn parse_with_cfg(source: &str, cfg: &CfgSet) -> ParseResult<Vec<Item>> {
+    parse_tokens(tokenize(source)?, cfg)
+}
+
+/// Parse an already-lexed, `Eof`-terminated token stream like [`parse_with_cfg`].
+///
+/// With [`lexical_analysis::tokenize_all`], this parses the tokens around lexical
+/// errors, so a driver can report syntax errors alongside every lexical one.
+///
+/// # Examples
+///
+/// ```
+/// use lexical_analysis::tokenize_all;
+/// use syntax_parsing::{parse_tokens, CfgSet};
+///
+/// let (tokens, errors) = tokenize_all("func main() -> i32 { $ 0 }");
+/// assert_eq!(errors.len(), 1);
+/// assert_eq!(parse_tokens(tokens, &CfgSet::new()).unwrap().len(), 1);
+/// ```
+pub fn parse_tokens(tokens: Vec<Token>, cfg: &CfgSet) -> ParseResult<Vec<Item>> {
     let mut parser = Parser::new(tokens);
-    parser.parse_program()
+    let items = parser.parse_program()?;
+    cfg::strip_cfg(items, cfg)
 }
 
 /// Parse a standalone Neuro expression — a convenience for tests and REPLs.
@@ -1,32 +1,29 @@
 use lexical_analysis::{Token, TokenKind};
 use shared_types::{Identifier, Literal, Span};
 
-use crate::ast::{BinaryOp, ClosureParam, Expr, GenericArg, Stmt, UnaryOp};
+use crate::ast::{BinaryOp, ClosureParam, Expr, GenericArg, Spanned, Stmt, UnaryOp};
 use crate::errors::{ParseError, ParseResult};
 use crate::precedence::Precedence;
 
-use super::statements::stmt_span;
-use super::Parser;
-
-/// Maximum expression nesting depth to prevent stack overflow
-const MAX_EXPR_DEPTH: usize = 256;
+use super::{Parser, MAX_CHAIN_DEPTH};
 
 impl Parser {
     /// Parse an expression with the given precedence
     pub fn parse_expr(&mut self, precedence: Precedence) -> ParseResult<Expr> {
-        if self.expr_depth >= MAX_EXPR_DEPTH {
-            return Err(ParseError::MaxDepthExceeded(MAX_EXPR_DEPTH));
-        }
-
-        self.expr_depth += 1;
-        let result = self.parse_expr_inner(precedence);
-        self.expr_depth -= 1;
+        self.nested(|parser| parser.parse_expr_inner(precedence))
+    }
 
+    /// Inner expression parsing implementation. The operator links this call
+    /// chains count toward [`MAX_CHAIN_DEPTH`] only while it runs.
+    fn parse_expr_inner(&mut self, precedence: Precedence) -> ParseResult<Expr> {
+        let chain_depth = self.chain_depth;
+        let result = self.parse_expr_chain(precedence);
+        self.chain_depth = chain_depth;
         result
     }
 
-    /// Inner expression parsing implementation
-    fn parse_expr_inner(&mut self, precedence: Precedence) -> ParseResult<Expr> {
+    /// Parse a prefix expression and the operators chained onto it
+    fn parse_expr_chain(&mut self, precedence: Precedence) -> ParseResult<Expr> {
         self.skip_newlines();
 
         let mut left = self.parse_prefix()?;
@@ -51,6 +48,10 @@ impl Parser {
                     break;
                 }
 
+                self.chain_depth += 1;
+                if self.chain_depth > MAX_CHAIN_DEPTH {
+                    return Err(ParseError::MaxDepthExceeded(MAX_CHAIN_DEPTH));
+                }
                 left = self.parse_infix(left)?;
             } else {
                 break;
@@ -418,7 +419,7 @@ impl Parser {
             .and_then(|s| s.last())
             .or_else(|| else_if_blocks.last().and_then(|(_, s)| s.last()))
             .or_else(|| then_block.last())
-            .map(stmt_span)
+            .map(Stmt::span)
             .unwrap_or(start_span);
 
         Ok(Expr::If {
@@ -452,7 +453,7 @@ impl Parser {
     fn parse_loop_expr(&mut self, start_span: Span) -> ParseResult<Expr> {
         self.skip_newlines();
         let body = self.parse_block()?;
-        let end_span = body.last().map(stmt_span).unwrap_or(start_span);
+        let end_span = body.last().map(Stmt::span).unwrap_or(start_span);
         Ok(Expr::Loop {
             label: None,
             body,
@@ -479,7 +480,7 @@ impl Parser {
         self.active_labels.pop();
         let body = body?;
 
-        let end_span = body.last().map(stmt_span).unwrap_or(start_span);
+        let end_span = body.last().map(Stmt::span).unwrap_or(start_span);
         Ok(Expr::Loop {
             label: Some(label),
             body,
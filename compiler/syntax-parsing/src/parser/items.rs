@@ -4,12 +4,12 @@ use shared_types::Identifier;
 use crate::ast::{
     Attribute, ConstDef, EnumDef, EnumVariant, Expr, FieldDef, FieldInit, FunctionDef,
     GenericParam, GenericParamKind, ImplDef, Item, MethodDef, NewtypeDef, Parameter, SelfParam,
-    StructDef, TraitDef, TraitMethod, Type, VariantPayload,
+    Spanned, Stmt, StructDef, TraitDef, TraitMethod, Type, VariantPayload,
 };
+use crate::cfg::CFG_ATTRIBUTE;
 use crate::errors::{ParseError, ParseResult};
 use crate::precedence::Precedence;
 
-use super::statements::stmt_span;
 use super::type_aliases::{expand_type_aliases, TypeAliasDecl};
 use super::Parser;
 
@@ -99,8 +99,9 @@ impl Parser {
         Ok(attributes)
     }
 
-    /// Parse a single `@name` or `@name(arg, ...)` attribute. Assumes the
-    /// current token is `@`.
+    /// Parse a single `@name` or `@name(arg, ...)` attribute. Each argument is an
+    /// identifier, an integer literal, or, in `@cfg` only, `key = value` with an
+    /// identifier or string value. Assumes the current token is `@`.
     fn parse_attribute(&mut self) -> ParseResult<Attribute> {
         let at = self.consume(TokenKind::At, "'@'")?;
 
@@ -119,6 +120,7 @@ impl Parser {
         };
 
         let mut args: Vec<Identifier> = Vec::new();
+        let mut named_args: Vec<(Identifier, String)> = Vec::new();
         let mut end_span = name.span;
 
         if self.check(&TokenKind::LeftParen) {
@@ -127,21 +129,56 @@ impl Parser {
 
             if !self.check(&TokenKind::RightParen) {
                 loop {
-                    let arg_token =
-                        self.consume(TokenKind::Identifier(String::new()), "attribute argument")?;
-                    let arg = if let TokenKind::Identifier(n) = arg_token.kind {
-                        Identifier {
+                    let arg_token = if self.check(&TokenKind::Integer(0)) {
+                        self.consume(TokenKind::Integer(0), "attribute argument")?
+                    } else {
+                        self.consume(TokenKind::Identifier(String::new()), "attribute argument")?
+                    };
+                    // An integer argument (`@optimize(3)`) keeps its decimal text.
+                    let arg = match arg_token.kind {
+                        TokenKind::Identifier(n) => Identifier {
                             name: n,
                             span: arg_token.span,
-                        }
-                    } else {
-                        return Err(ParseError::UnexpectedToken {
-                            found: arg_token.kind,
-                            expected: "attribute argument".to_string(),
+                        },
+                        TokenKind::Integer(value) => Identifier {
+                            name: value.to_string(),
                             span: arg_token.span,
-                        });
+                        },
+                        found => {
+                            return Err(ParseError::UnexpectedToken {
+                                found,
+                                expected: "attribute argument".to_string(),
+                                span: arg_token.span,
+                            });
+                        }
                     };
-                    args.push(arg);
+                    if self.check(&TokenKind::Equal) {
+                        if name.name != CFG_ATTRIBUTE {
+                            return Err(ParseError::NamedAttributeArgument {
+                                attribute: name.name,
+                                span: arg.span,
+                            });
+                        }
+                        self.advance(); // consume '='
+                        let value_token = if self.check(&TokenKind::String(String::new())) {
+                            self.consume(TokenKind::String(String::new()), "attribute value")?
+                        } else {
+                            self.consume(TokenKind::Identifier(String::new()), "attribute value")?
+                        };
+                        let value = match value_token.kind {
+                            TokenKind::String(v) | TokenKind::Identifier(v) => v,
+                            found => {
+                                return Err(ParseError::UnexpectedToken {
+                                    found,
+                                    expected: "attribute value".to_string(),
+                                    span: value_token.span,
+                                });
+                            }
+                        };
+                        named_args.push((arg, value));
+                    } else {
+                        args.push(arg);
+                    }
                     self.skip_newlines();
                     if !self.check(&TokenKind::Comma) {
                         break;
@@ -158,6 +195,7 @@ impl Parser {
         Ok(Attribute {
             name,
             args,
+            named_args,
             span: at.span.merge(end_span),
         })
     }
@@ -336,7 +374,7 @@ impl Parser {
 
         let body = self.parse_block()?;
 
-        let end_span = body.last().map(stmt_span).unwrap_or(start.span);
+        let end_span = body.last().map(Stmt::span).unwrap_or(start.span);
 
         Ok(FunctionDef {
             name,
@@ -1108,7 +1146,7 @@ impl Parser {
         let end_span = default_body
             .as_ref()
             .and_then(|b| b.last())
-            .map(stmt_span)
+            .map(Stmt::span)
             .unwrap_or(start.span);
         Ok(TraitMethod {
             name,
@@ -1225,7 +1263,7 @@ impl Parser {
         self.skip_newlines();
         let body = self.parse_block()?;
 
-        let end_span = body.last().map(stmt_span).unwrap_or(start.span);
+        let end_span = body.last().map(Stmt::span).unwrap_or(start.span);
 
         Ok(MethodDef {
             name,
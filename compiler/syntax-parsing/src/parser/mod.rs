@@ -11,11 +11,26 @@ mod statements;
 mod type_aliases;
 mod types;
 
+/// Maximum nesting depth of expressions, types, and patterns combined. Deeper input
+/// is a `MaxDepthExceeded` error rather than a stack overflow.
+const MAX_NESTING_DEPTH: usize = 256;
+
+/// Maximum number of operators (binary, postfix, call, index, field access) chained
+/// onto the expressions enclosing the current parse position. A chain is parsed by a
+/// loop, not recursion, but each link still nests the AST one level deeper, and an
+/// unbounded chain would overflow the stack when the tree is dropped or walked.
+pub(super) const MAX_CHAIN_DEPTH: usize = 4096;
+
 /// Parser for Neuro source code
 pub(crate) struct Parser {
     pub(super) tokens: Vec<Token>,
     pub(super) current: usize,
-    pub(super) expr_depth: usize,
+    /// Current recursion depth across expressions, types, and patterns; see
+    /// [`Parser::nested`].
+    pub(super) depth: usize,
+    /// Operator links chained so far along the current expression path; bounded by
+    /// [`MAX_CHAIN_DEPTH`].
+    pub(super) chain_depth: usize,
     /// When true, an identifier followed by `{` is NOT parsed as a struct literal.
     /// Set to true inside if/while/for conditions to prevent consuming the block's `{`.
     pub(super) no_struct_lit: bool,
@@ -36,13 +51,31 @@ impl Parser {
         Self {
             tokens,
             current: 0,
-            expr_depth: 0,
+            depth: 0,
+            chain_depth: 0,
             no_struct_lit: false,
             active_labels: Vec::new(),
             destructure_counter: 0,
         }
     }
 
+    /// Run `parse` one nesting level deeper, failing with `MaxDepthExceeded` once
+    /// the recursive descent passes [`MAX_NESTING_DEPTH`].
+    pub(super) fn nested<T>(
+        &mut self,
+        parse: impl FnOnce(&mut Self) -> ParseResult<T>,
+    ) -> ParseResult<T> {
+        if self.depth >= MAX_NESTING_DEPTH {
+            return Err(ParseError::MaxDepthExceeded(MAX_NESTING_DEPTH));
+        }
+
+        self.depth += 1;
+        let result = parse(self);
+        self.depth -= 1;
+
+        result
+    }
+
     /// Get the current token without consuming it
     pub(super) fn peek(&self) -> Option<&Token> {
         self.tokens.get(self.current)
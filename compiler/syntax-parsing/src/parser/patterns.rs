@@ -3,7 +3,7 @@
 use lexical_analysis::TokenKind;
 use shared_types::{Identifier, Literal, Span};
 
-use crate::ast::{EnumPatternPayload, Expr, FieldPattern, MatchArm, Pattern};
+use crate::ast::{EnumPatternPayload, Expr, FieldPattern, MatchArm, Pattern, Spanned};
 use crate::errors::{ParseError, ParseResult};
 use crate::precedence::Precedence;
 
@@ -84,7 +84,12 @@ impl Parser {
 
     /// Parse a single pattern: a wildcard, binding, literal, range, or enum
     /// variant pattern.
-    fn parse_pattern(&mut self) -> ParseResult<Pattern> {
+    pub(super) fn parse_pattern(&mut self) -> ParseResult<Pattern> {
+        self.nested(Self::parse_pattern_inner)
+    }
+
+    /// Inner pattern parsing implementation
+    fn parse_pattern_inner(&mut self) -> ParseResult<Pattern> {
         self.skip_newlines();
         let token = self.peek().ok_or(ParseError::UnexpectedEof {
             expected: "pattern".to_string(),
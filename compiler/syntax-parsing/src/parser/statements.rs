@@ -1,7 +1,7 @@
 use lexical_analysis::TokenKind;
 use shared_types::{Identifier, Literal, Span};
 
-use crate::ast::{BinaryOp, Expr, Stmt};
+use crate::ast::{BinaryOp, Expr, Pattern, Spanned, Stmt};
 use crate::errors::{ParseError, ParseResult};
 use crate::precedence::Precedence;
 
@@ -240,6 +240,10 @@ impl Parser {
     pub(crate) fn parse_if_stmt(&mut self, start_span: Span) -> ParseResult<Stmt> {
         self.skip_newlines();
 
+        if self.check(&TokenKind::Let) {
+            return self.parse_if_let_stmt(start_span);
+        }
+
         self.no_struct_lit = true;
         let condition = self.parse_expr(Precedence::Lowest)?;
         self.no_struct_lit = false;
@@ -256,9 +260,15 @@ impl Parser {
             self.skip_newlines();
 
             if self.check(&TokenKind::If) {
-                self.advance(); // consume 'if'
+                let if_span = self.advance().map(|t| t.span).unwrap_or(start_span);
                 self.skip_newlines();
 
+                // `else if let` ends the flat chain: it nests as the else block.
+                if self.check(&TokenKind::Let) {
+                    else_block = Some(vec![self.parse_if_let_stmt(if_span)?]);
+                    break;
+                }
+
                 self.no_struct_lit = true;
                 let else_if_condition = self.parse_expr(Precedence::Lowest)?;
                 self.no_struct_lit = false;
@@ -278,7 +288,7 @@ impl Parser {
             .and_then(|stmts| stmts.last())
             .or_else(|| else_if_blocks.last().and_then(|(_, stmts)| stmts.last()))
             .or_else(|| then_block.last())
-            .map(stmt_span)
+            .map(Stmt::span)
             .unwrap_or(start_span);
 
         Ok(Stmt::If {
@@ -290,6 +300,63 @@ impl Parser {
         })
     }
 
+    /// Parse the `let pattern = scrutinee` head shared by `if let` and
+    /// `while let`. The `let` keyword is the current token. The scrutinee is
+    /// parsed with struct-literals suppressed so the block brace is not read
+    /// as one.
+    fn parse_let_condition(&mut self) -> ParseResult<(Pattern, Expr)> {
+        self.advance(); // consume 'let'
+        let pattern = self.parse_pattern()?;
+        self.skip_newlines();
+        self.consume(TokenKind::Equal, "'=' after `let` pattern")?;
+        self.skip_newlines();
+
+        self.no_struct_lit = true;
+        let scrutinee = self.parse_expr(Precedence::Lowest)?;
+        self.no_struct_lit = false;
+        self.skip_newlines();
+
+        Ok((pattern, scrutinee))
+    }
+
+    /// Parse an `if let` statement. The `if` keyword is already consumed and
+    /// `let` is the current token. Any `else if` chain nests inside the else
+    /// block.
+    fn parse_if_let_stmt(&mut self, start_span: Span) -> ParseResult<Stmt> {
+        let (pattern, scrutinee) = self.parse_let_condition()?;
+
+        let then_block = self.parse_block()?;
+        self.skip_newlines();
+
+        let mut else_block = None;
+        if self.check(&TokenKind::Else) {
+            self.advance(); // consume 'else'
+            self.skip_newlines();
+
+            if self.check(&TokenKind::If) {
+                let if_span = self.advance().map(|t| t.span).unwrap_or(start_span);
+                else_block = Some(vec![self.parse_if_stmt(if_span)?]);
+            } else {
+                else_block = Some(self.parse_block()?);
+            }
+        }
+
+        let end_span = else_block
+            .as_ref()
+            .and_then(|stmts| stmts.last())
+            .or_else(|| then_block.last())
+            .map(Stmt::span)
+            .unwrap_or(scrutinee.span());
+
+        Ok(Stmt::IfLet {
+            pattern,
+            scrutinee,
+            then_block,
+            else_block,
+            span: start_span.merge(end_span),
+        })
+    }
+
     /// Parse a while statement, optionally prefixed with a loop `label`.
     pub(crate) fn parse_while_stmt(
         &mut self,
@@ -298,6 +365,20 @@ impl Parser {
     ) -> ParseResult<Stmt> {
         self.skip_newlines();
 
+        if self.check(&TokenKind::Let) {
+            let (pattern, scrutinee) = self.parse_let_condition()?;
+            let body = self.parse_labeled_block(label.as_ref())?;
+            let end_span = body.last().map(Stmt::span).unwrap_or(scrutinee.span());
+
+            return Ok(Stmt::WhileLet {
+                label,
+                pattern,
+                scrutinee,
+                body,
+                span: start_span.merge(end_span),
+            });
+        }
+
         self.no_struct_lit = true;
         let condition = self.parse_expr(Precedence::Lowest)?;
         self.no_struct_lit = false;
@@ -305,7 +386,7 @@ impl Parser {
 
         let body = self.parse_labeled_block(label.as_ref())?;
 
-        let end_span = body.last().map(stmt_span).unwrap_or(condition.span());
+        let end_span = body.last().map(Stmt::span).unwrap_or(condition.span());
 
         Ok(Stmt::While {
             label,
@@ -325,7 +406,7 @@ impl Parser {
 
         let body = self.parse_labeled_block(label.as_ref())?;
 
-        let end_span = body.last().map(stmt_span).unwrap_or(start_span);
+        let end_span = body.last().map(Stmt::span).unwrap_or(start_span);
 
         Ok(Stmt::Loop {
             label,
@@ -394,7 +475,7 @@ impl Parser {
             // No range operator: iterate the parsed expression as an array.
             self.no_struct_lit = false;
             let body = self.parse_labeled_block(label.as_ref())?;
-            let end_span = body.last().map(stmt_span).unwrap_or(start.span());
+            let end_span = body.last().map(Stmt::span).unwrap_or(start.span());
             return Ok(Stmt::ForEach {
                 label,
                 iterator,
@@ -412,7 +493,7 @@ impl Parser {
 
         let body = self.parse_labeled_block(label.as_ref())?;
 
-        let end_span = body.last().map(stmt_span).unwrap_or(end.span());
+        let end_span = body.last().map(Stmt::span).unwrap_or(end.span());
 
         Ok(Stmt::ForRange {
             label,
@@ -576,6 +657,23 @@ impl Parser {
                 self.advance(); // consume 'return'
                 self.parse_return_stmt(start_span)
             }
+            // A nested function, with any attributes written above it.
+            TokenKind::At | TokenKind::Func => {
+                let attributes = self.parse_attributes()?;
+                self.skip_newlines();
+                if !self.check(&TokenKind::Func) {
+                    let token = self.peek().ok_or(ParseError::UnexpectedEof {
+                        expected: "nested function after attribute".to_string(),
+                    })?;
+                    return Err(ParseError::UnexpectedToken {
+                        found: token.kind.clone(),
+                        expected: "nested function after attribute".to_string(),
+                        span: token.span,
+                    });
+                }
+                let func = self.parse_function(attributes)?;
+                Ok(Stmt::Func(Box::new(func)))
+            }
             TokenKind::If => {
                 let start_span = token.span;
                 self.advance(); // consume 'if'
@@ -719,6 +817,22 @@ impl Parser {
                 }
                 Ok(Stmt::Expr(expr))
             }
+            // A bare block is a scoped statement unless it ends the enclosing
+            // body, where it is the body's value.
+            TokenKind::LeftBrace => {
+                let expr = self.parse_expr(Precedence::Lowest)?;
+                match expr {
+                    Expr::Block { stmts, span }
+                        if !matches!(
+                            self.peek_next_nonnewline_kind(),
+                            Some(TokenKind::RightBrace | TokenKind::Eof) | None
+                        ) =>
+                    {
+                        Ok(Stmt::Block(stmts, span))
+                    }
+                    expr => Ok(Stmt::Expr(expr)),
+                }
+            }
             _ => {
                 let expr = self.parse_expr(Precedence::Lowest)?;
                 Ok(Stmt::Expr(expr))
@@ -991,6 +1105,11 @@ impl Parser {
     /// Parse one element of a destructuring pattern: a nested tuple/array/struct
     /// pattern, the `_` wildcard, or a binding name.
     fn parse_pattern_element(&mut self) -> ParseResult<DestructurePattern> {
+        self.nested(Self::parse_pattern_element_inner)
+    }
+
+    /// Inner destructuring-element parsing implementation
+    fn parse_pattern_element_inner(&mut self) -> ParseResult<DestructurePattern> {
         if self.check(&TokenKind::LeftParen) {
             return self.parse_tuple_pattern();
         }
@@ -1134,6 +1253,11 @@ impl Parser {
 
     /// Parse a block of statements (within braces)
     pub(crate) fn parse_block(&mut self) -> ParseResult<Vec<Stmt>> {
+        self.nested(Self::parse_block_inner)
+    }
+
+    /// Inner block parsing implementation
+    fn parse_block_inner(&mut self) -> ParseResult<Vec<Stmt>> {
         self.consume(TokenKind::LeftBrace, "'{'")?;
         self.skip_newlines();
 
@@ -1237,24 +1361,3 @@ impl Parser {
         })
     }
 }
-
-/// Extract the span from a statement — shared by span-calculation in block-ending logic.
-pub(crate) fn stmt_span(stmt: &Stmt) -> shared_types::Span {
-    match stmt {
-        Stmt::VarDecl { span, .. } => *span,
-        Stmt::Const { span, .. } => *span,
-        Stmt::Assignment { span, .. } => *span,
-        Stmt::Return { span, .. } => *span,
-        Stmt::If { span, .. } => *span,
-        Stmt::While { span, .. } => *span,
-        Stmt::Loop { span, .. } => *span,
-        Stmt::ForRange { span, .. } => *span,
-        Stmt::ForEach { span, .. } => *span,
-        Stmt::Break { span, .. } => *span,
-        Stmt::Continue { span, .. } => *span,
-        Stmt::FieldAssignment { span, .. } => *span,
-        Stmt::IndexAssignment { span, .. } => *span,
-        Stmt::DerefAssignment { span, .. } => *span,
-        Stmt::Expr(e) => e.span(),
-    }
-}
@@ -8,12 +8,12 @@
 // name is reported by the existing semantic `UnknownTypeName` check against the
 // real type, with the diagnostic pointing at the alias *use* site.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use lexical_analysis::TokenKind;
 use shared_types::Identifier;
 
-use crate::ast::{Expr, Item, Stmt, Type};
+use crate::ast::{Expr, FunctionDef, Item, Stmt, Type};
 use crate::errors::{ParseError, ParseResult};
 
 use super::Parser;
@@ -98,8 +98,7 @@ pub(crate) fn expand_type_aliases(
 
     let mut resolved: HashMap<String, Type> = HashMap::new();
     for (name, span) in &spans {
-        let ultimate = resolve_alias(name, *span, &direct)?;
-        resolved.insert(name.clone(), ultimate);
+        resolve_alias(name, *span, &direct, &mut resolved)?;
     }
 
     for item in items.iter_mut() {
@@ -108,40 +107,52 @@ pub(crate) fn expand_type_aliases(
     Ok(())
 }
 
-/// Follow an alias chain to its ultimate non-alias target. A name that revisits
-/// itself is a cycle, reported against the chain's starting alias.
+/// Follow an alias chain to its ultimate non-alias target and record it in
+/// `resolved` for every alias on the chain, so a long chain is walked once rather
+/// than once per alias. A name that revisits itself is a cycle, reported against
+/// the chain's starting alias.
 fn resolve_alias(
     start: &str,
     start_span: shared_types::Span,
     direct: &HashMap<String, Type>,
-) -> ParseResult<Type> {
+    resolved: &mut HashMap<String, Type>,
+) -> ParseResult<()> {
     let mut current = start.to_string();
-    let mut visited: Vec<String> = Vec::new();
-    loop {
-        if visited.iter().any(|v| v == &current) {
+    let mut chain: Vec<String> = Vec::new();
+    let mut visited: HashSet<String> = HashSet::new();
+    let ultimate = loop {
+        if let Some(known) = resolved.get(&current) {
+            break known.clone();
+        }
+        if !visited.insert(current.clone()) {
             return Err(ParseError::CyclicTypeAlias {
                 name: start.to_string(),
                 span: start_span,
             });
         }
-        visited.push(current.clone());
+        chain.push(current.clone());
 
         match direct.get(&current) {
             Some(Type::Named(ident)) if direct.contains_key(&ident.name) => {
                 current = ident.name.clone();
             }
-            Some(other) => return Ok(other.clone()),
+            Some(other) => break other.clone(),
             // `current` is always an alias key on entry and is only reassigned to
             // another alias key, so this arm is unreachable; resolve to the name
             // itself as a terminal type rather than panicking.
             None => {
-                return Ok(Type::Named(Identifier {
+                break Type::Named(Identifier {
                     name: current,
                     span: start_span,
-                }))
+                })
             }
         }
+    };
+
+    for name in chain {
+        resolved.insert(name, ultimate.clone());
     }
+    Ok(())
 }
 
 fn rewrite_type(ty: &mut Type, resolved: &HashMap<String, Type>) {
@@ -185,15 +196,7 @@ fn rewrite_type(ty: &mut Type, resolved: &HashMap<String, Type>) {
 
 fn rewrite_item(item: &mut Item, resolved: &HashMap<String, Type>) {
     match item {
-        Item::Function(func) => {
-            for param in &mut func.params {
-                rewrite_type(&mut param.ty, resolved);
-            }
-            if let Some(ret) = &mut func.return_type {
-                rewrite_type(ret, resolved);
-            }
-            rewrite_block(&mut func.body, resolved);
-        }
+        Item::Function(func) => rewrite_function(func, resolved),
         Item::Struct(def) => {
             for field in &mut def.fields {
                 rewrite_type(&mut field.ty, resolved);
@@ -249,6 +252,16 @@ fn rewrite_item(item: &mut Item, resolved: &HashMap<String, Type>) {
     }
 }
 
+fn rewrite_function(func: &mut FunctionDef, resolved: &HashMap<String, Type>) {
+    for param in &mut func.params {
+        rewrite_type(&mut param.ty, resolved);
+    }
+    if let Some(ret) = &mut func.return_type {
+        rewrite_type(ret, resolved);
+    }
+    rewrite_block(&mut func.body, resolved);
+}
+
 fn rewrite_block(stmts: &mut [Stmt], resolved: &HashMap<String, Type>) {
     for stmt in stmts.iter_mut() {
         rewrite_stmt(stmt, resolved);
@@ -288,13 +301,31 @@ fn rewrite_stmt(stmt: &mut Stmt, resolved: &HashMap<String, Type>) {
                 rewrite_block(block, resolved);
             }
         }
+        Stmt::IfLet {
+            scrutinee,
+            then_block,
+            else_block,
+            ..
+        } => {
+            rewrite_expr(scrutinee, resolved);
+            rewrite_block(then_block, resolved);
+            if let Some(block) = else_block {
+                rewrite_block(block, resolved);
+            }
+        }
         Stmt::While {
             condition, body, ..
         } => {
             rewrite_expr(condition, resolved);
             rewrite_block(body, resolved);
         }
-        Stmt::Loop { body, .. } => {
+        Stmt::WhileLet {
+            scrutinee, body, ..
+        } => {
+            rewrite_expr(scrutinee, resolved);
+            rewrite_block(body, resolved);
+        }
+        Stmt::Loop { body, .. } | Stmt::Block(body, _) => {
             rewrite_block(body, resolved);
         }
         Stmt::ForRange {
@@ -328,6 +359,7 @@ fn rewrite_stmt(stmt: &mut Stmt, resolved: &HashMap<String, Type>) {
             }
         }
         Stmt::Continue { .. } => {}
+        Stmt::Func(func) => rewrite_function(func, resolved),
     }
 }
 
@@ -1,4 +1,4 @@
-use lexical_analysis::TokenKind;
+use lexical_analysis::{Token, TokenKind};
 use shared_types::Identifier;
 
 use crate::ast::{ArraySize, GenericArg, Type};
@@ -9,6 +9,11 @@ use super::Parser;
 impl Parser {
     /// Parse a type annotation
     pub(crate) fn parse_type(&mut self) -> ParseResult<Type> {
+        self.nested(Self::parse_type_inner)
+    }
+
+    /// Inner type parsing implementation
+    fn parse_type_inner(&mut self) -> ParseResult<Type> {
         // Fixed-size array type `[T; N]`: element type, `;`, then either a
         // non-negative integer length literal or a `const` generic parameter name
         // (`[T; CAP]`), closed by `]`.
@@ -51,20 +56,7 @@ impl Parser {
             let open = self.advance().ok_or(ParseError::UnexpectedEof {
                 expected: "'('".to_string(),
             })?;
-            let mut elements = Vec::new();
-            self.skip_newlines();
-            if !self.check(&TokenKind::RightParen) {
-                loop {
-                    self.skip_newlines();
-                    elements.push(self.parse_type()?);
-                    self.skip_newlines();
-                    if !self.check(&TokenKind::Comma) {
-                        break;
-                    }
-                    self.advance(); // consume ','
-                }
-            }
-            let close = self.consume(TokenKind::RightParen, "')' to close type list")?;
+            let (elements, close) = self.parse_type_list()?;
             // `(T1, ...) -> R` is a closure/function type.
             if self.check(&TokenKind::Arrow) {
                 self.advance(); // consume '->'
@@ -87,6 +79,30 @@ impl Parser {
             let span = open.span.merge(close.span);
             return Ok(Type::Tuple { elements, span });
         }
+        // Keyword-spelled function type `func(T1, ...) -> R`, the same `Type::Function`
+        // as `(T1, ...) -> R`. Without `->` the function returns `void`.
+        if self.check(&TokenKind::Func) {
+            let kw = self.advance().ok_or(ParseError::UnexpectedEof {
+                expected: "'func'".to_string(),
+            })?;
+            self.consume(TokenKind::LeftParen, "'(' after `func` in a function type")?;
+            let (params, close) = self.parse_type_list()?;
+            let ret = if self.check(&TokenKind::Arrow) {
+                self.advance(); // consume '->'
+                self.parse_type()?
+            } else {
+                Type::Named(Identifier {
+                    name: "void".to_string(),
+                    span: close.span,
+                })
+            };
+            let span = kw.span.merge(ret.span());
+            return Ok(Type::Function {
+                params,
+                ret: Box::new(ret),
+                span,
+            });
+        }
         // Borrow type `&T` / `&mut T`, with an optional explicit lifetime
         // `&'a T` / `&'a mut T`. The referent is parsed recursively, so the `&`
         // distributes over whatever type follows. Order after `&`: an optional lifetime,
@@ -173,6 +189,26 @@ impl Parser {
         }
     }
 
+    /// Parse the comma-separated types after an opening `(` through the closing
+    /// `)`, returning them with the `)` token. The list may be empty.
+    fn parse_type_list(&mut self) -> ParseResult<(Vec<Type>, Token)> {
+        let mut elements = Vec::new();
+        self.skip_newlines();
+        if !self.check(&TokenKind::RightParen) {
+            loop {
+                self.skip_newlines();
+                elements.push(self.parse_type()?);
+                self.skip_newlines();
+                if !self.check(&TokenKind::Comma) {
+                    break;
+                }
+                self.advance(); // consume ','
+            }
+        }
+        let close = self.consume(TokenKind::RightParen, "')' to close type list")?;
+        Ok((elements, close))
+    }
+
     /// Parse the trait-name identifier following an `impl` / `dyn` keyword.
     fn parse_trait_ref_name(&mut self, context: &str) -> ParseResult<Identifier> {
         let token = self
@@ -0,0 +1,124 @@
+// Conditional compilation tests: `@cfg(...)` attribute parsing and item stripping
+
+use syntax_parsing::{parse, parse_with_cfg, CfgSet, Item, ParseError};
+
+const GATED: &str = r#"
+@cfg(target = "cuda")
+func kernel() -> i32 { 1 }
+
+func main() -> i32 { 0 }
+"#;
+
+fn function_names(items: &[Item]) -> Vec<&str> {
+    items
+        .iter()
+        .filter_map(|item| match item {
+            Item::Function(f) => Some(f.name.name.as_str()),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn parses_key_value_attribute_arguments() {
+    let mut cfg = CfgSet::new();
+    cfg.set_value("target", "cuda");
+    let items = parse_with_cfg(GATED, &cfg).expect("parse should succeed");
+    let Item::Function(kernel) = &items[0] else {
+        panic!("expected function, got {:?}", items[0]);
+    };
+    let attr = &kernel.attributes[0];
+    assert_eq!(attr.name.name, "cfg");
+    assert!(attr.args.is_empty());
+    assert_eq!(attr.named_args.len(), 1);
+    assert_eq!(attr.named_args[0].0.name, "target");
+    assert_eq!(attr.named_args[0].1, "cuda");
+}
+
+#[test]
+fn item_gated_on_unset_cfg_is_excluded() {
+    let items = parse(GATED).expect("parse should succeed");
+    assert_eq!(function_names(&items), vec!["main"]);
+}
+
+#[test]
+fn item_gated_on_set_cfg_is_included() {
+    let mut cfg = CfgSet::new();
+    cfg.set_value("target", "cuda");
+    let items = parse_with_cfg(GATED, &cfg).expect("parse should succeed");
+    assert_eq!(function_names(&items), vec!["kernel", "main"]);
+}
+
+#[test]
+fn cfg_value_must_match() {
+    let mut cfg = CfgSet::new();
+    cfg.set_value("target", "cpu");
+    let items = parse_with_cfg(GATED, &cfg).expect("parse should succeed");
+    assert_eq!(function_names(&items), vec!["main"]);
+}
+
+#[test]
+fn bare_flag_gates_structs_and_methods() {
+    let source = r#"
+@cfg(debug)
+struct Trace { depth: i32 }
+
+struct Counter { value: i32 }
+
+impl Counter {
+    @cfg(debug)
+    func dump(&self) -> i32 { self.value }
+
+    func get(&self) -> i32 { self.value }
+}
+"#;
+    let items = parse(source).expect("parse should succeed");
+    assert_eq!(items.len(), 2, "the gated struct should be dropped");
+    let Item::Impl(def) = &items[1] else {
+        panic!("expected impl, got {:?}", items[1]);
+    };
+    assert_eq!(def.methods.len(), 1);
+    assert_eq!(def.methods[0].name.name, "get");
+
+    let mut cfg = CfgSet::new();
+    cfg.set_flag("debug");
+    let items = parse_with_cfg(source, &cfg).expect("parse should succeed");
+    assert_eq!(items.len(), 3);
+}
+
+#[test]
+fn every_condition_must_hold() {
+    let source = r#"
+@cfg(debug, target = "cuda")
+func kernel() -> i32 { 1 }
+"#;
+    let mut cfg = CfgSet::new();
+    cfg.set_flag("debug");
+    assert!(parse_with_cfg(source, &cfg)
+        .expect("parse should succeed")
+        .is_empty());
+
+    cfg.set_value("target", "cuda");
+    assert_eq!(
+        parse_with_cfg(source, &cfg)
+            .expect("parse should succeed")
+            .len(),
+        1
+    );
+}
+
+#[test]
+fn empty_cfg_is_rejected() {
+    let err = parse("@cfg()\nfunc f() -> i32 { 1 }").expect_err("empty @cfg should fail");
+    assert!(matches!(err, ParseError::EmptyCfg { .. }), "got {err:?}");
+}
+
+#[test]
+fn named_argument_outside_cfg_is_rejected() {
+    let err = parse("@inline(mode = always)\nfunc f() -> i32 { 1 }")
+        .expect_err("`key = value` outside @cfg should fail");
+    match err {
+        ParseError::NamedAttributeArgument { attribute, .. } => assert_eq!(attribute, "inline"),
+        other => panic!("expected NamedAttributeArgument, got {other:?}"),
+    }
+}
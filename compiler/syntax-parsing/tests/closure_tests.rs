@@ -1,6 +1,6 @@
 // Closure literal and function-type parsing tests (§3.12)
 
-use syntax_parsing::{parse_expr, Expr, Type};
+use syntax_parsing::{parse, parse_expr, Expr, Item, Stmt, Type};
 
 #[test]
 fn parses_single_expression_closure() {
@@ -66,3 +66,83 @@ fn parses_function_type_annotation() {
         other => panic!("expected closure, got {:?}", other),
     }
 }
+
+/// The type annotation of the single parameter of `func take(f: <ty>) {}`.
+fn param_type(ty: &str) -> Type {
+    let items = parse(&format!("func take(f: {ty}) {{}}")).expect("annotation should parse");
+    let Item::Function(func) = &items[0] else {
+        panic!("expected function, got {:?}", items[0]);
+    };
+    func.params[0].ty.clone()
+}
+
+#[test]
+fn parses_func_keyword_function_types() {
+    // `func(...)` spells the same `Type::Function` as `(...) -> R`; without `->`
+    // it returns `void`.
+    for (source, arity, ret) in [
+        ("func()", 0, "void"),
+        ("func(i32)", 1, "void"),
+        ("func(i32, f64, bool)", 3, "void"),
+        ("func(i32, i32) -> i32", 2, "i32"),
+    ] {
+        match param_type(source) {
+            Type::Function {
+                params,
+                ret: ret_ty,
+                ..
+            } => {
+                assert_eq!(params.len(), arity, "{source}");
+                assert_eq!(ret_ty.to_string(), ret, "{source}");
+            }
+            other => panic!("expected function type for {source}, got {:?}", other),
+        }
+    }
+    assert_eq!(
+        param_type("func(i32, i32) -> i32").to_string(),
+        param_type("(i32, i32) -> i32").to_string()
+    );
+    assert_eq!(
+        param_type("func() -> func(i32) -> i32").to_string(),
+        "() -> (i32) -> i32"
+    );
+}
+
+#[test]
+fn parses_nested_function_statement() {
+    let items = parse("func outer() -> i32 {\n    func helper(x: i32) -> i32 {\n        return x + 1\n    }\n    return helper(1)\n}")
+        .expect("nested function should parse");
+    let Item::Function(outer) = &items[0] else {
+        panic!("expected function, got {:?}", items[0]);
+    };
+    match &outer.body[0] {
+        Stmt::Func(helper) => {
+            assert_eq!(helper.name.name, "helper");
+            assert_eq!(helper.params.len(), 1);
+            assert!(helper.return_type.is_some());
+        }
+        other => panic!("expected nested function, got {:?}", other),
+    }
+}
+
+#[test]
+fn nested_function_keeps_its_attributes() {
+    let items = parse(
+        "func outer() -> i32 {\n    @allow(redundant_parens)\n    func helper() -> i32 { return (1) }\n    helper()\n}",
+    )
+    .expect("attributed nested function should parse");
+    let Item::Function(outer) = &items[0] else {
+        panic!("expected function, got {:?}", items[0]);
+    };
+    match &outer.body[0] {
+        Stmt::Func(helper) => {
+            assert_eq!(helper.attributes.len(), 1);
+            assert_eq!(helper.attributes[0].name.name, "allow");
+        }
+        other => panic!("expected nested function, got {:?}", other),
+    }
+
+    assert!(
+        parse("func outer() -> i32 {\n    @allow(dead_code)\n    val x = 1\n    x\n}").is_err()
+    );
+}
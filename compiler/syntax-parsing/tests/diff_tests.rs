@@ -0,0 +1,110 @@
+// Item-level AST diff tests: which top-level items changed between two parses
+
+use syntax_parsing::{ast_diff, parse, ChangedItem, ItemChange};
+
+fn diff(old: &str, new: &str) -> Vec<ChangedItem> {
+    let old = parse(old).expect("old source should parse");
+    let new = parse(new).expect("new source should parse");
+    ast_diff(&old, &new)
+}
+
+fn changed(name: &str, change: ItemChange) -> ChangedItem {
+    ChangedItem {
+        name: name.to_string(),
+        change,
+    }
+}
+
+#[test]
+fn ast_diff_reports_only_the_edited_function() {
+    let old = "func a() -> i32 { return 1 }\nfunc b() -> i32 { return 2 }\n";
+    let new = "func a() -> i32 { return 1 }\nfunc b() -> i32 { return 3 }\n";
+
+    assert_eq!(diff(old, new), vec![changed("b", ItemChange::Modified)]);
+}
+
+#[test]
+fn ast_diff_ignores_reordered_functions() {
+    let old = "func a() -> i32 { return 1 }\nfunc b() -> i32 { return 2 }\n";
+    let new = "func b() -> i32 { return 2 }\nfunc a() -> i32 { return 1 }\n";
+
+    assert!(diff(old, new).is_empty());
+}
+
+#[test]
+fn ast_diff_reports_added_and_removed_functions() {
+    let old = "func a() -> i32 { return 1 }\n";
+    let new = "func b() -> i32 { return 1 }\n";
+
+    assert_eq!(
+        diff(old, new),
+        vec![
+            changed("b", ItemChange::Added),
+            changed("a", ItemChange::Removed),
+        ]
+    );
+}
+
+#[test]
+fn ast_diff_ignores_whitespace_and_comments_inside_a_body() {
+    // Every nested node moves: the match, its arms and patterns, the closure and
+    // its parameter, and the struct literal's fields.
+    let old = r#"
+struct P { x: i32 }
+
+func pick(n: i32) -> i32 {
+    val f = |v: i32| v + 1
+    val p = P { x: f(n) }
+    return match p.x { 0 => 1, k if k > 2 => k, _ => 0 }
+}
+"#;
+    let new = r#"
+// A comment pushes everything down.
+struct P {
+    x: i32,
+}
+
+func pick(n: i32) -> i32 {
+    val f = |v: i32|   v + 1
+    val p = P {
+        x: f(n),
+    }
+    return match p.x {
+        0 => 1,
+        k if k > 2 => k,
+        _ => 0,
+    }
+}
+"#;
+
+    assert!(diff(old, new).is_empty(), "{:?}", diff(old, new));
+}
+
+#[test]
+fn ast_diff_reports_an_edit_deep_inside_a_body() {
+    let old = "func pick(n: i32) -> i32 {\n    return match n { 0 => 1, _ => 0 }\n}\n";
+    let new = "func pick(n: i32) -> i32 {\n    return match n { 0 => 2, _ => 0 }\n}\n";
+
+    assert_eq!(diff(old, new), vec![changed("pick", ItemChange::Modified)]);
+}
+
+#[test]
+fn ast_diff_matches_impl_blocks_by_type_and_trait() {
+    let old = r#"
+struct S { v: i32 }
+impl S {
+    func get(&self) -> i32 { return self.v }
+}
+"#;
+    let new = r#"
+struct S { v: i32 }
+impl S {
+    func get(&self) -> i32 { return self.v + 1 }
+}
+"#;
+
+    assert_eq!(
+        diff(old, new),
+        vec![changed("impl S", ItemChange::Modified)]
+    );
+}
@@ -1,6 +1,6 @@
 // Error case tests
 
-use syntax_parsing::{parse, parse_expr};
+use syntax_parsing::{parse, parse_expr, ParseError};
 
 #[test]
 fn test_error_unexpected_token() {
@@ -8,6 +8,13 @@ fn test_error_unexpected_token() {
     assert!(result.is_err());
 }
 
+#[test]
+fn test_error_span_points_at_offending_token() {
+    // `->` where a parameter name belongs, at byte 11.
+    let err = parse("func main( -> i32 {}").expect_err("should fail");
+    assert_eq!(err.span().map(|span| span.start), Some(11));
+}
+
 #[test]
 fn test_error_unclosed_paren() {
     let result = parse_expr("(42");
@@ -118,6 +125,20 @@ fn test_error_if_without_body() {
     assert!(result.is_err());
 }
 
+#[test]
+fn test_error_for_without_in() {
+    let source = r#"
+        func test() {
+            for i 0..10 { }
+        }
+    "#;
+    let err = parse(source).expect_err("a for loop needs `in`");
+    assert!(
+        matches!(&err, ParseError::UnexpectedToken { expected, .. } if expected.contains("in")),
+        "got {err:?}"
+    );
+}
+
 #[test]
 fn test_error_else_without_if() {
     let source = r#"
@@ -246,6 +267,71 @@ fn test_error_max_depth_exceeded() {
     }
 }
 
+#[test]
+fn test_error_max_depth_exceeded_in_types() {
+    for ty in [
+        "[".repeat(300) + "i32",
+        "& ".repeat(300) + "i32",
+        "(".repeat(300),
+    ] {
+        let source = format!("func f(x: {ty}) {{}}");
+        assert!(matches!(
+            parse(&source),
+            Err(ParseError::MaxDepthExceeded(_))
+        ));
+    }
+}
+
+#[test]
+fn test_error_max_depth_exceeded_in_patterns() {
+    let match_source = format!("func f() {{ match x {{ {} }} }}", "E::V(".repeat(300));
+    let destructure_source = format!("func f() {{ val {}a = x }}", "(".repeat(300));
+    for source in [match_source, destructure_source] {
+        assert!(matches!(
+            parse(&source),
+            Err(ParseError::MaxDepthExceeded(_))
+        ));
+    }
+}
+
+#[test]
+fn test_error_max_depth_exceeded_in_blocks() {
+    let source = format!("func f() {{ {} }}", "while x { ".repeat(300));
+    assert!(matches!(
+        parse(&source),
+        Err(ParseError::MaxDepthExceeded(_))
+    ));
+}
+
+#[test]
+fn test_error_max_depth_exceeded_in_operator_chain() {
+    // A chain is parsed iteratively but nests the AST one level per operator.
+    let long = format!("x{}", " + x".repeat(10_000));
+    assert!(matches!(
+        parse_expr(&long),
+        Err(ParseError::MaxDepthExceeded(_))
+    ));
+    let postfix = format!("x{}", ".f".repeat(10_000));
+    assert!(matches!(
+        parse_expr(&postfix),
+        Err(ParseError::MaxDepthExceeded(_))
+    ));
+
+    let moderate = format!("x{}", " + x".repeat(1_000));
+    assert!(parse_expr(&moderate).is_ok());
+}
+
+#[test]
+fn test_long_type_alias_chain_resolves() {
+    // Each alias names the previous one; resolution walks the chain once.
+    let mut source = String::from("type A0 = i32\n");
+    for i in 1..5_000 {
+        source.push_str(&format!("type A{i} = A{}\n", i - 1));
+    }
+    source.push_str("func f(x: A4999) {}");
+    assert!(parse(&source).is_ok());
+}
+
 #[test]
 fn test_error_duplicate_parameter_names() {
     let source = "func test(x: i32, y: i32, x: i32) {}";
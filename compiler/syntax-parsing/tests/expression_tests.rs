@@ -1,6 +1,6 @@
 // Expression parsing tests
 
-use shared_types::{FloatSuffix, Literal};
+use shared_types::{FloatSuffix, IntRadix, Literal};
 use syntax_parsing::{parse_expr, BinaryOp, Expr, UnaryOp};
 
 #[test]
@@ -14,6 +14,36 @@ fn test_parse_integer_literal() {
     }
 }
 
+/// Reprint a literal expression the way a formatter would: the base comes from
+/// the source text under the literal's span.
+fn reformat_literal(source: &str) -> String {
+    let expr = parse_expr(source).expect("literal should parse");
+    let Expr::Literal(lit, span) = &expr else {
+        panic!("Expected literal, got {:?}", expr);
+    };
+    lit.to_source(IntRadix::of_literal(&source[span.start..span.end]))
+}
+
+#[test]
+fn test_hex_literal_round_trips_as_hex() {
+    assert_eq!(reformat_literal("0xFF"), "0xFF");
+    assert_eq!(reformat_literal("0b1010i64"), "0b1010i64");
+}
+
+#[test]
+fn test_decimal_literal_round_trips_as_decimal() {
+    assert_eq!(reformat_literal("255u8"), "255u8");
+}
+
+#[test]
+fn test_integer_literals_compare_by_value_not_base() {
+    let literal = |source| match parse_expr(source) {
+        Ok(Expr::Literal(lit, _)) => lit,
+        other => panic!("Expected literal, got {:?}", other),
+    };
+    assert_eq!(literal("0x10"), literal("16"));
+}
+
 #[test]
 fn test_parse_tuple_literal() {
     // A comma after the first element makes `( ... )` a tuple literal.
@@ -45,6 +75,64 @@ fn test_parse_tuple_index() {
     }
 }
 
+#[test]
+fn test_parse_array_literal() {
+    let expr = parse_expr("[1, 2, 3]").expect("array literal should parse");
+    match expr {
+        Expr::ArrayLiteral { elements, span } => {
+            assert_eq!(elements.len(), 3);
+            assert!(matches!(
+                elements[2],
+                Expr::Literal(Literal::Integer(3, _), _)
+            ));
+            assert_eq!((span.start, span.end), (0, 9));
+        }
+        other => panic!("expected array literal, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_empty_array_literal() {
+    // The element type of `[]` comes from an annotation, checked later.
+    let expr = parse_expr("[]").expect("empty array literal should parse");
+    match expr {
+        Expr::ArrayLiteral { elements, .. } => assert!(elements.is_empty()),
+        other => panic!("expected array literal, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_nested_array_literal() {
+    // A 2x2 matrix is an array literal whose elements are array literals.
+    let expr = parse_expr("[[1.0, 2.0], [3.0, 4.0]]").expect("nested array literal should parse");
+    match expr {
+        Expr::ArrayLiteral { elements, .. } => {
+            assert_eq!(elements.len(), 2);
+            for row in &elements {
+                match row {
+                    Expr::ArrayLiteral { elements, .. } => {
+                        assert_eq!(elements.len(), 2);
+                        assert!(matches!(elements[0], Expr::Literal(Literal::Float(..), _)));
+                    }
+                    other => panic!("expected a row literal, got {:?}", other),
+                }
+            }
+        }
+        other => panic!("expected array literal, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_index_of_array_literal() {
+    let expr = parse_expr("[10, 20][1]").expect("indexed array literal should parse");
+    match expr {
+        Expr::Index { object, .. } => {
+            assert!(matches!(*object, Expr::ArrayLiteral { .. }));
+        }
+        other => panic!("expected index expression, got {:?}", other),
+    }
+}
+
 #[test]
 fn test_parse_float_literal() {
     let result = parse_expr("2.5");
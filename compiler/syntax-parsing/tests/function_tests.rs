@@ -320,6 +320,24 @@ fn test_parse_function_with_bare_attribute() {
     assert!(func.attributes[0].args.is_empty());
 }
 
+#[test]
+fn test_parse_function_with_integer_attribute_argument() {
+    use syntax_parsing::Item;
+
+    let source = r#"
+        @optimize(3)
+        func hot() -> i32 { 1 }
+    "#;
+    let items = parse(source).expect("parse should succeed");
+    let func = match &items[0] {
+        Item::Function(f) => f,
+        _ => panic!("expected function"),
+    };
+    assert_eq!(func.attributes[0].name.name, "optimize");
+    assert_eq!(func.attributes[0].args.len(), 1);
+    assert_eq!(func.attributes[0].args[0].name, "3");
+}
+
 #[test]
 fn test_parse_function_with_multi_arg_attribute() {
     use syntax_parsing::Item;
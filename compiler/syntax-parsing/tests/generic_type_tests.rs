@@ -78,6 +78,20 @@ fn test_parse_const_generic_parameter() {
     }
 }
 
+#[test]
+fn test_parse_literal_array_size() {
+    use syntax_parsing::ArraySize;
+    let source = "struct Triple { data: [i32; 3] }";
+    let items = parse(source).expect("should parse");
+    match &items[0] {
+        Item::Struct(def) => match &def.fields[0].ty {
+            Type::Array { size, .. } => assert_eq!(*size, ArraySize::Literal(3)),
+            other => panic!("expected an array type, got {other:?}"),
+        },
+        other => panic!("expected struct item, got {other:?}"),
+    }
+}
+
 #[test]
 fn test_parse_const_generic_array_size() {
     // The array length `CAP` parses as a const-parameter size, not a literal.
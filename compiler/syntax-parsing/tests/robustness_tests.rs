@@ -0,0 +1,77 @@
+// No-panic tests: `tokenize` and `parse` must return `Err` on malformed input,
+// never panic or overflow the stack. The coverage-guided counterpart lives in
+// `fuzz/` (see `fuzz/fuzz_targets/parse.rs`); this runs a fixed-seed sample of the
+// same idea under `cargo test`.
+
+use syntax_parsing::parse;
+
+/// Source fragments the generator glues together: keywords, punctuation, literal
+/// prefixes, and half-literals that are likely to hit lexer and parser edge cases.
+const FRAGMENTS: &str = r#"func val mut type struct enum trait impl match if else while for in
+    loop break return as dyn where @cfg f x A _ ( ) { } [ ] < > 'a ' " \ u{ : :: ; , . .. ..=
+    = => -> | & - + * ! ? # @ 0x 0b 0o 1 1.5 e9 i8 u64 f32 99999999999999999999 /* */ // é"#;
+
+/// What goes between two fragments; the empty separator fuses them into one token.
+const SEPARATORS: [&str; 5] = ["", " ", "\t", "\n", "\r\n"];
+
+/// xorshift64: a dependency-free, reproducible source of pseudo-random indices.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, bound: usize) -> usize {
+        (self.next() % bound as u64) as usize
+    }
+}
+
+#[test]
+fn random_fragment_soup_never_panics() {
+    let fragments: Vec<&str> = FRAGMENTS.split_whitespace().collect();
+    let mut rng = Rng(0x5eed_1234_abcd_ef01);
+    for _ in 0..5_000 {
+        let len = rng.below(64) + 1;
+        let mut source = String::new();
+        for _ in 0..len {
+            source.push_str(fragments[rng.below(fragments.len())]);
+            source.push_str(SEPARATORS[rng.below(SEPARATORS.len())]);
+        }
+        // Either outcome is fine; reaching the next iteration is the assertion.
+        let _ = lexical_analysis::tokenize(&source);
+        let _ = parse(&source);
+    }
+}
+
+#[test]
+fn random_bytes_never_panic() {
+    let mut rng = Rng(0x0dd_b17e_5eed_0042);
+    for _ in 0..5_000 {
+        let len = rng.below(128);
+        let bytes: Vec<u8> = (0..len).map(|_| rng.next() as u8).collect();
+        // Invalid UTF-8 never reaches the lexer; lossy decoding keeps the rest.
+        let source = String::from_utf8_lossy(&bytes);
+        let _ = lexical_analysis::tokenize(&source);
+        let _ = parse(&source);
+    }
+}
+
+#[test]
+fn deeply_nested_input_is_an_error_not_a_stack_overflow() {
+    let depth = 100_000;
+    let sources = [
+        format!("func f() {{ {}1 }}", "(".repeat(depth)),
+        format!("func f() {{ {} }}", "if x { ".repeat(depth)),
+        format!("func f(x: {}i32) {{}}", "[".repeat(depth)),
+        format!("func f() {{ match x {{ {} }} }}", "(".repeat(depth)),
+        format!("func f() {{ x{} }}", " + x".repeat(depth)),
+        format!("func f() {{ x{} }}", "[0]".repeat(depth)),
+    ];
+    for source in sources {
+        assert!(parse(&source).is_err());
+    }
+}
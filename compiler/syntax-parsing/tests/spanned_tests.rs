@@ -0,0 +1,147 @@
+// Spanned trait tests: every statement and item variant reports its source span
+
+use syntax_parsing::{parse, Expr, Item, Spanned, Stmt};
+
+const SOURCE: &str = r#"
+struct Point { x: i32, y: i32 }
+
+enum Color { Red, Green }
+
+trait Shape {
+    func area(&self) -> i32
+}
+
+impl Point {
+    func sum(&self) -> i32 { self.x + self.y }
+}
+
+newtype Meters = i32
+
+const LIMIT: i32 = 10
+
+func all_statements(p: &mut i32, arr: [i32; 2], pt: Point, c: Color) -> i32 {
+    mut total: i32 = 0
+    const STEP: i32 = 1
+    total = total + STEP
+    if total > 0 { total = 1 }
+    if let Color::Red = c { total = 2 }
+    while total < 3 { total += 1 }
+    while let Color::Green = c { break }
+    outer: loop { break outer }
+    for i in 0..2 { continue }
+    for v in arr { total += v }
+    mut q = pt
+    q.x = 5
+    *p = 4
+    mut a = arr
+    a[0] = 1
+    func helper() -> i32 { 1 }
+    helper()
+    return total
+}
+"#;
+
+fn text(node: &impl Spanned) -> &'static str {
+    let span = node.span();
+    &SOURCE[span.start..span.end]
+}
+
+fn items() -> Vec<Item> {
+    parse(SOURCE).expect("parse should succeed")
+}
+
+fn body(items: &[Item]) -> &[Stmt] {
+    let Some(Item::Function(func)) = items.last() else {
+        panic!("expected a trailing function, got {:?}", items.last());
+    };
+    &func.body
+}
+
+#[test]
+fn items_report_their_span() {
+    let items = items();
+    let expected = [
+        "struct Point { x: i32, y: i32 }",
+        "enum Color { Red, Green }",
+        "trait Shape {\n    func area(&self) -> i32\n}",
+        "impl Point {\n    func sum(&self) -> i32 { self.x + self.y }\n}",
+        "newtype Meters = i32",
+        "const LIMIT: i32 = 10",
+    ];
+    for (item, expected) in items.iter().zip(expected) {
+        assert_eq!(text(item), expected, "span of {item:?}");
+    }
+    assert!(text(&items[6]).starts_with("func all_statements("));
+}
+
+#[test]
+fn simple_statements_report_their_span() {
+    let items = items();
+    let stmts = body(&items);
+    let expected = [
+        (0, "mut total: i32 = 0"),
+        (1, "const STEP: i32 = 1"),
+        (2, "total = total + STEP"),
+        (11, "q.x = 5"),
+        (12, "*p = 4"),
+        (14, "a[0] = 1"),
+        (16, "helper()"),
+        (17, "return total"),
+    ];
+    for (index, expected) in expected {
+        assert_eq!(text(&stmts[index]), expected, "span of {:?}", stmts[index]);
+    }
+}
+
+#[test]
+fn block_statements_span_from_their_keyword() {
+    let items = items();
+    let stmts = body(&items);
+    let expected = [
+        (3, "if total > 0 {"),
+        (4, "if let Color::Red = c {"),
+        (5, "while total < 3 {"),
+        (6, "while let Color::Green = c {"),
+        (7, "loop {"),
+        (8, "for i in 0..2 {"),
+        (9, "for v in arr {"),
+        (15, "func helper() -> i32 {"),
+    ];
+    for (index, expected) in expected {
+        assert!(
+            text(&stmts[index]).starts_with(expected),
+            "span of {:?} is {:?}",
+            stmts[index],
+            text(&stmts[index])
+        );
+    }
+}
+
+#[test]
+fn statement_span_matches_its_expression() {
+    let items = items();
+    let stmts = body(&items);
+    let Stmt::Expr(call @ Expr::Call { .. }) = &stmts[16] else {
+        panic!("expected a call statement, got {:?}", stmts[16]);
+    };
+    assert_eq!(stmts[16].span(), call.span());
+    assert_eq!(text(call), "helper()");
+}
+
+#[test]
+fn nested_statements_report_their_span() {
+    let items = items();
+    let stmts = body(&items);
+    let Stmt::If { then_block, .. } = &stmts[3] else {
+        panic!("expected an if statement, got {:?}", stmts[3]);
+    };
+    assert_eq!(text(&then_block[0]), "total = 1");
+    let Stmt::Loop { body, .. } = &stmts[7] else {
+        panic!("expected a loop, got {:?}", stmts[7]);
+    };
+    assert_eq!(text(&body[0]), "break outer");
+    let Stmt::ForRange { body, .. } = &stmts[8] else {
+        panic!("expected a range loop, got {:?}", stmts[8]);
+    };
+    assert_eq!(text(&body[0]), "continue");
+}
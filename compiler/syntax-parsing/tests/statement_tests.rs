@@ -1,6 +1,6 @@
 // Statement parsing tests
 
-use syntax_parsing::{parse, Item, Stmt};
+use syntax_parsing::{parse, BinaryOp, Expr, Item, Stmt};
 
 /// Count the statements the first function body desugars to.
 fn first_fn_body_len(source: &str) -> usize {
@@ -197,6 +197,23 @@ fn test_parse_assignment_with_expression() {
     assert!(result.is_ok(), "Parse error: {:?}", result.err());
 }
 
+#[test]
+fn test_assignment_carries_target_and_value() {
+    let body =
+        first_fn_body("func test() {\n    mut counter: i32 = 0\n    counter = counter + 1\n}");
+    let [_, Stmt::Assignment { target, value, .. }] = body.as_slice() else {
+        panic!("expected a declaration then an assignment, got {body:?}");
+    };
+    assert_eq!(target.name, "counter");
+    assert!(matches!(
+        value,
+        Expr::Binary {
+            op: BinaryOp::Add,
+            ..
+        }
+    ));
+}
+
 #[test]
 fn test_parse_return_with_value() {
     let source = r#"
@@ -339,6 +356,78 @@ fn test_parse_nested_while_in_if() {
     assert!(result.is_ok(), "Parse error: {:?}", result.err());
 }
 
+/// The statements of the first function's body.
+fn first_fn_body(source: &str) -> Vec<Stmt> {
+    let items = parse(source).expect("parse failed");
+    for item in items {
+        if let Item::Function(func) = item {
+            return func.body;
+        }
+    }
+    panic!("no function found");
+}
+
+#[test]
+fn test_while_statement_carries_condition_and_body() {
+    let body = first_fn_body(
+        r#"
+        func test() {
+            while x > 0 {
+                x = x - 1
+            }
+        }
+    "#,
+    );
+    let [Stmt::While {
+        label: None,
+        condition,
+        body,
+        ..
+    }] = body.as_slice()
+    else {
+        panic!("expected a single while statement, got {body:?}");
+    };
+    assert!(matches!(
+        condition,
+        Expr::Binary {
+            op: BinaryOp::Greater,
+            ..
+        }
+    ));
+    assert!(matches!(body.as_slice(), [Stmt::Assignment { .. }]));
+}
+
+#[test]
+fn test_while_statement_with_empty_body() {
+    let body = first_fn_body("func test() { while running() { } }");
+    assert!(
+        matches!(body.as_slice(), [Stmt::While { body, .. }] if body.is_empty()),
+        "got {body:?}"
+    );
+}
+
+#[test]
+fn test_while_statement_nested_in_if_branch() {
+    let body = first_fn_body(
+        r#"
+        func test() {
+            if ready {
+                while x > 0 {
+                    x = x - 1
+                }
+            }
+        }
+    "#,
+    );
+    let [Stmt::If { then_block, .. }] = body.as_slice() else {
+        panic!("expected a single if statement, got {body:?}");
+    };
+    assert!(
+        matches!(then_block.as_slice(), [Stmt::While { .. }]),
+        "got {then_block:?}"
+    );
+}
+
 #[test]
 fn test_parse_loop_statement() {
     let source = r#"
@@ -376,6 +465,58 @@ fn test_parse_for_range_statement() {
     assert!(result.is_ok(), "Parse error: {:?}", result.err());
 }
 
+/// The iterator name, bounds, and inclusivity of the single `for` in `source`'s
+/// first function.
+fn single_for_range(source: &str) -> (String, Expr, Expr, bool) {
+    let body = first_fn_body(source);
+    match body.as_slice() {
+        [Stmt::ForRange {
+            iterator,
+            start,
+            end,
+            inclusive,
+            ..
+        }] => (
+            iterator.name.clone(),
+            start.clone(),
+            end.clone(),
+            *inclusive,
+        ),
+        _ => panic!("expected a single range for loop, got {body:?}"),
+    }
+}
+
+#[test]
+fn test_for_exclusive_range() {
+    let (iterator, start, end, inclusive) = single_for_range("func test() { for i in 0..n { } }");
+    assert_eq!(iterator, "i");
+    assert!(matches!(start, Expr::Literal(..)));
+    assert!(matches!(end, Expr::Identifier(ref id) if id.name == "n"));
+    assert!(!inclusive);
+}
+
+#[test]
+fn test_for_inclusive_range() {
+    let (_, _, end, inclusive) = single_for_range("func test() { for i in 0..=n { } }");
+    assert!(matches!(end, Expr::Identifier(ref id) if id.name == "n"));
+    assert!(inclusive);
+}
+
+#[test]
+fn test_for_range_with_expression_bounds() {
+    // `..` binds looser than `+`, so the end bound is the whole `b + 1`.
+    let (_, start, end, inclusive) = single_for_range("func test() { for i in a..b+1 { } }");
+    assert!(matches!(start, Expr::Identifier(ref id) if id.name == "a"));
+    assert!(matches!(
+        end,
+        Expr::Binary {
+            op: BinaryOp::Add,
+            ..
+        }
+    ));
+    assert!(!inclusive);
+}
+
 #[test]
 fn test_parse_expression_statement() {
     let source = r#"
@@ -534,6 +675,36 @@ fn test_unlabeled_break_has_no_label() {
     ));
 }
 
+#[test]
+fn test_unlabeled_continue_inside_while() {
+    let body = first_fn_body("func test() { while x > 0 { continue } }");
+    let [Stmt::While { body, .. }] = body.as_slice() else {
+        panic!("expected a single while loop, got {body:?}");
+    };
+    assert!(matches!(
+        body.as_slice(),
+        [Stmt::Continue { label: None, .. }]
+    ));
+}
+
+#[test]
+fn test_break_and_continue_outside_loop_still_parse() {
+    // Whether a `break` or `continue` has a loop to leave is checked by semantic
+    // analysis, not the parser.
+    let body = first_fn_body("func test() {\n    break\n    continue\n}");
+    assert!(matches!(
+        body.as_slice(),
+        [
+            Stmt::Break {
+                label: None,
+                value: None,
+                ..
+            },
+            Stmt::Continue { label: None, .. }
+        ]
+    ));
+}
+
 #[test]
 fn test_parse_loop_value_expression() {
     let source = r#"
@@ -601,3 +772,126 @@ fn test_break_value_is_not_parsed_as_label() {
         })
     ));
 }
+
+#[test]
+fn test_if_let_parses_pattern_and_nests_else_if() {
+    let source = r#"
+        func test(s: Shape) {
+            if let Shape::Circle(r) = s {
+                print(r)
+            } else if let Shape::Square { side } = s {
+                print(side)
+            } else {
+                print(0)
+            }
+        }
+    "#;
+    let items = parse(source).expect("if let should parse");
+    let Item::Function(func) = &items[0] else {
+        panic!("expected a function item");
+    };
+    let Some(Stmt::IfLet {
+        pattern,
+        then_block,
+        else_block: Some(else_block),
+        ..
+    }) = func.body.first()
+    else {
+        panic!("expected an if-let statement");
+    };
+    assert!(matches!(
+        pattern,
+        ast_types::Pattern::Enum { variant, .. } if variant.name == "Circle"
+    ));
+    assert_eq!(then_block.len(), 1);
+    // The `else if let` chain nests as the sole statement of the else block.
+    assert!(matches!(
+        else_block.as_slice(),
+        [Stmt::IfLet {
+            else_block: Some(_),
+            ..
+        }]
+    ));
+}
+
+#[test]
+fn test_labeled_while_let_parses() {
+    let source = r#"
+        func test() {
+            outer: while let Step::Next(n) = step() {
+                break outer
+            }
+        }
+    "#;
+    let items = parse(source).expect("labeled while let should parse");
+    let Item::Function(func) = &items[0] else {
+        panic!("expected a function item");
+    };
+    assert!(matches!(
+        func.body.first(),
+        Some(Stmt::WhileLet { label: Some(l), body, .. }) if l.name == "outer" && body.len() == 1
+    ));
+}
+
+#[test]
+fn test_bare_block_statement_parses_as_block() {
+    let source = r#"
+        func test() -> i32 {
+            {
+                val x = 1
+                print(x)
+            }
+            { 2 }
+        }
+    "#;
+    let items = parse(source).expect("bare blocks should parse");
+    let Item::Function(func) = &items[0] else {
+        panic!("expected a function item");
+    };
+    assert!(matches!(
+        func.body.first(),
+        Some(Stmt::Block(stmts, _)) if stmts.len() == 2
+    ));
+    // A block that ends the body is its value, so it stays an expression.
+    assert!(matches!(
+        func.body.last(),
+        Some(Stmt::Expr(Expr::Block { .. }))
+    ));
+}
+
+#[test]
+fn test_compound_assignment_desugars_to_plain_assignment() {
+    // `x OP= e` parses as `x = x OP e`, so later stages only see `Stmt::Assignment`.
+    let source = r#"
+        func test() {
+            x += 1
+            x -= 2
+            x *= 3
+            x /= 4
+            x %= 5
+        }
+    "#;
+    let items = parse(source).expect("compound assignments should parse");
+    let Item::Function(func) = &items[0] else {
+        panic!("expected a function item");
+    };
+    let expected = [
+        BinaryOp::Add,
+        BinaryOp::Subtract,
+        BinaryOp::Multiply,
+        BinaryOp::Divide,
+        BinaryOp::Modulo,
+    ];
+    assert_eq!(func.body.len(), expected.len());
+    for (stmt, expected_op) in func.body.iter().zip(expected) {
+        let Stmt::Assignment { target, value, .. } = stmt else {
+            panic!("expected an assignment, got {stmt:?}");
+        };
+        assert_eq!(target.name, "x");
+        let Expr::Binary { left, op, .. } = value else {
+            panic!("expected a binary value, got {value:?}");
+        };
+        assert_eq!(*op, expected_op);
+        assert!(matches!(left.as_ref(), Expr::Identifier(id) if id.name == "x"));
+    }
+}
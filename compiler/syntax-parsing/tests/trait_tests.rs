@@ -1,6 +1,6 @@
 // Trait declaration parsing and default-method injection.
 
-use syntax_parsing::{parse, Item};
+use syntax_parsing::{parse, Item, SelfParam};
 
 #[test]
 fn parses_required_and_default_trait_methods() {
@@ -110,3 +110,38 @@ impl Add for Vec2 {
     assert_eq!(imp.methods.len(), 1);
     assert_eq!(imp.methods[0].name.name, "add");
 }
+
+#[test]
+fn parses_trait_and_impl_for_trait() {
+    let source = r#"
+trait Printable { func show(self) -> string }
+
+struct Point { x: i32 }
+
+impl Printable for Point {
+    func show(self) -> string { "point" }
+}
+"#;
+    let items = parse(source).expect("program should parse");
+    let Item::Trait(def) = &items[0] else {
+        panic!("expected a trait item");
+    };
+    assert_eq!(def.name.name, "Printable");
+    assert_eq!(def.methods.len(), 1);
+    assert_eq!(def.methods[0].name.name, "show");
+    assert_eq!(def.methods[0].self_param, Some(SelfParam::Owned));
+    assert!(def.methods[0].default_body.is_none());
+
+    let imp = items
+        .iter()
+        .find_map(|item| match item {
+            Item::Impl(def) if def.type_name.name == "Point" => Some(def),
+            _ => None,
+        })
+        .expect("Point impl present");
+    assert_eq!(
+        imp.trait_name.as_ref().map(|t| t.name.as_str()),
+        Some("Printable")
+    );
+    assert_eq!(imp.methods.len(), 1);
+}